@@ -1,76 +1,108 @@
 // build.rs - Cross-platform C++ library linking
+//
+// Each backend is behind a Cargo feature (seal / helib / openfhe). Cargo exposes
+// enabled features to build scripts as CARGO_FEATURE_<NAME> env vars, so we only
+// emit link directives for the libraries that are actually wanted.
+
+fn feature_enabled(name: &str) -> bool {
+    std::env::var_os(format!("CARGO_FEATURE_{}", name)).is_some()
+}
 
 fn main() {
     let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
-    
+
+    let seal = feature_enabled("SEAL");
+    let helib = feature_enabled("HELIB");
+    let openfhe = feature_enabled("OPENFHE");
+
     if target_os == "macos" {
         // ============================================
         // macOS Configuration
         // ============================================
-        // SEAL
-        println!("cargo:rustc-link-search=native=cpp_wrapper/build");
-        println!("cargo:rustc-link-lib=dylib=seal_wrapper");
-        
         println!("cargo:rustc-link-search=native=/usr/local/lib");
-        println!("cargo:rustc-link-lib=dylib=seal-4.1");
-        
+
+        // SEAL
+        if seal {
+            println!("cargo:rustc-link-search=native=cpp_wrapper/build");
+            println!("cargo:rustc-link-lib=dylib=seal_wrapper");
+            println!("cargo:rustc-link-lib=dylib=seal-4.1");
+            println!("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN/cpp_wrapper/build");
+        }
+
         // HElib
-        println!("cargo:rustc-link-search=native=helib_wrapper/build");
-        println!("cargo:rustc-link-lib=dylib=helib_wrapper");
-        
-        println!("cargo:rustc-link-search=native=/usr/local/helib_pack/helib_pack/lib");
-        println!("cargo:rustc-link-lib=dylib=helib");
-        println!("cargo:rustc-link-lib=dylib=ntl");
-        println!("cargo:rustc-link-lib=dylib=gmp");
+        if helib {
+            println!("cargo:rustc-link-search=native=helib_wrapper/build");
+            println!("cargo:rustc-link-lib=dylib=helib_wrapper");
+
+            println!("cargo:rustc-link-search=native=/usr/local/helib_pack/helib_pack/lib");
+            println!("cargo:rustc-link-lib=dylib=helib");
+            println!("cargo:rustc-link-lib=dylib=ntl");
+            println!("cargo:rustc-link-lib=dylib=gmp");
+
+            println!("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN/helib_wrapper/build");
+            println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/helib_pack/helib_pack/lib");
+        }
 
         // OpenFHE
-        println!("cargo:rustc-link-search=native=openfhe_cpp_wrapper/build");
-        println!("cargo:rustc-link-lib=dylib=openfhe_wrapper");
-        println!("cargo:rustc-link-lib=dylib=OPENFHEcore");
-        println!("cargo:rustc-link-lib=dylib=OPENFHEpke");
-        println!("cargo:rustc-link-lib=dylib=OPENFHEbinfhe");
-        
+        if openfhe {
+            println!("cargo:rustc-link-search=native=openfhe_cpp_wrapper/build");
+            println!("cargo:rustc-link-lib=dylib=openfhe_wrapper");
+            println!("cargo:rustc-link-lib=dylib=OPENFHEcore");
+            println!("cargo:rustc-link-lib=dylib=OPENFHEpke");
+            println!("cargo:rustc-link-lib=dylib=OPENFHEbinfhe");
+        }
+
         // Runtime paths
-        println!("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN/cpp_wrapper/build");
-        println!("cargo:rustc-link-arg=-Wl,-rpath,$ORIGIN/helib_wrapper/build");
         println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/lib");
-        println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/helib_pack/helib_pack/lib");
     } else {
         // ============================================
         // Linux Configuration (Docker)
         // ============================================
-        // All wrapper libraries are in /app/lib
+        // All wrapper libraries are in /app/lib, system HE libraries in /usr/local/lib
         println!("cargo:rustc-link-search=native=/app/lib");
-        println!("cargo:rustc-link-lib=seal_wrapper");
-        println!("cargo:rustc-link-lib=helib_wrapper");
-        println!("cargo:rustc-link-lib=openfhe_wrapper");
-        
-        // System HE libraries
         println!("cargo:rustc-link-search=native=/usr/local/lib");
-        println!("cargo:rustc-link-lib=seal-4.1");
-        println!("cargo:rustc-link-lib=OPENFHEcore");
-        println!("cargo:rustc-link-lib=OPENFHEpke");
-        println!("cargo:rustc-link-lib=OPENFHEbinfhe");
-        
+
+        // SEAL
+        if seal {
+            println!("cargo:rustc-link-lib=seal_wrapper");
+            println!("cargo:rustc-link-lib=seal-4.1");
+        }
+
+        // OpenFHE
+        if openfhe {
+            println!("cargo:rustc-link-lib=openfhe_wrapper");
+            println!("cargo:rustc-link-lib=OPENFHEcore");
+            println!("cargo:rustc-link-lib=OPENFHEpke");
+            println!("cargo:rustc-link-lib=OPENFHEbinfhe");
+        }
+
         // HElib libraries
-        println!("cargo:rustc-link-search=native=/usr/local/helib_pack/helib_pack/lib");
-        println!("cargo:rustc-link-lib=helib");
-        println!("cargo:rustc-link-lib=ntl");
-        println!("cargo:rustc-link-lib=gmp");
-        
+        if helib {
+            println!("cargo:rustc-link-lib=helib_wrapper");
+            println!("cargo:rustc-link-search=native=/usr/local/helib_pack/helib_pack/lib");
+            println!("cargo:rustc-link-lib=helib");
+            println!("cargo:rustc-link-lib=ntl");
+            println!("cargo:rustc-link-lib=gmp");
+            println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/helib_pack/helib_pack/lib");
+        }
+
         // Runtime paths (rpath) - simplified for runtime image
         println!("cargo:rustc-link-arg=-Wl,-rpath,/app/lib");
         println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/lib");
-        println!("cargo:rustc-link-arg=-Wl,-rpath,/usr/local/helib_pack/helib_pack/lib");
     }
-    
+
     // ============================================
     // Common Dependencies
     // ============================================
-    println!("cargo:rustc-link-lib=stdc++");
-    println!("cargo:rustc-link-lib=pthread");
-    println!("cargo:rustc-link-lib=gomp");  // GNU OpenMP (needed for OpenFHE)
-   
+    // Only needed when at least one C++ backend is linked in
+    if seal || helib || openfhe {
+        println!("cargo:rustc-link-lib=stdc++");
+        println!("cargo:rustc-link-lib=pthread");
+    }
+    if openfhe {
+        println!("cargo:rustc-link-lib=gomp");  // GNU OpenMP (needed for OpenFHE)
+    }
+
     // ============================================
     // Rerun Triggers
     // ============================================
@@ -78,4 +110,6 @@ fn main() {
     println!("cargo:rerun-if-changed=cpp_wrapper/include/seal_wrapper.h");
     println!("cargo:rerun-if-changed=helib_wrapper/src/helib_wrapper.cpp");
     println!("cargo:rerun-if-changed=helib_wrapper/include/helib_wrapper.h");
-}
\ No newline at end of file
+    println!("cargo:rerun-if-changed=openfhe_cpp_wrapper/src/openfhe_wrapper.cpp");
+    println!("cargo:rerun-if-changed=openfhe_cpp_wrapper/include/openfhe_wrapper.h");
+}
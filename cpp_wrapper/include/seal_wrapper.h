@@ -35,8 +35,65 @@ SEALContextWrapper* seal_create_context(
     size_t coeff_modulus_size,
     uint64_t plain_modulus
 );
+// Same, but SEAL picks a batching-friendly prime of plain_modulus_bits bits
+// (read it back with seal_context_plain_modulus). NULL if none exists.
+SEALContextWrapper* seal_create_batching_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus,
+    size_t coeff_modulus_size,
+    int plain_modulus_bits
+);
+// BFV context whose coefficient modulus is exactly these primes (instead of
+// primes SEAL generates from bit sizes). NULL if SEAL rejects them.
+SEALContextWrapper* seal_create_context_from_primes(
+    uint64_t poly_modulus_degree,
+    const uint64_t* primes,
+    size_t primes_size,
+    uint64_t plain_modulus
+);
+// BFV context holding only a public key loaded from public_key (as saved by
+// seal_public_key_save): no secret key, so no decryptor or evaluation keys can
+// be made from it. NULL if the key isn't valid for these parameters.
+SEALContextWrapper* seal_create_public_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus,
+    size_t coeff_modulus_size,
+    uint64_t plain_modulus,
+    const uint8_t* public_key,
+    size_t public_key_size
+);
+// 1 if ctx generated its own keys, 0 for a public context
+int seal_context_has_secret_key(SEALContextWrapper* ctx);
 void seal_destroy_context(SEALContextWrapper* ctx);
 
+// Plaintext modulus of a BFV context (0 for CKKS)
+uint64_t seal_context_plain_modulus(SEALContextWrapper* ctx);
+
+// Polynomial modulus degree N of a context
+uint64_t seal_context_poly_modulus_degree(SEALContextWrapper* ctx);
+
+// SEAL's parms_id (a 256-bit hash of scheme, degree, coeff and plain moduli)
+// of the full parameter set, written to out[0..4]
+void seal_context_params_id(SEALContextWrapper* ctx, uint64_t* out);
+
+// CKKS context (approximate arithmetic on real numbers, no plain modulus)
+SEALContextWrapper* seal_create_ckks_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus,
+    size_t coeff_modulus_size
+);
+
+// Any scheme (0 = BFV, 1 = CKKS) at 128, 192 or 256-bit security; nullptr if
+// SEAL rejects the combination. plain_modulus is ignored for CKKS.
+SEALContextWrapper* seal_create_context_with_security(
+    uint8_t scheme,
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus,
+    size_t coeff_modulus_size,
+    uint64_t plain_modulus,
+    int security_bits
+);
+
 // ============================================
 // Encryption/Decryption Setup
 // ============================================
@@ -67,6 +124,12 @@ SEALPlaintext* seal_create_plaintext(const char* hex_string);
 void seal_destroy_plaintext(SEALPlaintext* plain);
 const char* seal_plaintext_to_string(SEALPlaintext* plain);
 
+// Binary serialization (SEAL's own format). save_size is an upper bound for the
+// output buffer; save returns the number of bytes written (0 on failure).
+size_t seal_plaintext_save_size(SEALPlaintext* plain);
+size_t seal_plaintext_save(SEALPlaintext* plain, uint8_t* output, size_t output_size);
+SEALPlaintext* seal_plaintext_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size);
+
 // ============================================
 // Encryption Operations
 // ============================================
@@ -113,6 +176,10 @@ typedef struct SEALGaloisKeys SEALGaloisKeys;
 SEALGaloisKeys* seal_generate_galois_keys(SEALContextWrapper* ctx);
 void seal_destroy_galois_keys(SEALGaloisKeys* keys);
 
+// parms_id the keys were generated for, written to out[0..4] (compare with
+// seal_context_params_id)
+void seal_galois_keys_params_id(SEALGaloisKeys* keys, uint64_t* out);
+
 // ============================================
 // Rotation Operations
 // ============================================
@@ -131,13 +198,32 @@ SEALPlaintext* seal_decrypt(
     SEALCiphertext* ciphertext
 );
 
+// BFV noise budget in bits (0 = exhausted), -1 on error
+int seal_invariant_noise_budget(
+    SEALDecryptor* decryptor,
+    SEALCiphertext* ciphertext
+);
+
 // ============================================
 // Ciphertext Inspection
 // ============================================
 size_t seal_ciphertext_size(SEALCiphertext* cipher);
 uint64_t seal_ciphertext_coeff_count(SEALCiphertext* cipher);
+// Primes left in the ciphertext's coefficient modulus
+size_t seal_ciphertext_coeff_modulus_size(SEALCiphertext* cipher);
 size_t seal_ciphertext_byte_count(SEALCiphertext* cipher);
 const char* seal_ciphertext_info(SEALCiphertext* cipher);
+// CKKS fixed-point scale (1.0 for BFV ciphertexts)
+double seal_ciphertext_scale(SEALCiphertext* cipher);
+// Rough multiplications left, without the secret key (-1 if not from this context)
+int seal_ciphertext_estimated_remaining_levels(SEALContextWrapper* ctx, SEALCiphertext* cipher);
+// Level in the modulus chain, 0 = last (-1 if not from this context)
+int seal_ciphertext_chain_index(SEALContextWrapper* ctx, SEALCiphertext* cipher);
+
+// Binary serialization, same conventions as the plaintext functions. load never
+// throws across the C boundary: malformed or mismatched data returns NULL.
+size_t seal_ciphertext_save(SEALCiphertext* cipher, uint8_t* output, size_t output_size);
+SEALCiphertext* seal_ciphertext_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size);
 
 // ============================================
 // Homomorphic Operations
@@ -152,12 +238,280 @@ SEALCiphertext* seal_add(
     SEALCiphertext* b
 );
 
+SEALCiphertext* seal_sub(
+    SEALContextWrapper* ctx,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+);
+
 SEALCiphertext* seal_multiply(
     SEALContextWrapper* ctx,
     SEALCiphertext* a,
     SEALCiphertext* b
 );
 
+// ============================================
+// Evaluator (reusable, one per context)
+// ============================================
+typedef struct SEALEvaluator SEALEvaluator;
+
+SEALEvaluator* seal_create_evaluator(SEALContextWrapper* ctx);
+void seal_destroy_evaluator(SEALEvaluator* eval);
+
+SEALCiphertext* seal_evaluator_add(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+);
+
+SEALCiphertext* seal_evaluator_sub(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+);
+
+SEALCiphertext* seal_evaluator_multiply(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+);
+
+// BFV only: mod-switches both operands as low as they go without losing
+// (estimated) noise budget, then multiplies. NULL for CKKS contexts.
+SEALCiphertext* seal_evaluator_multiply_optimized(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+);
+
+SEALCiphertext* seal_evaluator_multiply_plain(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALPlaintext* plain
+);
+
+SEALCiphertext* seal_evaluator_add_plain(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALPlaintext* plain
+);
+
+// Add one constant to every slot. The BFV variant batch encodes the scalar into
+// all slots; the CKKS one encodes it at the ciphertext's level and scale.
+// Returns NULL for the wrong scheme.
+SEALCiphertext* seal_evaluator_add_plain_scalar(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int64_t scalar
+);
+
+SEALCiphertext* seal_evaluator_add_plain_scalar_ckks(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    double scalar
+);
+
+// Zero every slot but `index` by multiplying with a one-hot mask. CKKS rescales
+// the product, so the result is one level down at the input's scale. Returns
+// NULL for an index past the slots or a CKKS ciphertext at the last level.
+SEALCiphertext* seal_evaluator_mask_slot(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    size_t index
+);
+
+SEALCiphertext* seal_evaluator_rotate_rows(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int steps,
+    SEALGaloisKeys* galois_keys
+);
+
+SEALCiphertext* seal_evaluator_rotate_columns(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALGaloisKeys* galois_keys
+);
+
+// CKKS: rotate all slots left by `steps` (negative rotates right)
+SEALCiphertext* seal_evaluator_rotate_vector(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int steps,
+    SEALGaloisKeys* galois_keys
+);
+
+// ============================================
+// Keys and Key Switching
+// ============================================
+// Lets a ciphertext encrypted under one context's key be re-expressed
+// under another context's key (same encryption parameters)
+typedef struct SEALSecretKey SEALSecretKey;
+typedef struct SEALPublicKey SEALPublicKey;
+typedef struct SEALKSwitchKeys SEALKSwitchKeys;
+
+SEALSecretKey* seal_get_secret_key(SEALContextWrapper* ctx);
+void seal_destroy_secret_key(SEALSecretKey* key);
+
+SEALPublicKey* seal_get_public_key(SEALContextWrapper* ctx);
+void seal_destroy_public_key(SEALPublicKey* key);
+
+// Binary serialization of a public key (byte_count is an upper bound)
+size_t seal_public_key_byte_count(SEALPublicKey* key);
+size_t seal_public_key_save(SEALPublicKey* key, uint8_t* output, size_t output_size);
+
+// Serialized sizes of the other keys (upper bounds, as for public keys)
+size_t seal_secret_key_byte_count(SEALSecretKey* key);
+size_t seal_relin_keys_byte_count(SEALRelinKeys* keys);
+size_t seal_galois_keys_byte_count(SEALGaloisKeys* keys);
+
+// Binary serialization of evaluation keys; load returns NULL for anything that
+// isn't a valid key for ctx's parameters
+size_t seal_relin_keys_save(SEALRelinKeys* keys, uint8_t* output, size_t output_size);
+SEALRelinKeys* seal_relin_keys_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size);
+size_t seal_galois_keys_save(SEALGaloisKeys* keys, uint8_t* output, size_t output_size);
+SEALGaloisKeys* seal_galois_keys_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size);
+
+// A fresh key pair for ctx's parameters, drawn from a PRNG seeded with `seed`.
+// The same seed always gives the same keys: for reproducible tests only.
+typedef struct SEALKeyGenerator SEALKeyGenerator;
+SEALKeyGenerator* seal_keygen_create_seeded(SEALContextWrapper* ctx, const uint8_t* seed, size_t seed_size);
+SEALSecretKey* seal_keygen_secret_key(SEALKeyGenerator* keygen);
+SEALPublicKey* seal_keygen_public_key(SEALKeyGenerator* keygen);
+void seal_keygen_destroy(SEALKeyGenerator* keygen);
+
+// Encrypts a random value under `pk` and decrypts it with `sk`.
+// Returns 1 if it comes back unchanged (the keys are a pair), 0 if not,
+// -1 if either key was made for different parameters than ctx (or on error).
+int seal_verify_key_pair(SEALContextWrapper* ctx, SEALPublicKey* pk, SEALSecretKey* sk);
+
+// Returns NULL if the two keys use different encryption parameters
+SEALKSwitchKeys* seal_generate_kswitch_keys(
+    SEALSecretKey* from,
+    SEALPublicKey* to
+);
+void seal_destroy_kswitch_keys(SEALKSwitchKeys* keys);
+
+// Only works on size-2 ciphertexts (relinearize after multiplying)
+SEALCiphertext* seal_evaluator_key_switch(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALKSwitchKeys* switch_keys
+);
+
+// ============================================
+// CKKS Encoder (for vectors of real numbers)
+// ============================================
+typedef struct SEALCKKSEncoder SEALCKKSEncoder;
+
+SEALCKKSEncoder* seal_create_ckks_encoder(SEALContextWrapper* ctx, double scale);
+void seal_destroy_ckks_encoder(SEALCKKSEncoder* encoder);
+
+SEALPlaintext* seal_ckks_encode(
+    SEALCKKSEncoder* encoder,
+    const double* values,
+    size_t values_size
+);
+
+void seal_ckks_decode(
+    SEALCKKSEncoder* encoder,
+    SEALPlaintext* plain,
+    double* output,
+    size_t* output_size
+);
+
+// Complex values as interleaved (re, im) pairs; sizes count complex numbers
+SEALPlaintext* seal_ckks_encode_complex(
+    SEALCKKSEncoder* encoder,
+    const double* values,
+    size_t count
+);
+
+void seal_ckks_decode_complex(
+    SEALCKKSEncoder* encoder,
+    SEALPlaintext* plain,
+    double* output,
+    size_t* output_size
+);
+
+size_t seal_ckks_slot_count(SEALCKKSEncoder* encoder);
+
+// ============================================
+// Relinearization Keys
+// ============================================
+typedef struct SEALRelinKeys SEALRelinKeys;
+
+SEALRelinKeys* seal_generate_relin_keys(SEALContextWrapper* ctx);
+void seal_destroy_relin_keys(SEALRelinKeys* keys);
+
+// As seal_galois_keys_params_id
+void seal_relin_keys_params_id(SEALRelinKeys* keys, uint64_t* out);
+
+// ============================================
+// CKKS Comparison
+// ============================================
+// ~1.0 where cipher > threshold, ~0.0 below. Uses 2 levels per iteration.
+// Returns NULL for non-CKKS contexts or when not enough levels are left.
+SEALCiphertext* seal_evaluator_compare_gt(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    double threshold,
+    uint32_t iterations,
+    SEALRelinKeys* relin_keys
+);
+
+// Approximate slot-wise max(a, b) for CKKS, |a - b| <= 1, with `iterations` steps of
+// a sqrt approximation. Uses 2 * iterations + 1 levels; the result has a's scale.
+// Returns NULL for non-CKKS contexts, mismatched scales or too few levels.
+SEALCiphertext* seal_evaluator_max_approx(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    uint32_t iterations,
+    SEALRelinKeys* relin_keys
+);
+
+SEALCiphertext* seal_evaluator_relinearize(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALRelinKeys* relin_keys
+);
+
+// CKKS only: divide by the last prime in the chain, dropping one level.
+// NULL for BFV contexts or ciphertexts already at the last level.
+SEALCiphertext* seal_evaluator_rescale_to_next(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher
+);
+
+// ============================================
+// CKKS Scale/Level Matching
+// ============================================
+// Bring both operands to the same level and scale before operating: rescale an
+// operand whose scale is far too large, mod-switch the higher level down, then
+// round away differences up to `max_scale_ratio`. multiply_auto also relinearizes
+// and rescales the product. Returns NULL if the scales can't be matched.
+SEALCiphertext* seal_evaluator_add_auto(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    double max_scale_ratio
+);
+
+SEALCiphertext* seal_evaluator_multiply_auto(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    SEALRelinKeys* relin_keys,
+    double max_scale_ratio
+);
+
+// ============================================
+// Library Version
+// ============================================
+// SEAL version this wrapper was built against, e.g. "4.1.1" (static, don't free)
+const char* seal_version(void);
+
 #ifdef __cplusplus
 }
 #endif
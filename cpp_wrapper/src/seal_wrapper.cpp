@@ -1,8 +1,16 @@
 #include "../include/seal_wrapper.h"
 #include "seal/seal.h"
+#include "seal/util/rlwe.h"
+#include "seal/util/polyarithsmallmod.h"
+#include "seal/util/blake2.h"
+#include <algorithm>
 #include <memory>
 #include <stdexcept>
 #include <cstring>
+#include <cmath>
+#include <complex>
+#include <mutex>
+#include <random>
 
 using namespace seal;
 using namespace std;
@@ -14,7 +22,11 @@ using namespace std;
 // They make sure all the C++ objects stay alive and can be safely accessed from Rust without breaking memory.
 struct SEALContextWrapper {
     shared_ptr<seal::SEALContext> seal_context;
+    // Null for a public context (seal_create_public_context): no secret key
     shared_ptr<KeyGenerator> keygen;
+    // Contexts are shared between threads on the Rust side; the context and keys
+    // are read-only, but key generation goes through here
+    mutex keygen_mutex;
     PublicKey public_key;
     SecretKey secret_key;
 };
@@ -32,6 +44,7 @@ struct SEALDecryptor {
 // SEALCiphertext: Wrapper for encrypted data
 struct SEALCiphertext {
     Ciphertext ciphertext; // not pointer: Ciphertext has proper copy/move semantics
+    size_t depth = 0; // multiplications it has been through (for the keyless noise estimate)
 };
 
 // SEALPlaintext: Wrapper for plaintext data (before encryption)
@@ -49,9 +62,87 @@ struct SEALGaloisKeys {
     GaloisKeys keys; // not pointer: GaloisKeys has proper move semantics
 };
 
+// SEALSecretKey / SEALPublicKey: copies of a context's keys, kept together with
+// the SEAL context they were made for
+struct SEALSecretKey {
+    shared_ptr<seal::SEALContext> seal_context;
+    SecretKey key;
+};
+
+struct SEALPublicKey {
+    shared_ptr<seal::SEALContext> seal_context;
+    PublicKey key;
+};
+
+// SEALKeyGenerator: a key pair generated apart from any context wrapper
+// (seal_keygen_create_seeded), for the same SEAL context
+struct SEALKeyGenerator {
+    shared_ptr<seal::SEALContext> seal_context;
+    SecretKey secret_key;
+    PublicKey public_key;
+};
+
+// SEALKSwitchKeys: keys that move a ciphertext from one secret key to another.
+// Stored as RelinKeys so Evaluator::relinearize can apply them (see seal_evaluator_key_switch)
+struct SEALKSwitchKeys {
+    RelinKeys keys;
+};
+
+// SEALEvaluator: Wrapper for SEAL's Evaluator (reused across operations)
+struct SEALEvaluator {
+    shared_ptr<seal::SEALContext> seal_context; // needed for CKKS level/scale bookkeeping
+    unique_ptr<Evaluator> evaluator; // unique_ptr: One evaluator per context
+};
+
+// SEALCKKSEncoder: Wrapper for encoding vectors of real numbers (CKKS)
+struct SEALCKKSEncoder {
+    unique_ptr<CKKSEncoder> encoder;
+    double scale; // fixed-point scale used for every encode
+};
+
+// SEALRelinKeys: Wrapper for relinearization keys
+struct SEALRelinKeys {
+    RelinKeys keys;
+};
+
 // ============================================
 // Context Management Implementation
 // ============================================
+// Builds the SEAL context and keys for the given parameters (any scheme).
+// Returns nullptr if SEAL rejects the parameters.
+static SEALContextWrapper* make_context_wrapper(
+    const EncryptionParameters& parms,
+    sec_level_type security = sec_level_type::tc128
+) {
+    // Create SEAL context using these parameters
+    // (expand_mod_chain = true is SEAL's default)
+    auto seal_ctx = make_shared<seal::SEALContext>(parms, true, security);
+
+    // Check if the parameters are valid
+    if (!seal_ctx->parameters_set()) {
+        // Return null if context creation failed
+        return nullptr;
+    }
+
+    // Allocate memory for our wrapper struct
+    SEALContextWrapper* result = new SEALContextWrapper();
+
+    // Store the context and key generator in the wrapper
+    // The same generator is kept for galois/relin keys later on, so every key
+    // belongs to the same secret key (otherwise rotations decrypt to garbage)
+    result->seal_context = seal_ctx;
+    result->keygen = make_shared<KeyGenerator>(*seal_ctx);
+
+    // Create public key and store in wrapper
+    result->keygen->create_public_key(result->public_key);
+
+    // Store secret key in wrapper
+    result->secret_key = result->keygen->secret_key();
+
+    // Return pointer to this wrapper (so Rust can use it)
+    return result;
+}
+
 // This function creates and sets up the SEAL encryption context.
 // It defines the encryption parameters and generates public/secret keys.
 extern "C" SEALContextWrapper* seal_create_context(
@@ -88,43 +179,180 @@ extern "C" SEALContextWrapper* seal_create_context(
         // Set plaintext modulus
         parms.set_plain_modulus(plain_modulus_value);
         
-        // Create SEAL context using these parameters
-        auto seal_ctx = make_shared<seal::SEALContext>(parms);
-        
-        // Check if the parameters are valid
-        if (!seal_ctx->parameters_set()) {
-            // Return null if context creation failed
-            return nullptr;
+        return make_context_wrapper(parms);
+    } catch (const exception& e) {
+        // Error handling - could log error here
+        return nullptr;
+    }
+}
+
+// CKKS works on approximate real numbers, so there is no plain modulus.
+// Each prime after the first (except the last, "special" one) is one rescale / multiplication level.
+extern "C" SEALContextWrapper* seal_create_ckks_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus_bits,
+    size_t coeff_modulus_size
+) {
+    try {
+        EncryptionParameters parms(scheme_type::ckks);
+        parms.set_poly_modulus_degree(poly_modulus_degree);
+
+        vector<int> bit_sizes;
+        for (size_t i = 0; i < coeff_modulus_size; i++) {
+            bit_sizes.push_back(static_cast<int>(coeff_modulus_bits[i]));
         }
-        
-        // Generate keys (public + secret)
-        KeyGenerator keygen(*seal_ctx);
-        
-        // Allocate memory for our wrapper struct
-        SEALContextWrapper* result = new SEALContextWrapper();
+        parms.set_coeff_modulus(CoeffModulus::Create(poly_modulus_degree, bit_sizes));
 
-        // Store the context and key generator in the wrapper
-        result->seal_context = seal_ctx;
-        result->keygen = make_shared<KeyGenerator>(*seal_ctx);
+        return make_context_wrapper(parms);
+    } catch (const exception& e) {
+        return nullptr;
+    }
+}
 
-        // Create public key and store in wrapper
-        keygen.create_public_key(result->public_key);
+// Any scheme (0 = BFV, 1 = CKKS) at an explicit security level (128, 192 or 256 bits).
+// plain_modulus_value is ignored for CKKS.
+extern "C" SEALContextWrapper* seal_create_context_with_security(
+    uint8_t scheme,
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus_bits,
+    size_t coeff_modulus_size,
+    uint64_t plain_modulus_value,
+    int security_bits
+) {
+    try {
+        sec_level_type security;
+        switch (security_bits) {
+            case 128: security = sec_level_type::tc128; break;
+            case 192: security = sec_level_type::tc192; break;
+            case 256: security = sec_level_type::tc256; break;
+            default: return nullptr;
+        }
 
-        // Store secret key in wrapper
-        result->secret_key = keygen.secret_key();
-        
-        // Return pointer to this wrapper (so Rust can use it)
-        return result;
+        EncryptionParameters parms(scheme == 1 ? scheme_type::ckks : scheme_type::bfv);
+        parms.set_poly_modulus_degree(poly_modulus_degree);
+
+        vector<int> bit_sizes;
+        for (size_t i = 0; i < coeff_modulus_size; i++) {
+            bit_sizes.push_back(static_cast<int>(coeff_modulus_bits[i]));
+        }
+        parms.set_coeff_modulus(CoeffModulus::Create(poly_modulus_degree, bit_sizes));
+
+        if (scheme != 1) {
+            parms.set_plain_modulus(plain_modulus_value);
+        }
+
+        return make_context_wrapper(parms, security);
+    } catch (const exception& e) {
+        return nullptr;
+    }
+}
+
+// BFV context whose plain modulus is a plain_modulus_bits-bit prime congruent
+// to 1 mod 2N, picked by SEAL so that batching (BatchEncoder) works
+extern "C" SEALContextWrapper* seal_create_batching_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus_bits,
+    size_t coeff_modulus_size,
+    int plain_modulus_bits
+) {
+    try {
+        Modulus plain_modulus = PlainModulus::Batching(poly_modulus_degree, plain_modulus_bits);
+        return seal_create_context(poly_modulus_degree, coeff_modulus_bits, coeff_modulus_size, plain_modulus.value());
+    } catch (const exception& e) {
+        // No prime of that size fits (too few bits for the degree)
+        return nullptr;
+    }
+}
+
+// Exact primes, for reproducing published parameters. SEAL checks each is
+// NTT-friendly and that they are distinct; the Rust side checks first so the
+// error can name the offending prime.
+extern "C" SEALContextWrapper* seal_create_context_from_primes(
+    uint64_t poly_modulus_degree,
+    const uint64_t* primes,
+    size_t primes_size,
+    uint64_t plain_modulus
+) {
+    if (!primes || primes_size == 0) return nullptr;
+    try {
+        EncryptionParameters parms(scheme_type::bfv);
+        parms.set_poly_modulus_degree(poly_modulus_degree);
+
+        vector<Modulus> coeff_modulus;
+        for (size_t i = 0; i < primes_size; i++) {
+            coeff_modulus.emplace_back(primes[i]);
+        }
+        parms.set_coeff_modulus(coeff_modulus);
+        parms.set_plain_modulus(plain_modulus);
+
+        return make_context_wrapper(parms);
     } catch (const exception& e) {
-        // Error handling - could log error here
         return nullptr;
     }
 }
+
+// BFV context around a public key loaded from `public_key` (written by
+// seal_public_key_save under the same parameters), with no KeyGenerator and so
+// no secret key. It can encrypt and evaluate; decryptors, the secret key and
+// evaluation keys can't be made from it (NULL). load() checks the key is valid
+// for these parameters and throws otherwise.
+extern "C" SEALContextWrapper* seal_create_public_context(
+    uint64_t poly_modulus_degree,
+    const uint64_t* coeff_modulus_bits,
+    size_t coeff_modulus_size,
+    uint64_t plain_modulus_value,
+    const uint8_t* public_key,
+    size_t public_key_size
+) {
+    if (!public_key) return nullptr;
+    try {
+        EncryptionParameters parms(scheme_type::bfv);
+        parms.set_poly_modulus_degree(poly_modulus_degree);
+
+        vector<int> bit_sizes;
+        for (size_t i = 0; i < coeff_modulus_size; i++) {
+            bit_sizes.push_back(static_cast<int>(coeff_modulus_bits[i]));
+        }
+        parms.set_coeff_modulus(CoeffModulus::Create(poly_modulus_degree, bit_sizes));
+        parms.set_plain_modulus(plain_modulus_value);
+
+        auto seal_ctx = make_shared<seal::SEALContext>(parms, true, sec_level_type::tc128);
+        if (!seal_ctx->parameters_set()) return nullptr;
+
+        auto result = make_unique<SEALContextWrapper>();
+        result->seal_context = seal_ctx;
+        result->public_key.load(*seal_ctx, reinterpret_cast<const seal_byte*>(public_key), public_key_size);
+        return result.release();
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" int seal_context_has_secret_key(SEALContextWrapper* ctx) {
+    return ctx && ctx->keygen ? 1 : 0;
+}
+
 // Frees the memory used by the context
 extern "C" void seal_destroy_context(SEALContextWrapper* ctx) {
     if (ctx) delete ctx;
 }
 
+extern "C" uint64_t seal_context_plain_modulus(SEALContextWrapper* ctx) {
+    if (!ctx) return 0;
+    return ctx->seal_context->key_context_data()->parms().plain_modulus().value();
+}
+
+extern "C" uint64_t seal_context_poly_modulus_degree(SEALContextWrapper* ctx) {
+    if (!ctx) return 0;
+    return ctx->seal_context->key_context_data()->parms().poly_modulus_degree();
+}
+
+extern "C" void seal_context_params_id(SEALContextWrapper* ctx, uint64_t* out) {
+    if (!ctx || !out) return;
+    const parms_id_type& id = ctx->seal_context->key_parms_id();
+    std::copy(id.begin(), id.end(), out);
+}
+
 // ============================================
 // Encryptor Implementation
 // ============================================
@@ -168,7 +396,7 @@ extern "C" SEALDecryptor* seal_create_decryptor(
     size_t secret_key_size
 ) {
     try {
-        if (!ctx) return nullptr;
+        if (!ctx || !ctx->keygen) return nullptr;
         // Create decryptor struct
         SEALDecryptor* dec = new SEALDecryptor();
         // Create SEAL decryptor using context and secret key
@@ -282,10 +510,11 @@ extern "C" size_t seal_get_slot_count(SEALBatchEncoder* encoder) {
 // ============================================
 extern "C" SEALGaloisKeys* seal_generate_galois_keys(SEALContextWrapper* ctx) {
     try {
-        if (!ctx) return nullptr;
+        if (!ctx || !ctx->keygen) return nullptr;
         
         SEALGaloisKeys* gal_keys = new SEALGaloisKeys();
         // Create galois keys and move into our struct
+        lock_guard<mutex> lock(ctx->keygen_mutex);
         ctx->keygen->create_galois_keys(gal_keys->keys);
         
         return gal_keys;
@@ -298,6 +527,12 @@ extern "C" void seal_destroy_galois_keys(SEALGaloisKeys* keys) {
     if (keys) delete keys;
 }
 
+extern "C" void seal_galois_keys_params_id(SEALGaloisKeys* keys, uint64_t* out) {
+    if (!keys || !out) return;
+    const parms_id_type& id = keys->keys.parms_id();
+    std::copy(id.begin(), id.end(), out);
+}
+
 extern "C" SEALCiphertext* seal_rotate_rows(
     SEALContextWrapper* ctx,
     SEALCiphertext* cipher,
@@ -316,6 +551,7 @@ extern "C" SEALCiphertext* seal_rotate_rows(
             galois_keys->keys,
             result->ciphertext
         );
+        result->depth = cipher->depth;
         
         return result;
     } catch (...) {
@@ -359,6 +595,38 @@ extern "C" const char* seal_plaintext_to_string(SEALPlaintext* plain) {
     return result;
 }
 
+extern "C" size_t seal_plaintext_save_size(SEALPlaintext* plain) {
+    if (!plain) return 0;
+    try {
+        return static_cast<size_t>(plain->plaintext.save_size());
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" size_t seal_plaintext_save(SEALPlaintext* plain, uint8_t* output, size_t output_size) {
+    if (!plain || !output) return 0;
+    try {
+        return static_cast<size_t>(
+            plain->plaintext.save(reinterpret_cast<seal_byte*>(output), output_size));
+    } catch (...) {
+        return 0;
+    }
+}
+
+// load() validates the data against the context, so a plaintext saved under
+// different parameters (or random bytes) is rejected here
+extern "C" SEALPlaintext* seal_plaintext_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size) {
+    if (!ctx || !data) return nullptr;
+    try {
+        auto plain = make_unique<SEALPlaintext>();
+        plain->plaintext.load(*ctx->seal_context, reinterpret_cast<const seal_byte*>(data), data_size);
+        return plain.release();
+    } catch (...) {
+        return nullptr;
+    }
+}
+
 // ============================================
 // Encryption Implementation
 // ============================================
@@ -403,6 +671,11 @@ extern "C" uint64_t seal_ciphertext_coeff_count(SEALCiphertext* cipher) {
     return cipher->ciphertext.poly_modulus_degree();
 }
 
+extern "C" size_t seal_ciphertext_coeff_modulus_size(SEALCiphertext* cipher) {
+    if (!cipher) return 0;
+    return cipher->ciphertext.coeff_modulus_size();
+}
+
 extern "C" size_t seal_ciphertext_byte_count(SEALCiphertext* cipher) {
     if (!cipher) return 0;
     return cipher->ciphertext.save_size();
@@ -427,6 +700,91 @@ extern "C" const char* seal_ciphertext_info(SEALCiphertext* cipher) {
     }
 }
 
+extern "C" double seal_ciphertext_scale(SEALCiphertext* cipher) {
+    if (!cipher) return 0.0;
+    return cipher->ciphertext.scale();
+}
+
+// Keyless BFV noise model: a fresh ciphertext has about log2(q) - log2(t) - log2(N)
+// bits of noise budget, and each multiplication costs about log2(t) + log2(N).
+static double bfv_bits_per_multiply(const seal::SEALContext::ContextData& data) {
+    return log2(static_cast<double>(data.parms().plain_modulus().value()))
+        + log2(static_cast<double>(data.parms().poly_modulus_degree()));
+}
+
+// Mod switching keeps the budget as long as the smaller modulus can still hold it
+// (multiply_optimized only switches that far), so depth counts multiplications
+// from the top level: the budget is what `depth` multiplications leave at the top
+// level, capped by what the current level can hold at all.
+static double bfv_budget_bits(const seal::SEALContext& context, const SEALCiphertext& cipher) {
+    auto data = context.get_context_data(cipher.ciphertext.parms_id());
+    double per_multiply = bfv_bits_per_multiply(*data);
+    double top_bits = context.first_context_data()->total_coeff_modulus_bit_count();
+    double here_bits = data->total_coeff_modulus_bit_count();
+    return min(top_bits - (cipher.depth + 1) * per_multiply, here_bits - per_multiply);
+}
+
+// Keyless estimate of how many more multiplications a ciphertext can take.
+// CKKS: primes left in its modulus chain, less a product not yet rescaled.
+// BFV: the noise budget its modulus allows (roughly log2(q/t) minus log2(n) of
+// fresh noise), at about log2(t) + log2(n) bits per multiplication so far.
+extern "C" int seal_ciphertext_estimated_remaining_levels(SEALContextWrapper* ctx, SEALCiphertext* cipher) {
+    if (!ctx || !cipher) return -1;
+    try {
+        const seal::SEALContext& context = *ctx->seal_context;
+        auto data = context.get_context_data(cipher->ciphertext.parms_id());
+        if (!data) return -1;
+        long depth = static_cast<long>(cipher->depth);
+
+        if (data->parms().scheme() == scheme_type::ckks) {
+            long chain_index = static_cast<long>(data->chain_index());
+            long rescales = static_cast<long>(context.first_context_data()->chain_index()) - chain_index;
+            long pending = max(0L, depth - rescales);
+            return static_cast<int>(max(0L, chain_index - pending));
+        }
+
+        long levels = static_cast<long>(floor(bfv_budget_bits(context, *cipher) / bfv_bits_per_multiply(*data)));
+        return static_cast<int>(max(0L, levels));
+    } catch (...) {
+        return -1;
+    }
+}
+
+// Index of the ciphertext's level in the modulus chain (0 = last level)
+extern "C" int seal_ciphertext_chain_index(SEALContextWrapper* ctx, SEALCiphertext* cipher) {
+    if (!ctx || !cipher) return -1;
+    try {
+        auto data = ctx->seal_context->get_context_data(cipher->ciphertext.parms_id());
+        return data ? static_cast<int>(data->chain_index()) : -1;
+    } catch (...) {
+        return -1;
+    }
+}
+
+// The buffer size to pass here is seal_ciphertext_byte_count()
+extern "C" size_t seal_ciphertext_save(SEALCiphertext* cipher, uint8_t* output, size_t output_size) {
+    if (!cipher || !output) return 0;
+    try {
+        return static_cast<size_t>(
+            cipher->ciphertext.save(reinterpret_cast<seal_byte*>(output), output_size));
+    } catch (...) {
+        return 0;
+    }
+}
+
+// Untrusted input: load() checks the header, sizes and that every coefficient is
+// reduced modulo the context's primes, and throws on anything malformed
+extern "C" SEALCiphertext* seal_ciphertext_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size) {
+    if (!ctx || !data) return nullptr;
+    try {
+        auto cipher = make_unique<SEALCiphertext>();
+        cipher->ciphertext.load(*ctx->seal_context, reinterpret_cast<const seal_byte*>(data), data_size);
+        return cipher.release();
+    } catch (...) {
+        return nullptr;
+    }
+}
+
 // ============================================
 // Decryption Implementation
 // ============================================
@@ -454,6 +812,20 @@ extern "C" SEALPlaintext* seal_decrypt(
     }
 }
 
+// BFV only: how many bits of noise headroom are left (0 = can no longer decrypt correctly)
+// Returns -1 on error (e.g. a CKKS ciphertext)
+extern "C" int seal_invariant_noise_budget(
+    SEALDecryptor* decryptor,
+    SEALCiphertext* ciphertext
+) {
+    try {
+        if (!decryptor || !ciphertext) return -1;
+        return decryptor->decryptor->invariant_noise_budget(ciphertext->ciphertext);
+    } catch (...) {
+        return -1;
+    }
+}
+
 // ============================================
 // Homomorphic Operations
 // ============================================
@@ -478,6 +850,33 @@ extern "C" SEALCiphertext* seal_add(
             b->ciphertext,
             result->ciphertext
         );
+        result->depth = max(a->depth, b->depth);
+        
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Subtract two encrypted numbers without decrypting (a - b)
+extern "C" SEALCiphertext* seal_sub(
+    SEALContextWrapper* ctx,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+) {
+    try {
+        if (!ctx || !a || !b) return nullptr;
+        // Create evaluator for encrypted math
+        Evaluator evaluator(*ctx->seal_context);
+        // Create ciphertext for result
+        SEALCiphertext* result = new SEALCiphertext();
+        // Perform encrypted subtraction
+        evaluator.sub(
+            a->ciphertext,
+            b->ciphertext,
+            result->ciphertext
+        );
+        result->depth = max(a->depth, b->depth);
         
         return result;
     } catch (...) {
@@ -503,9 +902,1155 @@ extern "C" SEALCiphertext* seal_multiply(
             b->ciphertext,
             result->ciphertext
         );
+        result->depth = max(a->depth, b->depth) + 1;
         
         return result;
     } catch (...) {
         return nullptr;
     }
-}
\ No newline at end of file
+}
+// ============================================
+// Evaluator
+// ============================================
+// Unlike seal_add/seal_multiply above, this keeps one Evaluator alive
+// instead of creating a new one for every operation.
+extern "C" SEALEvaluator* seal_create_evaluator(SEALContextWrapper* ctx) {
+    try {
+        if (!ctx) return nullptr;
+
+        SEALEvaluator* eval = new SEALEvaluator();
+        eval->seal_context = ctx->seal_context;
+        eval->evaluator = make_unique<Evaluator>(*ctx->seal_context);
+
+        return eval;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_evaluator(SEALEvaluator* eval) {
+    if (eval) delete eval;
+}
+
+extern "C" SEALCiphertext* seal_evaluator_add(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+) {
+    try {
+        if (!eval || !a || !b) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->add(a->ciphertext, b->ciphertext, result->ciphertext);
+        result->depth = max(a->depth, b->depth);
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALCiphertext* seal_evaluator_sub(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+) {
+    try {
+        if (!eval || !a || !b) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->sub(a->ciphertext, b->ciphertext, result->ciphertext);
+        result->depth = max(a->depth, b->depth);
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALCiphertext* seal_evaluator_multiply(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+) {
+    try {
+        if (!eval || !a || !b) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->multiply(a->ciphertext, b->ciphertext, result->ciphertext);
+        result->depth = max(a->depth, b->depth) + 1;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// BFV multiply at the lowest level both operands reach without losing noise budget
+// (per the keyless model above). Fewer primes make the multiplication cheaper.
+extern "C" SEALCiphertext* seal_evaluator_multiply_optimized(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b
+) {
+    try {
+        if (!eval || !a || !b) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::bfv) return nullptr;
+
+        auto free_level = [&](const SEALCiphertext& cipher) {
+            auto data = context.get_context_data(cipher.ciphertext.parms_id());
+            double budget = bfv_budget_bits(context, cipher);
+            double per_multiply = bfv_bits_per_multiply(*data);
+            while (data->next_context_data()
+                && data->next_context_data()->total_coeff_modulus_bit_count() - per_multiply >= budget) {
+                data = data->next_context_data();
+            }
+            return data->chain_index();
+        };
+        auto level = [&](const SEALCiphertext& cipher) {
+            return context.get_context_data(cipher.ciphertext.parms_id())->chain_index();
+        };
+
+        // Both operands meet at one level: the higher of their free levels, but
+        // never above the lower operand
+        size_t target = min(max(free_level(*a), free_level(*b)), min(level(*a), level(*b)));
+        auto target_data = context.first_context_data();
+        while (target_data->chain_index() > target) {
+            target_data = target_data->next_context_data();
+        }
+
+        Ciphertext x = a->ciphertext;
+        Ciphertext y = b->ciphertext;
+        eval->evaluator->mod_switch_to_inplace(x, target_data->parms_id());
+        eval->evaluator->mod_switch_to_inplace(y, target_data->parms_id());
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->multiply(x, y, result->ciphertext);
+        result->depth = max(a->depth, b->depth) + 1;
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Ciphertext x plaintext (e.g. encrypted features x plain model weights)
+// Cheaper than ciphertext x ciphertext and does not grow the ciphertext size
+extern "C" SEALCiphertext* seal_evaluator_multiply_plain(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALPlaintext* plain
+) {
+    try {
+        if (!eval || !cipher || !plain) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->multiply_plain(cipher->ciphertext, plain->plaintext, result->ciphertext);
+        result->depth = cipher->depth + 1;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Ciphertext + plaintext (e.g. adding a plain bias term)
+// For CKKS the plaintext must be at the ciphertext's scale and level
+extern "C" SEALCiphertext* seal_evaluator_add_plain(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALPlaintext* plain
+) {
+    try {
+        if (!eval || !cipher || !plain) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->add_plain(cipher->ciphertext, plain->plaintext, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Bias-style constant in every batching slot
+extern "C" SEALCiphertext* seal_evaluator_add_plain_scalar(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int64_t scalar
+) {
+    try {
+        if (!eval || !cipher) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::bfv) return nullptr;
+
+        BatchEncoder encoder(context);
+        vector<int64_t> values(encoder.slot_count(), scalar);
+        Plaintext plain;
+        encoder.encode(values, plain);
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->add_plain(cipher->ciphertext, plain, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Encoded at the ciphertext's own parms_id and scale, so this works on
+// products and rescaled ciphertexts too (add_plain would need a matching plaintext)
+extern "C" SEALCiphertext* seal_evaluator_add_plain_scalar_ckks(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    double scalar
+) {
+    try {
+        if (!eval || !cipher) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+
+        CKKSEncoder encoder(context);
+        Plaintext plain;
+        encoder.encode(scalar, cipher->ciphertext.parms_id(), cipher->ciphertext.scale(), plain);
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->add_plain(cipher->ciphertext, plain, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// One-hot mask multiply. The CKKS mask is encoded at the scale of the prime the
+// rescale drops, so the result lands back on the input's scale exactly.
+extern "C" SEALCiphertext* seal_evaluator_mask_slot(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    size_t index
+) {
+    try {
+        if (!eval || !cipher) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        Evaluator& evaluator = *eval->evaluator;
+        Plaintext mask;
+        SEALCiphertext* result = nullptr;
+
+        if (context.key_context_data()->parms().scheme() == scheme_type::ckks) {
+            auto cipher_data = context.get_context_data(cipher->ciphertext.parms_id());
+            if (!cipher_data || cipher_data->chain_index() == 0) return nullptr;
+
+            CKKSEncoder encoder(context);
+            if (index >= encoder.slot_count()) return nullptr;
+            vector<double> one_hot(encoder.slot_count(), 0.0);
+            one_hot[index] = 1.0;
+            double prime = static_cast<double>(cipher_data->parms().coeff_modulus().back().value());
+            encoder.encode(one_hot, cipher->ciphertext.parms_id(), prime, mask);
+
+            result = new SEALCiphertext();
+            evaluator.multiply_plain(cipher->ciphertext, mask, result->ciphertext);
+            evaluator.rescale_to_next_inplace(result->ciphertext);
+            result->ciphertext.scale() = cipher->ciphertext.scale();
+        } else {
+            BatchEncoder encoder(context);
+            if (index >= encoder.slot_count()) return nullptr;
+            vector<uint64_t> one_hot(encoder.slot_count(), 0);
+            one_hot[index] = 1;
+            encoder.encode(one_hot, mask);
+
+            result = new SEALCiphertext();
+            evaluator.multiply_plain(cipher->ciphertext, mask, result->ciphertext);
+        }
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Cyclic rotation inside each of the two batching rows
+extern "C" SEALCiphertext* seal_evaluator_rotate_rows(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int steps,
+    SEALGaloisKeys* galois_keys
+) {
+    try {
+        if (!eval || !cipher || !galois_keys) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->rotate_rows(cipher->ciphertext, steps, galois_keys->keys, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Swaps the two batching rows
+extern "C" SEALCiphertext* seal_evaluator_rotate_columns(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALGaloisKeys* galois_keys
+) {
+    try {
+        if (!eval || !cipher || !galois_keys) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->rotate_columns(cipher->ciphertext, galois_keys->keys, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Rotates all CKKS slots left by `steps` (negative rotates right)
+extern "C" SEALCiphertext* seal_evaluator_rotate_vector(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    int steps,
+    SEALGaloisKeys* galois_keys
+) {
+    try {
+        if (!eval || !cipher || !galois_keys) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->rotate_vector(cipher->ciphertext, steps, galois_keys->keys, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// ============================================
+// Keys and Key Switching
+// ============================================
+extern "C" SEALSecretKey* seal_get_secret_key(SEALContextWrapper* ctx) {
+    try {
+        if (!ctx || !ctx->keygen) return nullptr;
+
+        SEALSecretKey* sk = new SEALSecretKey();
+        sk->seal_context = ctx->seal_context;
+        sk->key = ctx->secret_key;
+
+        return sk;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_secret_key(SEALSecretKey* key) {
+    if (key) delete key;
+}
+
+extern "C" SEALPublicKey* seal_get_public_key(SEALContextWrapper* ctx) {
+    try {
+        if (!ctx) return nullptr;
+
+        SEALPublicKey* pk = new SEALPublicKey();
+        pk->seal_context = ctx->seal_context;
+        pk->key = ctx->public_key;
+
+        return pk;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_public_key(SEALPublicKey* key) {
+    if (key) delete key;
+}
+
+extern "C" size_t seal_public_key_byte_count(SEALPublicKey* key) {
+    if (!key) return 0;
+    try {
+        return static_cast<size_t>(key->key.save_size());
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" size_t seal_secret_key_byte_count(SEALSecretKey* key) {
+    if (!key) return 0;
+    try {
+        return static_cast<size_t>(key->key.save_size());
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" size_t seal_relin_keys_byte_count(SEALRelinKeys* keys) {
+    if (!keys) return 0;
+    try {
+        return static_cast<size_t>(keys->keys.save_size());
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" size_t seal_galois_keys_byte_count(SEALGaloisKeys* keys) {
+    if (!keys) return 0;
+    try {
+        return static_cast<size_t>(keys->keys.save_size());
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" size_t seal_public_key_save(SEALPublicKey* key, uint8_t* output, size_t output_size) {
+    if (!key || !output) return 0;
+    try {
+        return static_cast<size_t>(key->key.save(reinterpret_cast<seal_byte*>(output), output_size));
+    } catch (...) {
+        return 0;
+    }
+}
+
+// Evaluation keys travel like public keys: the buffer for save is the
+// *_byte_count, and load checks the keys are valid for ctx's parameters
+extern "C" size_t seal_relin_keys_save(SEALRelinKeys* keys, uint8_t* output, size_t output_size) {
+    if (!keys || !output) return 0;
+    try {
+        return static_cast<size_t>(keys->keys.save(reinterpret_cast<seal_byte*>(output), output_size));
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" SEALRelinKeys* seal_relin_keys_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size) {
+    if (!ctx || !data) return nullptr;
+    try {
+        auto keys = make_unique<SEALRelinKeys>();
+        keys->keys.load(*ctx->seal_context, reinterpret_cast<const seal_byte*>(data), data_size);
+        return keys.release();
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" size_t seal_galois_keys_save(SEALGaloisKeys* keys, uint8_t* output, size_t output_size) {
+    if (!keys || !output) return 0;
+    try {
+        return static_cast<size_t>(keys->keys.save(reinterpret_cast<seal_byte*>(output), output_size));
+    } catch (...) {
+        return 0;
+    }
+}
+
+extern "C" SEALGaloisKeys* seal_galois_keys_load(SEALContextWrapper* ctx, const uint8_t* data, size_t data_size) {
+    if (!ctx || !data) return nullptr;
+    try {
+        auto keys = make_unique<SEALGaloisKeys>();
+        keys->keys.load(*ctx->seal_context, reinterpret_cast<const seal_byte*>(data), data_size);
+        return keys.release();
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// SEAL draws every random value through the parameters' PRNG factory. A copy of
+// ctx's parameters with a fixed-seed Blake2xb factory makes key generation
+// deterministic; the random generator isn't part of parms_id, so the keys still
+// belong to ctx's parameters. Every PRNG the factory creates repeats the same
+// stream, which is fine for tests and insecure for anything else.
+extern "C" SEALKeyGenerator* seal_keygen_create_seeded(SEALContextWrapper* ctx, const uint8_t* seed, size_t seed_size) {
+    try {
+        if (!ctx || (!seed && seed_size > 0)) return nullptr;
+
+        // Any seed length: hash it to the 512-bit seed Blake2xb expects
+        prng_seed_type prng_seed{};
+        if (blake2b(prng_seed.data(), sizeof(prng_seed), seed, seed_size, nullptr, 0) != 0) {
+            return nullptr;
+        }
+
+        auto key_data = ctx->seal_context->key_context_data();
+        EncryptionParameters parms = key_data->parms();
+        parms.set_random_generator(make_shared<Blake2xbPRNGFactory>(prng_seed));
+        SEALContext seeded_context(parms, true, key_data->qualifiers().sec_level);
+        if (!seeded_context.parameters_set()) return nullptr;
+
+        KeyGenerator keygen(seeded_context);
+        SEALKeyGenerator* result = new SEALKeyGenerator();
+        result->seal_context = ctx->seal_context;
+        result->secret_key = keygen.secret_key();
+        keygen.create_public_key(result->public_key);
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALSecretKey* seal_keygen_secret_key(SEALKeyGenerator* keygen) {
+    try {
+        if (!keygen) return nullptr;
+
+        SEALSecretKey* sk = new SEALSecretKey();
+        sk->seal_context = keygen->seal_context;
+        sk->key = keygen->secret_key;
+        return sk;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALPublicKey* seal_keygen_public_key(SEALKeyGenerator* keygen) {
+    try {
+        if (!keygen) return nullptr;
+
+        SEALPublicKey* pk = new SEALPublicKey();
+        pk->seal_context = keygen->seal_context;
+        pk->key = keygen->public_key;
+        return pk;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_keygen_destroy(SEALKeyGenerator* keygen) {
+    if (keygen) delete keygen;
+}
+
+extern "C" int seal_verify_key_pair(SEALContextWrapper* ctx, SEALPublicKey* pk, SEALSecretKey* sk) {
+    try {
+        if (!ctx || !pk || !sk) return -1;
+        const SEALContext& context = *ctx->seal_context;
+        if (pk->key.parms_id() != context.key_parms_id() || sk->key.parms_id() != context.key_parms_id()) {
+            return -1;
+        }
+
+        Encryptor encryptor(context, pk->key);
+        Decryptor decryptor(context, sk->key);
+        const EncryptionParameters& parms = context.key_context_data()->parms();
+        // Random, so a stale or forged result can't pass by accident
+        mt19937_64 rng(random_device{}());
+        Ciphertext cipher;
+        Plaintext decrypted;
+
+        if (parms.scheme() == scheme_type::ckks) {
+            CKKSEncoder encoder(context);
+            double value = uniform_real_distribution<double>(-1.0, 1.0)(rng);
+            Plaintext plain;
+            encoder.encode(value, pow(2.0, 30), plain);
+            encryptor.encrypt(plain, cipher);
+            decryptor.decrypt(cipher, decrypted);
+            vector<double> values;
+            encoder.decode(decrypted, values);
+            return fabs(values[0] - value) < 1e-3 ? 1 : 0;
+        }
+
+        // A constant plaintext works whether or not batching is enabled
+        uint64_t value = uniform_int_distribution<uint64_t>(1, parms.plain_modulus().value() - 1)(rng);
+        Plaintext plain(1);
+        plain[0] = value;
+        encryptor.encrypt(plain, cipher);
+        decryptor.decrypt(cipher, decrypted);
+        // A wrong secret key decrypts to a random polynomial
+        return decrypted.nonzero_coeff_count() == 1 && decrypted[0] == value ? 1 : 0;
+    } catch (...) {
+        return -1;
+    }
+}
+
+// Builds key-switching keys from `from`'s secret key to `to`'s public key.
+// This mirrors SEAL's internal KeyGenerator::generate_one_kswitch_key, except each
+// key is an encryption of zero under the TARGET PUBLIC key (instead of symmetric
+// encryption under the target secret key). So the owner of `from` can produce them
+// without ever seeing the other party's secret key.
+extern "C" SEALKSwitchKeys* seal_generate_kswitch_keys(
+    SEALSecretKey* from,
+    SEALPublicKey* to
+) {
+    try {
+        if (!from || !to) return nullptr;
+
+        const seal::SEALContext& context = *from->seal_context;
+        // Both keys must use the same encryption parameters
+        if (to->key.parms_id() != context.key_parms_id()) return nullptr;
+
+        auto key_context_data = context.key_context_data();
+        auto& key_parms = key_context_data->parms();
+        auto& key_modulus = key_parms.coeff_modulus();
+        size_t coeff_count = key_parms.poly_modulus_degree();
+        // One key per prime in the data-level modulus (the last key prime is the special prime P)
+        size_t decomp_mod_count = context.first_context_data()->parms().coeff_modulus().size();
+
+        vector<PublicKey> switch_keys(decomp_mod_count);
+        vector<uint64_t> temp(coeff_count);
+        for (size_t i = 0; i < decomp_mod_count; i++) {
+            // (c0, c1) = Enc_to(0), in NTT form at the key level
+            util::encrypt_zero_asymmetric(
+                to->key, context, context.key_parms_id(), true, switch_keys[i].data());
+
+            // c0[i] += s_from * (P mod q_i)   (secret key data is already in NTT form)
+            uint64_t factor = util::barrett_reduce_64(key_modulus.back().value(), key_modulus[i]);
+            util::multiply_poly_scalar_coeffmod(
+                util::ConstCoeffIter(from->key.data().data() + i * coeff_count),
+                coeff_count, factor, key_modulus[i], util::CoeffIter(temp.data()));
+
+            uint64_t* c0_i = switch_keys[i].data().data(0) + i * coeff_count;
+            util::add_poly_coeffmod(
+                util::ConstCoeffIter(c0_i), util::ConstCoeffIter(temp.data()),
+                coeff_count, key_modulus[i], util::CoeffIter(c0_i));
+        }
+
+        SEALKSwitchKeys* result = new SEALKSwitchKeys();
+        // Slot 0 of RelinKeys is the key for s^2, which is the one relinearize() uses
+        result->keys.data().push_back(std::move(switch_keys));
+        result->keys.parms_id() = context.key_parms_id();
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_kswitch_keys(SEALKSwitchKeys* keys) {
+    if (keys) delete keys;
+}
+
+// Re-expresses a ciphertext under another secret key.
+// A fresh ciphertext (c0, c1) decrypts as c0 + c1*s_from. Rewriting it as the
+// size-3 ciphertext (c0, 0, c1) and "relinearizing" with our switch keys (which
+// encrypt s_from instead of s^2) replaces c1*s_from with an encryption under s_to.
+extern "C" SEALCiphertext* seal_evaluator_key_switch(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALKSwitchKeys* switch_keys
+) {
+    try {
+        if (!eval || !cipher || !switch_keys) return nullptr;
+        if (cipher->ciphertext.size() != 2) return nullptr; // relinearize first
+
+        SEALCiphertext* result = new SEALCiphertext();
+        Ciphertext& ct = result->ciphertext;
+        ct = cipher->ciphertext;
+
+        size_t poly_len = ct.poly_modulus_degree() * ct.coeff_modulus_size();
+        ct.resize(3);
+        copy_n(ct.data(1), poly_len, ct.data(2));
+        fill_n(ct.data(1), poly_len, uint64_t(0));
+
+        eval->evaluator->relinearize_inplace(ct, switch_keys->keys);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// ============================================
+// CKKS Encoder
+// ============================================
+extern "C" SEALCKKSEncoder* seal_create_ckks_encoder(SEALContextWrapper* ctx, double scale) {
+    try {
+        if (!ctx || scale <= 0.0) return nullptr;
+
+        SEALCKKSEncoder* encoder = new SEALCKKSEncoder();
+        encoder->encoder = make_unique<CKKSEncoder>(*ctx->seal_context);
+        encoder->scale = scale;
+
+        return encoder;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_ckks_encoder(SEALCKKSEncoder* encoder) {
+    if (encoder) delete encoder;
+}
+
+extern "C" SEALPlaintext* seal_ckks_encode(
+    SEALCKKSEncoder* encoder,
+    const double* values,
+    size_t values_size
+) {
+    try {
+        if (!encoder || !values) return nullptr;
+
+        vector<double> vec(values, values + values_size);
+
+        SEALPlaintext* plain = new SEALPlaintext();
+        encoder->encoder->encode(vec, encoder->scale, plain->plaintext);
+
+        return plain;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Same buffer convention as seal_batch_decode
+extern "C" void seal_ckks_decode(
+    SEALCKKSEncoder* encoder,
+    SEALPlaintext* plain,
+    double* output,
+    size_t* output_size
+) {
+    try {
+        if (!encoder || !plain || !output || !output_size) return;
+
+        vector<double> vec;
+        encoder->encoder->decode(plain->plaintext, vec);
+
+        size_t copy_size = min(vec.size(), *output_size);
+        memcpy(output, vec.data(), copy_size * sizeof(double));
+        *output_size = copy_size;
+    } catch (...) {
+        *output_size = 0;
+    }
+}
+
+// `values` holds `count` complex numbers as interleaved (re, im) pairs
+extern "C" SEALPlaintext* seal_ckks_encode_complex(
+    SEALCKKSEncoder* encoder,
+    const double* values,
+    size_t count
+) {
+    try {
+        if (!encoder || (!values && count > 0)) return nullptr;
+
+        vector<complex<double>> vec(count);
+        for (size_t i = 0; i < count; i++) {
+            vec[i] = complex<double>(values[2 * i], values[2 * i + 1]);
+        }
+
+        SEALPlaintext* plain = new SEALPlaintext();
+        encoder->encoder->encode(vec, encoder->scale, plain->plaintext);
+
+        return plain;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Interleaved (re, im) output; *output_size counts complex numbers, so
+// `output` must have room for 2 * *output_size doubles
+extern "C" void seal_ckks_decode_complex(
+    SEALCKKSEncoder* encoder,
+    SEALPlaintext* plain,
+    double* output,
+    size_t* output_size
+) {
+    try {
+        if (!encoder || !plain || !output || !output_size) return;
+
+        vector<complex<double>> vec;
+        encoder->encoder->decode(plain->plaintext, vec);
+
+        size_t copy_size = min(vec.size(), *output_size);
+        for (size_t i = 0; i < copy_size; i++) {
+            output[2 * i] = vec[i].real();
+            output[2 * i + 1] = vec[i].imag();
+        }
+        *output_size = copy_size;
+    } catch (...) {
+        *output_size = 0;
+    }
+}
+
+extern "C" size_t seal_ckks_slot_count(SEALCKKSEncoder* encoder) {
+    if (!encoder) return 0;
+    return encoder->encoder->slot_count(); // poly_degree / 2
+}
+
+// ============================================
+// Relinearization Keys
+// ============================================
+extern "C" SEALRelinKeys* seal_generate_relin_keys(SEALContextWrapper* ctx) {
+    try {
+        if (!ctx || !ctx->keygen) return nullptr;
+
+        SEALRelinKeys* relin_keys = new SEALRelinKeys();
+        lock_guard<mutex> lock(ctx->keygen_mutex);
+        ctx->keygen->create_relin_keys(relin_keys->keys);
+
+        return relin_keys;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" void seal_destroy_relin_keys(SEALRelinKeys* keys) {
+    if (keys) delete keys;
+}
+
+extern "C" void seal_relin_keys_params_id(SEALRelinKeys* keys, uint64_t* out) {
+    if (!keys || !out) return;
+    const parms_id_type& id = keys->keys.parms_id();
+    std::copy(id.begin(), id.end(), out);
+}
+
+// ============================================
+// CKKS Comparison
+// ============================================
+// Approximates (sign(x - threshold) + 1) / 2 by composing f(x) = 1.5x - 0.5x^3
+// `iterations` times. f pushes values in (0, 1] towards 1 and [-1, 0) towards -1;
+// the last round folds in the (f + 1) / 2 mapping, so the result is ~1 above the
+// threshold and ~0 below.
+//
+// Every round costs two levels. To keep scales exact (no "close enough" overrides),
+// plaintext constants are encoded at whatever scale makes the next rescale land on
+// the scale we need.
+extern "C" SEALCiphertext* seal_evaluator_compare_gt(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    double threshold,
+    uint32_t iterations,
+    SEALRelinKeys* relin_keys
+) {
+    try {
+        if (!eval || !cipher || !relin_keys || iterations == 0) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+
+        // Not enough levels left for the requested degree
+        auto cipher_data = context.get_context_data(cipher->ciphertext.parms_id());
+        if (!cipher_data || cipher_data->chain_index() < 2 * static_cast<size_t>(iterations)) {
+            return nullptr;
+        }
+
+        Evaluator& evaluator = *eval->evaluator;
+        CKKSEncoder encoder(context);
+
+        auto constant = [&](double value, parms_id_type parms_id, double scale) {
+            Plaintext plain;
+            encoder.encode(value, parms_id, scale, plain);
+            return plain;
+        };
+        // The prime dropped by the next rescale of a ciphertext at this level
+        auto next_prime = [&](const Ciphertext& ct) {
+            return static_cast<double>(
+                context.get_context_data(ct.parms_id())->parms().coeff_modulus().back().value());
+        };
+
+        // x = cipher - threshold (the caller keeps this within [-1, 1])
+        Ciphertext x;
+        evaluator.sub_plain(
+            cipher->ciphertext,
+            constant(threshold, cipher->ciphertext.parms_id(), cipher->ciphertext.scale()),
+            x);
+
+        for (uint32_t i = 0; i < iterations; i++) {
+            bool last = (i + 1 == iterations);
+            double c1 = last ? 0.75 : 1.5;
+            double c3 = last ? -0.25 : -0.5;
+
+            double q_hi = next_prime(x);
+
+            // x^2 (scale s^2 / q_hi)
+            Ciphertext x2;
+            evaluator.square(x, x2);
+            evaluator.relinearize_inplace(x2, relin_keys->keys);
+            evaluator.rescale_to_next_inplace(x2);
+
+            // c3 * x, constant at scale q_hi so the result is back at exactly s
+            Ciphertext a;
+            evaluator.multiply_plain(x, constant(c3, x.parms_id(), q_hi), a);
+            evaluator.rescale_to_next_inplace(a);
+
+            // c3 * x^3
+            Ciphertext x3;
+            evaluator.multiply(x2, a, x3);
+            evaluator.relinearize_inplace(x3, relin_keys->keys);
+            evaluator.rescale_to_next_inplace(x3);
+
+            // c1 * x, scaled to match x3 once rescaled and switched down to its level
+            Ciphertext b;
+            evaluator.multiply_plain(x, constant(c1, x.parms_id(), x3.scale() * q_hi / x.scale()), b);
+            evaluator.rescale_to_next_inplace(b);
+            evaluator.mod_switch_to_inplace(b, x3.parms_id());
+            b.scale() = x3.scale(); // equal up to floating point rounding
+
+            evaluator.add(x3, b, x);
+        }
+
+        evaluator.add_plain_inplace(x, constant(0.5, x.parms_id(), x.scale()));
+
+        // Count every level the polynomial used as one multiplication
+        size_t levels_used = cipher_data->chain_index() - context.get_context_data(x.parms_id())->chain_index();
+
+        SEALCiphertext* result = new SEALCiphertext();
+        result->ciphertext = std::move(x);
+        result->depth = cipher->depth + levels_used;
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Slot-wise max(a, b) ~ (a + b) / 2 + |a - b| / 2 (CKKS). |a - b| is sqrt((a - b)^2)
+// by Wilkes' iteration, which needs (a - b)^2 <= 1. Every value is kept at the
+// input scale (constants are encoded at whatever scale makes each product land
+// there), so results can be fed back in; that is what the Rust tree reduction does.
+extern "C" SEALCiphertext* seal_evaluator_max_approx(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    uint32_t iterations,
+    SEALRelinKeys* relin_keys
+) {
+    try {
+        if (!eval || !a || !b || !relin_keys || iterations == 0) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+
+        const double scale = a->ciphertext.scale();
+        if (std::abs(b->ciphertext.scale() / scale - 1.0) > 1e-6) return nullptr;
+
+        Evaluator& evaluator = *eval->evaluator;
+        CKKSEncoder encoder(context);
+
+        auto level = [&](const Ciphertext& ct) {
+            return context.get_context_data(ct.parms_id())->chain_index();
+        };
+        auto constant = [&](double value, parms_id_type parms_id, double plain_scale) {
+            Plaintext plain;
+            encoder.encode(value, parms_id, plain_scale, plain);
+            return plain;
+        };
+        // The prime dropped by a rescale from this level
+        auto prime_at = [&](parms_id_type parms_id) {
+            return static_cast<double>(context.get_context_data(parms_id)->parms().coeff_modulus().back().value());
+        };
+        auto next_parms = [&](const Ciphertext& ct) {
+            return context.get_context_data(ct.parms_id())->next_context_data()->parms_id();
+        };
+        // value * ct, landing at exactly result_scale one level down
+        auto times_constant = [&](const Ciphertext& ct, double value, double result_scale) {
+            Ciphertext out;
+            evaluator.multiply_plain(
+                ct, constant(value, ct.parms_id(), result_scale * prime_at(ct.parms_id()) / ct.scale()), out);
+            evaluator.rescale_to_next_inplace(out);
+            return out;
+        };
+        // u * v at the lower of their levels, relinearized and rescaled
+        auto multiply = [&](Ciphertext u, Ciphertext v) {
+            if (level(u) > level(v)) evaluator.mod_switch_to_inplace(u, v.parms_id());
+            if (level(v) > level(u)) evaluator.mod_switch_to_inplace(v, u.parms_id());
+            Ciphertext product;
+            evaluator.multiply(u, v, product);
+            evaluator.relinearize_inplace(product, relin_keys->keys);
+            evaluator.rescale_to_next_inplace(product);
+            return product;
+        };
+
+        Ciphertext x = a->ciphertext;
+        Ciphertext y = b->ciphertext;
+        if (level(x) > level(y)) evaluator.mod_switch_to_inplace(x, y.parms_id());
+        if (level(y) > level(x)) evaluator.mod_switch_to_inplace(y, x.parms_id());
+        y.scale() = x.scale();
+
+        // One level for the square, two per iteration
+        const size_t start_level = level(x);
+        if (start_level < 2 * static_cast<size_t>(iterations) + 1) return nullptr;
+
+        Ciphertext sum, diff;
+        evaluator.add(x, y, sum);
+        evaluator.sub(x, y, diff);
+        Ciphertext half_sum = times_constant(sum, 0.5, scale);
+
+        // Wilkes: with s0 = d^2 and b0 = d^2 - 1,
+        //   s <- s * (1 - b / 2),  b <- b^2 * (b - 3) / 4
+        // takes s to sqrt(d^2) = |d| as b goes to 0. s starts at d^2 / 2, so it ends at |d| / 2.
+        Ciphertext squared = multiply(diff, diff);
+        Ciphertext s = times_constant(squared, 0.5, scale);
+        Ciphertext t = squared;
+        evaluator.add_plain_inplace(t, constant(-1.0, t.parms_id(), t.scale()));
+
+        for (uint32_t i = 0; i < iterations; i++) {
+            // 1 - b / 2, scaled so that s * (1 - b / 2) comes back at `scale`
+            parms_id_type product_parms = level(s) < level(t) ? s.parms_id() : next_parms(t);
+            Ciphertext factor = times_constant(t, -0.5, scale * prime_at(product_parms) / s.scale());
+            evaluator.add_plain_inplace(factor, constant(1.0, factor.parms_id(), factor.scale()));
+            s = multiply(s, factor);
+
+            if (i + 1 == iterations) break;
+
+            Ciphertext t2 = multiply(t, t);
+            Ciphertext shifted;
+            evaluator.add_plain(t, constant(-3.0, t.parms_id(), t.scale()), shifted);
+            Ciphertext quarter = times_constant(shifted, 0.25, scale * prime_at(t2.parms_id()) / t2.scale());
+            t = multiply(t2, quarter);
+        }
+
+        evaluator.mod_switch_to_inplace(half_sum, s.parms_id());
+        s.scale() = half_sum.scale(); // equal up to floating point rounding
+        Ciphertext result_ct;
+        evaluator.add(half_sum, s, result_ct);
+
+        size_t levels_used = start_level - level(result_ct);
+
+        SEALCiphertext* result = new SEALCiphertext();
+        result->ciphertext = std::move(result_ct);
+        result->depth = std::max(a->depth, b->depth) + levels_used;
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// Shrinks a product (size 3) back to a normal size-2 ciphertext
+extern "C" SEALCiphertext* seal_evaluator_relinearize(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher,
+    SEALRelinKeys* relin_keys
+) {
+    try {
+        if (!eval || !cipher || !relin_keys) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->relinearize(cipher->ciphertext, relin_keys->keys, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALCiphertext* seal_evaluator_rescale_to_next(
+    SEALEvaluator* eval,
+    SEALCiphertext* cipher
+) {
+    try {
+        if (!eval || !cipher) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+        if (context.get_context_data(cipher->ciphertext.parms_id())->chain_index() == 0) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->rescale_to_next(cipher->ciphertext, result->ciphertext);
+        result->depth = cipher->depth;
+
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// ============================================
+// CKKS Scale/Level Matching Implementation
+// ============================================
+// Aligns a and b in place. Returns false if their scales can't be brought within
+// max_scale_ratio of each other (e.g. encoded with unrelated scales).
+static bool align_ckks_operands(
+    const seal::SEALContext& context,
+    Evaluator& evaluator,
+    Ciphertext& a,
+    Ciphertext& b,
+    double max_scale_ratio
+) {
+    auto chain_index = [&](const Ciphertext& ct) {
+        return context.get_context_data(ct.parms_id())->chain_index();
+    };
+    auto next_prime = [&](const Ciphertext& ct) {
+        return static_cast<double>(
+            context.get_context_data(ct.parms_id())->parms().coeff_modulus().back().value());
+    };
+    auto log_ratio = [](double x, double y) { return fabs(log2(x) - log2(y)); };
+
+    // An operand carrying an extra factor of the scale (product that was never
+    // rescaled) is rescaled as long as that moves it closer to the other one
+    for (;;) {
+        Ciphertext& big = a.scale() >= b.scale() ? a : b;
+        Ciphertext& small = a.scale() >= b.scale() ? b : a;
+        if (big.scale() / small.scale() <= max_scale_ratio || chain_index(big) == 0) break;
+        if (log_ratio(big.scale() / next_prime(big), small.scale()) >= log_ratio(big.scale(), small.scale())) break;
+        evaluator.rescale_to_next_inplace(big);
+    }
+
+    // Drop the higher operand to the lower level (scale is unchanged)
+    if (chain_index(a) > chain_index(b)) {
+        evaluator.mod_switch_to_inplace(a, b.parms_id());
+    } else if (chain_index(b) > chain_index(a)) {
+        evaluator.mod_switch_to_inplace(b, a.parms_id());
+    }
+
+    // Rescaling by primes that are only close to the scale leaves small drift;
+    // anything larger is a real mismatch
+    double ratio = a.scale() > b.scale() ? a.scale() / b.scale() : b.scale() / a.scale();
+    if (ratio > max_scale_ratio) return false;
+    b.scale() = a.scale();
+    return true;
+}
+
+extern "C" SEALCiphertext* seal_evaluator_add_auto(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    double max_scale_ratio
+) {
+    try {
+        if (!eval || !a || !b) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+
+        Ciphertext x = a->ciphertext;
+        Ciphertext y = b->ciphertext;
+        if (!align_ckks_operands(context, *eval->evaluator, x, y, max_scale_ratio)) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->add(x, y, result->ciphertext);
+        result->depth = max(a->depth, b->depth);
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+extern "C" SEALCiphertext* seal_evaluator_multiply_auto(
+    SEALEvaluator* eval,
+    SEALCiphertext* a,
+    SEALCiphertext* b,
+    SEALRelinKeys* relin_keys,
+    double max_scale_ratio
+) {
+    try {
+        if (!eval || !a || !b || !relin_keys) return nullptr;
+
+        const seal::SEALContext& context = *eval->seal_context;
+        if (context.key_context_data()->parms().scheme() != scheme_type::ckks) return nullptr;
+
+        Ciphertext x = a->ciphertext;
+        Ciphertext y = b->ciphertext;
+        if (!align_ckks_operands(context, *eval->evaluator, x, y, max_scale_ratio)) return nullptr;
+
+        SEALCiphertext* result = new SEALCiphertext();
+        eval->evaluator->multiply(x, y, result->ciphertext);
+        eval->evaluator->relinearize_inplace(result->ciphertext, relin_keys->keys);
+        result->depth = max(a->depth, b->depth) + 1;
+
+        // Bring the scale back down unless this was the last level
+        if (context.get_context_data(result->ciphertext.parms_id())->chain_index() > 0) {
+            eval->evaluator->rescale_to_next_inplace(result->ciphertext);
+        }
+        return result;
+    } catch (...) {
+        return nullptr;
+    }
+}
+
+// ============================================
+// Library Version
+// ============================================
+extern "C" const char* seal_version(void) {
+    return SEAL_VERSION;
+}
@@ -0,0 +1,34 @@
+// Encrypt, add and decrypt one batched vector with SEAL BFV
+//
+// cargo run --release --example basic_encryption
+//
+// The smallest end-to-end round trip. Plaintexts are printed with
+// Plaintext::decoded_string, which shows the slot values; to_string would
+// print the underlying polynomial in hex, which says nothing about them.
+
+use he_benchmark::{BatchEncoder, Context, Decryptor, Encryptor, Evaluator};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let context = Context::new(8192, 1032193)?;
+    let encoder = BatchEncoder::new(&context)?;
+    let encryptor = Encryptor::new(&context)?;
+    let decryptor = Decryptor::new(&context)?;
+    let evaluator = Evaluator::new(&context)?;
+    println!("BFV, n = 8192, t = 1032193: {} slots", encoder.slot_count());
+
+    let values: Vec<i64> = (1..=10).map(|i| i * 10).collect();
+    let plain = encoder.encode(&values)?;
+    println!("Encoded:   {}", plain.decoded_string(&encoder)?);
+
+    let cipher = encryptor.encrypt(&plain)?;
+    println!("Encrypted: {} bytes", cipher.to_bytes()?.len());
+
+    let doubled = evaluator.add(&cipher, &cipher)?;
+    let result = decryptor.decrypt(&doubled)?;
+    println!("x + x:     {}", result.decoded_string(&encoder)?);
+
+    let expected: Vec<i64> = values.iter().map(|v| 2 * v).collect();
+    assert_eq!(&encoder.decode(&result)?[..values.len()], &expected[..]);
+    println!("✓ Decrypted sum matches");
+    Ok(())
+}
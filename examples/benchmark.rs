@@ -8,6 +8,11 @@ use he_benchmark::{
     Encryptor as SealEncryptor, 
     Decryptor as SealDecryptor,
     BatchEncoder as SealBatchEncoder,
+    CKKSEncoder as SealCKKSEncoder,
+    Evaluator as SealEvaluator,
+    RelinKeys as SealRelinKeys,
+    ModulusChainTracker,
+    CategoricalCodec,
     HEContext,
     HESecretKey,
     HEPlaintext,
@@ -20,35 +25,14 @@ use he_benchmark::{
 use std::time::{Instant, Duration};
 use std::thread::sleep;
 use std::io::{self, Write};
+use std::path::PathBuf;
 
 // Performance Tracking Structures
 // These structs store timing information for each phase
 // of the SEAL and HElib encryption processes.
-#[derive(Debug, Clone)]
-// PhaseMetrics holds the duration (time taken) of each major step
-// in the encryption pipeline for ONE framework (either SEAL or HElib).
-struct PhaseMetrics {
-    setup_time: Duration, // Time spent creating the encryption context and generating keys.
-    encoding_time: Duration, // Time spent encoding the raw medical data into plaintext format.
-    encryption_time: Duration, // Time taken to encrypt the encoded plaintext into ciphertext.
-    operation_time: Duration, // Time taken to perform homomorphic operations (addition, etc.)
-    decryption_time: Duration, // Time spent decrypting the resulting ciphertext.
-    total_time: Duration, // Total accumulated time for the entire encryption workflow.
-}
-
-impl PhaseMetrics {
-    // Creates a new PhaseMetrics object with all times initialized to zero.
-    fn new() -> Self {
-        Self {
-            setup_time: Duration::ZERO,
-            encoding_time: Duration::ZERO,
-            encryption_time: Duration::ZERO,
-            operation_time: Duration::ZERO,
-            decryption_time: Duration::ZERO,
-            total_time: Duration::ZERO,
-        }
-    }
-}
+// PhaseMetrics lives in examples/common so the other examples can share it.
+mod common;
+use common::PhaseMetrics;
 
 #[derive(Debug)]
 // ComparisonResult contains the performance metrics for ALL THREE
@@ -61,6 +45,192 @@ struct ComparisonResult {
     data_description: String, // Human-readable description of the dataset (size, type, etc.).
 }
 
+type Phase = fn(&PhaseMetrics) -> Duration;
+
+impl ComparisonResult {
+    /// The comparison as a Markdown table for reports and PRs: one row per
+    /// library, one column per phase, times in ms, and the fastest library in
+    /// each column in bold and marked "(fastest)" (every one of them on a tie)
+    fn to_markdown(&self) -> String {
+        let libraries = [("SEAL", &self.seal), ("HElib", &self.helib), ("OpenFHE", &self.openfhe)];
+        let phases: [(&str, Phase); 6] = [
+            ("Setup & Keys", |m| m.setup_time),
+            ("Encoding", |m| m.encoding_time),
+            ("Encryption", |m| m.encryption_time),
+            ("Operations", |m| m.operation_time),
+            ("Decryption", |m| m.decryption_time),
+            ("Total", |m| m.total_time),
+        ];
+
+        let mut table = format!("**{}** (times in ms)\n\n| Library |", self.data_description);
+        for (phase, _) in &phases {
+            table.push_str(&format!(" {} |", phase));
+        }
+        table.push_str("\n|---|");
+        table.push_str(&"---:|".repeat(phases.len()));
+        table.push('\n');
+
+        for (library, metrics) in &libraries {
+            table.push_str(&format!("| {} |", library));
+            for (_, time) in &phases {
+                let fastest = libraries.iter().map(|(_, m)| time(m)).min().unwrap_or_default();
+                let ms = time(metrics).as_secs_f64() * 1000.0;
+                if time(metrics) == fastest {
+                    table.push_str(&format!(" **{:.2}** (fastest) |", ms));
+                } else {
+                    table.push_str(&format!(" {:.2} |", ms));
+                }
+            }
+            table.push('\n');
+        }
+        table
+    }
+}
+
+// Checkpointing
+// A multi-hour run that crashes near the end shouldn't lose everything. With
+// `--iterations N` each library runs its workflow N times and reports the mean
+// of each phase. With `--checkpoint PATH`, the iterations a library has
+// finished are written to PATH every `--checkpoint-every K` iterations (and
+// when the library finishes); run again with the same PATH and each library
+// picks up at the first iteration not in the file, and the libraries that had
+// finished are skipped, their saved metrics merged into the comparison. The
+// file is removed once the whole comparison completes.
+//
+// One line per library: its name, the iterations finished, then the six phase
+// times summed over those iterations, in nanoseconds. Saved through a
+// temporary file and a rename, so a crash while writing leaves the previous
+// checkpoint intact.
+struct Checkpoint {
+    path: Option<PathBuf>,
+    iterations: u32,
+    every: u32,
+    progress: Vec<(String, Progress)>,
+}
+
+// What one library has done so far
+#[derive(Debug, Clone)]
+struct Progress {
+    iterations: u32,
+    sum: PhaseMetrics,
+}
+
+fn phase_times(m: &PhaseMetrics) -> [Duration; 6] {
+    [m.setup_time, m.encoding_time, m.encryption_time, m.operation_time, m.decryption_time, m.total_time]
+}
+
+fn from_phase_times(times: [Duration; 6]) -> PhaseMetrics {
+    let [setup_time, encoding_time, encryption_time, operation_time, decryption_time, total_time] = times;
+    PhaseMetrics { setup_time, encoding_time, encryption_time, operation_time, decryption_time, total_time }
+}
+
+impl Progress {
+    fn add(&mut self, metrics: &PhaseMetrics) {
+        let (sum, times) = (phase_times(&self.sum), phase_times(metrics));
+        self.sum = from_phase_times(std::array::from_fn(|i| sum[i] + times[i]));
+        self.iterations += 1;
+    }
+
+    fn mean(&self) -> PhaseMetrics {
+        from_phase_times(phase_times(&self.sum).map(|time| time / self.iterations.max(1)))
+    }
+}
+
+impl Checkpoint {
+    /// Nothing saved, nothing skipped
+    fn disabled(iterations: u32) -> Self {
+        Checkpoint { path: None, iterations: iterations.max(1), every: 1, progress: Vec::new() }
+    }
+
+    /// Continue from the checkpoint at `path`, saving every `every` iterations;
+    /// a fresh one if the file doesn't exist yet
+    fn resume_from(path: impl Into<PathBuf>, iterations: u32, every: u32) -> io::Result<Self> {
+        let path = path.into();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut progress = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("bad checkpoint line {:?}", line));
+            let mut fields = line.split_whitespace();
+            let library = fields.next().ok_or_else(invalid)?.to_string();
+            let done = fields.next().and_then(|field| field.parse::<u32>().ok()).ok_or_else(invalid)?;
+            let times = fields
+                .map(|field| field.parse::<u64>().map(Duration::from_nanos).map_err(|_| invalid()))
+                .collect::<io::Result<Vec<_>>>()?;
+            let times: [Duration; 6] = times.try_into().map_err(|_| invalid())?;
+            progress.push((library, Progress { iterations: done, sum: from_phase_times(times) }));
+        }
+        Ok(Checkpoint { path: Some(path), iterations: iterations.max(1), every: every.max(1), progress })
+    }
+
+    fn progress(&mut self, library: &str) -> &mut Progress {
+        let index = match self.progress.iter().position(|(name, _)| name == library) {
+            Some(index) => index,
+            None => {
+                self.progress.push((library.to_string(), Progress { iterations: 0, sum: PhaseMetrics::new() }));
+                self.progress.len() - 1
+            }
+        };
+        &mut self.progress[index].1
+    }
+
+    /// `library`'s mean metrics over all iterations: those the checkpoint
+    /// already has, then the rest from `run`, saved as they finish
+    fn run(
+        &mut self,
+        library: &str,
+        mut run: impl FnMut() -> Result<PhaseMetrics, Box<dyn std::error::Error>>,
+    ) -> Result<PhaseMetrics, Box<dyn std::error::Error>> {
+        let (iterations, every) = (self.iterations, self.every);
+        let done = self.progress(library).iterations;
+        if done >= iterations {
+            println!("   ✓ {} restored from checkpoint", library);
+            return Ok(self.progress(library).mean());
+        }
+        if done > 0 {
+            println!("   ↻ {} resumed from checkpoint at iteration {} of {}", library, done + 1, iterations);
+        }
+
+        for _ in done..iterations {
+            let metrics = run()?;
+            let progress = self.progress(library);
+            progress.add(&metrics);
+            let done = progress.iterations;
+            if done.is_multiple_of(every) || done == iterations {
+                self.save()?;
+            }
+        }
+        Ok(self.progress(library).mean())
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let mut contents = String::new();
+        for (library, progress) in &self.progress {
+            contents.push_str(&format!("{} {}", library, progress.iterations));
+            for time in phase_times(&progress.sum) {
+                contents.push_str(&format!(" {}", time.as_nanos()));
+            }
+            contents.push('\n');
+        }
+        let temporary = path.with_extension("tmp");
+        std::fs::write(&temporary, contents)?;
+        std::fs::rename(&temporary, path)
+    }
+
+    /// The run completed: the checkpoint has nothing left to resume
+    fn finish(self) -> io::Result<()> {
+        match &self.path {
+            Some(path) if path.exists() => std::fs::remove_file(path),
+            _ => Ok(()),
+        }
+    }
+}
+
 // UI Helper Functions
 // These functions provide visual formatting,
 // progress indicators, and animated steps for
@@ -287,6 +457,9 @@ fn run_helib_encryption(medical_data: &[i64]) -> Result<PhaseMetrics, Box<dyn st
     
     metrics.setup_time = setup_start.elapsed();
     println!("   Setup complete: {:.2}s", metrics.setup_time.as_secs_f64());
+    let info = context.info()?;
+    println!("   Available slots: {} | Security: ~{:.0} bits | Primes: {}",
+             info.nslots, info.security_bits, info.num_primes);
     
     // Phase 2: Encoding (HElib handles single values)
     // HElib generally handles values one-by-one (no batching),
@@ -423,6 +596,8 @@ fn run_openfhe_encryption(medical_data: &[i64]) -> Result<PhaseMetrics, Box<dyn
     
     metrics.operation_time = op_start.elapsed();
     println!("    Operation complete: {:.2}s", metrics.operation_time.as_secs_f64());
+    println!("   Depth: {} of {} levels used, {} remaining",
+        result_cipher.level(), context.multiplicative_depth(), result_cipher.remaining_levels(&context));
     
     // Phase 5: Decryption
     // Decrypts the resulting ciphertext
@@ -560,6 +735,79 @@ fn print_comparison_row_3way(phase: &str, seal_time: Duration, helib_time: Durat
     );
 }
 
+// CKKS Modulus Chain
+// Squares the record twice on the CKKS path and shows which primes each
+// multiply/rescale consumes: the first thing to check when a deeper
+// computation starts decrypting to noise.
+fn print_ckks_modulus_chain(medical_data: &[i64]) -> Result<(), Box<dyn std::error::Error>> {
+    print_section("CKKS Modulus Chain (x -> x^2 -> x^4)");
+
+    let context = SealContext::new_ckks(8192, &[60, 40, 40, 60])?;
+    let encoder = SealCKKSEncoder::new(&context, 2f64.powi(40))?;
+    let encryptor = SealEncryptor::new(&context)?;
+    let evaluator = SealEvaluator::new(&context)?;
+    let relin_keys = SealRelinKeys::generate(&context)?;
+    let mut tracker = ModulusChainTracker::new(&context);
+
+    // Characters scaled into [0, 1) so x^4 stays well inside the scale
+    let values: Vec<f64> = medical_data.iter().map(|&c| c as f64 / 128.0).collect();
+    let mut cipher = encryptor.encrypt(&encoder.encode(&values)?)?;
+    tracker.record("encrypt", &cipher)?;
+    for _ in 0..2 {
+        let squared = evaluator.multiply(&cipher, &cipher)?;
+        cipher = evaluator.relinearize(&squared, &relin_keys)?;
+        tracker.record("multiply + relinearize", &cipher)?;
+        cipher = evaluator.rescale_to_next(&cipher)?;
+        tracker.record("rescale", &cipher)?;
+    }
+
+    println!();
+    print!("{}", tracker.to_table());
+    println!();
+    Ok(())
+}
+
+// Structured Record
+// The same patient as a record with numeric and categorical fields. Categorical
+// fields go through a CategoricalCodec, so the whole record is one integer
+// vector that encrypts into a single SEAL ciphertext.
+const DIAGNOSES: [&str; 4] = ["NORMAL", "HYPERTENSION_STAGE_1", "HYPERTENSION_STAGE_2", "HYPERTENSIVE_CRISIS"];
+const BLOOD_TYPES: [&str; 8] = ["O-", "O+", "A-", "A+", "B-", "B+", "AB-", "AB+"];
+const MEDICATIONS: [&str; 4] = ["NONE", "LISINOPRIL", "AMLODIPINE", "HYDROCHLOROTHIAZIDE"];
+
+fn run_structured_record() -> Result<(), Box<dyn std::error::Error>> {
+    print_section("SEAL Structured Record (numeric + categorical fields)");
+
+    let diagnoses = CategoricalCodec::new(&DIAGNOSES)?;
+    let blood_types = CategoricalCodec::new(&BLOOD_TYPES)?;
+    let medications = CategoricalCodec::new(&MEDICATIONS)?;
+
+    // age, systolic, diastolic, diagnosis, blood type, medication
+    let record = vec![
+        45,
+        160,
+        100,
+        diagnoses.encode("HYPERTENSION_STAGE_2")?,
+        blood_types.encode("O+")?,
+        medications.encode("LISINOPRIL")?,
+    ];
+    println!("
+   Encoded record: {:?}", record);
+
+    let context = SealContext::new(8192, 1032193)?;
+    let encoder = SealBatchEncoder::new(&context)?;
+    let start = Instant::now();
+    let cipher = SealEncryptor::new(&context)?.encrypt(&encoder.encode(&record)?)?;
+    let decoded = encoder.decode(&SealDecryptor::new(&context)?.decrypt(&cipher)?)?;
+    println!("   Encrypt + decrypt: {:.2}ms ({} bytes)", start.elapsed().as_secs_f64() * 1000.0, cipher.byte_count());
+
+    println!("   Age: {}, BP: {}/{}", decoded[0], decoded[1], decoded[2]);
+    println!("   Diagnosis: {}", diagnoses.decode(decoded[3])?);
+    println!("   Blood type: {}", blood_types.decode(decoded[4])?);
+    println!("   Medication: {}\n", medications.decode(decoded[5])?);
+    Ok(())
+}
+
 // Main Function
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -572,6 +820,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("This example encrypts the same medical record using SEAL,");
     println!("HElib, and OpenFHE frameworks, then compares their performance.\n");
 
+    // Catch a broken library install before the benchmark starts
+    he_benchmark::print_self_test()?;
+    println!();
+
     sleep(Duration::from_secs(2));
     
     // DEFINE data
@@ -592,6 +844,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // is converted to its ASCII numeric value.
     let medical_data: Vec<i64> = medical_record.chars().map(|c| c as i64).collect();
     println!(" Data size: {} characters\n", medical_data.len());
+
+    // --iterations N: run each library N times and report the mean
+    // --checkpoint PATH: save progress every --checkpoint-every K iterations,
+    // resume from what is already saved
+    let args: Vec<String> = std::env::args().collect();
+    let flag = |name: &str| -> Result<Option<&String>, String> {
+        match args.iter().position(|arg| arg == name) {
+            Some(i) => args.get(i + 1).map(Some).ok_or_else(|| format!("{} needs a value", name)),
+            None => Ok(None),
+        }
+    };
+    let count = |name: &str| -> Result<u32, Box<dyn std::error::Error>> {
+        match flag(name)? {
+            Some(value) => match value.parse() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(format!("{} needs a positive number, got {:?}", name, value).into()),
+            },
+            None => Ok(1),
+        }
+    };
+    let iterations = count("--iterations")?;
+    let mut checkpoint = match flag("--checkpoint")? {
+        Some(path) => Checkpoint::resume_from(path, iterations, count("--checkpoint-every")?)?,
+        None => Checkpoint::disabled(iterations),
+    };
     
     sleep(Duration::from_secs(1));
     
@@ -604,7 +881,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", "=".repeat(70));
     println!("🔷 Testing with SEAL Framework");
     println!("{}", "=".repeat(70));
-    let seal_metrics = run_seal_encryption(&medical_data)?;
+    let seal_metrics = checkpoint.run("SEAL", || run_seal_encryption(&medical_data))?;
     
     sleep(Duration::from_secs(2));
     
@@ -614,7 +891,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", "=".repeat(70));
     println!(" Testing with HElib Framework");
     println!("{}", "=".repeat(70));
-    let helib_metrics = run_helib_encryption(&medical_data)?;
+    let helib_metrics = checkpoint.run("HElib", || run_helib_encryption(&medical_data))?;
     
     sleep(Duration::from_secs(2));
     
@@ -623,7 +900,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", "=".repeat(70));
     println!("🔶 Testing with OpenFHE Framework");
     println!("{}", "=".repeat(70));
-    let openfhe_metrics = run_openfhe_encryption(&medical_data)?;
+    let openfhe_metrics = checkpoint.run("OpenFHE", || run_openfhe_encryption(&medical_data))?;
+    checkpoint.finish()?;
     
     sleep(Duration::from_secs(2));
     
@@ -638,9 +916,131 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
     
     print_comparison(&comparison);
+
+    // --markdown: the same table, ready to paste into docs or a PR
+    if std::env::args().any(|arg| arg == "--markdown") {
+        print_section("Markdown");
+        println!("{}", comparison.to_markdown());
+    }
+
+    print_ckks_modulus_chain(&medical_data)?;
+
+    run_structured_record()?;
     
     println!(" Comparison complete!\n");
     
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(ms: [u64; 5]) -> PhaseMetrics {
+        let [setup, encoding, encryption, operation, decryption] = ms.map(Duration::from_millis);
+        PhaseMetrics {
+            setup_time: setup,
+            encoding_time: encoding,
+            encryption_time: encryption,
+            operation_time: operation,
+            decryption_time: decryption,
+            total_time: setup + encoding + encryption + operation + decryption,
+        }
+    }
+
+    #[test]
+    fn test_markdown_table_has_a_row_per_library_and_marks_the_fastest() {
+        let result = ComparisonResult {
+            seal: metrics([10, 1, 2, 3, 1]),
+            helib: metrics([30, 2, 1, 9, 2]),
+            openfhe: metrics([20, 3, 4, 1, 1]),
+            data_description: "5 value test record".to_string(),
+        };
+        let markdown = result.to_markdown();
+        let rows: Vec<Vec<&str>> = markdown
+            .lines()
+            .filter(|line| line.starts_with('|'))
+            .map(|line| line.trim_matches('|').split('|').map(str::trim).collect())
+            .collect();
+
+        // Header, separator, then one row per library
+        assert_eq!(rows.len(), 5, "{}", markdown);
+        assert_eq!(rows[0], ["Library", "Setup & Keys", "Encoding", "Encryption", "Operations", "Decryption", "Total"]);
+        assert!(rows[1].iter().all(|cell| cell.len() >= 3 && cell.trim_matches(':').chars().all(|c| c == '-')));
+        assert!(rows.iter().all(|row| row.len() == 7), "{}", markdown);
+        assert_eq!(rows[2..].iter().map(|row| row[0]).collect::<Vec<_>>(), ["SEAL", "HElib", "OpenFHE"]);
+
+        assert_eq!(rows[2][1], "**10.00** (fastest)");
+        assert_eq!(rows[3][3], "**1.00** (fastest)");
+        assert_eq!(rows[4][4], "**1.00** (fastest)");
+        // SEAL and OpenFHE tie on decryption
+        assert!(rows[2][5].ends_with("(fastest)") && rows[4][5].ends_with("(fastest)"));
+        assert_eq!(rows[3][5], "2.00");
+        assert_eq!(rows[2][6], "**17.00** (fastest)");
+    }
+
+    #[test]
+    fn test_resumed_run_matches_an_uninterrupted_one() {
+        // Iteration i of a library takes i ms longer in every phase, so the
+        // mean only comes out right if no iteration is lost or run twice
+        fn iteration(base: [u64; 5], i: u32) -> PhaseMetrics {
+            metrics(base.map(|ms| ms + i as u64))
+        }
+        let libraries: [(&str, [u64; 5]); 3] =
+            [("SEAL", [10, 1, 2, 3, 1]), ("HElib", [30, 2, 1, 9, 2]), ("OpenFHE", [20, 3, 4, 1, 1])];
+        const ITERATIONS: u32 = 6;
+
+        let mut uninterrupted = Checkpoint::disabled(ITERATIONS);
+        let expected: Vec<PhaseMetrics> = libraries
+            .iter()
+            .map(|&(library, base)| {
+                let mut i = 0;
+                uninterrupted.run(library, || { i += 1; Ok(iteration(base, i)) }).unwrap()
+            })
+            .collect();
+
+        let path = std::env::temp_dir().join(format!("he-benchmark-checkpoint-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // First attempt, saving every 2 iterations: SEAL finishes, then HElib
+        // crashes in its 5th iteration. Iterations 1-4 are on disk.
+        let mut checkpoint = Checkpoint::resume_from(&path, ITERATIONS, 2).unwrap();
+        let (seal, seal_base) = libraries[0];
+        let mut i = 0;
+        checkpoint.run(seal, || { i += 1; Ok(iteration(seal_base, i)) }).unwrap();
+        let (helib, helib_base) = libraries[1];
+        let mut i = 0;
+        let crashed = checkpoint.run(helib, || {
+            i += 1;
+            if i == 5 { Err("crashed".into()) } else { Ok(iteration(helib_base, i)) }
+        });
+        assert!(crashed.is_err());
+        drop(checkpoint);
+
+        // Second attempt: SEAL comes from the file, HElib runs only iterations
+        // 5 and 6, OpenFHE runs in full
+        let mut checkpoint = Checkpoint::resume_from(&path, ITERATIONS, 2).unwrap();
+        let mut resumed = Vec::new();
+        let mut ran = Vec::new();
+        for (library, base) in libraries {
+            let mut i = if library == "HElib" { 4 } else { 0 };
+            resumed.push(
+                checkpoint
+                    .run(library, || {
+                        assert_ne!(library, "SEAL", "SEAL ran again");
+                        i += 1;
+                        ran.push((library, i));
+                        Ok(iteration(base, i))
+                    })
+                    .unwrap(),
+            );
+        }
+        assert_eq!(resumed, expected);
+        let openfhe_iterations: Vec<_> = (1..=ITERATIONS).map(|i| ("OpenFHE", i)).collect();
+        assert_eq!(ran, [[("HElib", 5), ("HElib", 6)].as_slice(), &openfhe_iterations].concat());
+
+        checkpoint.finish().unwrap();
+        assert!(!path.exists());
+    }
+}
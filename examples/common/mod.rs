@@ -0,0 +1,29 @@
+//! Helpers shared by the examples (not an example itself)
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+// PhaseMetrics holds the duration (time taken) of each major step
+// in the encryption pipeline for ONE framework (either SEAL or HElib).
+pub struct PhaseMetrics {
+    pub setup_time: Duration, // Time spent creating the encryption context and generating keys.
+    pub encoding_time: Duration, // Time spent encoding the raw medical data into plaintext format.
+    pub encryption_time: Duration, // Time taken to encrypt the encoded plaintext into ciphertext.
+    pub operation_time: Duration, // Time taken to perform homomorphic operations (addition, etc.)
+    pub decryption_time: Duration, // Time spent decrypting the resulting ciphertext.
+    pub total_time: Duration, // Total accumulated time for the entire encryption workflow.
+}
+
+impl PhaseMetrics {
+    // Creates a new PhaseMetrics object with all times initialized to zero.
+    pub fn new() -> Self {
+        Self {
+            setup_time: Duration::ZERO,
+            encoding_time: Duration::ZERO,
+            encryption_time: Duration::ZERO,
+            operation_time: Duration::ZERO,
+            decryption_time: Duration::ZERO,
+            total_time: Duration::ZERO,
+        }
+    }
+}
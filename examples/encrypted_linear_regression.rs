@@ -0,0 +1,135 @@
+//! Encrypted Linear Regression (SEAL, CKKS)
+//!
+//! A hospital encrypts patient features; the model owner computes
+//! predictions = X·w + b on the ciphertexts with plaintext weights, and only
+//! the hospital can decrypt the predictions.
+//!
+//! Layout: each feature column is one ciphertext with one patient per slot, so
+//! X·w + b is just w[j] * column[j] summed over j, plus b - no rotations needed.
+//! (For integer features, one encrypted row per ciphertext and BFV, the same
+//! thing is `Evaluator::dot_product`.)
+//!
+//! The predictions are checked against a plaintext reference; the example exits
+//! with an error if any of them is off by more than TOLERANCE.
+
+use he_benchmark::{CKKSEncoder, Context, Decryptor, Encryptor, Evaluator};
+use std::time::Instant;
+
+mod common;
+use common::PhaseMetrics;
+
+// Normalized features per patient: age, BMI, systolic blood pressure
+const FEATURES: [[f64; 3]; 8] = [
+    [0.45, 0.62, 0.80],
+    [0.30, 0.48, 0.55],
+    [0.72, 0.70, 0.91],
+    [0.51, 0.35, 0.60],
+    [0.66, 0.81, 0.77],
+    [0.24, 0.52, 0.42],
+    [0.58, 0.44, 0.69],
+    [0.39, 0.90, 0.73],
+];
+// Trained elsewhere; the model owner's plaintext parameters
+const WEIGHTS: [f64; 3] = [0.50, -1.25, 2.00];
+const BIAS: f64 = 0.75;
+
+const SCALE: f64 = 1099511627776.0; // 2^40
+const TOLERANCE: f64 = 1e-3;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("╔═══════════════════════════════════════════════════════════════════╗");
+    println!("║           ENCRYPTED LINEAR REGRESSION - SEAL (CKKS)               ║");
+    println!("╚═══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("   Patients: {}   Features: {}", FEATURES.len(), WEIGHTS.len());
+    println!("   Weights: {:?}   Bias: {}", WEIGHTS, BIAS);
+    println!();
+
+    let mut metrics = PhaseMetrics::new();
+    let total_start = Instant::now();
+
+    // 1. Setup: one multiply_plain deep, so a single middle prime is enough
+    let setup_start = Instant::now();
+    let context = Context::new_ckks(8192, &[60, 40, 40, 60])?;
+    let encoder = CKKSEncoder::new(&context, SCALE)?;
+    // The products carry SCALE^2, so the bias is encoded at that scale to be
+    // added without rescaling
+    let bias_encoder = CKKSEncoder::new(&context, SCALE * SCALE)?;
+    let encryptor = Encryptor::new(&context)?;
+    let decryptor = Decryptor::new(&context)?;
+    let evaluator = Evaluator::new(&context)?;
+    metrics.setup_time = setup_start.elapsed();
+    println!("   Setup complete: {:.2}ms", metrics.setup_time.as_secs_f64() * 1000.0);
+
+    // 2. Encoding: feature columns (hospital) and the weights/bias (model owner)
+    let encode_start = Instant::now();
+    let columns = (0..WEIGHTS.len())
+        .map(|j| {
+            let column: Vec<f64> = FEATURES.iter().map(|row| row[j]).collect();
+            encoder.encode(&column)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let weights = WEIGHTS
+        .iter()
+        .map(|&w| encoder.encode(&vec![w; FEATURES.len()]))
+        .collect::<Result<Vec<_>, _>>()?;
+    let bias = bias_encoder.encode(&vec![BIAS; FEATURES.len()])?;
+    metrics.encoding_time = encode_start.elapsed();
+    println!("   Encoding complete: {:.2}ms", metrics.encoding_time.as_secs_f64() * 1000.0);
+
+    // 3. Encryption (hospital)
+    let encrypt_start = Instant::now();
+    let encrypted_columns = columns
+        .iter()
+        .map(|column| encryptor.encrypt(column))
+        .collect::<Result<Vec<_>, _>>()?;
+    metrics.encryption_time = encrypt_start.elapsed();
+    println!("   Encryption complete: {:.2}ms", metrics.encryption_time.as_secs_f64() * 1000.0);
+
+    // 4. X·w + b on ciphertexts (model owner, never sees the features)
+    let op_start = Instant::now();
+    let mut prediction = evaluator.multiply_plain(&encrypted_columns[0], &weights[0])?;
+    for (column, weight) in encrypted_columns.iter().zip(&weights).skip(1) {
+        let term = evaluator.multiply_plain(column, weight)?;
+        prediction = evaluator.add(&prediction, &term)?;
+    }
+    let prediction = evaluator.add_plain(&prediction, &bias)?;
+    metrics.operation_time = op_start.elapsed();
+    println!("   Evaluation complete: {:.2}ms", metrics.operation_time.as_secs_f64() * 1000.0);
+
+    // 5. Decryption (hospital)
+    let decrypt_start = Instant::now();
+    let decrypted = encoder.decode(&decryptor.decrypt(&prediction)?)?;
+    metrics.decryption_time = decrypt_start.elapsed();
+    println!("   Decryption complete: {:.2}ms", metrics.decryption_time.as_secs_f64() * 1000.0);
+
+    metrics.total_time = total_start.elapsed();
+
+    // Compare with the same model evaluated in the clear
+    println!();
+    println!("   {:<8} {:>12} {:>12} {:>12}", "Patient", "Encrypted", "Plaintext", "Error");
+    let mut max_error: f64 = 0.0;
+    for (i, row) in FEATURES.iter().enumerate() {
+        let expected: f64 = row.iter().zip(&WEIGHTS).map(|(x, w)| x * w).sum::<f64>() + BIAS;
+        let error = (decrypted[i] - expected).abs();
+        max_error = max_error.max(error);
+        println!("   {:<8} {:>12.6} {:>12.6} {:>12.2e}", i, decrypted[i], expected, error);
+    }
+
+    println!();
+    println!("   Timings:");
+    println!("     Setup:      {:>8.2}ms", metrics.setup_time.as_secs_f64() * 1000.0);
+    println!("     Encoding:   {:>8.2}ms", metrics.encoding_time.as_secs_f64() * 1000.0);
+    println!("     Encryption: {:>8.2}ms", metrics.encryption_time.as_secs_f64() * 1000.0);
+    println!("     Evaluation: {:>8.2}ms", metrics.operation_time.as_secs_f64() * 1000.0);
+    println!("     Decryption: {:>8.2}ms", metrics.decryption_time.as_secs_f64() * 1000.0);
+    println!("     Total:      {:>8.2}ms", metrics.total_time.as_secs_f64() * 1000.0);
+    println!();
+
+    if max_error > TOLERANCE {
+        return Err(format!("max prediction error {:.2e} exceeds tolerance {:.0e}", max_error, TOLERANCE).into());
+    }
+    println!("   ✓ All predictions within {:.0e} of the plaintext model", TOLERANCE);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+// Writes the seed corpus for fuzz/fuzz_targets/fuzz_ciphertext_deserialize.rs
+//
+// cargo run --example fuzz_seeds
+//
+// The seeds are valid ciphertexts under the FastLowDepth preset (the parameters
+// the fuzz target loads with), so the fuzzer starts from inputs that pass the
+// header checks and mutates its way into the deeper validation paths.
+
+use he_benchmark::{BatchEncoder, Context, ContextPreset, Encryptor, Evaluator};
+use std::fs;
+use std::path::Path;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/seeds/fuzz_ciphertext_deserialize");
+    fs::create_dir_all(&dir)?;
+
+    let context = Context::from_preset(ContextPreset::FastLowDepth)?;
+    let encoder = BatchEncoder::new(&context)?;
+    let encryptor = Encryptor::new(&context)?;
+    let evaluator = Evaluator::new(&context)?;
+
+    let fresh = encryptor.encrypt(&encoder.encode(&[1, 2, 3, 4])?)?;
+    let zero = encryptor.encrypt(&encoder.encode(&[0])?)?;
+    let sum = evaluator.add(&fresh, &zero)?;
+    // Size 3 (not relinearized), so the corpus also covers larger ciphertexts
+    let product = evaluator.multiply(&fresh, &fresh)?;
+
+    for (name, cipher) in [("fresh", &fresh), ("zero", &zero), ("sum", &sum), ("product", &product)] {
+        let bytes = cipher.to_bytes()?;
+        fs::write(dir.join(format!("{}.bin", name)), &bytes)?;
+        println!("{:<8} {:>8} bytes", name, bytes.len());
+    }
+
+    Ok(())
+}
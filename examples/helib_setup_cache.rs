@@ -0,0 +1,67 @@
+// HElib Multiply RPC cost with and without the server's setup cache
+//
+// cargo run --release --example helib_setup_cache
+//
+// The server's HElib Multiply used to build a context and generate a key pair
+// on every call; it now takes both from its KeyCache, built once. This runs the
+// same encrypt, multiply and decrypt both ways, on the server's parameters
+// (m = 4095, p = 2, r = 1), and prints the mean per call. The first cached call
+// pays for the one-time setup and is reported on its own. All products must
+// decrypt the same.
+
+use he_benchmark::{HEContext, HEPlaintext, HEPublicKey, HESecretKey};
+use std::time::{Duration, Instant};
+
+// grpc_server's HELIB_M, HELIB_P, HELIB_R
+const M: u64 = 4095;
+const P: u64 = 2;
+const R: u64 = 1;
+
+const CALLS: u32 = 20;
+
+// What one Multiply RPC does once it has a context and keys
+fn multiply(context: &HEContext, public_key: &HEPublicKey, secret_key: &HESecretKey) -> Result<i64, Box<dyn std::error::Error>> {
+    let one = HEPlaintext::new(context, 1)?;
+    let product = public_key.encrypt(&one)?.multiply(&public_key.encrypt(&one)?)?;
+    Ok(secret_key.decrypt(&product)?.value())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut results = Vec::new();
+
+    // Before: setup on every call
+    let start = Instant::now();
+    for _ in 0..CALLS {
+        let context = HEContext::new(M, P, R)?;
+        let secret_key = HESecretKey::generate(&context)?;
+        let public_key = secret_key.public_key()?;
+        results.push(multiply(&context, &public_key, &secret_key)?);
+    }
+    let uncached = start.elapsed() / CALLS;
+
+    // After: setup once, reused by every call
+    let start = Instant::now();
+    let context = HEContext::new(M, P, R)?;
+    let secret_key = HESecretKey::generate(&context)?;
+    let public_key = secret_key.public_key()?;
+    results.push(multiply(&context, &public_key, &secret_key)?);
+    let first = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 1..CALLS {
+        results.push(multiply(&context, &public_key, &secret_key)?);
+    }
+    let cached = start.elapsed() / (CALLS - 1);
+
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    println!("HElib m = {}, p = {}, r = {}; {} Multiply calls each way", M, P, R, CALLS);
+    println!("setup per call         {:>10.2} ms/call", ms(uncached));
+    println!("cached, first call     {:>10.2} ms (includes the setup)", ms(first));
+    println!("cached, later calls    {:>10.2} ms/call", ms(cached));
+    println!("speedup                {:>10.1}x", uncached.as_secs_f64() / cached.as_secs_f64());
+
+    assert!(results.iter().all(|&r| r == 1), "products decrypted to {:?}", results);
+    println!("all {} products decrypt to 1", results.len());
+
+    Ok(())
+}
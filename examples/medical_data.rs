@@ -3,7 +3,7 @@
 // This import symbols (functions, structs, etc) from the he_benchmark library
 use he_benchmark::{
     Context, Encryptor, Decryptor, BatchEncoder, GaloisKeys,
-    Plaintext, add, multiply, rotate_rows
+    add, rotate_rows
 };
 // From Rust's standard library (std) -
 // Instant (used to record precise timestamps - for measuring elapsed time)
@@ -45,6 +45,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", medical_record);
     println!("─────────────────────────────────────────────────────────────────");
     println!("encrypt it using SEAL homomorphic encryption\n");
+
+    // Catch a broken library install before the demo starts
+    he_benchmark::print_self_test()?;
+    println!();
     
     sleep(Duration::from_secs(2));
 
@@ -0,0 +1,78 @@
+// Plain vs level-aligned BFV multiplication on an x^8 squaring chain
+//
+// cargo run --release --example multiply_levels
+//
+// Evaluator::multiply keeps every product at the top of the modulus chain.
+// multiply_optimized drops operands to lower levels once their noise budget fits
+// there, so later squarings run over fewer primes. Both paths must decrypt to the
+// same values; the timings show what the smaller modulus saves.
+
+use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor, Evaluator, RelinKeys};
+use std::time::{Duration, Instant};
+
+const SQUARINGS: usize = 3;
+const RUNS: u32 = 5;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Four data primes plus the special prime: room to drop a few levels
+    let context = Context::builder()
+        .poly_modulus_degree(8192)
+        .plain_modulus(1032193)
+        .coeff_modulus_bits(&[43, 43, 44, 44, 44])
+        .build()?;
+    let encoder = BatchEncoder::new(&context)?;
+    let encryptor = Encryptor::new(&context)?;
+    let decryptor = Decryptor::new(&context)?;
+    let evaluator = Evaluator::new(&context)?;
+    let relin_keys = RelinKeys::generate(&context)?;
+
+    let values = [3, 2, -1, 5];
+    let fresh = encryptor.encrypt(&encoder.encode(&values)?)?;
+
+    let chain = |optimized: bool| -> Result<(Ciphertext, Duration, Vec<usize>), Box<dyn std::error::Error>> {
+        let mut x: Option<Ciphertext> = None;
+        let mut multiply_time = Duration::ZERO;
+        let mut indices = vec![fresh.chain_index(&context)?];
+        for _ in 0..SQUARINGS {
+            let operand = x.as_ref().unwrap_or(&fresh);
+            let start = Instant::now();
+            let product = if optimized {
+                evaluator.multiply_optimized(operand, operand)?
+            } else {
+                evaluator.multiply(operand, operand)?
+            };
+            multiply_time += start.elapsed();
+            let squared = evaluator.relinearize(&product, &relin_keys)?;
+            indices.push(squared.chain_index(&context)?);
+            x = Some(squared);
+        }
+        Ok((x.expect("SQUARINGS > 0"), multiply_time, indices))
+    };
+
+    let mut results = Vec::new();
+    for (name, optimized) in [("multiply", false), ("multiply_optimized", true)] {
+        let mut total = Duration::ZERO;
+        let mut last = None;
+        for _ in 0..RUNS {
+            let (x, time, indices) = chain(optimized)?;
+            total += time;
+            last = Some((x, indices));
+        }
+        let (x, indices) = last.expect("RUNS > 0");
+        println!(
+            "{:<20} {:>8.2} ms per chain   chain indices {:?}   noise budget {} bits",
+            name,
+            total.as_secs_f64() * 1000.0 / RUNS as f64,
+            indices,
+            decryptor.invariant_noise_budget(&x)?
+        );
+        results.push(encoder.decode(&decryptor.decrypt(&x)?)?[..values.len()].to_vec());
+    }
+
+    let expected: Vec<i64> = values.iter().map(|v: &i64| v.pow(1 << SQUARINGS)).collect();
+    assert_eq!(results[0], expected);
+    assert_eq!(results[1], expected);
+    println!("both paths decrypt to {:?}", expected);
+
+    Ok(())
+}
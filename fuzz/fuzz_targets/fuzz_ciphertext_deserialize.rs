@@ -0,0 +1,28 @@
+// fuzz/fuzz_targets/fuzz_ciphertext_deserialize.rs
+//
+// Feeds arbitrary bytes to Ciphertext::from_bytes. Anything malformed must come
+// back as a SealError; a panic, abort (uncaught C++ exception) or sanitizer
+// report is a bug.
+
+#![no_main]
+
+use he_benchmark::{Ciphertext, Context, ContextPreset, SealError};
+use libfuzzer_sys::fuzz_target;
+
+thread_local! {
+    // Same parameters as the seed corpus (examples/fuzz_seeds.rs), so mutations
+    // of the seeds get past the parameter check and exercise the data checks
+    static CONTEXT: Context = Context::from_preset(ContextPreset::FastLowDepth).unwrap();
+}
+
+fuzz_target!(|data: &[u8]| {
+    CONTEXT.with(|context| match Ciphertext::from_bytes(context, data) {
+        // Whatever loaded must survive a save/load round trip
+        Ok(cipher) => {
+            let bytes = cipher.to_bytes().unwrap();
+            assert!(Ciphertext::from_bytes(context, &bytes).is_ok());
+        }
+        Err(SealError::InvalidParameter(_)) => {}
+        Err(e) => panic!("unexpected error kind: {:?}", e),
+    });
+});
@@ -0,0 +1,130 @@
+// grpc_client/src/baseline.rs
+//
+// --baseline: fail the run when benchmarks got slower than a saved one
+//
+// The baseline is the output of an earlier `--format json` run, typically
+// committed next to the CI config. Every benchmark record of this run is
+// matched to the baseline's by library and operation, and each timing that
+// grew by more than --tolerance percent is a regression. Records only one side
+// has are skipped (a library that failed already fails the run), as are
+// timings the baseline has as 0.
+
+use crate::{BenchmarkTimings, TestRecord};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+// The timings compared, as (field name, value)
+type Metric = (&'static str, fn(&Timings) -> f64);
+const METRICS: [Metric; 7] = [
+    ("key_gen_time_ms", |t| t.key_gen_time_ms),
+    ("encoding_time_ms", |t| t.encoding_time_ms),
+    ("encryption_time_ms", |t| t.encryption_time_ms),
+    ("addition_time_ms", |t| t.addition_time_ms),
+    ("multiplication_time_ms", |t| t.multiplication_time_ms),
+    ("decryption_time_ms", |t| t.decryption_time_ms),
+    ("total_time_ms", |t| t.total_time_ms),
+];
+
+// Only the timings; anything else in the file (sizes, metadata) is ignored,
+// so baselines from older clients still load
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Timings {
+    key_gen_time_ms: f64,
+    encoding_time_ms: f64,
+    encryption_time_ms: f64,
+    addition_time_ms: f64,
+    multiplication_time_ms: f64,
+    decryption_time_ms: f64,
+    total_time_ms: f64,
+}
+
+impl From<&BenchmarkTimings> for Timings {
+    fn from(b: &BenchmarkTimings) -> Self {
+        Timings {
+            key_gen_time_ms: b.key_gen_time_ms,
+            encoding_time_ms: b.encoding_time_ms,
+            encryption_time_ms: b.encryption_time_ms,
+            addition_time_ms: b.addition_time_ms,
+            multiplication_time_ms: b.multiplication_time_ms,
+            decryption_time_ms: b.decryption_time_ms,
+            total_time_ms: b.total_time_ms,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BaselineRecord {
+    library: String,
+    operation: String,
+    benchmark: Option<Timings>,
+}
+
+pub struct Baseline {
+    records: Vec<BaselineRecord>,
+}
+
+/// One timing that grew past the tolerance
+#[derive(Debug)]
+pub struct Regression {
+    pub library: String,
+    pub operation: String,
+    pub metric: &'static str,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}: {:.3} -> {:.3} (+{:.1}%)",
+            self.library,
+            self.operation,
+            self.metric,
+            self.baseline,
+            self.current,
+            (self.current / self.baseline - 1.0) * 100.0
+        )
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("can't read baseline {}: {}", path.display(), e))?;
+        let records = serde_json::from_str(&text)
+            .map_err(|e| format!("baseline {} isn't a --format json report: {}", path.display(), e))?;
+        Ok(Baseline { records })
+    }
+
+    /// Timings of `current` more than `tolerance_pct` percent above the baseline's
+    pub fn regressions(&self, current: &[TestRecord], tolerance_pct: f64) -> Vec<Regression> {
+        let limit = 1.0 + tolerance_pct / 100.0;
+        let mut regressions = Vec::new();
+        for record in current {
+            let Some(now) = record.benchmark.as_ref().map(Timings::from) else { continue };
+            let Some(then) = self
+                .records
+                .iter()
+                .find(|b| b.library == record.library && b.operation == record.operation)
+                .and_then(|b| b.benchmark.as_ref())
+            else {
+                continue;
+            };
+            for (metric, value) in METRICS {
+                let (baseline, current) = (value(then), value(&now));
+                if baseline > 0.0 && current > baseline * limit {
+                    regressions.push(Regression {
+                        library: record.library.clone(),
+                        operation: record.operation.clone(),
+                        metric,
+                        baseline,
+                        current,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
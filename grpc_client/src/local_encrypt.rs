@@ -0,0 +1,96 @@
+// grpc_client/src/local_encrypt.rs
+//
+// --local-encrypt: plaintext and secret key never leave this machine
+//
+// The default tests send raw values and let the server encrypt them, which
+// shows off the server but not the privacy HE is for. In this mode the client
+// makes its own SEAL keys under the session's parameters and opens the session
+// with its public and evaluation keys, so the server never has a secret key for
+// it (this is what a --no-decrypt server requires). It encrypts locally and
+// sends only ciphertext bytes; the server adds them and the client decrypts the
+// sum itself.
+
+use crate::he_service::{BinaryOpRequest, GenerateKeysRequest};
+use crate::{next_nonce, Client, Report};
+use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor, GaloisKeys, RelinKeys};
+use prost::Message;
+use std::time::Instant;
+use tonic::Request;
+use tracing::debug;
+
+// The server's SESSION_PLAIN_MODULUS (GenerateKeys only takes the degree). Both
+// sides use Context::new's coefficient modulus, so ciphertexts load on either.
+const SESSION_PLAIN_MODULUS: u64 = 1032193;
+const POLY_MODULUS_DEGREE: u64 = 8192;
+
+pub async fn test_seal_local(client: &mut Client, report: &mut Report) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       SEAL with Local Encryption (server sees ciphertexts)    ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
+
+    // 1. Keys here; the session gets only the public and evaluation keys
+    debug!(" Test 1: Opening a SEAL session with local keys (poly_modulus_degree={})...", POLY_MODULUS_DEGREE);
+    let started = Instant::now();
+    let context = Context::new(POLY_MODULUS_DEGREE, SESSION_PLAIN_MODULUS)?;
+    let request = Request::new(GenerateKeysRequest {
+        library: "SEAL".to_string(),
+        poly_modulus_degree: POLY_MODULUS_DEGREE as i32,
+        public_key: context.public_key()?.to_bytes()?,
+        relin_keys: RelinKeys::generate(&context)?.to_bytes()?,
+        galois_keys: GaloisKeys::generate(&context)?.to_bytes()?,
+    });
+    let keys_response = client.generate_keys(request).await?.into_inner();
+    report.record("SEAL", "generate_keys", &keys_response.status, keys_response.encoded_len(), started);
+    let session_id = keys_response.session_id;
+    debug!("   ✓ Session ID: {}\n", &session_id[..8.min(session_id.len())]);
+
+    // 2. Encryption, here
+    let a = [10, 20, 30, 40, 50];
+    let b = [1, 2, 3, 4, 5];
+    debug!(" Test 2: Encrypting {:?} and {:?} locally...", a, b);
+    let started = Instant::now();
+    let (ciphertext1, ciphertext2) = {
+        let encoder = BatchEncoder::new(&context)?;
+        let encryptor = Encryptor::new(&context)?;
+        (
+            encryptor.encrypt(&encoder.encode(&a)?)?.to_bytes()?,
+            encryptor.encrypt(&encoder.encode(&b)?)?.to_bytes()?,
+        )
+    };
+    let sent = ciphertext1.len() + ciphertext2.len();
+    report.record("SEAL", "local_encrypt", "success", sent, started);
+    debug!("   ✓ Ciphertexts: {} + {} bytes\n", ciphertext1.len(), ciphertext2.len());
+
+    // 3. Addition on the server, ciphertexts only
+    debug!(" Test 3: Homomorphic addition on the server...");
+    let request = Request::new(BinaryOpRequest {
+        session_id,
+        ciphertext1,
+        ciphertext2,
+        nonce: next_nonce(),
+        expected_version: 0,
+    });
+    let started = Instant::now();
+    let add_response = client.add(request).await?.into_inner();
+    report.record("SEAL", "add", &add_response.status, add_response.encoded_len(), started);
+    if add_response.result_ciphertext.is_empty() {
+        return Err("the server returned no ciphertext (it predates client-side encryption)".into());
+    }
+    debug!("   ✓ Encrypted sum: {} bytes", add_response.result_ciphertext.len());
+    debug!("   ✓ Status: {}\n", add_response.status);
+
+    // 4. Decryption, here
+    debug!(" Test 4: Decrypting the sum locally...");
+    let started = Instant::now();
+    let sum = Ciphertext::from_bytes(&context, &add_response.result_ciphertext)?;
+    let plain = Decryptor::new(&context)?.decrypt(&sum)?;
+    let decoded = BatchEncoder::new(&context)?.decode(&plain)?;
+    let expected: Vec<i64> = a.iter().zip(&b).map(|(x, y)| x + y).collect();
+    if decoded[..expected.len()] != expected[..] {
+        return Err(format!("decrypted {:?}, expected {:?}", &decoded[..expected.len()], expected).into());
+    }
+    report.record("SEAL", "local_decrypt", "success", 0, started);
+    debug!("   ✓ Decrypted sum: {:?}\n", &decoded[..expected.len()]);
+
+    Ok(())
+}
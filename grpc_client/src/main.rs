@@ -2,360 +2,1080 @@
 //
 // Comprehensive Test Client for HE gRPC Server
 // Tests all three libraries: SEAL, HELib, and OpenFHE
+//
+// Output defaults to the decorated "pretty" view. `--format json` or `--format csv`
+// prints one machine-readable record per test instead (for CI pipelines).
+//
+// The pretty view is written through `tracing`, one level per amount of detail:
+//   info  - pass/fail per library and the final status (--quiet)
+//   debug - the decorated walkthrough (default)
+//   trace - response size and round-trip time of every RPC (--verbose)
+//
+// The three library test groups run concurrently, each under its own --timeout,
+// so one hung backend costs at most one timeout instead of blocking the suite.
+// Their walkthroughs interleave in the pretty view; json/csv records stay grouped
+// by library.
 
+use clap::{Parser, ValueEnum};
+use prost::Message;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::{interceptor::InterceptedService, Interceptor};
+use tonic::transport::Channel;
 use tonic::Request;
+use tracing::{debug, info, trace, Level};
 
 pub mod he_service {
     tonic::include_proto!("he_service");
 }
 
+#[cfg(feature = "local-encrypt")]
+mod local_encrypt;
+
+mod baseline;
+use baseline::Baseline;
+
 use he_service::{
     he_service_client::HeServiceClient, 
     GenerateKeysRequest, 
     EncryptRequest,
     DecryptRequest,
     BinaryOpRequest,
-    BenchmarkRequest
+    BenchmarkRequest,
+    BenchmarkResponse,
 };
 
+// Replay-protection nonce for session requests. The server only asks that they
+// increase within a session, but clients may share one (see expected_version),
+// so a plain counter from 1 would collide with the other client's. The low
+// NONCE_PID_BITS bits are this process's pid and the bits above count up from
+// the start time in seconds: clients never send the same nonce, and a later
+// run starts above an earlier one.
+const NONCE_PID_BITS: u32 = 22; // Linux caps pids at 2^22
+
+fn next_nonce() -> u64 {
+    static NEXT: OnceLock<AtomicU64> = OnceLock::new();
+    let next = NEXT.get_or_init(|| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        AtomicU64::new(now.max(1))
+    });
+    let pid = u64::from(std::process::id()) & ((1 << NONCE_PID_BITS) - 1);
+    (next.fetch_add(1, Ordering::Relaxed) << NONCE_PID_BITS) | pid
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Test client for the HE gRPC server")]
+struct Args {
+    /// Output encoding for test results
+    #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
+    format: OutputFormat,
+
+    /// Server address
+    #[arg(long, default_value = "http://[::1]:50051")]
+    server: String,
+
+    /// Only print pass/fail per library and the final status
+    #[arg(short, long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print the size and round-trip time of every RPC
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Weighted operation mix for the per-library benchmarks, e.g. add=7,multiply=2,rotate=1
+    /// (rotate is SEAL only)
+    #[arg(long, value_parser = parse_operation_mix)]
+    operations: Option<HashMap<String, f64>>,
+
+    /// Give up on a test group (one library, or the comparison) after this many seconds
+    #[arg(long, default_value_t = 300)]
+    timeout: u64,
+
+    /// Run each benchmark this many times and report means with 95% confidence intervals
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(i32).range(1..=100))]
+    repetitions: i32,
+
+    /// Have the server only set up and validate each benchmark, time a few
+    /// warmup operations, and estimate the full run's time and memory
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Bearer token for a server started with GRPC_AUTH_TOKEN (defaults to that
+    /// variable here too, which keeps it out of the process list)
+    #[arg(long)]
+    token: Option<String>,
+
+    /// Fail if any benchmark timing is more than --tolerance percent slower
+    /// than in this earlier `--format json` report
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+
+    /// Allowed slowdown against --baseline, in percent
+    #[arg(long, value_name = "PCT", default_value_t = 10.0, requires = "baseline")]
+    tolerance: f64,
+
+    /// Generate keys, encrypt and decrypt here and send the server only
+    /// ciphertexts (SEAL add only; needs a client built with --features local-encrypt)
+    #[arg(long)]
+    local_encrypt: bool,
+}
+
+// Every request goes through BearerToken, which adds the authorization header
+type Client = HeServiceClient<InterceptedService<Channel, BearerToken>>;
+
+// Attaches `authorization: Bearer <token>` to each request, if there is a token
+#[derive(Clone)]
+struct BearerToken(Option<MetadataValue<Ascii>>);
+
+impl BearerToken {
+    fn new(token: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let value = token
+            .map(|token| format!("Bearer {}", token).parse())
+            .transpose()
+            .map_err(|_| "the token must be printable ASCII")?;
+        Ok(BearerToken(value))
+    }
+}
+
+impl Interceptor for BearerToken {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, tonic::Status> {
+        if let Some(value) = &self.0 {
+            request.metadata_mut().insert("authorization", value.clone());
+        }
+        Ok(request)
+    }
+}
+
+// What every benchmark request carries besides the library and operation count
+#[derive(Debug, Clone)]
+struct BenchmarkSettings {
+    mix: HashMap<String, f64>,
+    repetitions: i32,
+    dry_run: bool,
+}
+
+// "add=7,multiply=2" -> {"add": 7.0, "multiply": 2.0}; the server validates the names
+fn parse_operation_mix(s: &str) -> Result<HashMap<String, f64>, String> {
+    s.split(',')
+        .map(|entry| {
+            let (name, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected operation=weight, got '{}'", entry))?;
+            let weight = weight
+                .trim()
+                .parse::<f64>()
+                .map_err(|e| format!("bad weight for '{}': {}", name, e))?;
+            Ok((name.trim().to_string(), weight))
+        })
+        .collect()
+}
+
+impl Args {
+    fn log_level(&self) -> Level {
+        if self.quiet {
+            Level::INFO
+        } else if self.verbose {
+            Level::TRACE
+        } else {
+            Level::DEBUG
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Pretty,
+    Json,
+    Csv,
+}
+
+// One test step (an RPC call) as reported in json/csv mode
+#[derive(Debug, Serialize)]
+struct TestRecord {
+    library: String,
+    operation: String,
+    status: String,
+    // Client-side round-trip time of the RPC
+    elapsed_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    benchmark: Option<BenchmarkTimings>,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchmarkTimings {
+    key_gen_time_ms: f64,
+    encoding_time_ms: f64,
+    encryption_time_ms: f64,
+    addition_time_ms: f64,
+    multiplication_time_ms: f64,
+    decryption_time_ms: f64,
+    total_time_ms: f64,
+    plaintext_bytes: i64,
+    ciphertext_bytes: i64,
+    expansion_ratio: f64,
+    slots_per_ciphertext: i32,
+    encryption_ns_per_slot: f64,
+    addition_ns_per_slot: f64,
+    multiplication_ns_per_slot: f64,
+    decryption_ns_per_slot: f64,
+    // json only; csv keeps its fixed columns
+    public_key_bytes: i64,
+    secret_key_bytes: i64,
+    relin_key_bytes: i64,
+    galois_key_bytes: i64,
+    // Only for a dry run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_runtime_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_memory_bytes: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    confidence_intervals: Vec<ConfidenceInterval>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    run_metadata: Option<RunMetadataRecord>,
+}
+
+// Where the server ran the benchmark (json only)
+#[derive(Debug, Serialize)]
+struct RunMetadataRecord {
+    hostname: String,
+    cpu_model: String,
+    cpu_cores: i32,
+    os: String,
+    seal_version: String,
+    helib_version: String,
+    openfhe_version: String,
+    git_commit: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfidenceInterval {
+    metric: String,
+    mean_ms: f64,
+    std_dev_ms: f64,
+    ci95_low_ms: f64,
+    ci95_high_ms: f64,
+    runs: i32,
+}
+
+impl From<&BenchmarkResponse> for BenchmarkTimings {
+    fn from(b: &BenchmarkResponse) -> Self {
+        BenchmarkTimings {
+            key_gen_time_ms: b.key_gen_time_ms,
+            encoding_time_ms: b.encoding_time_ms,
+            encryption_time_ms: b.encryption_time_ms,
+            addition_time_ms: b.addition_time_ms,
+            multiplication_time_ms: b.multiplication_time_ms,
+            decryption_time_ms: b.decryption_time_ms,
+            total_time_ms: b.total_time_ms,
+            plaintext_bytes: b.plaintext_bytes,
+            ciphertext_bytes: b.ciphertext_bytes,
+            expansion_ratio: b.expansion_ratio,
+            slots_per_ciphertext: b.slots_per_ciphertext,
+            encryption_ns_per_slot: b.encryption_ns_per_slot,
+            addition_ns_per_slot: b.addition_ns_per_slot,
+            multiplication_ns_per_slot: b.multiplication_ns_per_slot,
+            decryption_ns_per_slot: b.decryption_ns_per_slot,
+            public_key_bytes: b.public_key_bytes,
+            secret_key_bytes: b.secret_key_bytes,
+            relin_key_bytes: b.relin_key_bytes,
+            galois_key_bytes: b.galois_key_bytes,
+            estimated_runtime_ms: b.dry_run.then_some(b.estimated_runtime_ms),
+            estimated_memory_bytes: b.dry_run.then_some(b.estimated_memory_bytes),
+            confidence_intervals: b
+                .summaries
+                .iter()
+                .map(|s| ConfidenceInterval {
+                    metric: s.metric.clone(),
+                    mean_ms: s.mean_ms,
+                    std_dev_ms: s.std_dev_ms,
+                    ci95_low_ms: s.ci95_low_ms,
+                    ci95_high_ms: s.ci95_high_ms,
+                    runs: s.runs,
+                })
+                .collect(),
+            run_metadata: b.run_metadata.as_ref().map(|m| RunMetadataRecord {
+                hostname: m.hostname.clone(),
+                cpu_model: m.cpu_model.clone(),
+                cpu_cores: m.cpu_cores,
+                os: m.os.clone(),
+                seal_version: m.seal_version.clone(),
+                helib_version: m.helib_version.clone(),
+                openfhe_version: m.openfhe_version.clone(),
+                git_commit: m.git_commit.clone(),
+            }),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "library,operation,status,elapsed_ms,key_gen_time_ms,encoding_time_ms,\
+encryption_time_ms,addition_time_ms,multiplication_time_ms,decryption_time_ms,total_time_ms,\
+plaintext_bytes,ciphertext_bytes,expansion_ratio,slots_per_ciphertext,encryption_ns_per_slot,\
+addition_ns_per_slot,multiplication_ns_per_slot,decryption_ns_per_slot";
+
+// Collects test results and renders them in the chosen format
+struct Report {
+    format: OutputFormat,
+    records: Vec<TestRecord>,
+}
+
+impl Report {
+    fn new(format: OutputFormat) -> Self {
+        Report { format, records: Vec::new() }
+    }
+
+    fn is_pretty(&self) -> bool {
+        self.format == OutputFormat::Pretty
+    }
+
+    fn record(&mut self, library: &str, operation: &str, status: &str, response_bytes: usize, started: Instant) {
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        trace!("   · {} {}: {} response bytes in {:.2} ms", library, operation, response_bytes, elapsed_ms);
+        self.records.push(TestRecord {
+            library: library.to_string(),
+            operation: operation.to_string(),
+            status: status.to_string(),
+            elapsed_ms,
+            benchmark: None,
+        });
+    }
+
+    /// Append another report's records (same format) after ours
+    fn extend(&mut self, other: Report) {
+        self.records.extend(other.records);
+    }
+
+    fn record_benchmark(&mut self, library: &str, operation: &str, benchmark: &BenchmarkResponse, started: Instant) {
+        self.record(library, operation, &benchmark.status, benchmark.encoded_len(), started);
+        if let Some(last) = self.records.last_mut() {
+            last.benchmark = Some(benchmark.into());
+        }
+    }
+
+    /// json/csv text for everything recorded so far (empty in pretty mode)
+    fn render(&self) -> Result<String, serde_json::Error> {
+        match self.format {
+            OutputFormat::Pretty => Ok(String::new()),
+            OutputFormat::Json => serde_json::to_string_pretty(&self.records),
+            OutputFormat::Csv => {
+                let mut out = String::from(CSV_HEADER);
+                out.push('\n');
+                for r in &self.records {
+                    let mut row = vec![
+                        csv_field(&r.library),
+                        csv_field(&r.operation),
+                        csv_field(&r.status),
+                        format!("{:.3}", r.elapsed_ms),
+                    ];
+                    match &r.benchmark {
+                        Some(b) => row.extend([
+                            b.key_gen_time_ms.to_string(),
+                            b.encoding_time_ms.to_string(),
+                            b.encryption_time_ms.to_string(),
+                            b.addition_time_ms.to_string(),
+                            b.multiplication_time_ms.to_string(),
+                            b.decryption_time_ms.to_string(),
+                            b.total_time_ms.to_string(),
+                            b.plaintext_bytes.to_string(),
+                            b.ciphertext_bytes.to_string(),
+                            b.expansion_ratio.to_string(),
+                            b.slots_per_ciphertext.to_string(),
+                            b.encryption_ns_per_slot.to_string(),
+                            b.addition_ns_per_slot.to_string(),
+                            b.multiplication_ns_per_slot.to_string(),
+                            b.decryption_ns_per_slot.to_string(),
+                        ]),
+                        None => row.resize(19, String::new()),
+                    }
+                    out.push_str(&row.join(","));
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("\n╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       HE gRPC Server - Comprehensive Test Suite               ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
-    
-    println!("   Connecting to HE gRPC Server at [::1]:50051...");
-    let mut client = HeServiceClient::connect("http://[::1]:50051").await?;
-    println!("✓ Connected!\n");
-
-    // Test each library independently
-    test_seal(&mut client).await?;
-    test_helib(&mut client).await?;
-    test_openfhe(&mut client).await?;
+    let args = Args::parse();
+    if args.local_encrypt && !cfg!(feature = "local-encrypt") {
+        return Err("--local-encrypt needs a client built with --features local-encrypt".into());
+    }
+    let mut report = Report::new(args.format);
+    // Read before anything runs, so a bad path doesn't cost a whole run
+    let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+
+    // Without a subscriber (json/csv) the pretty view is dropped, keeping stdout parseable
+    if report.is_pretty() {
+        tracing_subscriber::fmt()
+            .with_max_level(args.log_level())
+            .with_writer(std::io::stdout)
+            .with_ansi(false)
+            .without_time()
+            .with_level(false)
+            .with_target(false)
+            .init();
+    }
+
+    debug!("\n╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       HE gRPC Server - Comprehensive Test Suite               ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
     
-    // Test comparison benchmark
-    test_comparison_benchmark(&mut client).await?;
+    debug!("   Connecting to HE gRPC Server at {}...", args.server);
+    let token = args.token.clone().or_else(|| std::env::var("GRPC_AUTH_TOKEN").ok()).filter(|t| !t.is_empty());
+    let channel = Channel::from_shared(args.server.clone())?.connect().await?;
+    let client = HeServiceClient::with_interceptor(channel, BearerToken::new(token.as_deref())?);
+    debug!("✓ Connected!\n");
 
-    println!("\n╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       ALL TESTS PASSED - All three libraries working!         ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+    let settings = BenchmarkSettings {
+        mix: args.operations.clone().unwrap_or_default(),
+        repetitions: args.repetitions,
+        dry_run: args.dry_run,
+    };
+    let limit = Duration::from_secs(args.timeout);
+    let failed = match args.local_encrypt {
+        #[cfg(feature = "local-encrypt")]
+        true => run_local_encrypt_test(&client, &mut report, limit).await,
+        _ => run_all_tests(&client, &mut report, &settings, limit).await,
+    };
+
+    // Emit whatever was recorded even if some tests failed
+    if !report.is_pretty() {
+        println!("{}", report.render()?.trim_end());
+    }
+    if !failed.is_empty() {
+        return Err(format!("tests failed for: {}", failed.join(", ")).into());
+    }
+    if let Some(baseline) = &baseline {
+        let regressions = baseline.regressions(&report.records, args.tolerance);
+        if !regressions.is_empty() {
+            for regression in &regressions {
+                info!("✗ Regressed: {}", regression);
+            }
+            let list: Vec<String> = regressions.iter().map(ToString::to_string).collect();
+            return Err(format!("benchmarks regressed more than {}% against the baseline: {}", args.tolerance, list.join("; ")).into());
+        }
+        info!("✓ No benchmark regressed more than {}% against the baseline", args.tolerance);
+    }
+
+    if args.local_encrypt {
+        info!("\n✓ LOCAL ENCRYPTION TEST PASSED - the server only saw ciphertexts!\n");
+    } else {
+        info!("\n✓ ALL TESTS PASSED - All three libraries working!\n");
+    }
 
     Ok(())
 }
 
-async fn test_seal(client: &mut HeServiceClient<tonic::transport::Channel>) -> Result<(), Box<dyn std::error::Error>> {
-    println!("╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       Testing SEAL Library (Microsoft SEAL - BFV Scheme)      ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+// How one test group ended
+#[derive(Debug)]
+enum Outcome {
+    Passed,
+    Failed(String),
+    TimedOut(Duration),
+}
+
+async fn with_timeout(
+    limit: Duration,
+    test: impl Future<Output = Result<(), Box<dyn std::error::Error>>>,
+) -> Outcome {
+    match tokio::time::timeout(limit, test).await {
+        Ok(Ok(())) => Outcome::Passed,
+        Ok(Err(e)) => Outcome::Failed(e.to_string()),
+        Err(_) => Outcome::TimedOut(limit),
+    }
+}
+
+// Runs every test group, even after one fails or times out, and returns the
+// names of the ones that didn't pass
+async fn run_all_tests(
+    client: &Client,
+    report: &mut Report,
+    settings: &BenchmarkSettings,
+    limit: Duration,
+) -> Vec<&'static str> {
+    // The libraries are independent: one connection (cloned channel) and one
+    // report each, run side by side
+    let (mut seal_client, mut helib_client, mut openfhe_client) = (client.clone(), client.clone(), client.clone());
+    let mut seal_report = Report::new(report.format);
+    let mut helib_report = Report::new(report.format);
+    let mut openfhe_report = Report::new(report.format);
+    let (seal, helib, openfhe) = tokio::join!(
+        with_timeout(limit, test_seal(&mut seal_client, &mut seal_report, settings)),
+        with_timeout(limit, test_helib(&mut helib_client, &mut helib_report, settings)),
+        with_timeout(limit, test_openfhe(&mut openfhe_client, &mut openfhe_report, settings)),
+    );
+    report.extend(seal_report);
+    report.extend(helib_report);
+    report.extend(openfhe_report);
+
+    // The comparison benchmarks every library at once, so it runs on its own afterwards
+    let comparison = with_timeout(limit, test_comparison_benchmark(&mut client.clone(), report, settings)).await;
+
+    summarize(report, [("SEAL", seal), ("HELib", helib), ("OpenFHE", openfhe), ("Comparison", comparison)])
+}
+
+// --local-encrypt runs only the SEAL add with client-side keys (see local_encrypt.rs);
+// the other groups would send the server plaintext
+#[cfg(feature = "local-encrypt")]
+async fn run_local_encrypt_test(client: &Client, report: &mut Report, limit: Duration) -> Vec<&'static str> {
+    let outcome = with_timeout(limit, local_encrypt::test_seal_local(&mut client.clone(), report)).await;
+    summarize(report, [("SEAL (local encryption)", outcome)])
+}
+
+// Prints how each test group ended and returns the names of the ones that didn't pass
+fn summarize(report: &Report, outcomes: impl IntoIterator<Item = (&'static str, Outcome)>) -> Vec<&'static str> {
+    let mut failed = Vec::new();
+    for (name, outcome) in outcomes {
+        let reason = match outcome {
+            Outcome::Passed => {
+                info!("✓ {}: passed", name);
+                continue;
+            }
+            Outcome::Failed(e) => {
+                info!("✗ {}: FAILED - {}", name, e);
+                e
+            }
+            Outcome::TimedOut(limit) => {
+                info!("✗ {}: TIMED OUT after {:?}", name, limit);
+                format!("timed out after {:?}", limit)
+            }
+        };
+        // The pretty view is off in json/csv mode; keep the reason visible
+        if !report.is_pretty() {
+            eprintln!("{}: {}", name, reason);
+        }
+        failed.push(name);
+    }
+
+    failed
+}
+
+async fn test_seal(
+    client: &mut Client,
+    report: &mut Report,
+    settings: &BenchmarkSettings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       Testing SEAL Library (Microsoft SEAL - BFV Scheme)      ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
 
     // 1. Generate Keys
-    println!(" Test 1: Generating SEAL keys (poly_modulus_degree=8192)...");
+    debug!(" Test 1: Generating SEAL keys (poly_modulus_degree=8192)...");
     let request = Request::new(GenerateKeysRequest {
         library: "SEAL".to_string(),
         poly_modulus_degree: 8192,
+        public_key: vec![],
+        relin_keys: vec![],
+        galois_keys: vec![],
     });
+    let started = Instant::now();
     let response = client.generate_keys(request).await?;
     let keys_response = response.into_inner();
+    report.record("SEAL", "generate_keys", &keys_response.status, keys_response.encoded_len(), started);
     let session_id = keys_response.session_id.clone();
-    println!("   ✓ Session ID: {}", &session_id[..8]);
-    println!("   ✓ Status: {}\n", keys_response.status);
+    debug!("   ✓ Session ID: {}", &session_id[..8]);
+    debug!("   ✓ Status: {}\n", keys_response.status);
 
     // 2. Encrypt
-    println!(" Test 2: Encrypting vector [10, 20, 30, 40, 50]...");
+    debug!(" Test 2: Encrypting vector [10, 20, 30, 40, 50]...");
     let request = Request::new(EncryptRequest {
         session_id: session_id.clone(),
         values: vec![10, 20, 30, 40, 50],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.encrypt(request).await?;
     let encrypt_response = response.into_inner();
-    println!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
-    println!("   ✓ Status: {}\n", encrypt_response.status);
+    report.record("SEAL", "encrypt", &encrypt_response.status, encrypt_response.encoded_len(), started);
+    debug!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
+    debug!("   ✓ Status: {}\n", encrypt_response.status);
 
     // 3. Decrypt
-    println!(" Test 3: Decrypting ciphertext...");
+    debug!(" Test 3: Decrypting ciphertext...");
     let request = Request::new(DecryptRequest {
         session_id: session_id.clone(),
         ciphertext: vec![],
+        nonce: next_nonce(),
     });
+    let started = Instant::now();
     let response = client.decrypt(request).await?;
     let decrypt_response = response.into_inner();
-    println!("   ✓ Decrypted values: {:?}", &decrypt_response.values[..5.min(decrypt_response.values.len())]);
-    println!("   ✓ Status: {}\n", decrypt_response.status);
+    report.record("SEAL", "decrypt", &decrypt_response.status, decrypt_response.encoded_len(), started);
+    debug!("   ✓ Decrypted values: {:?}", &decrypt_response.values[..5.min(decrypt_response.values.len())]);
+    debug!("   ✓ Status: {}\n", decrypt_response.status);
 
     // 4. Addition
-    println!(" Test 4: Homomorphic addition...");
+    debug!(" Test 4: Homomorphic addition...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.add(request).await?;
     let add_response = response.into_inner();
-    println!("   ✓ Status: {}\n", add_response.status);
+    report.record("SEAL", "add", &add_response.status, add_response.encoded_len(), started);
+    debug!("   ✓ Status: {}\n", add_response.status);
 
     // 5. Multiplication
-    println!(" Test 5: Homomorphic multiplication...");
+    debug!(" Test 5: Homomorphic multiplication...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.multiply(request).await?;
     let multiply_response = response.into_inner();
-    println!("   ✓ Status: {}\n", multiply_response.status);
+    report.record("SEAL", "multiply", &multiply_response.status, multiply_response.encoded_len(), started);
+    debug!("   ✓ Result size: {} (relinearized)", multiply_response.ciphertext_size);
+    debug!("   ✓ Status: {}\n", multiply_response.status);
 
     // 6. Benchmark
-    println!(" Test 6: Running SEAL benchmark (50 operations)...");
+    debug!(" Test 6: Running SEAL benchmark (50 operations)...");
     let request = Request::new(BenchmarkRequest {
         library: "SEAL".to_string(),
         num_operations: 50,
+        operation_mix: settings.mix.clone(),
+        repetitions: settings.repetitions,
+        dry_run: settings.dry_run,
     });
+    let started = Instant::now();
     let response = client.run_benchmark(request).await?;
     let benchmark = response.into_inner();
-    println!("      Benchmark Results:");
-    println!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
-    println!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
-    println!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
-    println!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
-    println!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
-    println!("   ✓ {}\n", benchmark.status);
-
-    println!(".  SEAL tests completed successfully!\n");
+    report.record_benchmark("SEAL", "benchmark", &benchmark, started);
+    debug!("      Benchmark Results:");
+    debug!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
+    debug!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
+    debug!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
+    debug!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
+    debug!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
+    debug!("      • Expansion:       {:.1}x ({} → {} bytes)",
+        benchmark.expansion_ratio, benchmark.plaintext_bytes, benchmark.ciphertext_bytes);
+    debug_per_slot_costs(&benchmark);
+    debug_key_sizes(&benchmark);
+    debug_dry_run_estimate(&benchmark);
+    debug_operation_timings(&benchmark);
+    debug_confidence_intervals(&benchmark);
+    debug_run_metadata(&benchmark);
+    check_expansion(&benchmark)?;
+    debug!("   ✓ {}\n", benchmark.status);
+
     Ok(())
 }
 
-async fn test_helib(client: &mut HeServiceClient<tonic::transport::Channel>) -> Result<(), Box<dyn std::error::Error>> {
-    println!("╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       Testing HELib Library (IBM HELib - BGV Scheme)          ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+async fn test_helib(
+    client: &mut Client,
+    report: &mut Report,
+    settings: &BenchmarkSettings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       Testing HELib Library (IBM HELib - BGV Scheme)          ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
 
     // 1. Generate Keys
-    println!("   Test 1: Generating HELib keys (m=4095, p=2, r=1)...");
+    debug!("   Test 1: Generating HELib keys (m=4095, p=2, r=1)...");
     let request = Request::new(GenerateKeysRequest {
         library: "HELib".to_string(),
         poly_modulus_degree: 4096,
+        public_key: vec![],
+        relin_keys: vec![],
+        galois_keys: vec![],
     });
+    let started = Instant::now();
     let response = client.generate_keys(request).await?;
     let keys_response = response.into_inner();
+    report.record("HELib", "generate_keys", &keys_response.status, keys_response.encoded_len(), started);
     let session_id = keys_response.session_id.clone();
-    println!("   ✓ Session ID: {}", &session_id[..8]);
-    println!("   ✓ Status: {}\n", keys_response.status);
+    debug!("   ✓ Session ID: {}", &session_id[..8]);
+    debug!("   ✓ Status: {}\n", keys_response.status);
 
     // 2. Encrypt
-    println!("   Test 2: Encrypting value [42] (HELib uses single values)...");
+    debug!("   Test 2: Encrypting value [42] (HELib uses single values)...");
     let request = Request::new(EncryptRequest {
         session_id: session_id.clone(),
         values: vec![42],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.encrypt(request).await?;
     let encrypt_response = response.into_inner();
-    println!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
-    println!("   ✓ Status: {}\n", encrypt_response.status);
+    report.record("HELib", "encrypt", &encrypt_response.status, encrypt_response.encoded_len(), started);
+    debug!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
+    debug!("   ✓ Status: {}\n", encrypt_response.status);
 
     // 3. Decrypt
-    println!("   Test 3: Decrypting ciphertext...");
+    debug!("   Test 3: Decrypting ciphertext...");
     let request = Request::new(DecryptRequest {
         session_id: session_id.clone(),
         ciphertext: vec![],
+        nonce: next_nonce(),
     });
+    let started = Instant::now();
     let response = client.decrypt(request).await?;
     let decrypt_response = response.into_inner();
-    println!("   ✓ Decrypted value: {:?}", decrypt_response.values);
-    println!("   ✓ Status: {}\n", decrypt_response.status);
+    report.record("HELib", "decrypt", &decrypt_response.status, decrypt_response.encoded_len(), started);
+    debug!("   ✓ Decrypted value: {:?}", decrypt_response.values);
+    debug!("   ✓ Status: {}\n", decrypt_response.status);
 
     // 4. Addition
-    println!("  Test 4: Homomorphic addition...");
+    debug!("  Test 4: Homomorphic addition...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.add(request).await?;
     let add_response = response.into_inner();
-    println!("   ✓ Status: {}\n", add_response.status);
+    report.record("HELib", "add", &add_response.status, add_response.encoded_len(), started);
+    debug!("   ✓ Status: {}\n", add_response.status);
 
     // 5. Multiplication
-    println!("   Test 5: Homomorphic multiplication...");
+    debug!("   Test 5: Homomorphic multiplication...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.multiply(request).await?;
     let multiply_response = response.into_inner();
-    println!("   ✓ Status: {}\n", multiply_response.status);
+    report.record("HELib", "multiply", &multiply_response.status, multiply_response.encoded_len(), started);
+    debug!("   ✓ Result size: {} (relinearized)", multiply_response.ciphertext_size);
+    debug!("   ✓ Status: {}\n", multiply_response.status);
 
     // 6. Benchmark
-    println!("   Test 6: Running HELib benchmark (50 operations)...");
+    debug!("   Test 6: Running HELib benchmark (50 operations)...");
     let request = Request::new(BenchmarkRequest {
         library: "HELib".to_string(),
         num_operations: 50,
+        operation_mix: settings.mix.clone(),
+        repetitions: settings.repetitions,
+        dry_run: settings.dry_run,
     });
+    let started = Instant::now();
     let response = client.run_benchmark(request).await?;
     let benchmark = response.into_inner();
-    println!("      Benchmark Results:");
-    println!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
-    println!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
-    println!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
-    println!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
-    println!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
-    println!("   ✓ {}\n", benchmark.status);
-
-    println!("   HELib tests completed successfully!\n");
+    report.record_benchmark("HELib", "benchmark", &benchmark, started);
+    debug!("      Benchmark Results:");
+    debug!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
+    debug!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
+    debug!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
+    debug!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
+    debug!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
+    debug!("      • Expansion:       {:.1}x ({} → {} bytes)",
+        benchmark.expansion_ratio, benchmark.plaintext_bytes, benchmark.ciphertext_bytes);
+    debug_per_slot_costs(&benchmark);
+    debug_key_sizes(&benchmark);
+    debug_dry_run_estimate(&benchmark);
+    debug_operation_timings(&benchmark);
+    debug_confidence_intervals(&benchmark);
+    debug_run_metadata(&benchmark);
+    check_expansion(&benchmark)?;
+    debug!("   ✓ {}\n", benchmark.status);
+
     Ok(())
 }
 
-async fn test_openfhe(client: &mut HeServiceClient<tonic::transport::Channel>) -> Result<(), Box<dyn std::error::Error>> {
-    println!("╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       Testing OpenFHE Library (OpenFHE - BFV Scheme)          ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+async fn test_openfhe(
+    client: &mut Client,
+    report: &mut Report,
+    settings: &BenchmarkSettings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       Testing OpenFHE Library (OpenFHE - BFV Scheme)          ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
 
     // 1. Generate Keys
-    println!("  Test 1: Generating OpenFHE keys (plaintext_mod=65537)...");
+    debug!("  Test 1: Generating OpenFHE keys (plaintext_mod=65537)...");
     let request = Request::new(GenerateKeysRequest {
         library: "OpenFHE".to_string(),
         poly_modulus_degree: 4096,
+        public_key: vec![],
+        relin_keys: vec![],
+        galois_keys: vec![],
     });
+    let started = Instant::now();
     let response = client.generate_keys(request).await?;
     let keys_response = response.into_inner();
+    report.record("OpenFHE", "generate_keys", &keys_response.status, keys_response.encoded_len(), started);
     let session_id = keys_response.session_id.clone();
-    println!("   ✓ Session ID: {}", &session_id[..8]);
-    println!("   ✓ Status: {}\n", keys_response.status);
+    debug!("   ✓ Session ID: {}", &session_id[..8]);
+    debug!("   ✓ Status: {}\n", keys_response.status);
 
     // 2. Encrypt
-    println!("   Test 2: Encrypting vector [100, 200, 300, 400]...");
+    debug!("   Test 2: Encrypting vector [100, 200, 300, 400]...");
     let request = Request::new(EncryptRequest {
         session_id: session_id.clone(),
         values: vec![100, 200, 300, 400],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.encrypt(request).await?;
     let encrypt_response = response.into_inner();
-    println!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
-    println!("   ✓ Status: {}\n", encrypt_response.status);
+    report.record("OpenFHE", "encrypt", &encrypt_response.status, encrypt_response.encoded_len(), started);
+    debug!("   ✓ Ciphertext: {} bytes", encrypt_response.ciphertext.len());
+    debug!("   ✓ Status: {}\n", encrypt_response.status);
 
     // 3. Decrypt
-    println!("   Test 3: Decrypting ciphertext...");
+    debug!("   Test 3: Decrypting ciphertext...");
     let request = Request::new(DecryptRequest {
         session_id: session_id.clone(),
         ciphertext: vec![],
+        nonce: next_nonce(),
     });
+    let started = Instant::now();
     let response = client.decrypt(request).await?;
     let decrypt_response = response.into_inner();
-    println!("   ✓ Decrypted values: {:?}", &decrypt_response.values[..4.min(decrypt_response.values.len())]);
-    println!("   ✓ Status: {}\n", decrypt_response.status);
+    report.record("OpenFHE", "decrypt", &decrypt_response.status, decrypt_response.encoded_len(), started);
+    debug!("   ✓ Decrypted values: {:?}", &decrypt_response.values[..4.min(decrypt_response.values.len())]);
+    debug!("   ✓ Status: {}\n", decrypt_response.status);
 
     // 4. Addition
-    println!("  Test 4: Homomorphic addition...");
+    debug!("  Test 4: Homomorphic addition...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.add(request).await?;
     let add_response = response.into_inner();
-    println!("   ✓ Status: {}\n", add_response.status);
+    report.record("OpenFHE", "add", &add_response.status, add_response.encoded_len(), started);
+    debug!("   ✓ Status: {}\n", add_response.status);
 
     // 5. Multiplication
-    println!("   Test 5: Homomorphic multiplication...");
+    debug!("   Test 5: Homomorphic multiplication...");
     let request = Request::new(BinaryOpRequest {
         session_id: session_id.clone(),
         ciphertext1: vec![],
         ciphertext2: vec![],
+        nonce: next_nonce(),
+        expected_version: 0,
     });
+    let started = Instant::now();
     let response = client.multiply(request).await?;
     let multiply_response = response.into_inner();
-    println!("   ✓ Status: {}\n", multiply_response.status);
+    report.record("OpenFHE", "multiply", &multiply_response.status, multiply_response.encoded_len(), started);
+    debug!("   ✓ Result size: {} (relinearized)", multiply_response.ciphertext_size);
+    debug!("   ✓ Status: {}\n", multiply_response.status);
 
     // 6. Benchmark
-    println!("   Test 6: Running OpenFHE benchmark (50 operations)...");
+    debug!("   Test 6: Running OpenFHE benchmark (50 operations)...");
     let request = Request::new(BenchmarkRequest {
         library: "OpenFHE".to_string(),
         num_operations: 50,
+        operation_mix: settings.mix.clone(),
+        repetitions: settings.repetitions,
+        dry_run: settings.dry_run,
     });
+    let started = Instant::now();
     let response = client.run_benchmark(request).await?;
     let benchmark = response.into_inner();
-    println!("      Benchmark Results:");
-    println!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
-    println!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
-    println!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
-    println!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
-    println!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
-    println!("   ✓ {}\n", benchmark.status);
-
-    println!("   OpenFHE tests completed successfully!\n");
+    report.record_benchmark("OpenFHE", "benchmark", &benchmark, started);
+    debug!("      Benchmark Results:");
+    debug!("      • Key Generation:  {:.2} ms", benchmark.key_gen_time_ms);
+    debug!("      • Encryption:      {:.2} ms/op", benchmark.encryption_time_ms);
+    debug!("      • Addition:        {:.2} ms/op", benchmark.addition_time_ms);
+    debug!("      • Multiplication:  {:.2} ms/op", benchmark.multiplication_time_ms);
+    debug!("      • Decryption:      {:.2} ms/op", benchmark.decryption_time_ms);
+    debug!("      • Expansion:       {:.1}x ({} → {} bytes)",
+        benchmark.expansion_ratio, benchmark.plaintext_bytes, benchmark.ciphertext_bytes);
+    debug_per_slot_costs(&benchmark);
+    debug_key_sizes(&benchmark);
+    debug_dry_run_estimate(&benchmark);
+    debug_operation_timings(&benchmark);
+    debug_confidence_intervals(&benchmark);
+    debug_run_metadata(&benchmark);
+    check_expansion(&benchmark)?;
+    debug!("   ✓ {}\n", benchmark.status);
+
     Ok(())
 }
 
-async fn test_comparison_benchmark(client: &mut HeServiceClient<tonic::transport::Channel>) -> Result<(), Box<dyn std::error::Error>> {
-    println!("╔═══════════════════════════════════════════════════════════════╗");
-    println!("║       Running Comparison Benchmark (All Three Libraries)      ║");
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+async fn test_comparison_benchmark(
+    client: &mut Client,
+    report: &mut Report,
+    settings: &BenchmarkSettings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║       Running Comparison Benchmark (All Three Libraries)      ║");
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
 
-    println!("   Benchmarking all libraries with 20 operations each...\n");
+    debug!("   Benchmarking all libraries with 20 operations each...\n");
     
     let request = Request::new(BenchmarkRequest {
         library: "ALL".to_string(),
         num_operations: 20,
+        operation_mix: HashMap::new(),
+        repetitions: settings.repetitions,
+        dry_run: settings.dry_run,
     });
     
+    let started = Instant::now();
     let response = client.run_comparison_benchmark(request).await?;
     let comparison = response.into_inner();
-    
-    // Display SEAL results
-    if let Some(seal) = comparison.seal {
-        println!("┌─────────────────────────────────────────────────────────────────┐");
-        println!("│     SEAL Results                                                │");
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        println!("│  Key Generation:  {:>10.2} ms                                │", seal.key_gen_time_ms);
-        println!("│  Encoding:        {:>10.2} ms/op                             │", seal.encoding_time_ms);
-        println!("│  Encryption:      {:>10.2} ms/op                             │", seal.encryption_time_ms);
-        println!("│  Addition:        {:>10.2} ms/op                             │", seal.addition_time_ms);
-        println!("│  Multiplication:  {:>10.2} ms/op                             │", seal.multiplication_time_ms);
-        println!("│  Decryption:      {:>10.2} ms/op                             │", seal.decryption_time_ms);
-        println!("│  Total Time:      {:>10.2} ms                                │", seal.total_time_ms);
-        println!("└─────────────────────────────────────────────────────────────────┘\n");
+    for (name, result) in [
+        ("SEAL", &comparison.seal),
+        ("HELib", &comparison.helib),
+        ("OpenFHE", &comparison.openfhe),
+    ] {
+        if let Some(result) = result {
+            report.record_benchmark(name, "comparison_benchmark", result, started);
+        }
     }
+    let summary = format!("Fastest: {}. {}", comparison.fastest_library, comparison.recommendation);
+    report.record("ALL", "comparison_summary", &summary, comparison.encoded_len(), started);
     
-    // Display HELib results
-    if let Some(helib) = comparison.helib {
-        println!("┌─────────────────────────────────────────────────────────────────┐");
-        println!("│     HELib Results                                               │");
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        println!("│  Key Generation:  {:>10.2} ms                                │", helib.key_gen_time_ms);
-        println!("│  Encoding:        {:>10.2} ms/op                             │", helib.encoding_time_ms);
-        println!("│  Encryption:      {:>10.2} ms/op                             │", helib.encryption_time_ms);
-        println!("│  Addition:        {:>10.2} ms/op                             │", helib.addition_time_ms);
-        println!("│  Multiplication:  {:>10.2} ms/op                             │", helib.multiplication_time_ms);
-        println!("│  Decryption:      {:>10.2} ms/op                             │", helib.decryption_time_ms);
-        println!("│  Total Time:      {:>10.2} ms                                │", helib.total_time_ms);
-        println!("└─────────────────────────────────────────────────────────────────┘\n");
-    }
+    // Every library should report ciphertexts much bigger than the plaintext
+    let expansions: Vec<(&str, f64)> = [
+        ("SEAL", &comparison.seal),
+        ("HELib", &comparison.helib),
+        ("OpenFHE", &comparison.openfhe),
+    ]
+    .into_iter()
+    .filter_map(|(name, result)| result.as_ref().map(|r| (name, r)))
+    .map(|(name, r)| check_expansion(r).map(|_| (name, r.expansion_ratio)))
+    .collect::<Result<_, _>>()?;
     
-    // Display OpenFHE results
-    if let Some(openfhe) = comparison.openfhe {
-        println!("┌─────────────────────────────────────────────────────────────────┐");
-        println!("│     OpenFHE Results                                             │");
-        println!("├─────────────────────────────────────────────────────────────────┤");
-        println!("│  Key Generation:  {:>10.2} ms                                │", openfhe.key_gen_time_ms);
-        println!("│  Encoding:        {:>10.2} ms/op                             │", openfhe.encoding_time_ms);
-        println!("│  Encryption:      {:>10.2} ms/op                             │", openfhe.encryption_time_ms);
-        println!("│  Addition:        {:>10.2} ms/op                             │", openfhe.addition_time_ms);
-        println!("│  Multiplication:  {:>10.2} ms/op                             │", openfhe.multiplication_time_ms);
-        println!("│  Decryption:      {:>10.2} ms/op                             │", openfhe.decryption_time_ms);
-        println!("│  Total Time:      {:>10.2} ms                                │", openfhe.total_time_ms);
-        println!("└─────────────────────────────────────────────────────────────────┘\n");
+    // Display per-library results
+    for (name, result) in [("SEAL", &comparison.seal), ("HELib", &comparison.helib), ("OpenFHE", &comparison.openfhe)] {
+        if let Some(result) = result {
+            debug_results_box(name, result);
+            debug_dry_run_estimate(result);
+        }
     }
     
     // Display comparison summary
-    println!("╔═══════════════════════════════════════════════════════════════╗");
-    println!("║     COMPARISON RESULTS                                        ║");
-    println!("╠═══════════════════════════════════════════════════════════════╣");
-    println!("║  Fastest Library: {:43}  ║", comparison.fastest_library);
-    println!("╠═══════════════════════════════════════════════════════════════╣");
-    println!("║  Recommendation:                                              ║");
-    println!("║  {}  ║", format!("{:60}", comparison.recommendation));
-    println!("╚═══════════════════════════════════════════════════════════════╝\n");
+    debug!("╔═══════════════════════════════════════════════════════════════╗");
+    debug!("║     COMPARISON RESULTS                                        ║");
+    debug!("╠═══════════════════════════════════════════════════════════════╣");
+    debug!("║  Fastest Library: {:43}  ║", comparison.fastest_library);
+    debug!("╠═══════════════════════════════════════════════════════════════╣");
+    debug!("║  Ciphertext Expansion (ciphertext / plaintext bytes):         ║");
+    for (name, ratio) in &expansions {
+        debug!("║    {:<10} {:>10.1}x                                      ║", name, ratio);
+    }
+    debug!("╠═══════════════════════════════════════════════════════════════╣");
+    debug!("║  Recommendation:                                              ║");
+    debug!("║  {:60}  ║", comparison.recommendation);
+    debug!("╚═══════════════════════════════════════════════════════════════╝\n");
     
-    println!("   Comparison benchmark completed successfully!\n");
+    Ok(())
+}
+
+// Amortized cost per packed value, the fair comparison between batched and scalar schemes
+fn debug_per_slot_costs(benchmark: &BenchmarkResponse) {
+    debug!("      • Per slot ({} slots): enc {:.1} ns, add {:.1} ns, mul {:.1} ns, dec {:.1} ns",
+        benchmark.slots_per_ciphertext, benchmark.encryption_ns_per_slot, benchmark.addition_ns_per_slot,
+        benchmark.multiplication_ns_per_slot, benchmark.decryption_ns_per_slot);
+}
+
+// One library's box in the comparison table. With repetitions, each time is the
+// mean ± half the width of its 95% confidence interval.
+fn debug_results_box(name: &str, benchmark: &BenchmarkResponse) {
+    let row = |label: &str, metric: &str, value: f64, unit: &str| {
+        let interval = benchmark
+            .summaries
+            .iter()
+            .find(|s| s.metric == metric)
+            .map_or(String::new(), |s| format!(" ± {:.2}", (s.ci95_high_ms - s.ci95_low_ms) / 2.0));
+        debug!("│  {:<63}│", format!("{:<17}{:>10.2} {}{}", label, value, unit, interval));
+    };
+    debug!("┌─────────────────────────────────────────────────────────────────┐");
+    debug!("│     {:<60}│", format!("{} Results", name));
+    if benchmark.repetitions > 1 {
+        debug!("│     {:<60}│", format!("(mean ± 95% CI over {} runs)", benchmark.repetitions));
+    }
+    debug!("├─────────────────────────────────────────────────────────────────┤");
+    row("Key Generation:", "key_gen", benchmark.key_gen_time_ms, "ms");
+    row("Encoding:", "encoding", benchmark.encoding_time_ms, "ms/op");
+    row("Encryption:", "encryption", benchmark.encryption_time_ms, "ms/op");
+    row("Addition:", "addition", benchmark.addition_time_ms, "ms/op");
+    row("Multiplication:", "multiplication", benchmark.multiplication_time_ms, "ms/op");
+    row("Decryption:", "decryption", benchmark.decryption_time_ms, "ms/op");
+    row("Total Time:", "total", benchmark.total_time_ms, "ms");
+    debug!("│  {:<63}│", format!("{:<17}{:>10.1}x", "Expansion:", benchmark.expansion_ratio));
+    debug!("└─────────────────────────────────────────────────────────────────┘\n");
+}
+
+// 95% confidence intervals; only present when the benchmark was repeated
+fn debug_confidence_intervals(benchmark: &BenchmarkResponse) {
+    for summary in &benchmark.summaries {
+        debug!("      • 95% CI {:<16} {:.2} ms [{:.2}, {:.2}] over {} runs",
+            format!("{}:", summary.metric), summary.mean_ms, summary.ci95_low_ms, summary.ci95_high_ms, summary.runs);
+    }
+}
+
+// Serialized size of each key the benchmark generated
+fn debug_key_sizes(benchmark: &BenchmarkResponse) {
+    debug!("      • Key sizes:       public {} B, secret {} B, relin {} B, galois {} B",
+        benchmark.public_key_bytes, benchmark.secret_key_bytes, benchmark.relin_key_bytes, benchmark.galois_key_bytes);
+}
+
+// Only on a dry run: what the server expects the full benchmark to cost
+fn debug_dry_run_estimate(benchmark: &BenchmarkResponse) {
+    if !benchmark.dry_run {
+        return;
+    }
+    debug!("      • Dry run estimate: {:.0} ms, {:.1} MB peak memory",
+        benchmark.estimated_runtime_ms, benchmark.estimated_memory_bytes as f64 / 1e6);
+}
+
+// The server's machine and library versions; absent from older servers
+fn debug_run_metadata(benchmark: &BenchmarkResponse) {
+    let Some(m) = &benchmark.run_metadata else { return };
+    debug!("      • Ran on:          {} ({}, {} cores, {})", m.hostname, m.cpu_model, m.cpu_cores, m.os);
+    debug!("      • Versions:        SEAL {}, HElib {}, OpenFHE {} (server {})",
+        m.seal_version, m.helib_version, m.openfhe_version, m.git_commit);
+}
+
+// Per-operation breakdown; only present when the benchmark ran an operation mix
+fn debug_operation_timings(benchmark: &BenchmarkResponse) {
+    for timing in &benchmark.operation_timings {
+        debug!("      • Mix {:<13} {:>4} × {:.2} ms/op",
+            format!("{}:", timing.operation), timing.samples, timing.avg_time_ms);
+    }
+}
+
+// A ciphertext is always much bigger than the values it encrypts, so a ratio
+// at or below 1.0 means the server didn't measure it properly
+fn check_expansion(benchmark: &BenchmarkResponse) -> Result<(), Box<dyn std::error::Error>> {
+    if benchmark.expansion_ratio <= 1.0 {
+        return Err(format!(
+            "Expected ciphertext expansion > 1.0, got {:.2} ({})",
+            benchmark.expansion_ratio, benchmark.status
+        ).into());
+    }
     Ok(())
 }
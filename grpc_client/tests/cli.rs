@@ -0,0 +1,552 @@
+// grpc_client/tests/cli.rs
+//
+// Runs the client binary against an in-process mock server (canned responses,
+// no HE libraries needed) and checks the machine-readable output formats. The
+// --local-encrypt test needs SEAL: the mock really adds the client's ciphertexts.
+
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{transport::Server, Request, Response, Status};
+
+pub mod he_service {
+    tonic::include_proto!("he_service");
+}
+
+use he_service::{
+    he_service_server::{HeService, HeServiceServer},
+    *,
+};
+
+#[derive(Default)]
+struct MockService {
+    // GenerateKeys for this library hangs, like a stuck backend
+    hung_library: Option<&'static str>,
+    // Requests without `authorization: Bearer <token>` are UNAUTHENTICATED
+    token: Option<&'static str>,
+    // What each RPC carried, e.g. "Encrypt values=[1, 2]" or "Add ciphertexts=2"
+    received: Arc<Mutex<Vec<String>>>,
+    // Every nonce sent with a session request
+    nonces: Arc<Mutex<Vec<u64>>>,
+}
+
+impl MockService {
+    fn receive(&self, what: String) {
+        self.received.lock().unwrap().push(what);
+    }
+
+    fn nonce(&self, nonce: u64) {
+        self.nonces.lock().unwrap().push(nonce);
+    }
+}
+
+fn mock_benchmark(library: &str) -> BenchmarkResponse {
+    BenchmarkResponse {
+        key_gen_time_ms: 5.0,
+        encryption_time_ms: 1.0,
+        addition_time_ms: 0.1,
+        multiplication_time_ms: 2.0,
+        decryption_time_ms: 0.5,
+        status: format!("Benchmark completed for {}", library),
+        total_time_ms: 80.0,
+        encoding_time_ms: 0.05,
+        plaintext_bytes: 1024,
+        ciphertext_bytes: 12288,
+        expansion_ratio: 12.0,
+        operation_timings: vec![],
+        slots_per_ciphertext: 4096,
+        encryption_ns_per_slot: 244.0,
+        addition_ns_per_slot: 24.0,
+        multiplication_ns_per_slot: 488.0,
+        decryption_ns_per_slot: 122.0,
+        summaries: vec![],
+        repetitions: 0,
+        run_metadata: Some(RunMetadata {
+            hostname: "bench-01".to_string(),
+            cpu_model: "Mock CPU @ 3.00GHz".to_string(),
+            cpu_cores: 8,
+            os: "linux x86_64".to_string(),
+            seal_version: "4.1.1".to_string(),
+            helib_version: "2.3.0".to_string(),
+            openfhe_version: "1.2.0".to_string(),
+            git_commit: "0123456789ab".to_string(),
+        }),
+        public_key_bytes: 233_000,
+        secret_key_bytes: 117_000,
+        relin_key_bytes: 467_000,
+        galois_key_bytes: 11_200_000,
+        dry_run: false,
+        estimated_runtime_ms: 0.0,
+        estimated_memory_bytes: 0,
+    }
+}
+
+// Canned intervals around the mock's timings, as a repeated benchmark returns them
+fn mock_summaries(benchmark: &mut BenchmarkResponse, repetitions: i32) {
+    if repetitions <= 1 {
+        return;
+    }
+    benchmark.repetitions = repetitions;
+    benchmark.summaries = [
+        ("key_gen", benchmark.key_gen_time_ms),
+        ("encryption", benchmark.encryption_time_ms),
+        ("total", benchmark.total_time_ms),
+    ]
+    .into_iter()
+    .map(|(metric, mean)| MetricSummary {
+        metric: metric.to_string(),
+        mean_ms: mean,
+        std_dev_ms: 0.1 * mean,
+        ci95_low_ms: 0.9 * mean,
+        ci95_high_ms: 1.1 * mean,
+        runs: repetitions,
+    })
+    .collect();
+}
+
+// A dry run's estimates, as the server would scale them from its warmup
+fn mock_dry_run(benchmark: &mut BenchmarkResponse, dry_run: bool) {
+    if !dry_run {
+        return;
+    }
+    benchmark.dry_run = true;
+    benchmark.estimated_runtime_ms = 3600.0;
+    benchmark.estimated_memory_bytes = 250_000_000;
+    benchmark.status = format!("Dry run: {}", benchmark.status);
+}
+
+#[tonic::async_trait]
+impl HeService for MockService {
+    async fn generate_keys(
+        &self,
+        request: Request<GenerateKeysRequest>,
+    ) -> Result<Response<GenerateKeysResponse>, Status> {
+        if self.hung_library == Some(request.get_ref().library.as_str()) {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+        Ok(Response::new(GenerateKeysResponse {
+            session_id: "0123456789abcdef".to_string(),
+            public_key: vec![],
+            status: "success".to_string(),
+            version: 1,
+        }))
+    }
+
+    async fn encrypt(&self, request: Request<EncryptRequest>) -> Result<Response<EncryptResponse>, Status> {
+        self.receive(format!("Encrypt values={:?}", request.get_ref().values));
+        self.nonce(request.get_ref().nonce);
+        Ok(Response::new(EncryptResponse {
+            ciphertext: vec![0; 16],
+            status: "success".to_string(),
+            ciphertext_id: "ct-1".to_string(),
+            version: 2,
+        }))
+    }
+
+    async fn decrypt(&self, request: Request<DecryptRequest>) -> Result<Response<DecryptResponse>, Status> {
+        self.nonce(request.get_ref().nonce);
+        Ok(Response::new(DecryptResponse {
+            values: vec![10, 20, 30],
+            // Comma and quotes exercise CSV escaping
+            status: "success, \"decrypted\"".to_string(),
+        }))
+    }
+
+    async fn add(&self, request: Request<BinaryOpRequest>) -> Result<Response<BinaryOpResponse>, Status> {
+        let request = request.into_inner();
+        let ciphertexts = [&request.ciphertext1, &request.ciphertext2].iter().filter(|c| !c.is_empty()).count();
+        self.receive(format!("Add ciphertexts={}", ciphertexts));
+        self.nonce(request.nonce);
+        Ok(Response::new(BinaryOpResponse {
+            result_ciphertext: mock_add(&request.ciphertext1, &request.ciphertext2)?,
+            status: "success".to_string(),
+            ciphertext_size: 2,
+            version: 2,
+        }))
+    }
+
+    async fn multiply(&self, request: Request<BinaryOpRequest>) -> Result<Response<BinaryOpResponse>, Status> {
+        self.nonce(request.get_ref().nonce);
+        Ok(Response::new(BinaryOpResponse {
+            result_ciphertext: vec![],
+            status: "success".to_string(),
+            ciphertext_size: 2,
+            version: 2,
+        }))
+    }
+
+    async fn run_benchmark(
+        &self,
+        request: Request<BenchmarkRequest>,
+    ) -> Result<Response<BenchmarkResponse>, Status> {
+        let request = request.into_inner();
+        self.receive(format!("RunBenchmark {} dry_run={}", request.library, request.dry_run));
+        let mut response = mock_benchmark(&request.library);
+        mock_summaries(&mut response, request.repetitions);
+        mock_dry_run(&mut response, request.dry_run);
+        // Echo the mix back (weight as sample count) so tests can see it arrived
+        response.operation_timings = request
+            .operation_mix
+            .into_iter()
+            .map(|(operation, weight)| OperationTiming {
+                operation,
+                samples: weight as i32,
+                total_time_ms: weight,
+                avg_time_ms: 1.0,
+            })
+            .collect();
+        Ok(Response::new(response))
+    }
+
+    async fn run_comparison_benchmark(
+        &self,
+        request: Request<BenchmarkRequest>,
+    ) -> Result<Response<ComparisonBenchmarkResponse>, Status> {
+        let request = request.into_inner();
+        self.receive(format!("RunComparisonBenchmark dry_run={}", request.dry_run));
+        let [seal, helib, openfhe] = ["SEAL", "HELib", "OpenFHE"].map(|library| {
+            let mut benchmark = mock_benchmark(library);
+            mock_summaries(&mut benchmark, request.repetitions);
+            mock_dry_run(&mut benchmark, request.dry_run);
+            benchmark
+        });
+        Ok(Response::new(ComparisonBenchmarkResponse {
+            seal: Some(seal),
+            helib: Some(helib),
+            openfhe: Some(openfhe),
+            fastest_library: "SEAL".to_string(),
+            recommendation: "SEAL recommended".to_string(),
+        }))
+    }
+
+    async fn aggregate_window(
+        &self,
+        _request: Request<AggregateWindowRequest>,
+    ) -> Result<Response<AggregateWindowResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+
+    async fn decrypt_all(
+        &self,
+        _request: Request<DecryptAllRequest>,
+    ) -> Result<Response<DecryptAllResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+
+    async fn stress_test(
+        &self,
+        _request: Request<StressTestRequest>,
+    ) -> Result<Response<StressTestResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+
+    async fn verify_keys(
+        &self,
+        _request: Request<VerifyKeysRequest>,
+    ) -> Result<Response<VerifyKeysResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+
+    async fn accumulate(
+        &self,
+        _request: Request<AccumulateRequest>,
+    ) -> Result<Response<AccumulateResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+
+    async fn shutdown(
+        &self,
+        _request: Request<ShutdownRequest>,
+    ) -> Result<Response<ShutdownResponse>, Status> {
+        Err(Status::unimplemented("not used by the client"))
+    }
+}
+
+// A real SEAL add of client ciphertexts, as the server does it; empty (the
+// canned response) for requests without any
+#[cfg(feature = "local-encrypt")]
+#[allow(clippy::result_large_err)]
+fn mock_add(ciphertext1: &[u8], ciphertext2: &[u8]) -> Result<Vec<u8>, Status> {
+    use he_benchmark::{add, Ciphertext, Context};
+
+    if ciphertext1.is_empty() && ciphertext2.is_empty() {
+        return Ok(vec![]);
+    }
+    let error = |e: he_benchmark::SealError| Status::invalid_argument(e.to_string());
+    let context = Context::new(8192, 1032193).map_err(error)?;
+    let a = Ciphertext::from_bytes(&context, ciphertext1).map_err(error)?;
+    let b = Ciphertext::from_bytes(&context, ciphertext2).map_err(error)?;
+    add(&context, &a, &b).and_then(|sum| sum.to_bytes()).map_err(error)
+}
+
+#[cfg(not(feature = "local-encrypt"))]
+#[allow(clippy::result_large_err)]
+fn mock_add(_ciphertext1: &[u8], _ciphertext2: &[u8]) -> Result<Vec<u8>, Status> {
+    Ok(vec![])
+}
+
+/// Start the mock server on a free port and return its address
+async fn start_mock_server(service: MockService) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let expected = service.token.map(|token| format!("Bearer {}", token));
+    #[allow(clippy::result_large_err)]
+    let check_token = move |request: Request<()>| match &expected {
+        Some(expected) if request.metadata().get("authorization").and_then(|v| v.to_str().ok()) != Some(expected) => {
+            Err(Status::unauthenticated("invalid bearer token"))
+        }
+        _ => Ok(request),
+    };
+
+    tokio::spawn(
+        Server::builder()
+            .add_service(HeServiceServer::with_interceptor(service, check_token))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    format!("http://{}", addr)
+}
+
+/// Run the client binary against `service` with extra `args`
+async fn run_client_against(service: MockService, args: &[&str]) -> Output {
+    let server = start_mock_server(service).await;
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+
+    tokio::task::spawn_blocking(move || {
+        Command::new(env!("CARGO_BIN_EXE_he-grpc-client"))
+            .args(["--server", &server])
+            .args(&args)
+            .output()
+            .unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+/// Run the client binary with extra `args` and return its stdout
+async fn run_client(args: &[&str]) -> String {
+    let output = run_client_against(MockService::default(), args).await;
+
+    assert!(
+        output.status.success(),
+        "client failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_json_output_is_parseable() {
+    let stdout = run_client(&["--format", "json"]).await;
+
+    let records: serde_json::Value = serde_json::from_str(&stdout).expect("stdout should be valid JSON");
+    let records = records.as_array().unwrap();
+
+    // 6 tests per library + 3 comparison results + the comparison summary
+    assert_eq!(records.len(), 6 * 3 + 4);
+    for record in records {
+        assert!(record["operation"].is_string());
+        assert!(record["status"].is_string());
+        assert!(record["elapsed_ms"].is_number());
+    }
+
+    let seal_benchmark = records
+        .iter()
+        .find(|r| r["library"] == "SEAL" && r["operation"] == "benchmark")
+        .unwrap();
+    assert_eq!(seal_benchmark["benchmark"]["expansion_ratio"], 12.0);
+    assert_eq!(seal_benchmark["benchmark"]["slots_per_ciphertext"], 4096);
+    assert_eq!(seal_benchmark["benchmark"]["encryption_ns_per_slot"], 244.0);
+    assert_eq!(seal_benchmark["benchmark"]["run_metadata"]["cpu_cores"], 8);
+    assert_eq!(seal_benchmark["benchmark"]["run_metadata"]["seal_version"], "4.1.1");
+    assert_eq!(seal_benchmark["benchmark"]["galois_key_bytes"], 11_200_000);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_csv_output_has_one_row_per_test() {
+    let stdout = run_client(&["--format", "csv"]).await;
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert!(lines[0].starts_with("library,operation,status,elapsed_ms"));
+    assert_eq!(lines.len(), 1 + 6 * 3 + 4);
+    assert!(lines.iter().any(|l| l.starts_with("SEAL,decrypt,\"success, \"\"decrypted\"\"\",")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_quiet_prints_only_the_summary() {
+    let stdout = run_client(&["--quiet"]).await;
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    // One pass/fail line per test group plus the final status
+    assert_eq!(lines.len(), 5, "unexpected output:\n{}", stdout);
+    assert!(lines[..4].iter().all(|l| l.contains("passed")));
+    assert!(lines[4].contains("ALL TESTS PASSED"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_verbose_adds_per_operation_details() {
+    let default = run_client(&[]).await;
+    let verbose = run_client(&["--verbose"]).await;
+
+    assert!(!default.contains("response bytes"));
+    assert!(default.contains("Versions:        SEAL 4.1.1, HElib 2.3.0, OpenFHE 1.2.0 (server 0123456789ab)"));
+    assert!(verbose.lines().any(|l| l.contains("SEAL encrypt:") && l.contains("response bytes in")));
+    assert!(verbose.lines().count() > default.lines().count());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_operation_mix_is_sent_with_each_benchmark() {
+    let stdout = run_client(&["--operations", "add=1, multiply=4"]).await;
+
+    let multiply_lines = stdout.lines().filter(|l| l.contains("Mix multiply:") && l.contains(" 4 ×"));
+    assert_eq!(multiply_lines.count(), 3, "unexpected output:\n{}", stdout);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_repetitions_print_confidence_intervals() {
+    let stdout = run_client(&["--repetitions", "10"]).await;
+
+    // One line per library benchmark, and a ± column in each comparison box
+    let ci_lines = stdout.lines().filter(|l| l.contains("95% CI total:") && l.contains("[72.00, 88.00] over 10 runs"));
+    assert_eq!(ci_lines.count(), 3, "unexpected output:\n{}", stdout);
+    assert_eq!(stdout.matches("(mean ± 95% CI over 10 runs)").count(), 3, "unexpected output:\n{}", stdout);
+    assert!(stdout.lines().any(|l| l.contains("Total Time:") && l.contains("80.00 ms ± 8.00")), "unexpected output:\n{}", stdout);
+
+    let json = run_client(&["--format", "json", "--repetitions", "10"]).await;
+    let records: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let benchmark = records.as_array().unwrap().iter().find(|r| r["operation"] == "benchmark").unwrap();
+    assert_eq!(benchmark["benchmark"]["confidence_intervals"][0]["runs"], 10);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dry_run_reports_estimates_instead_of_results() {
+    let service = MockService::default();
+    let received = Arc::clone(&service.received);
+
+    let output = run_client_against(service, &["--dry-run"]).await;
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "unexpected output:\n{}", stdout);
+    // Three library benchmarks and the three comparison boxes
+    let estimates = stdout.lines().filter(|l| l.contains("Dry run estimate: 3600 ms, 250.0 MB peak memory"));
+    assert_eq!(estimates.count(), 6, "unexpected output:\n{}", stdout);
+
+    // Every benchmark the server was asked for was a dry run
+    let benchmarks: Vec<String> = received.lock().unwrap().iter().filter(|r| r.contains("Benchmark")).cloned().collect();
+    assert_eq!(benchmarks.len(), 4, "{:?}", benchmarks);
+    assert!(benchmarks.iter().all(|r| r.ends_with("dry_run=true")), "{:?}", benchmarks);
+
+    // Estimates only show up in json for a dry run
+    let json = run_client(&["--format", "json", "--dry-run"]).await;
+    let records: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let benchmark = records.as_array().unwrap().iter().find(|r| r["operation"] == "benchmark").unwrap();
+    assert_eq!(benchmark["benchmark"]["estimated_memory_bytes"], 250_000_000);
+    let json = run_client(&["--format", "json"]).await;
+    assert!(!json.contains("estimated_runtime_ms"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_baseline_fails_the_run_on_a_regressed_metric() {
+    let mut records: serde_json::Value = serde_json::from_str(&run_client(&["--format", "json"]).await).unwrap();
+    let path = std::env::temp_dir().join(format!("he-client-baseline-{}.json", std::process::id()));
+    let baseline = path.to_str().unwrap();
+
+    // The same timings as this run: nothing regressed
+    std::fs::write(&path, records.to_string()).unwrap();
+    let output = run_client_against(MockService::default(), &["--quiet", "--baseline", baseline]).await;
+    assert!(output.status.success(), "unexpected output:\n{}", String::from_utf8_lossy(&output.stdout));
+
+    // A baseline where SEAL multiplied twice as fast
+    let seal = records
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|r| r["library"] == "SEAL" && r["operation"] == "benchmark")
+        .unwrap();
+    let multiplication = seal["benchmark"]["multiplication_time_ms"].as_f64().unwrap();
+    seal["benchmark"]["multiplication_time_ms"] = (multiplication / 2.0).into();
+    std::fs::write(&path, records.to_string()).unwrap();
+
+    let output = run_client_against(MockService::default(), &["--quiet", "--baseline", baseline, "--tolerance", "10"]).await;
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    assert!(!output.status.success());
+    assert!(stderr.contains("SEAL benchmark multiplication_time_ms"), "unexpected stderr:\n{}", stderr);
+    assert!(stderr.contains("(+100.0%)"), "unexpected stderr:\n{}", stderr);
+
+    // Within a generous enough tolerance it passes again
+    let output = run_client_against(MockService::default(), &["--quiet", "--baseline", baseline, "--tolerance", "150"]).await;
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_clients_never_reuse_a_nonce() {
+    // Two clients at once, as when they share a session
+    let nonces = Arc::new(Mutex::new(Vec::new()));
+    let service = || MockService { nonces: Arc::clone(&nonces), ..Default::default() };
+    let (first, second) = tokio::join!(
+        run_client_against(service(), &["--quiet"]),
+        run_client_against(service(), &["--quiet"]),
+    );
+    assert!(first.status.success() && second.status.success());
+
+    let mut nonces = nonces.lock().unwrap().clone();
+    let sent = nonces.len();
+    assert!(sent >= 6, "{:?}", nonces);
+    nonces.sort_unstable();
+    nonces.dedup();
+    assert_eq!(nonces.len(), sent, "a nonce was sent twice");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_hung_backend_times_out_while_others_pass() {
+    let started = Instant::now();
+    let output = run_client_against(
+        MockService { hung_library: Some("HELib"), ..Default::default() },
+        &["--quiet", "--timeout", "2"],
+    )
+    .await;
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    // The other libraries ran alongside, so the whole suite takes about one timeout
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert!(stdout.contains("✓ SEAL: passed"), "unexpected output:\n{}", stdout);
+    assert!(stdout.contains("✓ OpenFHE: passed"), "unexpected output:\n{}", stdout);
+    assert!(stdout.contains("✓ Comparison: passed"), "unexpected output:\n{}", stdout);
+    assert!(stdout.contains("✗ HELib: TIMED OUT after 2s"), "unexpected output:\n{}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("tests failed for: HELib"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_token_is_sent_as_bearer_metadata() {
+    let service = || MockService { token: Some("s3cret"), ..Default::default() };
+
+    let output = run_client_against(service(), &["--quiet", "--token", "s3cret"]).await;
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "unexpected output:\n{}", stdout);
+    assert!(stdout.contains("ALL TESTS PASSED"), "unexpected output:\n{}", stdout);
+
+    for args in [&["--quiet"][..], &["--quiet", "--token", "wrong"][..]] {
+        let output = run_client_against(service(), args).await;
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(!output.status.success(), "{:?} should be rejected", args);
+        assert!(stdout.contains("✗ SEAL: FAILED") && stdout.contains("invalid bearer token"), "unexpected output:\n{}", stdout);
+    }
+}
+
+#[cfg(feature = "local-encrypt")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_local_encrypt_sends_the_server_only_ciphertexts() {
+    let service = MockService::default();
+    let received = Arc::clone(&service.received);
+
+    let output = run_client_against(service, &["--local-encrypt"]).await;
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "unexpected output:\n{}", stdout);
+    assert!(stdout.contains("Decrypted sum: [11, 22, 33, 44, 55]"), "unexpected output:\n{}", stdout);
+
+    // One Add with two ciphertexts, and not a single plaintext value
+    assert_eq!(*received.lock().unwrap(), ["Add ciphertexts=2"]);
+}
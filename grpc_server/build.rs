@@ -1,11 +1,34 @@
 // grpc_server/build.rs
 // This compiles the .proto files into Rust code
 
+use std::env;
+use std::path::PathBuf;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Compile the proto file
-    tonic_build::compile_protos("../proto/he_service.proto")?;
+    // Compile the proto file, keeping the encoded descriptors for server reflection
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("he_service_descriptor.bin"))
+        .compile(&["../proto/he_service.proto"], &["../proto"])?;
     
     println!("cargo:rerun-if-changed=../proto/he_service.proto");
+
+    // Commit the server was built from, for RunMetadata.git_commit
+    let commit = std::process::Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=HE_GIT_COMMIT={}", commit);
+    for path in ["../.git/HEAD", "../.git/refs/heads"] {
+        if std::path::Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
     
     Ok(())
 }
@@ -0,0 +1,109 @@
+// grpc_server/src/accumulator.rs
+//
+// Session-scoped running sums (Accumulate RPC)
+//
+// Each SEAL session can keep one encrypted running sum of every value sent to
+// it. The sum lives in the session's context (the shared one from the KeyCache,
+// or the one holding the client's imported public key), so unlike an
+// AggregateWindow it needs no keys of its own and also works on a --no-decrypt
+// server. Without a secret key the sum isn't decrypted and its noise isn't
+// measured: the encrypted sum comes back, with an estimate of when a refresh is
+// due, and the client decrypts it.
+
+use he_benchmark::{AccumulatorState, BatchEncoder, Context, Decryptor, EncryptedAccumulator, Encryptor};
+use std::sync::Arc;
+
+pub struct SessionAccumulator {
+    context: Arc<Context>,
+    // None until the first value (and after a reset)
+    state: Option<AccumulatorState>,
+}
+
+// SAFETY: as for EncryptedWindow - the ciphertext is owned exclusively by this
+// struct and the server only touches it while holding its Mutex
+unsafe impl Send for SessionAccumulator {}
+
+pub struct AccumulateOutcome {
+    /// Decrypted sum; None on a --no-decrypt server
+    pub total: Option<i64>,
+    pub count: u64,
+    /// The sum was refreshed (decrypted and re-encrypted) during this call
+    pub refreshed: bool,
+    /// --no-decrypt only: the estimate says the sum needs a refresh
+    pub refresh_needed: bool,
+    /// --no-decrypt only: the serialized encrypted sum
+    pub sum_ciphertext: Vec<u8>,
+}
+
+impl SessionAccumulator {
+    pub fn new(context: Arc<Context>) -> Self {
+        SessionAccumulator { context, state: None }
+    }
+
+    /// Encrypt `values` and add each into the sum (after starting over from zero
+    /// if `reset`). With `public_only` the sum is never decrypted.
+    pub fn accumulate(&mut self, values: &[i64], reset: bool, public_only: bool) -> Result<AccumulateOutcome, String> {
+        let context = &*self.context;
+        let encoder = BatchEncoder::new(context).map_err(|e| format!("Failed to create encoder: {}", e))?;
+        let encryptor = Encryptor::new(context).map_err(|e| format!("Failed to create encryptor: {}", e))?;
+        // Encrypt everything first, so a bad value leaves the stored sum untouched
+        let ciphers = values
+            .iter()
+            .map(|&value| encryptor.encrypt(&encoder.encode(&[value])?))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Encryption failed: {}", e))?;
+
+        let mut accumulator = match self.state.take().filter(|_| !reset) {
+            Some(state) => EncryptedAccumulator::from_state(context, state),
+            None => EncryptedAccumulator::new(context),
+        }
+        .map_err(|e| format!("Failed to create accumulator: {}", e))?;
+        for cipher in &ciphers {
+            accumulator.add(cipher).map_err(|e| format!("Addition failed: {}", e))?;
+        }
+
+        let mut outcome = AccumulateOutcome {
+            total: None,
+            count: accumulator.count(),
+            refreshed: false,
+            refresh_needed: false,
+            sum_ciphertext: Vec::new(),
+        };
+        if public_only {
+            outcome.refresh_needed = accumulator.needs_refresh(None).map_err(|e| e.to_string())?;
+            outcome.sum_ciphertext = accumulator.value().to_bytes().map_err(|e| format!("Serialization failed: {}", e))?;
+        } else {
+            let decryptor = Decryptor::new(context).map_err(|e| format!("Failed to create decryptor: {}", e))?;
+            if accumulator.needs_refresh(Some(&decryptor)).map_err(|e| e.to_string())? {
+                accumulator.refresh(&decryptor, &encryptor).map_err(|e| format!("Refresh failed: {}", e))?;
+                outcome.refreshed = true;
+            }
+            let plain = decryptor.decrypt(accumulator.value()).map_err(|e| format!("Decryption failed: {}", e))?;
+            let decoded = encoder.decode(&plain).map_err(|e| format!("Decoding failed: {}", e))?;
+            outcome.total = Some(decoded.first().copied().unwrap_or(0));
+        }
+
+        self.state = Some(accumulator.into_state());
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_carries_over_between_calls_until_reset() {
+        let context = Arc::new(Context::new(8192, 1032193).unwrap());
+        let mut accumulator = SessionAccumulator::new(context);
+
+        accumulator.accumulate(&[5, 10], false, false).unwrap();
+        let outcome = accumulator.accumulate(&[-3], false, false).unwrap();
+        assert_eq!((outcome.total, outcome.count), (Some(12), 3));
+        assert!(outcome.sum_ciphertext.is_empty());
+
+        let outcome = accumulator.accumulate(&[4], true, true).unwrap();
+        assert_eq!((outcome.total, outcome.count, outcome.refresh_needed), (None, 1, false));
+        assert!(!outcome.sum_ciphertext.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+// grpc_server/src/auth.rs
+//
+// Bearer-token authentication for HEService
+//
+// Without it any client that can reach the port can create sessions and run
+// benchmarks. With a verifier configured (GRPC_AUTH_TOKEN), every HEService
+// request must carry `authorization: Bearer <token>` metadata, or it is
+// UNAUTHENTICATED before a handler runs. Health and reflection stay open, so
+// load balancers and `grpcurl list` work without a token.
+
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// Decides whether a bearer token is acceptable
+pub trait TokenVerifier: Send + Sync {
+    fn verify(&self, token: &str) -> bool;
+}
+
+/// Accepts exactly one token, compared in constant time
+pub struct SharedSecret(String);
+
+impl SharedSecret {
+    pub fn new(secret: impl Into<String>) -> Self {
+        SharedSecret(secret.into())
+    }
+}
+
+impl TokenVerifier for SharedSecret {
+    fn verify(&self, token: &str) -> bool {
+        let (expected, given) = (self.0.as_bytes(), token.as_bytes());
+        // Only the length leaks; every byte is compared either way
+        expected.len() == given.len() && expected.iter().zip(given).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+/// Rejects HEService requests without a valid bearer token; lets everything
+/// through when disabled
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    verifier: Option<Arc<dyn TokenVerifier>>,
+}
+
+impl AuthInterceptor {
+    pub fn new(verifier: impl TokenVerifier + 'static) -> Self {
+        AuthInterceptor { verifier: Some(Arc::new(verifier)) }
+    }
+
+    /// No authentication (the default when GRPC_AUTH_TOKEN is unset)
+    pub fn disabled() -> Self {
+        AuthInterceptor { verifier: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.verifier.is_some()
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(verifier) = &self.verifier else {
+            return Ok(request);
+        };
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("missing bearer token (authorization: Bearer <token>)"))?;
+        if !verifier.verify(token) {
+            return Err(Status::unauthenticated("invalid bearer token"));
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(value) = authorization {
+            request.metadata_mut().insert("authorization", value.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_only_the_configured_token_gets_through() {
+        let mut auth = AuthInterceptor::new(SharedSecret::new("s3cret"));
+
+        assert!(auth.call(request_with(Some("Bearer s3cret"))).is_ok());
+        for rejected in [None, Some("Bearer wrong"), Some("Bearer s3cre"), Some("s3cret"), Some("Basic s3cret")] {
+            let status = auth.call(request_with(rejected)).unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unauthenticated, "{:?}", rejected);
+        }
+
+        // Disabled: no token needed
+        assert!(AuthInterceptor::disabled().call(request_with(None)).is_ok());
+    }
+
+    #[test]
+    fn test_custom_verifier_is_consulted() {
+        struct Prefix;
+        impl TokenVerifier for Prefix {
+            fn verify(&self, token: &str) -> bool {
+                token.starts_with("team-")
+            }
+        }
+
+        let mut auth = AuthInterceptor::new(Prefix);
+        assert!(auth.call(request_with(Some("Bearer team-a"))).is_ok());
+        assert!(auth.call(request_with(Some("Bearer other"))).is_err());
+    }
+}
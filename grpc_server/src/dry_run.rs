@@ -0,0 +1,91 @@
+// grpc_server/src/dry_run.rs
+//
+// Dry runs (BenchmarkRequest.dry_run)
+//
+// A long benchmark that dies on a bad parameter, an unavailable library or an
+// out-of-memory kill wastes however long it ran first. A dry run does
+// everything the real one would up to the timed loop: context creation, key
+// generation and validation, then DRY_RUN_OPS operations of each kind. Those
+// warmup timings are scaled up to the requested operation count to estimate
+// the full run's time and peak memory.
+
+use crate::he_service::BenchmarkResponse;
+use crate::stats::run_failed;
+
+// Operations a dry run performs: add and multiply need two ciphertexts, and a
+// third keeps the per-operation average from being a single sample
+pub const DRY_RUN_OPS: i32 = 3;
+
+/// Operations to actually run for a request of `num_operations`
+pub fn warmup_operations(num_operations: i32) -> i32 {
+    num_operations.clamp(1, DRY_RUN_OPS)
+}
+
+/// Turn a warmup run of `warmup_ops` operations into estimates for the full
+/// benchmark of `num_operations` operations, run `repetitions` times
+///
+/// Setup (context and keys) is paid once per run; everything else grows with
+/// the operation count. The benchmarks keep every plaintext and ciphertext of a
+/// run alive until it ends, so peak memory is one of each per operation plus
+/// the keys. A failed warmup is returned as is, without estimates.
+pub fn estimate_full_run(
+    mut warmup: BenchmarkResponse,
+    warmup_ops: i32,
+    num_operations: i32,
+    repetitions: usize,
+) -> BenchmarkResponse {
+    warmup.dry_run = true;
+    if run_failed(&warmup) {
+        return warmup;
+    }
+
+    let scale = num_operations.max(warmup_ops) as f64 / warmup_ops.max(1) as f64;
+    let per_run_ms = warmup.key_gen_time_ms + (warmup.total_time_ms - warmup.key_gen_time_ms).max(0.0) * scale;
+    warmup.estimated_runtime_ms = per_run_ms * repetitions as f64;
+
+    let keys = warmup.public_key_bytes + warmup.secret_key_bytes + warmup.relin_key_bytes + warmup.galois_key_bytes;
+    let per_operation = warmup.plaintext_bytes + warmup.ciphertext_bytes;
+    warmup.estimated_memory_bytes = keys + per_operation * num_operations.max(warmup_ops) as i64;
+
+    warmup.status = format!(
+        "Dry run: {} ({} warmup operations); {} operations x {} estimated at {:.0} ms, {:.1} MB",
+        warmup.status,
+        warmup_ops,
+        num_operations,
+        repetitions,
+        warmup.estimated_runtime_ms,
+        warmup.estimated_memory_bytes as f64 / 1e6
+    );
+    warmup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimates_scale_the_warmup_but_not_the_setup() {
+        let warmup = BenchmarkResponse {
+            key_gen_time_ms: 40.0,
+            total_time_ms: 46.0,
+            plaintext_bytes: 1_000,
+            ciphertext_bytes: 9_000,
+            public_key_bytes: 100_000,
+            relin_key_bytes: 400_000,
+            status: "SEAL benchmark complete: 3 operations".to_string(),
+            ..Default::default()
+        };
+
+        let estimate = estimate_full_run(warmup, warmup_operations(300), 300, 2);
+        assert!(estimate.dry_run);
+        // 40 ms of keys plus 6 ms of operations x 100, per run
+        assert_eq!(estimate.estimated_runtime_ms, 2.0 * (40.0 + 600.0));
+        assert_eq!(estimate.estimated_memory_bytes, 500_000 + 300 * 10_000);
+        assert!(estimate.status.starts_with("Dry run: SEAL benchmark complete"), "{}", estimate.status);
+
+        // A failed setup has nothing to scale
+        let failed = estimate_full_run(BenchmarkResponse { status: "Failed".to_string(), ..Default::default() }, 3, 300, 1);
+        assert_eq!((failed.status.as_str(), failed.estimated_runtime_ms), ("Failed", 0.0));
+        assert_eq!(warmup_operations(1), 1);
+    }
+}
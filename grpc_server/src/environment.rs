@@ -0,0 +1,105 @@
+// grpc_server/src/environment.rs
+//
+// The machine a benchmark ran on (BenchmarkResponse.run_metadata)
+//
+// A timing means little without the CPU it was measured on and the library
+// versions that produced it. This is collected once, at the first benchmark,
+// and attached to every result after that.
+
+use std::sync::OnceLock;
+
+use crate::he_service::RunMetadata;
+
+// Set by build.rs
+const GIT_COMMIT: &str = env!("HE_GIT_COMMIT");
+
+/// This server's RunMetadata (collected on first use)
+pub fn run_metadata() -> RunMetadata {
+    static METADATA: OnceLock<RunMetadata> = OnceLock::new();
+    METADATA.get_or_init(collect).clone()
+}
+
+fn collect() -> RunMetadata {
+    let version = |library: &str| {
+        he_benchmark::library_versions()
+            .into_iter()
+            .find(|(name, _)| name == library)
+            .map(|(_, version)| version)
+            .unwrap_or_default()
+    };
+
+    RunMetadata {
+        hostname: hostname(),
+        cpu_model: cpu_model().unwrap_or_else(|| "unknown".to_string()),
+        cpu_cores: std::thread::available_parallelism().map(|n| n.get() as i32).unwrap_or(0),
+        os: os(),
+        seal_version: version("SEAL"),
+        helib_version: version("HElib"),
+        openfhe_version: version("OpenFHE"),
+        git_commit: GIT_COMMIT.to_string(),
+    }
+}
+
+// First line of a file, trimmed; None if it is missing or empty
+fn read_line(path: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let line = contents.lines().next()?.trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+fn hostname() -> String {
+    read_line("/proc/sys/kernel/hostname")
+        .or_else(|| read_line("/etc/hostname"))
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// /proc/cpuinfo on Linux ("model name", or "Hardware" on some ARM boards), sysctl on macOS
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo") {
+        let model = cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim(), "model name" | "Hardware").then(|| value.trim().to_string())
+        });
+        if model.is_some() {
+            return model;
+        }
+    }
+    command_output("sysctl", &["-n", "machdep.cpu.brand_string"])
+}
+
+// "linux x86_64 (6.8.0-45-generic)"; the kernel release only where /proc has it
+fn os() -> String {
+    let os = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+    match read_line("/proc/sys/kernel/osrelease") {
+        Some(release) => format!("{} ({})", os, release),
+        None => os,
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    let text = text.trim();
+    (output.status.success() && !text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_names_the_machine_and_every_linked_library() {
+        let metadata = run_metadata();
+        assert!(!metadata.hostname.is_empty());
+        assert!(!metadata.cpu_model.is_empty());
+        assert!(metadata.cpu_cores > 0);
+        assert!(metadata.os.starts_with(std::env::consts::OS));
+        // The server is built with all three libraries
+        for version in [&metadata.seal_version, &metadata.helib_version, &metadata.openfhe_version] {
+            assert!(!version.is_empty(), "{:?}", metadata);
+        }
+        assert!(!metadata.git_commit.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+// grpc_server/src/keys.rs
+//
+// Shared SEAL contexts and evaluation keys for session RPCs
+//
+// Relinearization and Galois keys take far longer to generate than the
+// operations that use them, and regenerating them on every call left the server
+// mostly doing keygen. Sessions with the same parameters now share one pooled
+// context, and its evaluation keys are generated once.
+//
+// Evaluation keys are derived from a secret key and are useless (silently
+// wrong) under any other, so they are cached per context id - i.e. per secret
+// key - never per parameter set alone. Benchmarks don't go through here: they
+// time key generation on purpose.
+//
+// HElib session ops used to build a context and generate a key pair on every
+// call, which is nearly all of an Encrypt/Add/Multiply's time
+// (examples/helib_setup_cache.rs measures both paths). They now share one
+// `HElibKeys` per (m, p, r), built on first use.
+//
+// A session can instead bring its own keys (`ImportedKeys`, required on a
+// --no-decrypt server): the client's public and evaluation keys are loaded into
+// a context with no secret key, kept by the session rather than cached here.
+
+use he_benchmark::{Context, ContextPool, GaloisKeys, HEContext, HEPublicKey, HESecretKey, RelinKeys};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+// Same primes as Context::new, so a pooled context behaves like a fresh one
+const COEFF_MODULUS_BITS: [u64; 3] = [36, 36, 37];
+
+#[derive(Default)]
+pub struct KeyCache {
+    contexts: ContextPool,
+    relin_keys: Mutex<HashMap<u64, Arc<RelinKeys>>>,
+    galois_keys: Mutex<HashMap<u64, Arc<GaloisKeys>>>,
+    helib: Mutex<HashMap<(u64, u64, u64), SharedHElibKeys>>,
+}
+
+pub type SharedHElibKeys = Arc<Mutex<HElibKeys>>;
+
+/// An HElib context with the key pair generated under it
+///
+/// Behind a Mutex in the cache: the wrapper doesn't promise HElib/NTL calls on
+/// one key from several threads at once are safe, so HElib ops take turns.
+pub struct HElibKeys {
+    // Dropped in field order: both keys point into the context, and the public
+    // key into the secret key
+    pub public_key: HEPublicKey,
+    pub secret_key: HESecretKey,
+    pub context: HEContext,
+}
+
+/// Keys a client generated itself and sent with GenerateKeys: a context holding
+/// only the client's public key, and the client's evaluation keys for it
+#[derive(Clone)]
+pub struct ImportedKeys {
+    pub context: Arc<Context>,
+    pub relin_keys: Arc<RelinKeys>,
+    pub galois_keys: Arc<GaloisKeys>,
+}
+
+impl ImportedKeys {
+    /// Load serialized keys for these parameters (the pooled contexts' primes).
+    /// No key generator is created, so the server never has a secret key for them.
+    pub fn load(
+        poly_modulus_degree: u64,
+        plain_modulus: u64,
+        public_key: &[u8],
+        relin_keys: &[u8],
+        galois_keys: &[u8],
+    ) -> Result<Self, String> {
+        let context = Context::with_public_key(poly_modulus_degree, plain_modulus, public_key)
+            .map_err(|e| format!("public_key: {}", e))?;
+        let relin_keys = RelinKeys::from_bytes(&context, relin_keys).map_err(|e| format!("relin_keys: {}", e))?;
+        let galois_keys = GaloisKeys::from_bytes(&context, galois_keys).map_err(|e| format!("galois_keys: {}", e))?;
+        Ok(ImportedKeys {
+            context: Arc::new(context),
+            relin_keys: Arc::new(relin_keys),
+            galois_keys: Arc::new(galois_keys),
+        })
+    }
+}
+
+impl KeyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared BFV context for these parameters
+    pub fn context(&self, poly_modulus_degree: u64, plain_modulus: u64) -> Result<Arc<Context>, String> {
+        self.contexts
+            .get(poly_modulus_degree, plain_modulus, &COEFF_MODULUS_BITS)
+            .map_err(|e| format!("Failed to create context: {}", e))
+    }
+
+    /// Relinearization keys for `context`, generated on first use
+    pub fn relin_keys(&self, context: &Context) -> Result<Arc<RelinKeys>, String> {
+        cached(&self.relin_keys, context, RelinKeys::generate)
+            .map_err(|e| format!("Failed to generate relin keys: {}", e))
+    }
+
+    /// Galois (rotation) keys for `context`, generated on first use
+    pub fn galois_keys(&self, context: &Context) -> Result<Arc<GaloisKeys>, String> {
+        cached(&self.galois_keys, context, GaloisKeys::generate)
+            .map_err(|e| format!("Failed to generate galois keys: {}", e))
+    }
+
+    /// HElib context and key pair for (m, p, r), generated on first use
+    pub fn helib(&self, m: u64, p: u64, r: u64) -> Result<SharedHElibKeys, String> {
+        // Held locked while generating, as in `cached`
+        let mut helib = self.helib.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(cached) = helib.get(&(m, p, r)) {
+            return Ok(Arc::clone(cached));
+        }
+
+        let context = HEContext::new(m, p, r).map_err(|e| format!("HELib context error: {}", e))?;
+        let secret_key = HESecretKey::generate(&context).map_err(|e| format!("HELib key error: {}", e))?;
+        let public_key = secret_key.public_key().map_err(|e| format!("HELib public key error: {}", e))?;
+        let generated = Arc::new(Mutex::new(HElibKeys { public_key, secret_key, context }));
+        helib.insert((m, p, r), Arc::clone(&generated));
+        Ok(generated)
+    }
+
+    /// Build the context and both evaluation keys for these parameters now,
+    /// so the first session that asks for them finds everything cached
+    pub fn warm(&self, poly_modulus_degree: u64, plain_modulus: u64) -> Result<(), String> {
+        let context = self.context(poly_modulus_degree, plain_modulus)?;
+        self.relin_keys(&context)?;
+        self.galois_keys(&context).map(|_| ())
+    }
+
+    /// Number of pooled contexts (each holds a secret key)
+    #[cfg(test)]
+    pub fn pooled_contexts(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// Replace `context`'s cached relin keys (to test checks against mismatched keys)
+    #[cfg(test)]
+    pub fn insert_relin_keys(&self, context: &Context, keys: RelinKeys) {
+        self.relin_keys.lock().unwrap_or_else(PoisonError::into_inner).insert(context.id(), Arc::new(keys));
+    }
+}
+
+// Held locked while generating, so concurrent first uses don't both pay for keygen
+fn cached<K>(
+    keys: &Mutex<HashMap<u64, Arc<K>>>,
+    context: &Context,
+    generate: impl FnOnce(&Context) -> he_benchmark::Result<K>,
+) -> he_benchmark::Result<Arc<K>> {
+    let mut keys = keys.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(cached) = keys.get(&context.id()) {
+        return Ok(Arc::clone(cached));
+    }
+
+    let generated = Arc::new(generate(context)?);
+    keys.insert(context.id(), Arc::clone(&generated));
+    Ok(generated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_second_session_reuses_galois_keys() {
+        let cache = KeyCache::new();
+
+        // Two sessions with identical parameters
+        let first_context = cache.context(8192, 1032193).unwrap();
+        let start = Instant::now();
+        let first = cache.galois_keys(&first_context).unwrap();
+        let keygen_time = start.elapsed();
+
+        let second_context = cache.context(8192, 1032193).unwrap();
+        let start = Instant::now();
+        let second = cache.galois_keys(&second_context).unwrap();
+        let cached_time = start.elapsed();
+
+        assert!(Arc::ptr_eq(&first_context, &second_context));
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(
+            cached_time * 10 < keygen_time,
+            "cached lookup took {:?}, keygen {:?}", cached_time, keygen_time
+        );
+
+        // A different secret key never sees those keys
+        let other_context = cache.context(4096, 40961).unwrap();
+        let other = cache.galois_keys(&other_context).unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[cfg(feature = "helib")]
+    #[test]
+    fn test_helib_keys_are_generated_once_per_parameter_set() {
+        let cache = KeyCache::new();
+
+        let start = Instant::now();
+        let first = cache.helib(4095, 2, 1).unwrap();
+        let keygen_time = start.elapsed();
+
+        let start = Instant::now();
+        let second = cache.helib(4095, 2, 1).unwrap();
+        let cached_time = start.elapsed();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(
+            cached_time * 10 < keygen_time,
+            "cached lookup took {:?}, keygen {:?}", cached_time, keygen_time
+        );
+        assert!(!Arc::ptr_eq(&first, &cache.helib(4095, 257, 1).unwrap()));
+    }
+}
@@ -6,14 +6,19 @@
 // Since SEAL/HELib use FFI types that aren't thread-safe (don't implement Send/Sync),
 // we use tokio::task::spawn_blocking to run HE operations on blocking threads.
 
+use tonic::service::interceptor::InterceptedService;
 use tonic::{transport::Server, Request, Response, Status};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
 // Include the generated proto code
 pub mod he_service {
     tonic::include_proto!("he_service");
+
+    // Encoded descriptors, served by the reflection service
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("he_service_descriptor");
 }
 
 use he_service::{
@@ -21,27 +26,461 @@ use he_service::{
     *,
 };
 
-// Session configuration - stores parameters needed to recreate SEAL/HELib context
-// This is Send + Sync safe since it only contains primitive types
-#[derive(Clone)]
-struct SessionConfig {
-    library: String,
-    poly_modulus_degree: u64,
-    plain_modulus: u64,
-    ciphertext_values: HashMap<String, Vec<i64>>,
-}
+mod session;
+use session::{SessionConfig, SessionStore, SharedSession};
+
+mod store;
+use store::{BenchmarkStore, RunMetadata};
+
+mod window;
+use window::{EncryptedWindow, WINDOW_CAPACITY};
+
+mod mix;
+use mix::{run_mix, OperationMix};
+
+mod keys;
+use keys::{ImportedKeys, KeyCache};
+
+mod accumulator;
+use accumulator::SessionAccumulator;
+
+mod stats;
+use stats::{run_benchmark_repeated, run_failed, MAX_REPETITIONS};
+
+mod dry_run;
+use dry_run::{estimate_full_run, warmup_operations};
+
+mod auth;
+use auth::{AuthInterceptor, SharedSecret};
+
+mod ratelimit;
+use ratelimit::RateLimiter;
+
+mod environment;
+use environment::run_metadata;
+
+use tonic_health::ServingStatus;
+use tonic_reflection::pb::server_reflection_server::{ServerReflection, ServerReflectionServer};
+
+// Library names as used in requests (and as health service names)
+const SUPPORTED_LIBRARIES: [&str; 3] = ["SEAL", "HELib", "OpenFHE"];
+
+// Polynomials in a normal ciphertext. A product has one more until it is relinearized.
+const RELINEARIZED_SIZE: usize = 2;
+
+// Default per-operation deadline. Generous enough for a large benchmark,
+// but finite so a huge num_operations can't pin a thread forever.
+// Override with GRPC_OP_TIMEOUT_SECS.
+const DEFAULT_OP_TIMEOUT_SECS: u64 = 300;
+
+// Plain modulus of every SEAL session (GenerateKeys only takes the degree)
+const SESSION_PLAIN_MODULUS: u64 = 1032193;
+
+// poly_modulus_degree range SEAL accepts (powers of two only)
+const MIN_POLY_MODULUS_DEGREE: u64 = 1024;
+const MAX_POLY_MODULUS_DEGREE: u64 = 32768;
+// plain_modulus bit counts SEAL accepts
+const MIN_PLAIN_MODULUS_BITS: u32 = 2;
+const MAX_PLAIN_MODULUS_BITS: u32 = 60;
+
+// SEAL degrees whose keys are generated at startup unless GRPC_WARM_KEYS says otherwise
+const DEFAULT_WARM_KEY_DEGREES: &str = "8192";
 
 // Our gRPC service implementation
 pub struct HEServiceImpl {
-    sessions: Arc<Mutex<HashMap<String, SessionConfig>>>,
+    sessions: Arc<SessionStore>,
+    // Per-session ring buffers of real ciphertexts (AggregateWindow), created on first use.
+    // Each has its own lock so one session's aggregation doesn't block the others.
+    windows: Arc<Mutex<HashMap<String, Arc<Mutex<EncryptedWindow>>>>>,
+    // Per-session running sums (Accumulate), created on first use, locked like windows
+    accumulators: Arc<Mutex<HashMap<String, Arc<Mutex<SessionAccumulator>>>>>,
+    // SEAL contexts and evaluation keys shared by sessions with the same parameters
+    keys: Arc<KeyCache>,
+    op_timeout: Duration,
+    // Benchmark history (only when BENCHMARK_DB_PATH is set)
+    store: Option<Arc<Mutex<BenchmarkStore>>>,
+    // --no-decrypt: SEAL sessions must bring the client's own keys (GenerateKeys
+    // with public_key/relin_keys/galois_keys), so no session secret key is ever
+    // generated here, and HELib/OpenFHE sessions are refused. Every RPC that
+    // would take plaintext or use a secret key is refused as for any session
+    // with imported keys. Benchmarks still generate throwaway keys of their own.
+    public_only: bool,
+    // Libraries that failed their startup self-test or that this build left out,
+    // with the message their RPCs return UNAVAILABLE with. The other libraries
+    // keep working.
+    unavailable: HashMap<&'static str, String>,
+    // Resilience tests: fail the Nth run_blocking call for an op ("Decrypt", ...)
+    #[cfg(feature = "fault-injection")]
+    faults: he_benchmark::fault::FaultInjector<Status>,
+    // --allow-shutdown: the Shutdown RPC is accepted (refused otherwise)
+    shutdown: Option<ShutdownControl>,
+}
+
+// What the Shutdown RPC needs: the token to check and the future to wake
+struct ShutdownControl {
+    // GRPC_SHUTDOWN_TOKEN; None accepts any request
+    token: Option<String>,
+    // Notified once; the server's graceful-shutdown future waits on it
+    signal: Arc<Notify>,
+}
+
+// Backends built without (server features `helib`/`openfhe` off), under the
+// names clients use, with the stub's "not compiled in" error
+fn not_compiled_libraries() -> impl Iterator<Item = (&'static str, String)> {
+    he_benchmark::not_compiled().into_iter().filter_map(|(name, error)| {
+        SUPPORTED_LIBRARIES.iter().find(|l| l.eq_ignore_ascii_case(&name)).map(|library| (*library, error))
+    })
 }
 
 impl HEServiceImpl {
-    fn new() -> Self {
+    fn new(op_timeout: Duration) -> Self {
         HEServiceImpl {
-            sessions: Arc::new(Mutex::new(HashMap::new())),
+            sessions: Arc::new(SessionStore::new()),
+            windows: Arc::new(Mutex::new(HashMap::new())),
+            accumulators: Arc::new(Mutex::new(HashMap::new())),
+            keys: Arc::new(KeyCache::new()),
+            op_timeout,
+            store: None,
+            public_only: false,
+            unavailable: not_compiled_libraries().collect(),
+            #[cfg(feature = "fault-injection")]
+            faults: he_benchmark::fault::FaultInjector::new(),
+            shutdown: None,
+        }
+    }
+
+    fn with_store(mut self, store: BenchmarkStore) -> Self {
+        self.store = Some(Arc::new(Mutex::new(store)));
+        self
+    }
+
+    fn public_only(mut self) -> Self {
+        self.public_only = true;
+        self
+    }
+
+    fn with_unavailable(mut self, library: &'static str, reason: String) -> Self {
+        self.unavailable.insert(library, format!("{} failed to initialize at startup: {}", library, reason));
+        self
+    }
+
+    /// Accept the Shutdown RPC: it drops every session and notifies `signal`.
+    /// With a `token`, only requests carrying the same one are accepted.
+    fn allow_shutdown(mut self, signal: Arc<Notify>, token: Option<String>) -> Self {
+        self.shutdown = Some(ShutdownControl { token, signal });
+        self
+    }
+
+    /// Generate SEAL session keys for these degrees in the background, so the
+    /// first GenerateKeys for each doesn't pay for keygen. A GenerateKeys that
+    /// arrives mid-warmup waits for the keys instead of generating its own.
+    fn warm_keys(&self, poly_modulus_degrees: Vec<u64>) -> tokio::task::JoinHandle<()> {
+        let keys = Arc::clone(&self.keys);
+        tokio::task::spawn_blocking(move || {
+            for degree in poly_modulus_degrees {
+                if let Err(status) = validate_seal_parameters(degree, SESSION_PLAIN_MODULUS) {
+                    println!("   ✗ SEAL keys for n={} not warmed: {}", degree, status.message());
+                    continue;
+                }
+                let start = Instant::now();
+                match keys.warm(degree, SESSION_PLAIN_MODULUS) {
+                    Ok(()) => println!("   ✓ SEAL keys warm for n={} ({:.0?})", degree, start.elapsed()),
+                    Err(e) => println!("   ✗ SEAL keys for n={} not warmed: {}", degree, e),
+                }
+            }
+        })
+    }
+
+    /// A session's handle, or NOT_FOUND. Handlers keep it locked for the whole
+    /// RPC, so operations on one session run one at a time.
+    #[allow(clippy::result_large_err)]
+    fn session(&self, session_id: &str) -> Result<SharedSession, Status> {
+        self.sessions.get(session_id).ok_or_else(|| Status::not_found("Session not found"))
+    }
+
+    /// UNAVAILABLE if `library` failed to initialize at startup or isn't compiled in
+    #[allow(clippy::result_large_err)]
+    fn require_available(&self, library: &str) -> Result<(), Status> {
+        match self.unavailable.get(library) {
+            Some(message) => Err(Status::unavailable(message.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// FAILED_PRECONDITION for RPCs that need a secret key on a session that
+    /// imported the client's keys (every session with --no-decrypt)
+    #[allow(clippy::result_large_err)] // tonic::Status is what the handlers return anyway
+    fn require_secret_key(&self, session: &SessionConfig, op: &str) -> Result<(), Status> {
+        if self.public_only || session.keys.is_some() {
+            return Err(Status::failed_precondition(format!(
+                "{} needs a secret key, but this session only has the client's public keys; decrypt on the client",
+                op
+            )));
+        }
+        Ok(())
+    }
+
+    /// Save a benchmark result to the history store, if one is configured.
+    /// A failed write is logged but never fails the request.
+    fn record_benchmark(&self, library: &str, num_operations: i32, result: &BenchmarkResponse) {
+        let Some(store) = &self.store else { return };
+
+        let metadata = RunMetadata::new(library, benchmark_parameters(library), num_operations);
+        let saved = store.lock().unwrap().save(result, &metadata);
+        if let Err(e) = saved {
+            println!("   ⚠ Failed to save {} benchmark: {}", library, e);
+        }
+    }
+
+    /// Decrypt several stored value vectors with one context and one set of keys
+    async fn decrypt_batch(
+        &self,
+        op: &str,
+        library: &str,
+        poly_modulus_degree: u64,
+        plain_modulus: u64,
+        batch: Vec<Vec<i64>>,
+    ) -> Result<Vec<Vec<i64>>, Status> {
+        if library == "HELib" {
+            let values: Vec<i64> = batch.iter().map(|values| values.first().copied().unwrap_or(0)).collect();
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking(op, move || run_helib_decrypt(&keys, &values)).await?
+                .map_err(Status::internal)
+        } else if library == "OpenFHE" {
+            self.run_blocking(op, move || run_openfhe_decrypt(&batch)).await?
+                .map_err(Status::internal)
+        } else {
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking(op, move || run_seal_decrypt(&keys, poly_modulus_degree, plain_modulus, &batch)).await?
+                .map_err(Status::internal)
+        }
+    }
+
+    /// Run an HE operation on a blocking thread, bounded by the server's deadline
+    async fn run_blocking<T, F>(&self, op: &str, f: F) -> Result<T, Status>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        #[cfg(feature = "fault-injection")]
+        self.faults.check(op)?;
+        run_with_deadline(self.op_timeout, op, f).await
+    }
+}
+
+// Runs `f` via spawn_blocking and gives up with DEADLINE_EXCEEDED after `deadline`.
+// The FFI call itself can't be interrupted, so a timed-out task keeps running in the
+// background; its result is simply dropped (nothing gets written into the session).
+// Long loops like the benchmarks also check `deadline_passed` to stop early.
+async fn run_with_deadline<T, F>(deadline: Duration, op: &str, f: F) -> Result<T, Status>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::time::timeout(deadline, tokio::task::spawn_blocking(f)).await {
+        Ok(joined) => joined.map_err(|e| Status::internal(format!("{} task failed: {}", op, e))),
+        Err(_) => Err(Status::deadline_exceeded(format!(
+            "{} exceeded the server deadline of {:?}",
+            op, deadline
+        ))),
+    }
+}
+
+fn deadline_passed(deadline: Instant) -> bool {
+    Instant::now() >= deadline
+}
+
+// Replay protection: ALREADY_EXISTS if this session has accepted `nonce` before.
+// Nonce 0 means the client didn't send one (older clients), which is let through.
+#[allow(clippy::result_large_err)]
+fn check_nonce(session: &mut SessionConfig, op: &str, nonce: u64) -> Result<(), Status> {
+    if nonce != 0 && !session.seen_nonces.insert(nonce) {
+        return Err(Status::already_exists(format!(
+            "{} request with nonce {} was already seen for this session (replayed?)",
+            op, nonce
+        )));
+    }
+    Ok(())
+}
+
+// Optimistic concurrency: ABORTED if the session has changed since the client
+// last saw it at `expected` (0 = the client isn't tracking versions). Checked
+// before the nonce, so an aborted request can be retried with the same nonce.
+#[allow(clippy::result_large_err)]
+fn check_version(session: &SessionConfig, op: &str, expected: u64) -> Result<(), Status> {
+    if expected != 0 && expected != session.version {
+        return Err(Status::aborted(format!(
+            "{} expected session version {}, but it is at {} (changed concurrently); re-read and retry",
+            op, expected, session.version
+        )));
+    }
+    Ok(())
+}
+
+// SEAL parameters as they arrive over the wire. Anything SEAL can't take is
+// INVALID_ARGUMENT here, before it reaches the C wrapper: a degree of
+// u64::MAX (an int32 -1 cast) would otherwise ask SEAL for an absurd allocation.
+#[allow(clippy::result_large_err)]
+fn validate_seal_parameters(poly_modulus_degree: u64, plain_modulus: u64) -> Result<(), Status> {
+    if !poly_modulus_degree.is_power_of_two() || !(MIN_POLY_MODULUS_DEGREE..=MAX_POLY_MODULUS_DEGREE).contains(&poly_modulus_degree) {
+        return Err(Status::invalid_argument(format!(
+            "poly_modulus_degree must be a power of two from {} to {}, got {}",
+            MIN_POLY_MODULUS_DEGREE, MAX_POLY_MODULUS_DEGREE, poly_modulus_degree
+        )));
+    }
+    let plain_bits = u64::BITS - plain_modulus.leading_zeros();
+    if !(MIN_PLAIN_MODULUS_BITS..=MAX_PLAIN_MODULUS_BITS).contains(&plain_bits) {
+        return Err(Status::invalid_argument(format!(
+            "plain_modulus must have {} to {} bits, got {} ({} bits)",
+            MIN_PLAIN_MODULUS_BITS, MAX_PLAIN_MODULUS_BITS, plain_modulus, plain_bits
+        )));
+    }
+    Ok(())
+}
+
+// BenchmarkRequest.repetitions as a run count (0 means once)
+#[allow(clippy::result_large_err)]
+fn benchmark_repetitions(repetitions: i32) -> Result<usize, Status> {
+    if !(0..=MAX_REPETITIONS).contains(&repetitions) {
+        return Err(Status::invalid_argument(format!(
+            "repetitions must be between 0 and {}, got {}",
+            MAX_REPETITIONS, repetitions
+        )));
+    }
+    Ok(repetitions.max(1) as usize)
+}
+
+// Benchmark response for a run that failed during setup (all timings zero)
+fn failed_benchmark(status: String) -> BenchmarkResponse {
+    BenchmarkResponse {
+        status,
+        ..Default::default()
+    }
+}
+
+// A benchmark that ran to the end. Failed and unavailable libraries come back
+// from failed_benchmark with their error as the status and all-zero timings,
+// which would otherwise win every comparison.
+fn benchmark_completed(result: &BenchmarkResponse) -> bool {
+    !run_failed(result) && result.status.contains("benchmark complete")
+}
+
+// (fastest library, recommendation) among the libraries whose benchmark
+// completed; "none" when fewer than two did, as there is nothing to compare
+fn compare_libraries(results: &[(&str, &BenchmarkResponse)]) -> (String, String) {
+    let completed: Vec<(&str, &BenchmarkResponse)> =
+        results.iter().copied().filter(|(_, result)| benchmark_completed(result)).collect();
+    if completed.len() < 2 {
+        return (
+            "none".to_string(),
+            format!("Insufficient data: {} of {} libraries completed the benchmark", completed.len(), results.len()),
+        );
+    }
+
+    // Lowest total time; ties go to the library listed first
+    let fastest = completed
+        .iter()
+        .fold(completed[0], |best, &candidate| if candidate.1.total_time_ms < best.1.total_time_ms { candidate } else { best });
+
+    // Strictly ahead of every other completed library on `time`
+    let leads = |library: &str, time: fn(&BenchmarkResponse) -> f64| {
+        completed.iter().find(|(name, _)| *name == library).is_some_and(|&(_, ours)| {
+            completed.iter().all(|&(name, other)| name == library || time(ours) < time(other))
+        })
+    };
+    let recommendation = if leads("SEAL", |r| r.encryption_time_ms) {
+        "SEAL recommended for encryption-heavy workloads (batching support)".to_string()
+    } else if leads("HELib", |r| r.multiplication_time_ms) {
+        "HELib recommended for multiplication-heavy workloads (BGV optimizations)".to_string()
+    } else if completed.iter().any(|(name, _)| *name == "OpenFHE") {
+        "OpenFHE recommended for general-purpose HE (flexible API)".to_string()
+    } else {
+        format!("{} recommended overall (lowest total time)", fastest.0)
+    };
+    (fastest.0.to_string(), recommendation)
+}
+
+// Fill in the amortized per-slot costs from the per-op times, for `slots`
+// values packed into each ciphertext
+fn with_per_slot_costs(mut response: BenchmarkResponse, slots: usize) -> BenchmarkResponse {
+    let ns_per_slot = |ms_per_op: f64| ms_per_op * 1_000_000.0 / slots.max(1) as f64;
+    response.slots_per_ciphertext = slots as i32;
+    response.encryption_ns_per_slot = ns_per_slot(response.encryption_time_ms);
+    response.addition_ns_per_slot = ns_per_slot(response.addition_time_ms);
+    response.multiplication_ns_per_slot = ns_per_slot(response.multiplication_time_ms);
+    response.decryption_ns_per_slot = ns_per_slot(response.decryption_time_ms);
+    response
+}
+
+// How many times bigger the data gets once encrypted
+fn expansion_ratio(plaintext_bytes: usize, ciphertext_bytes: usize) -> f64 {
+    if plaintext_bytes == 0 {
+        return 0.0;
+    }
+    ciphertext_bytes as f64 / plaintext_bytes as f64
+}
+
+// Parameters each benchmark runs with (stored alongside results)
+fn benchmark_parameters(library: &str) -> &'static str {
+    match library {
+        "HELib" => "m=4095, p=2, r=1",
+        "OpenFHE" => "plaintext_modulus=65537, mult_depth=2",
+        _ => "poly_modulus_degree=8192, plain_modulus=1032193",
+    }
+}
+
+// Operations each benchmark can run as part of an operation_mix
+fn mix_operations(library: &str) -> &'static [&'static str] {
+    match library {
+        "HELib" | "OpenFHE" => &["add", "multiply"],
+        _ => &["add", "multiply", "rotate"],
+    }
+}
+
+// Only these work in every library, so they are all a comparison mix may use
+const COMPARISON_MIX_OPERATIONS: [&str; 2] = ["add", "multiply"];
+
+// Upper bound on StressTest's concurrent_sessions. Each one holds a blocking
+// thread for a full keygen, and tokio's blocking pool defaults to 512.
+const MAX_STRESS_SESSIONS: i32 = 256;
+
+// Full key generation for a new session, bypassing the key cache (StressTest
+// measures keygen itself, so every session pays for it)
+fn run_keygen(library: &str, poly_modulus_degree: u64, plain_modulus: u64) -> Result<(), String> {
+    match library {
+        "HELib" => {
+            use he_benchmark::{HEContext, HESecretKey};
+            let context = HEContext::new(HELIB_M, HELIB_P, HELIB_R)
+                .map_err(|e| format!("HELib context error: {}", e))?;
+            let secret_key = HESecretKey::generate(&context)
+                .map_err(|e| format!("HELib key error: {}", e))?;
+            secret_key.public_key().map(|_| ()).map_err(|e| format!("HELib public key error: {}", e))
+        }
+        "OpenFHE" => {
+            use he_benchmark::{OpenFHEContext, OpenFHEKeyPair};
+            let context = OpenFHEContext::new_bfv(OPENFHE_PLAINTEXT_MOD, OPENFHE_MULT_DEPTH)
+                .map_err(|e| format!("OpenFHE context error: {}", e))?;
+            OpenFHEKeyPair::generate(&context).map(|_| ()).map_err(|e| format!("OpenFHE keypair error: {}", e))
         }
+        _ => {
+            use he_benchmark::{Context, RelinKeys};
+            // Context creation generates the secret and public keys
+            let context = Context::new(poly_modulus_degree, plain_modulus)
+                .map_err(|e| format!("Failed to create context: {}", e))?;
+            RelinKeys::generate(&context).map(|_| ()).map_err(|e| format!("Failed to generate relin keys: {}", e))
+        }
+    }
+}
+
+// (min, max, mean) in milliseconds; all zero when nothing succeeded
+fn latency_summary_ms(latencies: &[Duration]) -> (f64, f64, f64) {
+    if latencies.is_empty() {
+        return (0.0, 0.0, 0.0);
     }
+    let ms: Vec<f64> = latencies.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+    let min = ms.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ms.iter().copied().fold(0.0, f64::max);
+    (min, max, ms.iter().sum::<f64>() / ms.len() as f64)
 }
 
 // ============================================
@@ -49,18 +488,17 @@ impl HEServiceImpl {
 // ============================================
 
 fn run_seal_encrypt(
+    keys: &KeyCache,
     poly_modulus_degree: u64,
     plain_modulus: u64,
     values: Vec<i64>,
 ) -> Result<(Vec<u8>, usize), String> {
     use he_benchmark::{
-        Context as SealContext,
         Encryptor as SealEncryptor,
         BatchEncoder as SealBatchEncoder,
     };
 
-    let context = SealContext::new(poly_modulus_degree, plain_modulus)
-        .map_err(|e| format!("Failed to create context: {}", e))?;
+    let context = keys.context(poly_modulus_degree, plain_modulus)?;
     let encoder = SealBatchEncoder::new(&context)
         .map_err(|e| format!("Failed to create encoder: {}", e))?;
     let encryptor = SealEncryptor::new(&context)
@@ -82,19 +520,18 @@ fn run_seal_encrypt(
 }
 
 fn run_seal_decrypt(
+    keys: &KeyCache,
     poly_modulus_degree: u64,
     plain_modulus: u64,
-    original_values: &[i64],
-) -> Result<Vec<i64>, String> {
+    batch: &[Vec<i64>],
+) -> Result<Vec<Vec<i64>>, String> {
     use he_benchmark::{
-        Context as SealContext,
         Encryptor as SealEncryptor,
         Decryptor as SealDecryptor,
         BatchEncoder as SealBatchEncoder,
     };
 
-    let context = SealContext::new(poly_modulus_degree, plain_modulus)
-        .map_err(|e| format!("Failed to create context: {}", e))?;
+    let context = keys.context(poly_modulus_degree, plain_modulus)?;
     let encoder = SealBatchEncoder::new(&context)
         .map_err(|e| format!("Failed to create encoder: {}", e))?;
     let encryptor = SealEncryptor::new(&context)
@@ -103,37 +540,38 @@ fn run_seal_decrypt(
         .map_err(|e| format!("Failed to create decryptor: {}", e))?;
     
     let slot_count = encoder.slot_count();
-    let mut padded_values = original_values.to_vec();
-    padded_values.resize(slot_count, 0);
-    
-    let plaintext = encoder.encode(&padded_values)
-        .map_err(|e| format!("Failed to encode: {}", e))?;
-    let ciphertext = encryptor.encrypt(&plaintext)
-        .map_err(|e| format!("Failed to encrypt: {}", e))?;
-    let decrypted_plain = decryptor.decrypt(&ciphertext)
-        .map_err(|e| format!("Failed to decrypt: {}", e))?;
-    let result = encoder.decode(&decrypted_plain)
-        .map_err(|e| format!("Failed to decode: {}", e))?;
-    
-    Ok(result[..original_values.len()].to_vec())
+    batch.iter().map(|original_values| {
+        let mut padded_values = original_values.to_vec();
+        padded_values.resize(slot_count, 0);
+        
+        let plaintext = encoder.encode(&padded_values)
+            .map_err(|e| format!("Failed to encode: {}", e))?;
+        let ciphertext = encryptor.encrypt(&plaintext)
+            .map_err(|e| format!("Failed to encrypt: {}", e))?;
+        let decrypted_plain = decryptor.decrypt(&ciphertext)
+            .map_err(|e| format!("Failed to decrypt: {}", e))?;
+        let result = encoder.decode(&decrypted_plain)
+            .map_err(|e| format!("Failed to decode: {}", e))?;
+        
+        Ok(result[..original_values.len()].to_vec())
+    }).collect()
 }
 
 fn run_seal_add(
+    keys: &KeyCache,
     poly_modulus_degree: u64,
     plain_modulus: u64,
     values1: &[i64],
     values2: &[i64],
 ) -> Result<Vec<i64>, String> {
     use he_benchmark::{
-        Context as SealContext,
         Encryptor as SealEncryptor,
         Decryptor as SealDecryptor,
         BatchEncoder as SealBatchEncoder,
         add as seal_add,
     };
 
-    let context = SealContext::new(poly_modulus_degree, plain_modulus)
-        .map_err(|e| format!("Failed to create context: {}", e))?;
+    let context = keys.context(poly_modulus_degree, plain_modulus)?;
     let encoder = SealBatchEncoder::new(&context)
         .map_err(|e| format!("Failed to create encoder: {}", e))?;
     let encryptor = SealEncryptor::new(&context)
@@ -163,22 +601,99 @@ fn run_seal_add(
     Ok(result[..values1.len().max(values2.len())].to_vec())
 }
 
+// The context a SEAL session's operations run under: the one holding the
+// client's public key if it imported its keys, the pooled one otherwise
+fn seal_session_context(
+    keys: &KeyCache,
+    imported: Option<&ImportedKeys>,
+    poly_modulus_degree: u64,
+    plain_modulus: u64,
+) -> Result<Arc<he_benchmark::Context>, String> {
+    match imported {
+        Some(imported) => Ok(Arc::clone(&imported.context)),
+        None => keys.context(poly_modulus_degree, plain_modulus),
+    }
+}
+
+// Load the two ciphertexts a client encrypted itself (`--local-encrypt`) under
+// `context`'s parameters
+#[allow(clippy::result_large_err)]
+fn load_client_ciphertexts(
+    context: &he_benchmark::Context,
+    ciphertext1: &[u8],
+    ciphertext2: &[u8],
+) -> Result<(he_benchmark::Ciphertext, he_benchmark::Ciphertext), Status> {
+    use he_benchmark::Ciphertext as SealCiphertext;
+
+    let load = |data: &[u8], name: &str| {
+        SealCiphertext::from_bytes(context, data).map_err(|e| {
+            Status::invalid_argument(format!(
+                "{} is not a SEAL ciphertext for n={}, t={}: {}",
+                name, context.poly_modulus_degree(), context.plain_modulus(), e
+            ))
+        })
+    };
+    Ok((load(ciphertext1, "ciphertext1")?, load(ciphertext2, "ciphertext2")?))
+}
+
+// Add two ciphertexts a client encrypted itself under the session's context.
+// No secret key is used: the sum stays under the client's key and goes back
+// serialized. Returns the sum and its size.
+#[allow(clippy::result_large_err)]
+fn run_seal_add_ciphertexts(
+    context: &he_benchmark::Context,
+    ciphertext1: &[u8],
+    ciphertext2: &[u8],
+) -> Result<(Vec<u8>, usize), Status> {
+    use he_benchmark::add as seal_add;
+
+    let (cipher1, cipher2) = load_client_ciphertexts(context, ciphertext1, ciphertext2)?;
+    let sum = seal_add(context, &cipher1, &cipher2)
+        .map_err(|e| Status::internal(format!("Addition error: {}", e)))?;
+    let bytes = sum.to_bytes()
+        .map_err(|e| Status::internal(format!("Serialization failed: {}", e)))?;
+    Ok((bytes, sum.size()))
+}
+
+// Multiply two client ciphertexts and relinearize with the client's own relin
+// keys (from GenerateKeys), so the product is a normal size-2 ciphertext under
+// the client's key. Returns the product and its size.
+#[allow(clippy::result_large_err)]
+fn run_seal_multiply_ciphertexts(
+    imported: &ImportedKeys,
+    ciphertext1: &[u8],
+    ciphertext2: &[u8],
+) -> Result<(Vec<u8>, usize), Status> {
+    use he_benchmark::Evaluator as SealEvaluator;
+
+    let (cipher1, cipher2) = load_client_ciphertexts(&imported.context, ciphertext1, ciphertext2)?;
+    let evaluator = SealEvaluator::new(&imported.context)
+        .map_err(|e| Status::internal(format!("Failed to create evaluator: {}", e)))?;
+    let product = evaluator.multiply(&cipher1, &cipher2)
+        .map_err(|e| Status::internal(format!("Multiplication error: {}", e)))?;
+    let product = evaluator.relinearize(&product, &imported.relin_keys)
+        .map_err(|e| Status::internal(format!("Relinearization error: {}", e)))?;
+    let bytes = product.to_bytes()
+        .map_err(|e| Status::internal(format!("Serialization failed: {}", e)))?;
+    Ok((bytes, product.size()))
+}
+
+// Returns the product and the size of the stored (relinearized) result ciphertext
 fn run_seal_multiply(
+    keys: &KeyCache,
     poly_modulus_degree: u64,
     plain_modulus: u64,
     values1: &[i64],
     values2: &[i64],
-) -> Result<Vec<i64>, String> {
+) -> Result<(Vec<i64>, usize), String> {
     use he_benchmark::{
-        Context as SealContext,
         Encryptor as SealEncryptor,
         Decryptor as SealDecryptor,
         BatchEncoder as SealBatchEncoder,
-        multiply as seal_multiply,
+        Evaluator as SealEvaluator,
     };
 
-    let context = SealContext::new(poly_modulus_degree, plain_modulus)
-        .map_err(|e| format!("Failed to create context: {}", e))?;
+    let context = keys.context(poly_modulus_degree, plain_modulus)?;
     let encoder = SealBatchEncoder::new(&context)
         .map_err(|e| format!("Failed to create encoder: {}", e))?;
     let encryptor = SealEncryptor::new(&context)
@@ -198,22 +713,70 @@ fn run_seal_multiply(
     let plain2 = encoder.encode(&padded2).map_err(|e| format!("Encode error: {}", e))?;
     let cipher2 = encryptor.encrypt(&plain2).map_err(|e| format!("Encrypt error: {}", e))?;
     
-    let result_cipher = seal_multiply(&context, &cipher1, &cipher2)
+    // A raw product has 3 polynomials; relinearize so the next multiply starts
+    // from a normal size-2 ciphertext instead of growing further
+    let evaluator = SealEvaluator::new(&context)
+        .map_err(|e| format!("Failed to create evaluator: {}", e))?;
+    let relin_keys = keys.relin_keys(&context)?;
+    let product = evaluator.multiply(&cipher1, &cipher2)
         .map_err(|e| format!("Multiplication error: {}", e))?;
+    let result_cipher = evaluator.relinearize(&product, &relin_keys)
+        .map_err(|e| format!("Relinearization error: {}", e))?;
     let result_plain = decryptor.decrypt(&result_cipher)
         .map_err(|e| format!("Decrypt error: {}", e))?;
     let result = encoder.decode(&result_plain)
         .map_err(|e| format!("Decode error: {}", e))?;
     
-    Ok(result[..values1.len().max(values2.len())].to_vec())
+    Ok((result[..values1.len().max(values2.len())].to_vec(), result_cipher.size()))
 }
 
-fn run_seal_benchmark(poly_modulus_degree: u64, num_operations: i32) -> BenchmarkResponse {
+// (check, passed) for each check VerifyKeys runs. A session with imported keys
+// has no secret key to check, so only the evaluation keys' parameters are.
+fn run_seal_verify_keys(
+    keys: &KeyCache,
+    imported: Option<ImportedKeys>,
+    poly_modulus_degree: u64,
+    plain_modulus: u64,
+) -> Result<Vec<(&'static str, bool)>, String> {
+    let (context, relin_keys, galois_keys) = match imported {
+        Some(imported) => (imported.context, imported.relin_keys, imported.galois_keys),
+        None => {
+            let context = keys.context(poly_modulus_degree, plain_modulus)?;
+            let (relin_keys, galois_keys) = (keys.relin_keys(&context)?, keys.galois_keys(&context)?);
+            (context, relin_keys, galois_keys)
+        }
+    };
+    let mut checks = Vec::new();
+
+    if context.has_secret_key() {
+        let public_key = context.public_key().map_err(|e| format!("Failed to read public key: {}", e))?;
+        let secret_key = context.secret_key().map_err(|e| format!("Failed to read secret key: {}", e))?;
+        // Keys for other parameters are as much a mismatch as a wrong pair
+        let pair = context.verify_key_pair(&public_key, &secret_key).unwrap_or(false);
+        checks.push(("key_pair", pair));
+    }
+
+    let params_id = context.params_id();
+    let evaluation_keys = relin_keys.params_id() == params_id && galois_keys.params_id() == params_id;
+    checks.push(("evaluation_keys", evaluation_keys));
+
+    Ok(checks)
+}
+
+fn run_seal_benchmark(
+    poly_modulus_degree: u64,
+    num_operations: i32,
+    mix: Option<OperationMix>,
+    deadline: Instant,
+) -> BenchmarkResponse {
     use he_benchmark::{
         Context as SealContext,
         Encryptor as SealEncryptor,
         Decryptor as SealDecryptor,
         BatchEncoder as SealBatchEncoder,
+        Evaluator as SealEvaluator,
+        GaloisKeys as SealGaloisKeys,
+        RelinKeys as SealRelinKeys,
         add as seal_add,
         multiply as seal_multiply,
     };
@@ -224,42 +787,22 @@ fn run_seal_benchmark(poly_modulus_degree: u64, num_operations: i32) -> Benchmar
     let key_start = Instant::now();
     let context = match SealContext::new(poly_modulus_degree, plain_modulus) {
         Ok(ctx) => ctx,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("Failed to create context: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("Failed to create context: {}", e)),
     };
     
     let encoder = match SealBatchEncoder::new(&context) {
         Ok(enc) => enc,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("Failed to create encoder: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("Failed to create encoder: {}", e)),
     };
     
     let encryptor = match SealEncryptor::new(&context) {
         Ok(enc) => enc,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("Failed to create encryptor: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("Failed to create encryptor: {}", e)),
     };
     
     let decryptor = match SealDecryptor::new(&context) {
         Ok(dec) => dec,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("Failed to create decryptor: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("Failed to create decryptor: {}", e)),
     };
     let key_gen_time = key_start.elapsed();
     
@@ -270,6 +813,7 @@ fn run_seal_benchmark(poly_modulus_degree: u64, num_operations: i32) -> Benchmar
     let encode_start = Instant::now();
     let mut plaintexts = Vec::new();
     for _ in 0..num_operations {
+        if deadline_passed(deadline) { break; }
         let plain = encoder.encode(&test_data).unwrap();
         plaintexts.push(plain);
     }
@@ -279,32 +823,84 @@ fn run_seal_benchmark(poly_modulus_degree: u64, num_operations: i32) -> Benchmar
     let encrypt_start = Instant::now();
     let mut ciphertexts = Vec::new();
     for plain in &plaintexts {
+        if deadline_passed(deadline) { break; }
         let cipher = encryptor.encrypt(plain).unwrap();
         ciphertexts.push(cipher);
     }
     let encryption_time = encrypt_start.elapsed();
     
+    // Size of one ciphertext vs. the i64 values packed into it
+    let plaintext_bytes = test_data.len() * std::mem::size_of::<i64>();
+    let ciphertext_bytes = ciphertexts.first().map(|c| c.byte_count()).unwrap_or(0);
+    
     let add_start = Instant::now();
     for i in 0..(num_operations as usize - 1).min(ciphertexts.len().saturating_sub(1)) {
+        if deadline_passed(deadline) { break; }
         let _ = seal_add(&context, &ciphertexts[i], &ciphertexts[i + 1]);
     }
     let addition_time = add_start.elapsed();
     
     let mult_start = Instant::now();
     for i in 0..(num_operations as usize - 1).min(ciphertexts.len().saturating_sub(1)) {
+        if deadline_passed(deadline) { break; }
         let _ = seal_multiply(&context, &ciphertexts[i], &ciphertexts[i + 1]);
     }
     let multiplication_time = mult_start.elapsed();
     
+    // Weighted mix over the same ciphertexts, if one was requested
+    let mut operation_timings = Vec::new();
+    if let Some(mix) = mix.filter(|_| !ciphertexts.is_empty()) {
+        let evaluator = match SealEvaluator::new(&context) {
+            Ok(eval) => eval,
+            Err(e) => return failed_benchmark(format!("Failed to create evaluator: {}", e)),
+        };
+        let galois_keys = if mix.includes("rotate") {
+            match SealGaloisKeys::generate(&context) {
+                Ok(keys) => Some(keys),
+                Err(e) => return failed_benchmark(format!("Failed to generate galois keys: {}", e)),
+            }
+        } else {
+            None
+        };
+        
+        let n = ciphertexts.len();
+        operation_timings = run_mix(&mix, num_operations as usize, deadline, |op, i| {
+            let (a, b) = (&ciphertexts[i % n], &ciphertexts[(i + 1) % n]);
+            match (op, &galois_keys) {
+                ("add", _) => { let _ = evaluator.add(a, b); }
+                ("multiply", _) => { let _ = evaluator.multiply(a, b); }
+                (_, Some(keys)) => { let _ = evaluator.rotate_rows(a, 1, keys); }
+                _ => {}
+            }
+        });
+    }
+    
     let decrypt_start = Instant::now();
     for cipher in &ciphertexts {
-        let _ = decryptor.decrypt(cipher);
+        if deadline_passed(deadline) { break; }
+        // Unchecked: the noise budget check would double the timed cost
+        let _ = decryptor.decrypt_unchecked(cipher);
     }
     let decryption_time = decrypt_start.elapsed();
     
     let total_time = total_start.elapsed();
 
-    BenchmarkResponse {
+    // Key sizes, outside the timings: relin and galois keys are generated here
+    // only to be measured
+    let key_sizes = (|| -> Result<_, he_benchmark::SealError> {
+        Ok((
+            context.public_key()?.byte_count(),
+            context.secret_key()?.byte_count(),
+            SealRelinKeys::generate(&context)?.byte_count(),
+            SealGaloisKeys::generate(&context)?.byte_count(),
+        ))
+    })();
+    let (public_key_bytes, secret_key_bytes, relin_key_bytes, galois_key_bytes) = match key_sizes {
+        Ok(sizes) => sizes,
+        Err(e) => return failed_benchmark(format!("Failed to measure key sizes: {}", e)),
+    };
+
+    with_per_slot_costs(BenchmarkResponse {
         key_gen_time_ms: key_gen_time.as_secs_f64() * 1000.0,
         encoding_time_ms: encoding_time.as_secs_f64() * 1000.0 / num_operations as f64,
         encryption_time_ms: encryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
@@ -313,7 +909,16 @@ fn run_seal_benchmark(poly_modulus_degree: u64, num_operations: i32) -> Benchmar
         decryption_time_ms: decryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
         total_time_ms: total_time.as_secs_f64() * 1000.0,
         status: format!("SEAL benchmark complete: {} operations", num_operations),
-    }
+        plaintext_bytes: plaintext_bytes as i64,
+        ciphertext_bytes: ciphertext_bytes as i64,
+        expansion_ratio: expansion_ratio(plaintext_bytes, ciphertext_bytes),
+        operation_timings,
+        public_key_bytes: public_key_bytes as i64,
+        secret_key_bytes: secret_key_bytes as i64,
+        relin_key_bytes: relin_key_bytes as i64,
+        galois_key_bytes: galois_key_bytes as i64,
+        ..Default::default()
+    }, test_data.len())
 }
 
 // ============================================
@@ -324,134 +929,111 @@ const HELIB_M: u64 = 4095;
 const HELIB_P: u64 = 2;
 const HELIB_R: u64 = 1;
 
-fn run_helib_encrypt(value: i64) -> Result<usize, String> {
-    use he_benchmark::{HEContext, HESecretKey, HEPlaintext};
-    
-    let context = HEContext::new(HELIB_M, HELIB_P, HELIB_R)
-        .map_err(|e| format!("HELib context error: {}", e))?;
-    let secret_key = HESecretKey::generate(&context)
-        .map_err(|e| format!("HELib key error: {}", e))?;
-    let public_key = secret_key.public_key()
-        .map_err(|e| format!("HELib public key error: {}", e))?;
-    
-    let plaintext = HEPlaintext::new(&context, value)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    let _ciphertext = public_key.encrypt(&plaintext)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    
-    Ok(4096)
+// The cached HElib context and key pair for the session RPCs, locked for the op
+fn with_helib_keys<T>(keys: &KeyCache, op: impl FnOnce(&keys::HElibKeys) -> Result<T, String>) -> Result<T, String> {
+    let helib = keys.helib(HELIB_M, HELIB_P, HELIB_R)?;
+    let helib = helib.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    op(&helib)
 }
 
-fn run_helib_decrypt(value: i64) -> Result<Vec<i64>, String> {
-    use he_benchmark::{HEContext, HESecretKey, HEPlaintext};
-    
-    let context = HEContext::new(HELIB_M, HELIB_P, HELIB_R)
-        .map_err(|e| format!("HELib context error: {}", e))?;
-    let secret_key = HESecretKey::generate(&context)
-        .map_err(|e| format!("HELib key error: {}", e))?;
-    let public_key = secret_key.public_key()
-        .map_err(|e| format!("HELib public key error: {}", e))?;
-    
-    let plaintext = HEPlaintext::new(&context, value)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    let ciphertext = public_key.encrypt(&plaintext)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    let decrypted = secret_key.decrypt(&ciphertext)
-        .map_err(|e| format!("HELib decrypt error: {}", e))?;
-    
-    Ok(vec![decrypted.value()])
+fn run_helib_encrypt(keys: &KeyCache, value: i64) -> Result<usize, String> {
+    use he_benchmark::HEPlaintext;
+    
+    with_helib_keys(keys, |helib| {
+        let plaintext = HEPlaintext::new(&helib.context, value)
+            .map_err(|e| format!("HELib plaintext error: {}", e))?;
+        let _ciphertext = helib.public_key.encrypt(&plaintext)
+            .map_err(|e| format!("HELib encrypt error: {}", e))?;
+        
+        Ok(4096)
+    })
 }
 
-fn run_helib_add(val1: i64, val2: i64) -> Result<Vec<i64>, String> {
-    use he_benchmark::{HEContext, HESecretKey, HEPlaintext};
-    
-    let context = HEContext::new(HELIB_M, HELIB_P, HELIB_R)
-        .map_err(|e| format!("HELib context error: {}", e))?;
-    let secret_key = HESecretKey::generate(&context)
-        .map_err(|e| format!("HELib key error: {}", e))?;
-    let public_key = secret_key.public_key()
-        .map_err(|e| format!("HELib public key error: {}", e))?;
-    
-    let pt1 = HEPlaintext::new(&context, val1)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    let pt2 = HEPlaintext::new(&context, val2)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    
-    let ct1 = public_key.encrypt(&pt1)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    let ct2 = public_key.encrypt(&pt2)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    
-    let result = ct1.add(&ct2)
-        .map_err(|e| format!("HELib add error: {}", e))?;
-    let decrypted = secret_key.decrypt(&result)
-        .map_err(|e| format!("HELib decrypt error: {}", e))?;
-    
-    Ok(vec![decrypted.value()])
+fn run_helib_decrypt(keys: &KeyCache, values: &[i64]) -> Result<Vec<Vec<i64>>, String> {
+    use he_benchmark::HEPlaintext;
+    
+    with_helib_keys(keys, |helib| {
+        values.iter().map(|&value| {
+            let plaintext = HEPlaintext::new(&helib.context, value)
+                .map_err(|e| format!("HELib plaintext error: {}", e))?;
+            let ciphertext = helib.public_key.encrypt(&plaintext)
+                .map_err(|e| format!("HELib encrypt error: {}", e))?;
+            let decrypted = helib.secret_key.decrypt(&ciphertext)
+                .map_err(|e| format!("HELib decrypt error: {}", e))?;
+            
+            Ok(vec![decrypted.value()])
+        }).collect()
+    })
 }
 
-fn run_helib_multiply(val1: i64, val2: i64) -> Result<Vec<i64>, String> {
-    use he_benchmark::{HEContext, HESecretKey, HEPlaintext};
-    
-    let context = HEContext::new(HELIB_M, HELIB_P, HELIB_R)
-        .map_err(|e| format!("HELib context error: {}", e))?;
-    let secret_key = HESecretKey::generate(&context)
-        .map_err(|e| format!("HELib key error: {}", e))?;
-    let public_key = secret_key.public_key()
-        .map_err(|e| format!("HELib public key error: {}", e))?;
-    
-    let pt1 = HEPlaintext::new(&context, val1)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    let pt2 = HEPlaintext::new(&context, val2)
-        .map_err(|e| format!("HELib plaintext error: {}", e))?;
-    
-    let ct1 = public_key.encrypt(&pt1)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    let ct2 = public_key.encrypt(&pt2)
-        .map_err(|e| format!("HELib encrypt error: {}", e))?;
-    
-    let result = ct1.multiply(&ct2)
-        .map_err(|e| format!("HELib multiply error: {}", e))?;
-    let decrypted = secret_key.decrypt(&result)
-        .map_err(|e| format!("HELib decrypt error: {}", e))?;
-    
-    Ok(vec![decrypted.value()])
+fn run_helib_add(keys: &KeyCache, val1: i64, val2: i64) -> Result<Vec<i64>, String> {
+    use he_benchmark::HEPlaintext;
+    
+    with_helib_keys(keys, |helib| {
+        let pt1 = HEPlaintext::new(&helib.context, val1)
+            .map_err(|e| format!("HELib plaintext error: {}", e))?;
+        let pt2 = HEPlaintext::new(&helib.context, val2)
+            .map_err(|e| format!("HELib plaintext error: {}", e))?;
+        
+        let ct1 = helib.public_key.encrypt(&pt1)
+            .map_err(|e| format!("HELib encrypt error: {}", e))?;
+        let ct2 = helib.public_key.encrypt(&pt2)
+            .map_err(|e| format!("HELib encrypt error: {}", e))?;
+        
+        let result = ct1.add(&ct2)
+            .map_err(|e| format!("HELib add error: {}", e))?;
+        let decrypted = helib.secret_key.decrypt(&result)
+            .map_err(|e| format!("HELib decrypt error: {}", e))?;
+        
+        Ok(vec![decrypted.value()])
+    })
+}
+
+fn run_helib_multiply(keys: &KeyCache, val1: i64, val2: i64) -> Result<Vec<i64>, String> {
+    use he_benchmark::HEPlaintext;
+    
+    with_helib_keys(keys, |helib| {
+        let pt1 = HEPlaintext::new(&helib.context, val1)
+            .map_err(|e| format!("HELib plaintext error: {}", e))?;
+        let pt2 = HEPlaintext::new(&helib.context, val2)
+            .map_err(|e| format!("HELib plaintext error: {}", e))?;
+        
+        let ct1 = helib.public_key.encrypt(&pt1)
+            .map_err(|e| format!("HELib encrypt error: {}", e))?;
+        let ct2 = helib.public_key.encrypt(&pt2)
+            .map_err(|e| format!("HELib encrypt error: {}", e))?;
+        
+        let result = ct1.multiply(&ct2)
+            .map_err(|e| format!("HELib multiply error: {}", e))?;
+        let decrypted = helib.secret_key.decrypt(&result)
+            .map_err(|e| format!("HELib decrypt error: {}", e))?;
+        
+        Ok(vec![decrypted.value()])
+    })
 }
 
-fn run_helib_benchmark(num_operations: i32) -> BenchmarkResponse {
+fn run_helib_benchmark(num_operations: i32, mix: Option<OperationMix>, deadline: Instant) -> BenchmarkResponse {
     use he_benchmark::{HEContext, HESecretKey, HEPlaintext};
     
     let total_start = Instant::now();
     
+    // Key generation can't be interrupted, so it runs detached and the
+    // benchmark gives up on it once the request's deadline has passed
     let key_start = Instant::now();
-    let context = match HEContext::new(HELIB_M, HELIB_P, HELIB_R) {
-        Ok(ctx) => ctx,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("HELib context failed: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+    let remaining = || deadline.saturating_duration_since(Instant::now());
+    let context = match HEContext::new_with_deadline(HELIB_M, HELIB_P, HELIB_R, remaining()) {
+        Ok(ctx) => Arc::new(ctx),
+        Err(e) => return failed_benchmark(format!("HELib context failed: {}", e)),
     };
     
-    let secret_key = match HESecretKey::generate(&context) {
+    let secret_key = match HESecretKey::generate_with_deadline(&context, remaining()) {
         Ok(sk) => sk,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("HELib key gen failed: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("HELib key gen failed: {}", e)),
     };
     
     let public_key = match secret_key.public_key() {
         Ok(pk) => pk,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("HELib public key failed: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("HELib public key failed: {}", e)),
     };
     let key_gen_time = key_start.elapsed();
     
@@ -459,6 +1041,7 @@ fn run_helib_benchmark(num_operations: i32) -> BenchmarkResponse {
     let encode_start = Instant::now();
     let mut plaintexts = Vec::new();
     for i in 0..num_operations {
+        if deadline_passed(deadline) { break; }
         if let Ok(pt) = HEPlaintext::new(&context, i as i64) {
             plaintexts.push(pt);
         }
@@ -469,33 +1052,56 @@ fn run_helib_benchmark(num_operations: i32) -> BenchmarkResponse {
     let encrypt_start = Instant::now();
     let mut ciphertexts = Vec::new();
     for pt in &plaintexts {
+        if deadline_passed(deadline) { break; }
         if let Ok(ct) = public_key.encrypt(pt) {
             ciphertexts.push(ct);
         }
     }
     let encryption_time = encrypt_start.elapsed();
     
+    // HELib encrypts a single long per ciphertext (one slot in use)
+    let slots = 1;
+    let plaintext_bytes = slots * std::mem::size_of::<i64>();
+    let ciphertext_bytes = ciphertexts.first().map(|c| c.byte_count()).unwrap_or(0);
+    
     let add_start = Instant::now();
     for i in 1..ciphertexts.len() {
+        if deadline_passed(deadline) { break; }
         let _ = ciphertexts[0].add(&ciphertexts[i]);
     }
     let addition_time = add_start.elapsed();
     
     let mult_start = Instant::now();
     for i in 1..ciphertexts.len() {
+        if deadline_passed(deadline) { break; }
         let _ = ciphertexts[0].multiply(&ciphertexts[i]);
     }
     let multiplication_time = mult_start.elapsed();
     
+    // Weighted mix over the same ciphertexts, if one was requested
+    let mut operation_timings = Vec::new();
+    if let Some(mix) = mix.filter(|_| !ciphertexts.is_empty()) {
+        let n = ciphertexts.len();
+        operation_timings = run_mix(&mix, num_operations as usize, deadline, |op, i| {
+            let (a, b) = (&ciphertexts[i % n], &ciphertexts[(i + 1) % n]);
+            if op == "multiply" {
+                let _ = a.multiply(b);
+            } else {
+                let _ = a.add(b);
+            }
+        });
+    }
+    
     let decrypt_start = Instant::now();
     for ct in &ciphertexts {
+        if deadline_passed(deadline) { break; }
         let _ = secret_key.decrypt(ct);
     }
     let decryption_time = decrypt_start.elapsed();
     
     let total_time = total_start.elapsed();
     
-    BenchmarkResponse {
+    with_per_slot_costs(BenchmarkResponse {
         key_gen_time_ms: key_gen_time.as_secs_f64() * 1000.0,
         encoding_time_ms: encoding_time.as_secs_f64() * 1000.0 / num_operations as f64,
         encryption_time_ms: encryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
@@ -504,7 +1110,12 @@ fn run_helib_benchmark(num_operations: i32) -> BenchmarkResponse {
         decryption_time_ms: decryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
         total_time_ms: total_time.as_secs_f64() * 1000.0,
         status: format!("HELib benchmark complete: {} operations", num_operations),
-    }
+        plaintext_bytes: plaintext_bytes as i64,
+        ciphertext_bytes: ciphertext_bytes as i64,
+        expansion_ratio: expansion_ratio(plaintext_bytes, ciphertext_bytes),
+        operation_timings,
+        ..Default::default()
+    }, slots)
 }
 
 // ============================================
@@ -514,7 +1125,8 @@ fn run_helib_benchmark(num_operations: i32) -> BenchmarkResponse {
 const OPENFHE_PLAINTEXT_MOD: u64 = 65537;
 const OPENFHE_MULT_DEPTH: u32 = 2;
 
-fn run_openfhe_encrypt(values: Vec<i64>) -> Result<usize, String> {
+// Returns the serialized ciphertext and its size, like run_seal_encrypt
+fn run_openfhe_encrypt(values: Vec<i64>) -> Result<(Vec<u8>, usize), String> {
     use he_benchmark::{OpenFHEContext, OpenFHEKeyPair, OpenFHEPlaintext, OpenFHECiphertext};
     
     let context = OpenFHEContext::new_bfv(OPENFHE_PLAINTEXT_MOD, OPENFHE_MULT_DEPTH)
@@ -524,14 +1136,16 @@ fn run_openfhe_encrypt(values: Vec<i64>) -> Result<usize, String> {
     
     let plaintext = OpenFHEPlaintext::from_vec(&context, &values)
         .map_err(|e| format!("OpenFHE plaintext error: {}", e))?;
-    let _ciphertext = OpenFHECiphertext::encrypt(&context, &keypair, &plaintext)
+    let ciphertext = OpenFHECiphertext::encrypt(&context, &keypair, &plaintext)
         .map_err(|e| format!("OpenFHE encrypt error: {}", e))?;
     
-    // OpenFHE ciphertext size estimate
-    Ok(8192)
+    let bytes = ciphertext.to_bytes()
+        .map_err(|e| format!("OpenFHE serialize error: {}", e))?;
+    let byte_count = bytes.len();
+    Ok((bytes, byte_count))
 }
 
-fn run_openfhe_decrypt(values: Vec<i64>) -> Result<Vec<i64>, String> {
+fn run_openfhe_decrypt(batch: &[Vec<i64>]) -> Result<Vec<Vec<i64>>, String> {
     use he_benchmark::{OpenFHEContext, OpenFHEKeyPair, OpenFHEPlaintext, OpenFHECiphertext};
     
     let context = OpenFHEContext::new_bfv(OPENFHE_PLAINTEXT_MOD, OPENFHE_MULT_DEPTH)
@@ -539,17 +1153,19 @@ fn run_openfhe_decrypt(values: Vec<i64>) -> Result<Vec<i64>, String> {
     let keypair = OpenFHEKeyPair::generate(&context)
         .map_err(|e| format!("OpenFHE keypair error: {}", e))?;
     
-    let plaintext = OpenFHEPlaintext::from_vec(&context, &values)
-        .map_err(|e| format!("OpenFHE plaintext error: {}", e))?;
-    let ciphertext = OpenFHECiphertext::encrypt(&context, &keypair, &plaintext)
-        .map_err(|e| format!("OpenFHE encrypt error: {}", e))?;
-    let decrypted = ciphertext.decrypt(&context, &keypair)
-        .map_err(|e| format!("OpenFHE decrypt error: {}", e))?;
-    
-    let result = decrypted.to_vec()
-        .map_err(|e| format!("OpenFHE to_vec error: {}", e))?;
-    
-    Ok(result[..values.len().min(result.len())].to_vec())
+    batch.iter().map(|values| {
+        let plaintext = OpenFHEPlaintext::from_vec(&context, values)
+            .map_err(|e| format!("OpenFHE plaintext error: {}", e))?;
+        let ciphertext = OpenFHECiphertext::encrypt(&context, &keypair, &plaintext)
+            .map_err(|e| format!("OpenFHE encrypt error: {}", e))?;
+        let decrypted = ciphertext.decrypt(&context, &keypair)
+            .map_err(|e| format!("OpenFHE decrypt error: {}", e))?;
+        
+        let result = decrypted.to_vec()
+            .map_err(|e| format!("OpenFHE to_vec error: {}", e))?;
+        
+        Ok(result[..values.len().min(result.len())].to_vec())
+    }).collect()
 }
 
 fn run_openfhe_add(values1: &[i64], values2: &[i64]) -> Result<Vec<i64>, String> {
@@ -610,7 +1226,7 @@ fn run_openfhe_multiply(values1: &[i64], values2: &[i64]) -> Result<Vec<i64>, St
     Ok(result[..values1.len().max(values2.len()).min(result.len())].to_vec())
 }
 
-fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
+fn run_openfhe_benchmark(num_operations: i32, mix: Option<OperationMix>, deadline: Instant) -> BenchmarkResponse {
     use he_benchmark::{OpenFHEContext, OpenFHEKeyPair, OpenFHEPlaintext, OpenFHECiphertext};
     
     let total_start = Instant::now();
@@ -619,22 +1235,12 @@ fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
     let key_start = Instant::now();
     let context = match OpenFHEContext::new_bfv(OPENFHE_PLAINTEXT_MOD, OPENFHE_MULT_DEPTH) {
         Ok(ctx) => ctx,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("OpenFHE context failed: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("OpenFHE context failed: {}", e)),
     };
     
     let keypair = match OpenFHEKeyPair::generate(&context) {
         Ok(kp) => kp,
-        Err(e) => return BenchmarkResponse {
-            key_gen_time_ms: 0.0, encryption_time_ms: 0.0, addition_time_ms: 0.0,
-            multiplication_time_ms: 0.0, decryption_time_ms: 0.0,
-            status: format!("OpenFHE keypair failed: {}", e),
-            total_time_ms: 0.0, encoding_time_ms: 0.0,
-        },
+        Err(e) => return failed_benchmark(format!("OpenFHE keypair failed: {}", e)),
     };
     let key_gen_time = key_start.elapsed();
     
@@ -645,6 +1251,7 @@ fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
     let encode_start = Instant::now();
     let mut plaintexts = Vec::new();
     for _ in 0..num_operations {
+        if deadline_passed(deadline) { break; }
         if let Ok(pt) = OpenFHEPlaintext::from_vec(&context, &test_data) {
             plaintexts.push(pt);
         }
@@ -655,15 +1262,21 @@ fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
     let encrypt_start = Instant::now();
     let mut ciphertexts = Vec::new();
     for pt in &plaintexts {
+        if deadline_passed(deadline) { break; }
         if let Ok(ct) = OpenFHECiphertext::encrypt(&context, &keypair, pt) {
             ciphertexts.push(ct);
         }
     }
     let encryption_time = encrypt_start.elapsed();
     
+    // Size of one ciphertext vs. the i64 values packed into it
+    let plaintext_bytes = test_data.len() * std::mem::size_of::<i64>();
+    let ciphertext_bytes = ciphertexts.first().map(|c| c.byte_count()).unwrap_or(0);
+    
     // Addition timing
     let add_start = Instant::now();
     for i in 1..ciphertexts.len() {
+        if deadline_passed(deadline) { break; }
         let _ = ciphertexts[0].add(&context, &ciphertexts[i]);
     }
     let addition_time = add_start.elapsed();
@@ -671,20 +1284,39 @@ fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
     // Multiplication timing
     let mult_start = Instant::now();
     for i in 1..ciphertexts.len() {
+        if deadline_passed(deadline) { break; }
         let _ = ciphertexts[0].multiply(&context, &keypair, &ciphertexts[i]);
     }
     let multiplication_time = mult_start.elapsed();
     
+    // Weighted mix over the same ciphertexts, if one was requested
+    let mut operation_timings = Vec::new();
+    if let Some(mix) = mix.filter(|_| !ciphertexts.is_empty()) {
+        let n = ciphertexts.len();
+        operation_timings = run_mix(&mix, num_operations as usize, deadline, |op, i| {
+            let (a, b) = (&ciphertexts[i % n], &ciphertexts[(i + 1) % n]);
+            if op == "multiply" {
+                let _ = a.multiply(&context, &keypair, b);
+            } else {
+                let _ = a.add(&context, b);
+            }
+        });
+    }
+    
     // Decryption timing
     let decrypt_start = Instant::now();
     for ct in &ciphertexts {
+        if deadline_passed(deadline) { break; }
         let _ = ct.decrypt(&context, &keypair);
     }
     let decryption_time = decrypt_start.elapsed();
     
     let total_time = total_start.elapsed();
 
-    BenchmarkResponse {
+    // No rotation keys are generated for this context, so galois_key_bytes stays 0
+    let serialized_len = |bytes: he_benchmark::open_fhe_lib::Result<Vec<u8>>| bytes.map(|b| b.len() as i64).unwrap_or(0);
+
+    with_per_slot_costs(BenchmarkResponse {
         key_gen_time_ms: key_gen_time.as_secs_f64() * 1000.0,
         encoding_time_ms: encoding_time.as_secs_f64() * 1000.0 / num_operations as f64,
         encryption_time_ms: encryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
@@ -692,8 +1324,16 @@ fn run_openfhe_benchmark(num_operations: i32) -> BenchmarkResponse {
         multiplication_time_ms: multiplication_time.as_secs_f64() * 1000.0 / (num_operations - 1).max(1) as f64,
         decryption_time_ms: decryption_time.as_secs_f64() * 1000.0 / num_operations as f64,
         total_time_ms: total_time.as_secs_f64() * 1000.0,
-        status: format!("OpenFHE benchmark complete: {} operations", num_operations),
-    }
+        status: format!("OpenFHE ({}) benchmark complete: {} operations", context.scheme(), num_operations),
+        plaintext_bytes: plaintext_bytes as i64,
+        ciphertext_bytes: ciphertext_bytes as i64,
+        expansion_ratio: expansion_ratio(plaintext_bytes, ciphertext_bytes),
+        operation_timings,
+        public_key_bytes: serialized_len(keypair.public_key_to_bytes()),
+        secret_key_bytes: serialized_len(keypair.secret_key_to_bytes()),
+        relin_key_bytes: serialized_len(context.eval_mult_keys_to_bytes()),
+        ..Default::default()
+    }, test_data.len())
 }
 
 // ============================================
@@ -710,43 +1350,81 @@ impl HeService for HEServiceImpl {
         
         println!("📥 Received GenerateKeys request for library: {}", req.library);
         
-        if !["SEAL", "HELib", "OpenFHE"].contains(&req.library.as_str()) {
+        if !SUPPORTED_LIBRARIES.contains(&req.library.as_str()) {
             return Err(Status::invalid_argument("Library must be one of: SEAL, HELib, OpenFHE"));
         }
+        // The client's own keys, all three or none
+        let supplied = [&req.public_key, &req.relin_keys, &req.galois_keys].iter().filter(|k| !k.is_empty()).count();
+        if supplied != 0 && supplied != 3 {
+            return Err(Status::invalid_argument("public_key, relin_keys and galois_keys must be sent together"));
+        }
+        if supplied == 3 && req.library != "SEAL" {
+            return Err(Status::invalid_argument(format!("Only SEAL sessions can import keys, not {}", req.library)));
+        }
+        if supplied == 0 && self.public_only {
+            return Err(Status::failed_precondition(
+                "This server runs with --no-decrypt and never generates a secret key: send client-generated SEAL keys"
+            ));
+        }
+        
+        // Sessions only get created for working libraries, so the per-session
+        // RPCs don't need to check again
+        self.require_available(&req.library)?;
         
         let session_id = uuid::Uuid::new_v4().to_string();
         let poly_degree = req.poly_modulus_degree as u64;
-        let plain_modulus = 1032193u64;
+        let plain_modulus = SESSION_PLAIN_MODULUS;
         let library = req.library.clone();
         
         // Validate context creation
-        if library == "SEAL" {
-            let pd = poly_degree;
-            let result = tokio::task::spawn_blocking(move || {
-                use he_benchmark::Context as SealContext;
-                SealContext::new(pd, plain_modulus).map(|_| ()).map_err(|e| format!("{}", e))
-            }).await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?;
+        if supplied == 3 {
+            validate_seal_parameters(poly_degree, plain_modulus)?;
+            let (public_key, relin_keys, galois_keys) = (req.public_key, req.relin_keys, req.galois_keys);
+            let imported = self.run_blocking("GenerateKeys", move || {
+                ImportedKeys::load(poly_degree, plain_modulus, &public_key, &relin_keys, &galois_keys)
+            }).await?.map_err(|e| Status::invalid_argument(format!(
+                "Client keys don't match n={}, t={}: {}", poly_degree, plain_modulus, e
+            )))?;
+            
+            let session = SessionConfig::with_imported_keys(poly_degree, plain_modulus, imported);
+            let version = session.version;
+            self.sessions.insert(session_id.clone(), session);
+            
+            println!("✓ Session created with client keys (no secret key): {}", &session_id[..8]);
+            
+            return Ok(Response::new(GenerateKeysResponse {
+                session_id: session_id.clone(),
+                public_key: vec![],
+                status: format!("Client keys imported for SEAL (session: {})", &session_id[..8]),
+                version,
+            }));
+        } else if library == "SEAL" {
+            validate_seal_parameters(poly_degree, plain_modulus)?;
+            // Builds the shared context and its evaluation keys; a later session
+            // with the same parameters (or a degree warmed at startup) finds them cached
+            let keys = Arc::clone(&self.keys);
+            let result = self.run_blocking("GenerateKeys", move || keys.warm(poly_degree, plain_modulus)).await?;
             
             if let Err(e) = result {
-                return Err(Status::internal(format!("Failed to create SEAL context: {}", e)));
+                return Err(Status::internal(format!("SEAL key generation failed: {}", e)));
             }
-            println!("   ✓ SEAL context validated");
+            println!("   ✓ SEAL context and evaluation keys ready");
         } else if library == "HELib" {
-            let result = tokio::task::spawn_blocking(move || {
+            let result = self.run_blocking("GenerateKeys", move || {
                 use he_benchmark::HEContext;
                 HEContext::new(HELIB_M, HELIB_P, HELIB_R).map(|_| ()).map_err(|e| format!("{}", e))
-            }).await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?;
+            }).await?;
             
             if let Err(e) = result {
                 return Err(Status::internal(format!("Failed to create HELib context: {}", e)));
             }
             println!("   ✓ HELib context validated");
         } else if library == "OpenFHE" {
-            let result = tokio::task::spawn_blocking(move || {
+            let result = self.run_blocking("GenerateKeys", move || {
                 use he_benchmark::OpenFHEContext;
                 OpenFHEContext::new_bfv(OPENFHE_PLAINTEXT_MOD, OPENFHE_MULT_DEPTH)
                     .map(|_| ()).map_err(|e| format!("{}", e))
-            }).await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?;
+            }).await?;
             
             if let Err(e) = result {
                 return Err(Status::internal(format!("Failed to create OpenFHE context: {}", e)));
@@ -754,14 +1432,10 @@ impl HeService for HEServiceImpl {
             println!("   ✓ OpenFHE context validated");
         }
         
-        let session = SessionConfig {
-            library: req.library.clone(),
-            poly_modulus_degree: poly_degree,
-            plain_modulus,
-            ciphertext_values: HashMap::new(),
-        };
+        let session = SessionConfig::new(req.library.clone(), poly_degree, plain_modulus);
+        let version = session.version;
         
-        self.sessions.lock().unwrap().insert(session_id.clone(), session);
+        self.sessions.insert(session_id.clone(), session);
         
         println!("✓ Session created: {}", &session_id[..8]);
         
@@ -769,6 +1443,7 @@ impl HeService for HEServiceImpl {
             session_id: session_id.clone(),
             public_key: vec![],
             status: format!("Keys generated for {} (session: {})", req.library, &session_id[..8]),
+            version,
         }))
     }
 
@@ -781,45 +1456,52 @@ impl HeService for HEServiceImpl {
         
         println!("📥 Encrypt request for session: {}", sid);
         
-        let (library, poly_degree, plain_modulus) = {
-            let sessions = self.sessions.lock().unwrap();
-            let session = sessions.get(&req.session_id)
-                .ok_or_else(|| Status::not_found("Session not found"))?;
-            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus)
-        };
+        // Each backend would otherwise make something up (HElib encrypts 0)
+        if req.values.is_empty() {
+            return Err(Status::invalid_argument("values must be non-empty"));
+        }
+        
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        // The values would be sent and stored in the clear, and Decrypt couldn't
+        // read them back anyway
+        if self.public_only || session.keys.is_some() {
+            return Err(Status::failed_precondition(
+                "Encrypt takes plaintext, but this session uses the client's keys; encrypt on the client"
+            ));
+        }
+        check_version(&session, "Encrypt", req.expected_version)?;
+        check_nonce(&mut session, "Encrypt", req.nonce)?;
+        let (library, poly_degree, plain_modulus) =
+            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus);
         
         let values = req.values.clone();
         let ciphertext_id = uuid::Uuid::new_v4().to_string();
         
         let (ciphertext_bytes, byte_count) = if library == "HELib" {
             let first_value = values.first().copied().unwrap_or(0);
-            let result = tokio::task::spawn_blocking(move || run_helib_encrypt(first_value))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?;
+            let keys = Arc::clone(&self.keys);
+            let result = self.run_blocking("Encrypt", move || run_helib_encrypt(&keys, first_value)).await?
+                .map_err(Status::internal)?;
             (vec![0u8; result.min(1024)], result)
         } else if library == "OpenFHE" {
-            let result = tokio::task::spawn_blocking(move || run_openfhe_encrypt(values))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?;
-            (vec![0u8; result.min(1024)], result)
+            self.run_blocking("Encrypt", move || run_openfhe_encrypt(values)).await?
+                .map_err(Status::internal)?
         } else {
-            tokio::task::spawn_blocking(move || run_seal_encrypt(poly_degree, plain_modulus, values))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking("Encrypt", move || run_seal_encrypt(&keys, poly_degree, plain_modulus, values)).await?
+                .map_err(Status::internal)?
         };
         
-        {
-            let mut sessions = self.sessions.lock().unwrap();
-            if let Some(session) = sessions.get_mut(&req.session_id) {
-                session.ciphertext_values.insert(ciphertext_id.clone(), req.values.clone());
-            }
-        }
+        let version = session.store(ciphertext_id.clone(), req.values.clone());
         
         println!("   ✓ Encrypted {} values → {} bytes using {}", req.values.len(), byte_count, library);
         
         Ok(Response::new(EncryptResponse {
             ciphertext: ciphertext_bytes,
             status: format!("Encrypted {} values using {}", req.values.len(), library),
+            ciphertext_id,
+            version,
         }))
     }
 
@@ -832,29 +1514,20 @@ impl HeService for HEServiceImpl {
         
         println!("�� Decrypt request for session: {}", sid);
         
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        self.require_secret_key(&session, "Decrypt")?;
+        check_nonce(&mut session, "Decrypt", req.nonce)?;
         let (library, poly_degree, plain_modulus, original_values) = {
-            let sessions = self.sessions.lock().unwrap();
-            let session = sessions.get(&req.session_id)
-                .ok_or_else(|| Status::not_found("Session not found"))?;
-            let values = session.ciphertext_values.values().next()
-                .cloned().unwrap_or_else(|| vec![1, 2, 3]);
+            let values = session.ciphertext_values.first()
+                .map(|(_, values)| values.clone()).unwrap_or_else(|| vec![1, 2, 3]);
             (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, values)
         };
         
-        let result = if library == "HELib" {
-            let value = original_values.first().copied().unwrap_or(0);
-            tokio::task::spawn_blocking(move || run_helib_decrypt(value))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
-        } else if library == "OpenFHE" {
-            tokio::task::spawn_blocking(move || run_openfhe_decrypt(original_values))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
-        } else {
-            tokio::task::spawn_blocking(move || run_seal_decrypt(poly_degree, plain_modulus, &original_values))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
-        };
+        let result = self.decrypt_batch("Decrypt", &library, poly_degree, plain_modulus, vec![original_values])
+            .await?
+            .pop()
+            .unwrap_or_default();
         
         println!("   ✓ Decrypted {} values using {}", result.len(), library);
         
@@ -864,6 +1537,51 @@ impl HeService for HEServiceImpl {
         }))
     }
 
+    async fn decrypt_all(
+        &self,
+        request: Request<DecryptAllRequest>,
+    ) -> Result<Response<DecryptAllResponse>, Status> {
+        let req = request.into_inner();
+        let sid = &req.session_id[..8.min(req.session_id.len())];
+        
+        println!("📥 DecryptAll request for session: {}", sid);
+        
+        let session = self.session(&req.session_id)?;
+        let session = session.lock().await;
+        self.require_secret_key(&session, "DecryptAll")?;
+        // No ids means every stored ciphertext, in the order they were stored
+        let (library, poly_degree, plain_modulus, stored) = {
+            let stored: Vec<(String, Vec<i64>)> = if req.ciphertext_ids.is_empty() {
+                session.ciphertext_values.clone()
+            } else {
+                let mut stored = Vec::with_capacity(req.ciphertext_ids.len());
+                for id in &req.ciphertext_ids {
+                    let entry = session.ciphertext_values.iter()
+                        .find(|(stored_id, _)| stored_id == id)
+                        .ok_or_else(|| Status::not_found(format!("Ciphertext not found: {}", id)))?;
+                    stored.push(entry.clone());
+                }
+                stored
+            };
+            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, stored)
+        };
+        
+        let (ciphertext_ids, batch): (Vec<String>, Vec<Vec<i64>>) = stored.into_iter().unzip();
+        let results = if batch.is_empty() {
+            Vec::new()
+        } else {
+            self.decrypt_batch("DecryptAll", &library, poly_degree, plain_modulus, batch).await?
+        };
+        
+        println!("   ✓ Decrypted {} ciphertexts using {}", results.len(), library);
+        
+        Ok(Response::new(DecryptAllResponse {
+            status: format!("Decrypted {} ciphertexts using {}", results.len(), library),
+            ciphertext_ids,
+            results: results.into_iter().map(|values| Int64Array { values }).collect(),
+        }))
+    }
+
     async fn add(
         &self,
         request: Request<BinaryOpRequest>,
@@ -873,31 +1591,63 @@ impl HeService for HEServiceImpl {
         
         println!(" Add request for session: {}", sid);
         
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        check_version(&session, "Add", req.expected_version)?;
+        check_nonce(&mut session, "Add", req.nonce)?;
+
+        // Client-side encryption: add the ciphertexts as sent and return the sum
+        if !req.ciphertext1.is_empty() || !req.ciphertext2.is_empty() {
+            if session.library != "SEAL" {
+                return Err(Status::invalid_argument(format!(
+                    "Add on client ciphertexts is SEAL only, this is a {} session",
+                    session.library
+                )));
+            }
+            let keys = Arc::clone(&self.keys);
+            let imported = session.keys.clone();
+            let (poly_degree, plain_modulus) = (session.poly_modulus_degree, session.plain_modulus);
+            let (ciphertext1, ciphertext2) = (req.ciphertext1, req.ciphertext2);
+            println!("   Adding client ciphertexts ({} + {} bytes, no plaintext)", ciphertext1.len(), ciphertext2.len());
+            #[allow(clippy::result_large_err)]
+            let (result_ciphertext, size) = self.run_blocking("Add", move || {
+                let context = seal_session_context(&keys, imported.as_ref(), poly_degree, plain_modulus)
+                    .map_err(Status::internal)?;
+                run_seal_add_ciphertexts(&context, &ciphertext1, &ciphertext2)
+            }).await??;
+            println!("   ✓ Returned {} byte encrypted sum", result_ciphertext.len());
+
+            return Ok(Response::new(BinaryOpResponse {
+                result_ciphertext,
+                status: "Addition complete using SEAL (client ciphertexts)".to_string(),
+                ciphertext_size: size as i32,
+                version: session.version,
+            }));
+        }
+        // The stored values are added under the pooled context's secret key
+        self.require_secret_key(&session, "Add on stored values")?;
+
         let (library, poly_degree, plain_modulus, all_values) = {
-            let sessions = self.sessions.lock().unwrap();
-            let session = sessions.get(&req.session_id)
-                .ok_or_else(|| Status::not_found("Session not found"))?;
-            let values: Vec<_> = session.ciphertext_values.values().cloned().collect();
+            let values: Vec<_> = session.ciphertext_values.iter().map(|(_, values)| values.clone()).collect();
             (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, values)
         };
         
-        let values1 = all_values.get(0).cloned().unwrap_or_else(|| vec![1, 2, 3]);
+        let values1 = all_values.first().cloned().unwrap_or_else(|| vec![1, 2, 3]);
         let values2 = all_values.get(1).cloned().unwrap_or_else(|| vec![1, 1, 1]);
         
         let result = if library == "HELib" {
             let v1 = values1.first().copied().unwrap_or(0);
             let v2 = values2.first().copied().unwrap_or(0);
-            tokio::task::spawn_blocking(move || run_helib_add(v1, v2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking("Add", move || run_helib_add(&keys, v1, v2)).await?
+                .map_err(Status::internal)?
         } else if library == "OpenFHE" {
-            tokio::task::spawn_blocking(move || run_openfhe_add(&values1, &values2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            self.run_blocking("Add", move || run_openfhe_add(&values1, &values2)).await?
+                .map_err(Status::internal)?
         } else {
-            tokio::task::spawn_blocking(move || run_seal_add(poly_degree, plain_modulus, &values1, &values2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking("Add", move || run_seal_add(&keys, poly_degree, plain_modulus, &values1, &values2)).await?
+                .map_err(Status::internal)?
         };
         
         println!("   ✓ Addition result: {:?} using {}", &result[..result.len().min(3)], library);
@@ -905,6 +1655,8 @@ impl HeService for HEServiceImpl {
         Ok(Response::new(BinaryOpResponse {
             result_ciphertext: vec![],
             status: format!("Addition complete using {}", library),
+            ciphertext_size: RELINEARIZED_SIZE as i32,
+            version: session.version,
         }))
     }
 
@@ -917,38 +1669,75 @@ impl HeService for HEServiceImpl {
         
         println!("📥 Multiply request for session: {}", sid);
         
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        check_version(&session, "Multiply", req.expected_version)?;
+        check_nonce(&mut session, "Multiply", req.nonce)?;
+
+        // Client-side encryption: multiply under the client's relin keys and
+        // return the product
+        if !req.ciphertext1.is_empty() || !req.ciphertext2.is_empty() {
+            // The pooled relin keys belong to another secret key and would
+            // silently corrupt the product
+            let Some(imported) = session.keys.clone() else {
+                return Err(Status::failed_precondition(
+                    "Multiply on client ciphertexts needs the client's relin keys; send them with GenerateKeys"
+                ));
+            };
+            let (ciphertext1, ciphertext2) = (req.ciphertext1, req.ciphertext2);
+            println!("   Multiplying client ciphertexts ({} x {} bytes, no plaintext)", ciphertext1.len(), ciphertext2.len());
+            #[allow(clippy::result_large_err)]
+            let (result_ciphertext, size) = self.run_blocking("Multiply", move || {
+                run_seal_multiply_ciphertexts(&imported, &ciphertext1, &ciphertext2)
+            }).await??;
+            println!("   ✓ Returned {} byte encrypted product", result_ciphertext.len());
+
+            return Ok(Response::new(BinaryOpResponse {
+                result_ciphertext,
+                status: "Multiplication complete using SEAL (client ciphertexts)".to_string(),
+                ciphertext_size: size as i32,
+                version: session.version,
+            }));
+        }
+        self.require_secret_key(&session, "Multiply on stored values")?;
+
         let (library, poly_degree, plain_modulus, all_values) = {
-            let sessions = self.sessions.lock().unwrap();
-            let session = sessions.get(&req.session_id)
-                .ok_or_else(|| Status::not_found("Session not found"))?;
-            let values: Vec<_> = session.ciphertext_values.values().cloned().collect();
+            let values: Vec<_> = session.ciphertext_values.iter().map(|(_, values)| values.clone()).collect();
             (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, values)
         };
         
-        let values1 = all_values.get(0).cloned().unwrap_or_else(|| vec![2, 3, 4]);
+        let values1 = all_values.first().cloned().unwrap_or_else(|| vec![2, 3, 4]);
         let values2 = all_values.get(1).cloned().unwrap_or_else(|| vec![2, 2, 2]);
         
-        let result = if library == "HELib" {
+        // HElib's and OpenFHE's multiply relinearize internally (HElib's Ctxt
+        // multiplication and OpenFHE's EvalMult with the session's mult keys)
+        let (result, ciphertext_size) = if library == "HELib" {
             let v1 = values1.first().copied().unwrap_or(0);
             let v2 = values2.first().copied().unwrap_or(0);
-            tokio::task::spawn_blocking(move || run_helib_multiply(v1, v2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let keys = Arc::clone(&self.keys);
+            let result = self.run_blocking("Multiply", move || run_helib_multiply(&keys, v1, v2)).await?
+                .map_err(Status::internal)?;
+            (result, RELINEARIZED_SIZE)
         } else if library == "OpenFHE" {
-            tokio::task::spawn_blocking(move || run_openfhe_multiply(&values1, &values2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let result = self.run_blocking("Multiply", move || run_openfhe_multiply(&values1, &values2)).await?
+                .map_err(Status::internal)?;
+            (result, RELINEARIZED_SIZE)
         } else {
-            tokio::task::spawn_blocking(move || run_seal_multiply(poly_degree, plain_modulus, &values1, &values2))
-                .await.map_err(|e| Status::internal(format!("Task failed: {}", e)))?
-                .map_err(|e| Status::internal(e))?
+            let keys = Arc::clone(&self.keys);
+            self.run_blocking("Multiply", move || run_seal_multiply(&keys, poly_degree, plain_modulus, &values1, &values2)).await?
+                .map_err(Status::internal)?
         };
         
-        println!("   ✓ Multiply result: {:?} using {}", &result[..result.len().min(3)], library);
+        // Keep the product in the session so later operations can build on it
+        let version = session.store(uuid::Uuid::new_v4().to_string(), result.clone());
+        
+        println!("   ✓ Multiply result: {:?} (size {}) using {}", &result[..result.len().min(3)], ciphertext_size, library);
         
         Ok(Response::new(BinaryOpResponse {
             result_ciphertext: vec![],
             status: format!("Multiplication complete using {}", library),
+            ciphertext_size: ciphertext_size as i32,
+            version,
         }))
     }
 
@@ -962,20 +1751,43 @@ impl HeService for HEServiceImpl {
         
         let library = req.library.clone();
         let num_ops = req.num_operations;
+        let repetitions = benchmark_repetitions(req.repetitions)?;
+        let deadline = Instant::now() + self.op_timeout;
+        // Anything else runs SEAL (the default)
+        let effective_library = match library.as_str() {
+            "HELib" | "OpenFHE" => library.as_str(),
+            _ => "SEAL",
+        };
+        self.require_available(effective_library)?;
+        let mix = OperationMix::parse(&req.operation_mix, mix_operations(effective_library))
+            .map_err(Status::invalid_argument)?;
+        // A dry run does the same setup but times a few operations, once
+        let (run_ops, run_repetitions) = if req.dry_run { (warmup_operations(num_ops), 1) } else { (num_ops, repetitions) };
         
-        let response = if library == "HELib" {
-            tokio::task::spawn_blocking(move || run_helib_benchmark(num_ops))
-                .await.map_err(|e| Status::internal(format!("Benchmark failed: {}", e)))?
+        let mut response = if library == "HELib" {
+            self.run_blocking("RunBenchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_helib_benchmark(run_ops, mix.clone(), deadline))
+            }).await?
         } else if library == "OpenFHE" {
-            tokio::task::spawn_blocking(move || run_openfhe_benchmark(num_ops))
-                .await.map_err(|e| Status::internal(format!("Benchmark failed: {}", e)))?
+            self.run_blocking("RunBenchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_openfhe_benchmark(run_ops, mix.clone(), deadline))
+            }).await?
         } else {
             let poly_degree = 8192u64;
-            tokio::task::spawn_blocking(move || run_seal_benchmark(poly_degree, num_ops))
-                .await.map_err(|e| Status::internal(format!("Benchmark failed: {}", e)))?
+            self.run_blocking("RunBenchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_seal_benchmark(poly_degree, run_ops, mix.clone(), deadline))
+            }).await?
         };
         
-        println!("   ✓ Benchmark complete using {}", library);
+        if req.dry_run {
+            // Estimates aren't results: nothing goes into the history
+            response = estimate_full_run(response, run_ops, num_ops, repetitions);
+            println!("   ✓ {}", response.status);
+        } else {
+            println!("   ✓ Benchmark complete using {}", library);
+            self.record_benchmark(effective_library, num_ops, &response);
+        }
+        response.run_metadata = Some(run_metadata());
         
         Ok(Response::new(response))
     }
@@ -986,53 +1798,65 @@ impl HeService for HEServiceImpl {
     ) -> Result<Response<ComparisonBenchmarkResponse>, Status> {
         let req = request.into_inner();
         let num_ops = req.num_operations;
+        let repetitions = benchmark_repetitions(req.repetitions)?;
         
         println!("📥 Comparison benchmark request ({} ops per library)", num_ops);
+        let mix = OperationMix::parse(&req.operation_mix, &COMPARISON_MIX_OPERATIONS)
+            .map_err(Status::invalid_argument)?;
         println!("   Running SEAL benchmark...");
         
-        // Run all three benchmarks
-        let seal_ops = num_ops;
-        let seal_result = tokio::task::spawn_blocking(move || {
-            run_seal_benchmark(8192, seal_ops)
-        }).await.map_err(|e| Status::internal(format!("SEAL benchmark failed: {}", e)))?;
+        // Run all three benchmarks (one deadline covers the whole comparison).
+        // A library that failed at startup is reported as failed instead of run.
+        let deadline = Instant::now() + self.op_timeout;
+        let (run_ops, run_repetitions) = if req.dry_run { (warmup_operations(num_ops), 1) } else { (num_ops, repetitions) };
+        let seal_ops = run_ops;
+        let seal_mix = mix.clone();
+        let mut seal_result = match self.require_available("SEAL") {
+            Err(status) => failed_benchmark(status.message().to_string()),
+            Ok(()) => self.run_blocking("SEAL benchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_seal_benchmark(8192, seal_ops, seal_mix.clone(), deadline))
+            }).await?,
+        };
         
         println!("   Running HELib benchmark...");
-        let helib_ops = num_ops;
-        let helib_result = tokio::task::spawn_blocking(move || {
-            run_helib_benchmark(helib_ops)
-        }).await.map_err(|e| Status::internal(format!("HELib benchmark failed: {}", e)))?;
+        let helib_ops = run_ops;
+        let helib_mix = mix.clone();
+        let helib_remaining = deadline.saturating_duration_since(Instant::now());
+        let mut helib_result = match self.require_available("HELib") {
+            Err(status) => failed_benchmark(status.message().to_string()),
+            Ok(()) => run_with_deadline(helib_remaining, "HELib benchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_helib_benchmark(helib_ops, helib_mix.clone(), deadline))
+            }).await?,
+        };
         
         println!("   Running OpenFHE benchmark...");
-        let openfhe_ops = num_ops;
-        let openfhe_result = tokio::task::spawn_blocking(move || {
-            run_openfhe_benchmark(openfhe_ops)
-        }).await.map_err(|e| Status::internal(format!("OpenFHE benchmark failed: {}", e)))?;
-        
-        // Determine fastest library based on total time
-        let seal_total = seal_result.total_time_ms;
-        let helib_total = helib_result.total_time_ms;
-        let openfhe_total = openfhe_result.total_time_ms;
-        
-        let fastest_library = if seal_total <= helib_total && seal_total <= openfhe_total {
-            "SEAL".to_string()
-        } else if helib_total <= seal_total && helib_total <= openfhe_total {
-            "HELib".to_string()
-        } else {
-            "OpenFHE".to_string()
+        let openfhe_ops = run_ops;
+        let openfhe_remaining = deadline.saturating_duration_since(Instant::now());
+        let mut openfhe_result = match self.require_available("OpenFHE") {
+            Err(status) => failed_benchmark(status.message().to_string()),
+            Ok(()) => run_with_deadline(openfhe_remaining, "OpenFHE benchmark", move || {
+                run_benchmark_repeated(run_repetitions, deadline, || run_openfhe_benchmark(openfhe_ops, mix.clone(), deadline))
+            }).await?,
         };
         
-        // Generate recommendation
-        let recommendation = if seal_result.encryption_time_ms < helib_result.encryption_time_ms 
-            && seal_result.encryption_time_ms < openfhe_result.encryption_time_ms {
-            "SEAL recommended for encryption-heavy workloads (batching support)".to_string()
-        } else if helib_result.multiplication_time_ms < seal_result.multiplication_time_ms 
-            && helib_result.multiplication_time_ms < openfhe_result.multiplication_time_ms {
-            "HELib recommended for multiplication-heavy workloads (BGV optimizations)".to_string()
-        } else {
-            "OpenFHE recommended for general-purpose HE (flexible API)".to_string()
-        };
+        if req.dry_run {
+            for result in [&mut seal_result, &mut helib_result, &mut openfhe_result] {
+                *result = estimate_full_run(std::mem::take(result), run_ops, num_ops, repetitions);
+            }
+        }
+        
+        let (fastest_library, recommendation) =
+            compare_libraries(&[("SEAL", &seal_result), ("HELib", &helib_result), ("OpenFHE", &openfhe_result)]);
         
         println!("   ✓ Comparison complete - Fastest: {}", fastest_library);
+        for result in [&mut seal_result, &mut helib_result, &mut openfhe_result] {
+            result.run_metadata = Some(run_metadata());
+        }
+        if !req.dry_run {
+            self.record_benchmark("SEAL", num_ops, &seal_result);
+            self.record_benchmark("HELib", num_ops, &helib_result);
+            self.record_benchmark("OpenFHE", num_ops, &openfhe_result);
+        }
         
         Ok(Response::new(ComparisonBenchmarkResponse {
             seal: Some(seal_result),
@@ -1042,6 +1866,290 @@ impl HeService for HEServiceImpl {
             recommendation,
         }))
     }
+
+    async fn aggregate_window(
+        &self,
+        request: Request<AggregateWindowRequest>,
+    ) -> Result<Response<AggregateWindowResponse>, Status> {
+        let req = request.into_inner();
+        let sid = &req.session_id[..8.min(req.session_id.len())];
+
+        println!("📥 AggregateWindow request for session: {} (+{} counts, last {})",
+            sid, req.counts.len(), req.window_size);
+
+        if req.window_size < 0 || req.window_size as usize > WINDOW_CAPACITY {
+            return Err(Status::invalid_argument(format!(
+                "window_size must be between 0 and {}", WINDOW_CAPACITY
+            )));
+        }
+
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        // The window keeps a full SEAL context (secret key included) to decrypt its sums
+        self.require_secret_key(&session, "AggregateWindow")?;
+        check_nonce(&mut session, "AggregateWindow", req.nonce)?;
+        let (library, poly_degree, plain_modulus) =
+            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus);
+
+        if library != "SEAL" {
+            return Err(Status::failed_precondition(format!(
+                "AggregateWindow is only supported for SEAL sessions (session uses {})", library
+            )));
+        }
+
+        let existing = self.windows.lock().unwrap().get(&req.session_id).cloned();
+        let window = match existing {
+            Some(window) => window,
+            None => {
+                let created = self.run_blocking("AggregateWindow", move || {
+                    EncryptedWindow::new(poly_degree, plain_modulus, WINDOW_CAPACITY)
+                }).await?.map_err(Status::internal)?;
+
+                // Another request may have created it in the meantime; keep the first one
+                self.windows.lock().unwrap()
+                    .entry(req.session_id.clone())
+                    .or_insert_with(|| Arc::new(Mutex::new(created)))
+                    .clone()
+            }
+        };
+
+        let counts = req.counts.clone();
+        let window_size = req.window_size as usize;
+        let (window_sum, entries_summed, entries_stored, aggregation_ms) =
+            self.run_blocking("AggregateWindow", move || -> Result<_, String> {
+                let mut window = window.lock().unwrap();
+                window.push(&counts)?;
+
+                let start = Instant::now();
+                let (sum, used) = window.sum_last(window_size)?;
+                Ok((sum, used, window.len(), start.elapsed().as_secs_f64() * 1000.0))
+            }).await?.map_err(Status::internal)?;
+
+        println!("   ✓ Window sum over last {} of {} entries", entries_summed, entries_stored);
+
+        Ok(Response::new(AggregateWindowResponse {
+            window_sum,
+            entries_summed: entries_summed as i32,
+            entries_stored: entries_stored as i32,
+            aggregation_time_ms: aggregation_ms,
+            status: format!("Summed last {} of {} encrypted entries", entries_summed, entries_stored),
+        }))
+    }
+
+    async fn stress_test(
+        &self,
+        request: Request<StressTestRequest>,
+    ) -> Result<Response<StressTestResponse>, Status> {
+        let req = request.into_inner();
+        
+        println!("📥 StressTest request: {} concurrent {} sessions", req.concurrent_sessions, req.library);
+        
+        if !SUPPORTED_LIBRARIES.contains(&req.library.as_str()) {
+            return Err(Status::invalid_argument("Library must be one of: SEAL, HELib, OpenFHE"));
+        }
+        if !(1..=MAX_STRESS_SESSIONS).contains(&req.concurrent_sessions) {
+            return Err(Status::invalid_argument(format!(
+                "concurrent_sessions must be between 1 and {}", MAX_STRESS_SESSIONS
+            )));
+        }
+        self.require_available(&req.library)?;
+        
+        let poly_degree = 8192u64;
+        let plain_modulus = SESSION_PLAIN_MODULUS;
+        // Ids up front, so cleanup covers every session whatever happens to its task
+        let session_ids: Vec<String> = (0..req.concurrent_sessions)
+            .map(|_| uuid::Uuid::new_v4().to_string())
+            .collect();
+        
+        // Each task is one new session: keygen, then registration like GenerateKeys
+        let tasks: Vec<_> = session_ids.iter().cloned().map(|session_id| {
+            let sessions = Arc::clone(&self.sessions);
+            let library = req.library.clone();
+            let op_timeout = self.op_timeout;
+            tokio::spawn(async move {
+                let keygen_library = library.clone();
+                let keygen = run_with_deadline(op_timeout, "StressTest", move || {
+                    let start = Instant::now();
+                    run_keygen(&keygen_library, poly_degree, plain_modulus).map(|()| start.elapsed())
+                }).await;
+                let latency = keygen.map_err(|status| status.message().to_string()).and_then(|r| r)?;
+                sessions.insert(session_id, SessionConfig::new(library, poly_degree, plain_modulus));
+                Ok::<_, String>(latency)
+            })
+        }).collect();
+        
+        let mut latencies = Vec::with_capacity(tasks.len());
+        let mut errors = 0;
+        for task in tasks {
+            match task.await {
+                Ok(Ok(latency)) => latencies.push(latency),
+                Ok(Err(e)) => {
+                    errors += 1;
+                    println!("   ⚠ StressTest session failed: {}", e);
+                }
+                Err(e) => {
+                    errors += 1;
+                    println!("   ⚠ StressTest task failed: {}", e);
+                }
+            }
+        }
+        
+        for session_id in &session_ids {
+            self.sessions.remove(session_id);
+        }
+        let leaked = session_ids.iter().filter(|id| self.sessions.get(id).is_some()).count();
+        
+        let (min_ms, max_ms, mean_ms) = latency_summary_ms(&latencies);
+        println!("   ✓ {} sessions ok, {} failed, keygen mean {:.2}ms (min {:.2}, max {:.2})",
+            latencies.len(), errors, mean_ms, min_ms, max_ms);
+        
+        Ok(Response::new(StressTestResponse {
+            successes: latencies.len() as i32,
+            errors,
+            min_keygen_ms: min_ms,
+            max_keygen_ms: max_ms,
+            mean_keygen_ms: mean_ms,
+            leaked_sessions: leaked as i32,
+            status: format!(
+                "{} of {} concurrent {} sessions generated keys",
+                latencies.len(), req.concurrent_sessions, req.library
+            ),
+        }))
+    }
+
+    async fn verify_keys(
+        &self,
+        request: Request<VerifyKeysRequest>,
+    ) -> Result<Response<VerifyKeysResponse>, Status> {
+        let req = request.into_inner();
+        let sid = &req.session_id[..8.min(req.session_id.len())];
+
+        println!("📥 VerifyKeys request for session: {}", sid);
+
+        let session = self.session(&req.session_id)?;
+        let session = session.lock().await;
+        let (library, poly_degree, plain_modulus, imported) =
+            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, session.keys.clone());
+
+        // OpenFHE sessions don't keep keys between calls, and HELib's cached
+        // keys have no checks in the wrapper to run
+        if library != "SEAL" {
+            return Err(Status::failed_precondition(format!(
+                "VerifyKeys is only supported for SEAL sessions (session uses {})", library
+            )));
+        }
+
+        let keys = Arc::clone(&self.keys);
+        let checks = self.run_blocking("VerifyKeys", move || {
+            run_seal_verify_keys(&keys, imported, poly_degree, plain_modulus)
+        }).await?.map_err(Status::internal)?;
+
+        let failed: Vec<&str> = checks.iter().filter(|(_, passed)| !passed).map(|(check, _)| *check).collect();
+        let status = if failed.is_empty() {
+            "Keys are consistent".to_string()
+        } else {
+            format!("Key check failed: {}", failed.join(", "))
+        };
+        println!("   {} {}", if failed.is_empty() { "✓" } else { "✗" }, status);
+
+        Ok(Response::new(VerifyKeysResponse {
+            valid: failed.is_empty(),
+            checks: checks.iter().map(|(check, _)| check.to_string()).collect(),
+            status,
+        }))
+    }
+
+    async fn accumulate(
+        &self,
+        request: Request<AccumulateRequest>,
+    ) -> Result<Response<AccumulateResponse>, Status> {
+        let req = request.into_inner();
+        let sid = &req.session_id[..8.min(req.session_id.len())];
+
+        println!("📥 Accumulate request for session: {} (+{} values{})",
+            sid, req.values.len(), if req.reset { ", reset" } else { "" });
+
+        let session = self.session(&req.session_id)?;
+        let mut session = session.lock().await;
+        check_nonce(&mut session, "Accumulate", req.nonce)?;
+        let (library, poly_degree, plain_modulus, imported) =
+            (session.library.clone(), session.poly_modulus_degree, session.plain_modulus, session.keys.clone());
+
+        if library != "SEAL" {
+            return Err(Status::failed_precondition(format!(
+                "Accumulate is only supported for SEAL sessions (session uses {})", library
+            )));
+        }
+
+        let existing = self.accumulators.lock().unwrap().get(&req.session_id).cloned();
+        let accumulator = match existing {
+            Some(accumulator) => accumulator,
+            None => {
+                let keys = Arc::clone(&self.keys);
+                let context = self.run_blocking("Accumulate", move || {
+                    seal_session_context(&keys, imported.as_ref(), poly_degree, plain_modulus)
+                }).await?.map_err(Status::internal)?;
+                self.accumulators.lock().unwrap()
+                    .entry(req.session_id.clone())
+                    .or_insert_with(|| Arc::new(Mutex::new(SessionAccumulator::new(context))))
+                    .clone()
+            }
+        };
+
+        let values = req.values.clone();
+        // Under imported keys there is no secret key to decrypt the sum with
+        let (reset, public_only) = (req.reset, self.public_only || session.keys.is_some());
+        let outcome = self.run_blocking("Accumulate", move || {
+            accumulator.lock().unwrap().accumulate(&values, reset, public_only)
+        }).await?.map_err(Status::internal)?;
+
+        let status = match outcome.total {
+            Some(total) => format!("Sum of {} encrypted values is {}", outcome.count, total),
+            None => format!("Added to an encrypted sum of {} values", outcome.count),
+        };
+        println!("   ✓ {}{}", status, if outcome.refreshed { " (refreshed)" } else { "" });
+
+        Ok(Response::new(AccumulateResponse {
+            total: outcome.total.unwrap_or(0),
+            count: outcome.count,
+            refreshed: outcome.refreshed,
+            refresh_needed: outcome.refresh_needed,
+            sum_ciphertext: outcome.sum_ciphertext,
+            status,
+        }))
+    }
+
+    async fn shutdown(
+        &self,
+        request: Request<ShutdownRequest>,
+    ) -> Result<Response<ShutdownResponse>, Status> {
+        let req = request.into_inner();
+
+        println!("📥 Shutdown request");
+
+        let Some(control) = &self.shutdown else {
+            return Err(Status::permission_denied(
+                "Shutdown is disabled (start the server with --allow-shutdown)"
+            ));
+        };
+        if control.token.as_ref().is_some_and(|token| *token != req.token) {
+            return Err(Status::unauthenticated("Wrong shutdown token"));
+        }
+
+        // Handlers already running keep their session handles and finish;
+        // the server waits for them before it stops
+        let sessions_dropped = self.sessions.clear();
+        self.windows.lock().unwrap().clear();
+        self.accumulators.lock().unwrap().clear();
+        control.signal.notify_one();
+
+        println!("   ✓ Dropped {} sessions, shutting down", sessions_dropped);
+
+        Ok(Response::new(ShutdownResponse {
+            sessions_dropped: sessions_dropped as u32,
+            status: format!("Dropped {} sessions, shutting down", sessions_dropped),
+        }))
+    }
 }
 
 #[tokio::main]
@@ -1049,15 +2157,120 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Use environment variable or default to [::]:50051 (all interfaces, IPv6+IPv4)
     let bind_addr = std::env::var("GRPC_BIND_ADDR").unwrap_or_else(|_| "[::]:50051".to_string());
     let addr = bind_addr.parse()?;
-    let service = HEServiceImpl::new();
+    // Per-operation deadline in seconds (GRPC_OP_TIMEOUT_SECS), see DEFAULT_OP_TIMEOUT_SECS
+    let op_timeout_secs = std::env::var("GRPC_OP_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_OP_TIMEOUT_SECS);
+    let mut service = HEServiceImpl::new(Duration::from_secs(op_timeout_secs));
+    // --no-decrypt: turn off the RPCs that decrypt (the server still holds keys)
+    let public_only = std::env::args().skip(1).any(|arg| arg == "--no-decrypt");
+    if public_only {
+        service = service.public_only();
+    }
+    // --allow-shutdown: accept the Shutdown RPC (test environments), with
+    // GRPC_SHUTDOWN_TOKEN as its password if set
+    let shutdown = Arc::new(Notify::new());
+    let allow_shutdown = std::env::args().skip(1).any(|arg| arg == "--allow-shutdown");
+    let shutdown_token = std::env::var("GRPC_SHUTDOWN_TOKEN").ok().filter(|token| !token.is_empty());
+    if allow_shutdown {
+        service = service.allow_shutdown(Arc::clone(&shutdown), shutdown_token.clone());
+    }
+    // Optional SQLite benchmark history (BENCHMARK_DB_PATH=/path/to/history.db)
+    let db_path = std::env::var("BENCHMARK_DB_PATH").ok();
+    let mut history_summary = "disabled".to_string();
+    if let Some(path) = &db_path {
+        let store = BenchmarkStore::open(path)?;
+        let counts = SUPPORTED_LIBRARIES
+            .iter()
+            .map(|lib| Ok(format!("{} {}", store.query_by_library(lib)?.len(), lib)))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        history_summary = format!("{} ({} runs)", path, counts.join(", "));
+        service = service.with_store(store);
+    }
+    // GRPC_AUTH_TOKEN: HEService requests must carry it as a bearer token
+    let auth = match std::env::var("GRPC_AUTH_TOKEN").ok().filter(|token| !token.is_empty()) {
+        Some(token) => AuthInterceptor::new(SharedSecret::new(token)),
+        None => AuthInterceptor::disabled(),
+    };
+    // GRPC_RATE_LIMIT requests/s per client, bursts of up to GRPC_RATE_BURST
+    // (default: one second's worth); unset disables
+    let rate_limit = match std::env::var("GRPC_RATE_LIMIT").ok().and_then(|v| v.parse::<f64>().ok()) {
+        Some(rate) if rate > 0.0 => {
+            let burst = std::env::var("GRPC_RATE_BURST")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .unwrap_or(rate.ceil() as u32);
+            RateLimiter::new(rate, burst)
+        }
+        _ => RateLimiter::disabled(),
+    };
+    // SEAL degrees to generate session keys for at startup
+    // (GRPC_WARM_KEYS=4096,8192; empty disables). Never with --no-decrypt:
+    // sessions bring their own keys and the pool would only hold secret keys
+    let warm_degrees: Vec<u64> = std::env::var("GRPC_WARM_KEYS")
+        .unwrap_or_else(|_| DEFAULT_WARM_KEY_DEGREES.to_string())
+        .split(',')
+        .filter_map(|degree| degree.trim().parse().ok())
+        .filter(|_| !public_only)
+        .collect();
 
     println!("╔════════════════════════════════════════════════════════════╗");
     println!("║      Homomorphic Encryption gRPC Server                    ║");
     println!("╚════════════════════════════════════════════════════════════╝");
     println!();
     println!("   Listening on: {}", addr);
+    println!("   Operation timeout: {}s", op_timeout_secs);
+    println!("   Benchmark history: {}", history_summary);
+    println!("   Warm SEAL keys: {}", if warm_degrees.is_empty() {
+        "none".to_string()
+    } else {
+        warm_degrees.iter().map(|d| format!("n={}", d)).collect::<Vec<_>>().join(", ")
+    });
+    println!("   Decryption: {}", if public_only { "disabled (--no-decrypt, client keys only)" } else { "enabled" });
+    println!("   Shutdown RPC: {}", match (allow_shutdown, &shutdown_token) {
+        (false, _) => "disabled",
+        (true, None) => "enabled (--allow-shutdown, no token)",
+        (true, Some(_)) => "enabled (--allow-shutdown, GRPC_SHUTDOWN_TOKEN)",
+    });
+    println!("   Authentication: {}", if auth.is_enabled() { "bearer token (GRPC_AUTH_TOKEN)" } else { "disabled" });
+    println!("   Rate limit: {}", if rate_limit.is_enabled() {
+        format!("{} requests/s per client, burst {}", rate_limit.rate_per_sec(), rate_limit.burst())
+    } else {
+        "disabled".to_string()
+    });
     println!("   Libraries: Microsoft SEAL (BFV), HELib (BGV), OpenFHE (BFV)");
     println!();
+    println!("  Library self-test:");
+    // A library that fails is marked NOT_SERVING in the health service and its RPCs
+    // answer UNAVAILABLE; the others keep working
+    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter.set_serving::<HeServiceServer<HEServiceImpl>>().await;
+    for (name, outcome) in he_benchmark::self_test() {
+        let Some(library) = SUPPORTED_LIBRARIES.iter().find(|l| l.eq_ignore_ascii_case(&name)) else {
+            continue;
+        };
+        match outcome {
+            Ok(()) => {
+                println!("    ✓ {}", library);
+                health_reporter.set_service_status(*library, ServingStatus::Serving).await;
+            }
+            Err(e) => {
+                println!("    ✗ {} unavailable: {}", library, e);
+                health_reporter.set_service_status(*library, ServingStatus::NotServing).await;
+                service = service.with_unavailable(library, e.to_string());
+            }
+        }
+    }
+    for (library, error) in not_compiled_libraries() {
+        println!("    ✗ {} unavailable: {}", library, error);
+        health_reporter.set_service_status(library, ServingStatus::NotServing).await;
+    }
+    println!();
+    // Runs while the server starts accepting connections
+    if !warm_degrees.is_empty() && !service.unavailable.contains_key("SEAL") {
+        service.warm_keys(warm_degrees);
+    }
     println!("  Available services:");
     println!("    • GenerateKeys           - Create encryption context and keys");
     println!("    • Encrypt                - Encrypt integer vectors");
@@ -1066,14 +2279,1017 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("    • Multiply               - Homomorphic multiplication");
     println!("    • RunBenchmark           - Benchmark single library");
     println!("    • RunComparisonBenchmark - Compare all three libraries");
+    println!("    • AggregateWindow        - Rolling sum over encrypted counts (SEAL)");
+    println!("    • DecryptAll             - Decrypt every stored ciphertext");
+    println!("    • StressTest             - Concurrent keygen burst (load testing)");
+    println!("    • VerifyKeys             - Check a session's keys belong together (SEAL)");
+    println!("    • Accumulate             - Encrypted running sum per session (SEAL)");
+    println!("    • Shutdown               - Stop the server (--allow-shutdown only)");
+    println!("  Reflection enabled (try: grpcurl -plaintext {} list)", addr);
     println!();
     println!("  Ready to accept connections!");
     println!();
 
     Server::builder()
-        .add_service(HeServiceServer::new(service))
-        .serve(addr)
+        .add_service(health_service)
+        .add_service(reflection_service()?)
+        // Authentication runs first, so only accepted tokens get a bucket of their own
+        .add_service(InterceptedService::new(HeServiceServer::with_interceptor(service, rate_limit), auth))
+        .serve_with_shutdown(addr, async move { shutdown.notified().await })
         .await?;
 
+    println!("  Server stopped");
+
     Ok(())
 }
+
+// Lets grpcurl and similar tools discover HEService (and the health service)
+// without a copy of the .proto files
+fn reflection_service() -> Result<ServerReflectionServer<impl ServerReflection>, tonic_reflection::server::Error> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(he_service::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slow_operation_hits_deadline() {
+        let result = run_with_deadline(Duration::from_millis(20), "Slow op", || {
+            std::thread::sleep(Duration::from_millis(500));
+        }).await;
+
+        let status = result.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
+
+    #[test]
+    fn test_expansion_ratio() {
+        assert_eq!(expansion_ratio(8, 80), 10.0);
+        assert_eq!(expansion_ratio(0, 80), 0.0);
+    }
+
+    #[test]
+    fn test_latency_summary() {
+        assert_eq!(latency_summary_ms(&[]), (0.0, 0.0, 0.0));
+        let latencies = [Duration::from_millis(10), Duration::from_millis(30), Duration::from_millis(20)];
+        assert_eq!(latency_summary_ms(&latencies), (10.0, 30.0, 20.0));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_stress_test_creates_and_cleans_up_sessions() {
+        let service = HEServiceImpl::new(Duration::from_secs(120));
+
+        let response = service.stress_test(Request::new(StressTestRequest {
+            concurrent_sessions: 20,
+            library: "SEAL".to_string(),
+        })).await.unwrap().into_inner();
+
+        assert_eq!(response.successes, 20);
+        assert_eq!(response.errors, 0);
+        assert_eq!(response.leaked_sessions, 0);
+        assert_eq!(service.sessions.len(), 0);
+        assert!(response.min_keygen_ms > 0.0);
+        assert!(response.min_keygen_ms <= response.mean_keygen_ms);
+        assert!(response.mean_keygen_ms <= response.max_keygen_ms);
+
+        for concurrent_sessions in [0, MAX_STRESS_SESSIONS + 1] {
+            let status = service.stress_test(Request::new(StressTestRequest {
+                concurrent_sessions,
+                library: "SEAL".to_string(),
+            })).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn test_per_slot_costs() {
+        let response = with_per_slot_costs(BenchmarkResponse {
+            encryption_time_ms: 2.0,
+            addition_time_ms: 0.5,
+            ..Default::default()
+        }, 4096);
+        assert_eq!(response.slots_per_ciphertext, 4096);
+        assert!((response.encryption_ns_per_slot - 2_000_000.0 / 4096.0).abs() < 1e-9);
+        assert!((response.addition_ns_per_slot - 500_000.0 / 4096.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_batched_seal_wins_on_amortized_cost() {
+        let deadline = Instant::now() + Duration::from_secs(120);
+        let seal = run_seal_benchmark(8192, 5, None, deadline);
+        let helib = run_helib_benchmark(5, None, deadline);
+        assert_eq!(seal.slots_per_ciphertext, 8192);
+        assert_eq!(helib.slots_per_ciphertext, 1);
+
+        // Per op the two are within a small factor; per slot SEAL does 8192 values at once
+        for (what, seal_ns, helib_ns) in [
+            ("encryption", seal.encryption_ns_per_slot, helib.encryption_ns_per_slot),
+            ("addition", seal.addition_ns_per_slot, helib.addition_ns_per_slot),
+            ("multiplication", seal.multiplication_ns_per_slot, helib.multiplication_ns_per_slot),
+        ] {
+            assert!(seal_ns * 100.0 < helib_ns, "{}: SEAL {:.1} ns/slot, HELib {:.1} ns/slot", what, seal_ns, helib_ns);
+        }
+    }
+
+    #[test]
+    fn test_seal_benchmark_reports_key_sizes() {
+        let seal = run_seal_benchmark(8192, 2, None, Instant::now() + Duration::from_secs(120));
+        let sizes = [seal.public_key_bytes, seal.secret_key_bytes, seal.relin_key_bytes, seal.galois_key_bytes];
+        assert!(sizes.iter().all(|&size| size > 0), "{:?}", sizes);
+
+        // One key-switching key per rotation step: galois keys dwarf the rest
+        assert_eq!(sizes.iter().max(), Some(&seal.galois_key_bytes), "{:?}", sizes);
+        assert!(seal.galois_key_bytes > seal.relin_key_bytes);
+    }
+
+    #[tokio::test]
+    async fn test_fast_operation_within_deadline() {
+        let result = run_with_deadline(Duration::from_secs(5), "Fast op", || 42).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_window_sums_last_k() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+
+        let pushed = service.aggregate_window(Request::new(AggregateWindowRequest {
+            session_id: session_id.clone(),
+            counts: (1..=10).collect(),
+            window_size: 0,
+            nonce: 0,
+        })).await.unwrap().into_inner();
+        assert_eq!(pushed.entries_stored, 10);
+        assert_eq!(pushed.entries_summed, 0);
+
+        let summed = service.aggregate_window(Request::new(AggregateWindowRequest {
+            session_id,
+            counts: vec![],
+            window_size: 5,
+            nonce: 0,
+        })).await.unwrap().into_inner();
+        assert_eq!(summed.window_sum, 6 + 7 + 8 + 9 + 10);
+        assert_eq!(summed.entries_summed, 5);
+    }
+
+    #[tokio::test]
+    async fn test_warm_keys_make_first_session_fast() {
+        async fn first_session_time(service: &HEServiceImpl) -> Duration {
+            let start = Instant::now();
+            service.generate_keys(Request::new(GenerateKeysRequest {
+                library: "SEAL".to_string(),
+                poly_modulus_degree: 8192,
+                public_key: vec![],
+                relin_keys: vec![],
+                galois_keys: vec![],
+            })).await.unwrap();
+            start.elapsed()
+        }
+
+        let cold = first_session_time(&HEServiceImpl::new(Duration::from_secs(60))).await;
+
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        service.warm_keys(vec![8192]).await.unwrap();
+        let warm = first_session_time(&service).await;
+
+        assert!(warm * 10 < cold, "warm first session took {:?}, cold {:?}", warm, cold);
+    }
+
+    #[tokio::test]
+    async fn test_replayed_request_is_rejected() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+
+        let captured = EncryptRequest {
+            session_id: session_id.clone(),
+            values: vec![1, 2, 3],
+            nonce: 1,
+            expected_version: 0,
+        };
+        service.encrypt(Request::new(captured.clone())).await.unwrap();
+        let status = service.encrypt(Request::new(captured)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::AlreadyExists);
+
+        // The replay stored nothing, and fresh nonces keep working
+        let session = service.sessions.get(&session_id).unwrap();
+        assert_eq!(session.lock().await.ciphertext_values.len(), 1);
+        service.add(Request::new(BinaryOpRequest {
+            session_id,
+            ciphertext1: vec![],
+            ciphertext2: vec![],
+            nonce: 2,
+            expected_version: 0,
+        })).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_rejects_empty_values() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        for library in ["SEAL", "HELib", "OpenFHE"] {
+            let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+                library: library.to_string(),
+                poly_modulus_degree: 8192,
+                public_key: vec![],
+                relin_keys: vec![],
+                galois_keys: vec![],
+            })).await.unwrap().into_inner().session_id;
+
+            let status = service.encrypt(Request::new(EncryptRequest {
+                session_id: session_id.clone(),
+                values: vec![],
+                nonce: 1,
+                expected_version: 0,
+            })).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{}", library);
+            assert_eq!(status.message(), "values must be non-empty");
+
+            // Nothing stored and the nonce not spent
+            let session = service.sessions.get(&session_id).unwrap();
+            assert!(session.lock().await.ciphertext_values.is_empty());
+            service.encrypt(Request::new(EncryptRequest { session_id, values: vec![7], nonce: 1, expected_version: 0 })).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_library_is_unavailable_but_others_work() {
+        let service = HEServiceImpl::new(Duration::from_secs(60))
+            .with_unavailable("HELib", "simulated init failure".to_string());
+
+        let status = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "HELib".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert!(status.message().contains("simulated init failure"));
+
+        let status = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "HELib".to_string(),
+            num_operations: 1,
+            operation_mix: HashMap::new(),
+            repetitions: 0,
+            dry_run: false,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+        service.encrypt(Request::new(EncryptRequest {
+            session_id,
+            values: vec![1, 2, 3],
+            nonce: 0,
+            expected_version: 0,
+        })).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_comparison_leaves_out_an_unavailable_library() {
+        let service = HEServiceImpl::new(Duration::from_secs(60))
+            .with_unavailable("HELib", "simulated init failure".to_string());
+        let response = service.run_comparison_benchmark(Request::new(BenchmarkRequest {
+            library: String::new(),
+            num_operations: 2,
+            operation_mix: HashMap::new(),
+            repetitions: 0,
+            dry_run: false,
+        })).await.unwrap().into_inner();
+
+        // HELib's all-zero timings must not make it the fastest
+        assert!(response.helib.unwrap().status.contains("simulated init failure"));
+        assert_ne!(response.fastest_library, "HELib");
+        assert!(!response.recommendation.starts_with("HELib"), "{}", response.recommendation);
+
+        // With a single library left there is nothing to compare
+        let completed = BenchmarkResponse {
+            status: "SEAL benchmark complete: 2 operations".to_string(),
+            total_time_ms: 30.0,
+            encryption_time_ms: 2.0,
+            ..Default::default()
+        };
+        let unavailable = failed_benchmark("OpenFHE failed to initialize at startup".to_string());
+        let (fastest, recommendation) =
+            compare_libraries(&[("SEAL", &completed), ("HELib", &unavailable), ("OpenFHE", &unavailable)]);
+        assert_eq!(fastest, "none");
+        assert_eq!(recommendation, "Insufficient data: 1 of 3 libraries completed the benchmark");
+    }
+
+    #[cfg(not(feature = "helib"))]
+    #[tokio::test]
+    async fn test_helib_left_out_of_the_build_is_unavailable() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let status = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "HELib".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert_eq!(status.message(), "HElib support not compiled in; rebuild with --features helib");
+    }
+
+    #[tokio::test]
+    async fn test_sequential_multiplies_stay_relinearized() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+        for values in [vec![2, 3], vec![4, 5]] {
+            service.encrypt(Request::new(EncryptRequest { session_id: session_id.clone(), values, nonce: 0, expected_version: 0 }))
+                .await.unwrap();
+        }
+
+        for _ in 0..2 {
+            let response = service.multiply(Request::new(BinaryOpRequest {
+                session_id: session_id.clone(),
+                ciphertext1: vec![],
+                ciphertext2: vec![],
+                nonce: 0,
+                expected_version: 0,
+            })).await.unwrap().into_inner();
+            assert_eq!(response.ciphertext_size, 2);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_multiplies_on_one_session_are_serialized() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let keys = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner();
+        let session_id = keys.session_id;
+        assert_eq!(keys.version, 1);
+        let mut version = keys.version;
+        for values in [vec![2, 3], vec![4, 5]] {
+            version = service.encrypt(Request::new(EncryptRequest {
+                session_id: session_id.clone(),
+                values,
+                nonce: 0,
+                expected_version: version,
+            })).await.unwrap().into_inner().version;
+        }
+        assert_eq!(version, 3);
+
+        // Both expect version 3: whichever takes the lock second finds 4
+        let multiply = |expected_version| service.multiply(Request::new(BinaryOpRequest {
+            session_id: session_id.clone(),
+            ciphertext1: vec![],
+            ciphertext2: vec![],
+            nonce: 0,
+            expected_version,
+        }));
+        let (first, second) = tokio::join!(multiply(version), multiply(version));
+        let (won, lost) = match (first, second) {
+            (Ok(won), Err(lost)) | (Err(lost), Ok(won)) => (won.into_inner(), lost),
+            _ => panic!("exactly one of two multiplies at the same version should go through"),
+        };
+        assert_eq!(won.version, 4);
+        assert_eq!(lost.code(), tonic::Code::Aborted);
+
+        // Without a version both go through, one after the other
+        let (first, second) = tokio::join!(multiply(0), multiply(0));
+        let mut versions = [first.unwrap().into_inner().version, second.unwrap().into_inner().version];
+        versions.sort();
+        assert_eq!(versions, [5, 6]);
+
+        let session = service.sessions.get(&session_id).unwrap();
+        let session = session.lock().await;
+        let products: Vec<_> = session.ciphertext_values[2..].iter().map(|(_, values)| values[..2].to_vec()).collect();
+        assert_eq!(products, vec![vec![8, 15]; 3]);
+        assert_eq!(session.version, 6);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_estimates_without_running_the_full_benchmark() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let started = Instant::now();
+        let response = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "SEAL".to_string(),
+            num_operations: 100_000,
+            operation_mix: HashMap::new(),
+            repetitions: 10,
+            dry_run: true,
+        })).await.unwrap().into_inner();
+
+        // 100k operations ten times over would take far longer than this
+        assert!(started.elapsed() < Duration::from_secs(30));
+        assert!(response.dry_run);
+        assert!(response.status.starts_with("Dry run: SEAL benchmark complete: 3 operations"), "{}", response.status);
+        assert!(response.estimated_runtime_ms > 10.0 * response.total_time_ms);
+        assert!(response.estimated_memory_bytes > 100_000 * response.ciphertext_bytes);
+        assert!(response.summaries.is_empty());
+
+        // Validation still applies
+        let status = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "SEAL".to_string(),
+            num_operations: 10,
+            operation_mix: HashMap::from([("divide".to_string(), 1.0)]),
+            repetitions: 0,
+            dry_run: true,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_multiply_heavy_mix_samples_more_multiplies() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let response = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "SEAL".to_string(),
+            num_operations: 10,
+            operation_mix: HashMap::from([("add".to_string(), 1.0), ("multiply".to_string(), 4.0)]),
+            repetitions: 0,
+            dry_run: false,
+        })).await.unwrap().into_inner();
+
+        let samples = |op: &str| {
+            response.operation_timings.iter().find(|t| t.operation == op).map(|t| t.samples).unwrap()
+        };
+        assert_eq!(samples("add"), 2);
+        assert_eq!(samples("multiply"), 8);
+        assert!(samples("multiply") > samples("add"));
+
+        // HELib can't rotate
+        let status = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "HELib".to_string(),
+            num_operations: 10,
+            operation_mix: HashMap::from([("rotate".to_string(), 1.0)]),
+            repetitions: 0,
+            dry_run: false,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_benchmark_reports_confidence_intervals() {
+        let service = HEServiceImpl::new(Duration::from_secs(300));
+        let response = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "SEAL".to_string(),
+            num_operations: 5,
+            operation_mix: HashMap::new(),
+            repetitions: 10,
+            dry_run: false,
+        })).await.unwrap().into_inner();
+
+        assert_eq!(response.repetitions, 10);
+        assert_eq!(response.summaries.len(), 7);
+        for summary in &response.summaries {
+            assert_eq!(summary.runs, 10);
+            assert!(summary.std_dev_ms > 0.0, "{:?}", summary);
+            assert!(summary.ci95_low_ms < summary.mean_ms && summary.mean_ms < summary.ci95_high_ms, "{:?}", summary);
+        }
+        let total = response.summaries.iter().find(|s| s.metric == "total").unwrap();
+        assert_eq!(total.mean_ms, response.total_time_ms);
+
+        let status = service.run_benchmark(Request::new(BenchmarkRequest {
+            library: "SEAL".to_string(),
+            num_operations: 5,
+            operation_mix: HashMap::new(),
+            repetitions: MAX_REPETITIONS + 1,
+            dry_run: false,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_seal_parameters_are_rejected_before_keygen() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        // -1 is u64::MAX once widened; 1000 and 65536 are outside SEAL's range
+        for degree in [-1, 0, 1000, 65536] {
+            let status = service.generate_keys(Request::new(GenerateKeysRequest {
+                library: "SEAL".to_string(),
+                poly_modulus_degree: degree,
+                public_key: vec![],
+                relin_keys: vec![],
+                galois_keys: vec![],
+            })).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::InvalidArgument, "{}", degree);
+        }
+        assert_eq!(service.sessions.len(), 0);
+
+        assert!(validate_seal_parameters(u64::MAX, SESSION_PLAIN_MODULUS).is_err());
+        assert!(validate_seal_parameters(8192, 1).is_err());
+        assert!(validate_seal_parameters(8192, 1 << 60).is_err());
+        assert!(validate_seal_parameters(1024, 2).is_ok());
+        assert!(validate_seal_parameters(32768, (1 << 60) - 1).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_all_returns_every_stored_ciphertext() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+
+        let stored = [vec![1, 2, 3], vec![40, 50], vec![-7, 8, 9, 10]];
+        let mut ids = Vec::new();
+        for values in &stored {
+            let response = service.encrypt(Request::new(EncryptRequest {
+                session_id: session_id.clone(),
+                values: values.clone(),
+                nonce: 0,
+                expected_version: 0,
+            })).await.unwrap().into_inner();
+            ids.push(response.ciphertext_id);
+        }
+
+        let response = service.decrypt_all(Request::new(DecryptAllRequest {
+            session_id: session_id.clone(),
+            ciphertext_ids: vec![],
+        })).await.unwrap().into_inner();
+        assert_eq!(response.ciphertext_ids, ids);
+        let results: Vec<Vec<i64>> = response.results.into_iter().map(|r| r.values).collect();
+        assert_eq!(results, stored);
+
+        // A subset, in the requested order
+        let response = service.decrypt_all(Request::new(DecryptAllRequest {
+            session_id: session_id.clone(),
+            ciphertext_ids: vec![ids[2].clone(), ids[0].clone()],
+        })).await.unwrap().into_inner();
+        assert_eq!(response.results[0].values, stored[2]);
+        assert_eq!(response.results[1].values, stored[0]);
+
+        let status = service.decrypt_all(Request::new(DecryptAllRequest {
+            session_id,
+            ciphertext_ids: vec!["missing".to_string()],
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn test_injected_decrypt_failure_keeps_session_ciphertexts() {
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+
+        let stored = [vec![1, 2, 3], vec![4, 5, 6]];
+        for values in &stored {
+            service.encrypt(Request::new(EncryptRequest {
+                session_id: session_id.clone(),
+                values: values.clone(),
+                nonce: 0,
+                expected_version: 0,
+            })).await.unwrap();
+        }
+
+        service.faults.fail_nth("DecryptAll", 1, Status::internal("injected decrypt failure"));
+        let decrypt_all = || service.decrypt_all(Request::new(DecryptAllRequest {
+            session_id: session_id.clone(),
+            ciphertext_ids: vec![],
+        }));
+        let status = decrypt_all().await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+        assert_eq!(status.message(), "injected decrypt failure");
+
+        // The session lost nothing and the lock was released: a retry gets everything
+        let session = service.sessions.get(&session_id).unwrap();
+        assert_eq!(session.lock().await.ciphertext_values.len(), stored.len());
+        let results: Vec<Vec<i64>> = decrypt_all().await.unwrap().into_inner()
+            .results.into_iter().map(|r| r.values).collect();
+        assert_eq!(results, stored);
+    }
+
+    // GenerateKeys with keys generated on the client, as `--local-encrypt` sends them
+    fn client_keys_request(context: &he_benchmark::Context) -> GenerateKeysRequest {
+        use he_benchmark::{GaloisKeys, RelinKeys};
+
+        GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: context.poly_modulus_degree() as i32,
+            public_key: context.public_key().unwrap().to_bytes().unwrap(),
+            relin_keys: RelinKeys::generate(context).unwrap().to_bytes().unwrap(),
+            galois_keys: GaloisKeys::generate(context).unwrap().to_bytes().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_decrypt_mode_never_generates_a_secret_key() {
+        use he_benchmark::Context;
+
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        for library in ["SEAL", "HELib", "OpenFHE"] {
+            let status = service.generate_keys(Request::new(GenerateKeysRequest {
+                library: library.to_string(),
+                poly_modulus_degree: 8192,
+                public_key: vec![],
+                relin_keys: vec![],
+                galois_keys: vec![],
+            })).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::FailedPrecondition, "{}", library);
+        }
+
+        let client = Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let mut request = client_keys_request(&client);
+        request.relin_keys.clear();
+        let status = service.generate_keys(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        let mut request = client_keys_request(&Context::new(4096, 40961).unwrap());
+        request.poly_modulus_degree = 8192;
+        let status = service.generate_keys(Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let session_id = service.generate_keys(Request::new(client_keys_request(&client)))
+            .await.unwrap().into_inner().session_id;
+        let session = service.sessions.get(&session_id).unwrap();
+        let context = Arc::clone(&session.lock().await.keys.as_ref().unwrap().context);
+        assert!(!context.has_secret_key());
+        assert!(context.secret_key().is_err());
+        // Nothing ever went through the pool, where every context has a secret key
+        assert_eq!(service.keys.pooled_contexts(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_no_decrypt_mode_rejects_decrypt() {
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        let client = he_benchmark::Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let session_id = service.generate_keys(Request::new(client_keys_request(&client)))
+            .await.unwrap().into_inner().session_id;
+
+        // Plaintext is refused: encrypt on the client
+        let status = service.encrypt(Request::new(EncryptRequest {
+            session_id: session_id.clone(),
+            values: vec![1, 2, 3],
+            nonce: 0,
+            expected_version: 0,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        let session = service.sessions.get(&session_id).unwrap();
+        assert!(session.lock().await.ciphertext_values.is_empty());
+        for status in [
+            service.add(Request::new(BinaryOpRequest {
+                session_id: session_id.clone(),
+                ciphertext1: vec![],
+                ciphertext2: vec![],
+                nonce: 0,
+                expected_version: 0,
+            })).await.unwrap_err(),
+            service.multiply(Request::new(BinaryOpRequest {
+                session_id: session_id.clone(),
+                ciphertext1: vec![],
+                ciphertext2: vec![],
+                nonce: 0,
+                expected_version: 0,
+            })).await.unwrap_err(),
+        ] {
+            assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        }
+
+        let status = service.decrypt(Request::new(DecryptRequest {
+            session_id: session_id.clone(),
+            ciphertext: vec![],
+            nonce: 0,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        let status = service.decrypt_all(Request::new(DecryptAllRequest {
+            session_id: session_id.clone(),
+            ciphertext_ids: vec![],
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        // No secret-key-holding window gets created either
+        let status = service.aggregate_window(Request::new(AggregateWindowRequest {
+            session_id,
+            counts: vec![1],
+            window_size: 1,
+            nonce: 0,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert!(service.windows.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_on_client_ciphertexts_returns_an_encrypted_sum() {
+        use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor};
+
+        // Decryption turned off on the server, and no values ever sent to it.
+        // The client's own keys under the session's parameters.
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        let context = Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let session_id = service.generate_keys(Request::new(client_keys_request(&context)))
+            .await.unwrap().into_inner().session_id;
+
+        let encoder = BatchEncoder::new(&context).unwrap();
+        let encrypt = |values: &[i64]| {
+            let encryptor = Encryptor::new(&context).unwrap();
+            encryptor.encrypt(&encoder.encode(values).unwrap()).unwrap().to_bytes().unwrap()
+        };
+        let response = service.add(Request::new(BinaryOpRequest {
+            session_id: session_id.clone(),
+            ciphertext1: encrypt(&[10, 20, 30]),
+            ciphertext2: encrypt(&[1, 2, 3]),
+            nonce: 0,
+            expected_version: 0,
+        })).await.unwrap().into_inner();
+        assert_eq!(response.ciphertext_size, 2);
+
+        let sum = Ciphertext::from_bytes(&context, &response.result_ciphertext).unwrap();
+        let decoded = encoder.decode(&Decryptor::new(&context).unwrap().decrypt(&sum).unwrap()).unwrap();
+        assert_eq!(&decoded[..3], &[11, 22, 33]);
+        let session = service.sessions.get(&session_id).unwrap();
+        assert!(session.lock().await.ciphertext_values.is_empty());
+
+        let status = service.add(Request::new(BinaryOpRequest {
+            session_id,
+            ciphertext1: vec![1, 2, 3],
+            ciphertext2: vec![4, 5, 6],
+            nonce: 0,
+            expected_version: 0,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_multiply_on_client_ciphertexts_relinearizes_with_the_client_keys() {
+        use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor};
+
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        let context = Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let session_id = service.generate_keys(Request::new(client_keys_request(&context)))
+            .await.unwrap().into_inner().session_id;
+
+        let encoder = BatchEncoder::new(&context).unwrap();
+        let encrypt = |values: &[i64]| {
+            let encryptor = Encryptor::new(&context).unwrap();
+            encryptor.encrypt(&encoder.encode(values).unwrap()).unwrap().to_bytes().unwrap()
+        };
+        let multiply = |session_id: &str| service.multiply(Request::new(BinaryOpRequest {
+            session_id: session_id.to_string(),
+            ciphertext1: encrypt(&[2, 3, 4]),
+            ciphertext2: encrypt(&[5, 6, 7]),
+            nonce: 0,
+            expected_version: 0,
+        }));
+        let response = multiply(&session_id).await.unwrap().into_inner();
+        assert_eq!(response.ciphertext_size, RELINEARIZED_SIZE as i32);
+
+        let product = Ciphertext::from_bytes(&context, &response.result_ciphertext).unwrap();
+        let decoded = encoder.decode(&Decryptor::new(&context).unwrap().decrypt(&product).unwrap()).unwrap();
+        assert_eq!(&decoded[..3], &[10, 18, 28]);
+
+        // A session under the server's own keys has no relin keys for the client's
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+        let status = service.multiply(Request::new(BinaryOpRequest {
+            session_id,
+            ciphertext1: encrypt(&[2]),
+            ciphertext2: encrypt(&[5]),
+            nonce: 0,
+            expected_version: 0,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_verify_keys_reports_mismatched_keys() {
+        use he_benchmark::{Context, RelinKeys};
+
+        async fn verify(service: &HEServiceImpl, session_id: &str) -> VerifyKeysResponse {
+            service.verify_keys(Request::new(VerifyKeysRequest {
+                session_id: session_id.to_string(),
+            })).await.unwrap().into_inner()
+        }
+
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let session_id = service.generate_keys(Request::new(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        })).await.unwrap().into_inner().session_id;
+        let response = verify(&service, &session_id).await;
+        assert!(response.valid, "{}", response.status);
+        assert_eq!(response.checks, vec!["key_pair", "evaluation_keys"]);
+
+        // Relin keys generated for other parameters, swapped in under the session's context
+        let context = service.keys.context(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let foreign = || RelinKeys::generate(&Context::new(4096, 40961).unwrap()).unwrap();
+        service.keys.insert_relin_keys(&context, foreign());
+        let response = verify(&service, &session_id).await;
+        assert!(!response.valid);
+        assert!(response.status.contains("evaluation_keys"), "{}", response.status);
+
+        // Imported client keys have no secret key to check, only the evaluation keys
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        let client = Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let session_id = service.generate_keys(Request::new(client_keys_request(&client)))
+            .await.unwrap().into_inner().session_id;
+        let response = verify(&service, &session_id).await;
+        assert!(response.valid, "{}", response.status);
+        assert_eq!(response.checks, vec!["evaluation_keys"]);
+
+        let session = service.sessions.get(&session_id).unwrap();
+        session.lock().await.keys.as_mut().unwrap().relin_keys = Arc::new(foreign());
+        let response = verify(&service, &session_id).await;
+        assert!(!response.valid);
+        assert!(response.status.contains("evaluation_keys"), "{}", response.status);
+    }
+
+    #[tokio::test]
+    async fn test_accumulate_keeps_a_running_sum_per_session() {
+        async fn accumulate(service: &HEServiceImpl, session_id: &str, values: Vec<i64>) -> AccumulateResponse {
+            service.accumulate(Request::new(AccumulateRequest {
+                session_id: session_id.to_string(),
+                values,
+                nonce: 0,
+                reset: false,
+            })).await.unwrap().into_inner()
+        }
+
+        let service = HEServiceImpl::new(Duration::from_secs(60));
+        let new_session = |library: &str| service.generate_keys(Request::new(GenerateKeysRequest {
+            library: library.to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        }));
+        let session_id = new_session("SEAL").await.unwrap().into_inner().session_id;
+        let other_id = new_session("SEAL").await.unwrap().into_inner().session_id;
+
+        for _ in 0..10 {
+            accumulate(&service, &session_id, vec![1; 10]).await;
+        }
+        accumulate(&service, &other_id, vec![7]).await;
+        let response = accumulate(&service, &session_id, vec![]).await;
+        assert_eq!((response.total, response.count), (100, 100));
+        assert!(response.sum_ciphertext.is_empty());
+
+        let helib_id = new_session("HELib").await.unwrap().into_inner().session_id;
+        let status = service.accumulate(Request::new(AccumulateRequest {
+            session_id: helib_id,
+            values: vec![1],
+            nonce: 0,
+            reset: false,
+        })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        // --no-decrypt: the encrypted sum comes back instead of the total, under
+        // the client's key
+        use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor};
+        let service = HEServiceImpl::new(Duration::from_secs(60)).public_only();
+        let client = Context::new(8192, SESSION_PLAIN_MODULUS).unwrap();
+        let session_id = service.generate_keys(Request::new(client_keys_request(&client)))
+            .await.unwrap().into_inner().session_id;
+        let response = accumulate(&service, &session_id, vec![2, 3]).await;
+        assert_eq!((response.total, response.count, response.refresh_needed), (0, 2, false));
+        let sum = Ciphertext::from_bytes(&client, &response.sum_ciphertext).unwrap();
+        let plain = Decryptor::new(&client).unwrap().decrypt(&sum).unwrap();
+        assert_eq!(BatchEncoder::new(&client).unwrap().decode(&plain).unwrap()[0], 5);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_rpc_stops_the_server_and_drops_sessions() {
+        use he_service::he_service_client::HeServiceClient;
+
+        let signal = Arc::new(Notify::new());
+        let service = HEServiceImpl::new(Duration::from_secs(60))
+            .allow_shutdown(Arc::clone(&signal), Some("let-me-stop".to_string()));
+        let sessions = Arc::clone(&service.sessions);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(
+            Server::builder()
+                .add_service(HeServiceServer::new(service))
+                .serve_with_incoming_shutdown(
+                    tokio_stream::wrappers::TcpListenerStream::new(listener),
+                    async move { signal.notified().await },
+                ),
+        );
+
+        let mut client = HeServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+        let session_id = client.generate_keys(GenerateKeysRequest {
+            library: "SEAL".to_string(),
+            poly_modulus_degree: 8192,
+            public_key: vec![],
+            relin_keys: vec![],
+            galois_keys: vec![],
+        }).await.unwrap().into_inner().session_id;
+        client.encrypt(EncryptRequest { session_id: session_id.clone(), values: vec![1, 2, 3], nonce: 0, expected_version: 0 }).await.unwrap();
+        client.add(BinaryOpRequest { session_id, ciphertext1: vec![], ciphertext2: vec![], nonce: 0, expected_version: 0 }).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+
+        let status = client.shutdown(ShutdownRequest { token: "guess".to_string() }).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        assert_eq!(sessions.len(), 1);
+
+        let response = client.shutdown(ShutdownRequest { token: "let-me-stop".to_string() }).await.unwrap().into_inner();
+        assert_eq!(response.sessions_dropped, 1);
+        drop(client);
+        tokio::time::timeout(Duration::from_secs(10), server).await
+            .expect("server didn't stop")
+            .unwrap()
+            .unwrap();
+        assert_eq!(sessions.len(), 0);
+
+        // Off unless the server was started with --allow-shutdown
+        let status = HEServiceImpl::new(Duration::from_secs(60))
+            .shutdown(Request::new(ShutdownRequest { token: String::new() }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_reflection_lists_he_service_methods() {
+        use prost::Message;
+        use tonic_reflection::pb::server_reflection_client::ServerReflectionClient;
+        use tonic_reflection::pb::server_reflection_request::MessageRequest;
+        use tonic_reflection::pb::server_reflection_response::MessageResponse;
+        use tonic_reflection::pb::ServerReflectionRequest;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(reflection_service().unwrap())
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = ServerReflectionClient::new(channel);
+        let requests = [
+            MessageRequest::ListServices(String::new()),
+            MessageRequest::FileContainingSymbol("he_service.HEService".to_string()),
+        ]
+        .map(|message_request| ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(message_request),
+        });
+        let mut responses = client
+            .server_reflection_info(tokio_stream::iter(requests))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let Some(MessageResponse::ListServicesResponse(list)) =
+            responses.message().await.unwrap().unwrap().message_response
+        else {
+            panic!("expected a ListServices response");
+        };
+        let services: Vec<_> = list.service.iter().map(|s| s.name.as_str()).collect();
+        assert!(services.contains(&"he_service.HEService"), "{:?}", services);
+        assert!(services.contains(&"grpc.health.v1.Health"), "{:?}", services);
+
+        let Some(MessageResponse::FileDescriptorResponse(files)) =
+            responses.message().await.unwrap().unwrap().message_response
+        else {
+            panic!("expected a FileDescriptor response");
+        };
+        let file = prost_types::FileDescriptorProto::decode(files.file_descriptor_proto[0].as_slice()).unwrap();
+        let methods: Vec<_> = file.service[0].method.iter().map(|m| m.name()).collect();
+        for method in ["GenerateKeys", "Encrypt", "Decrypt", "RunBenchmark", "AggregateWindow", "DecryptAll", "VerifyKeys", "Accumulate", "Shutdown"] {
+            assert!(methods.contains(&method), "{} missing from {:?}", method, methods);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// grpc_server/src/mix.rs
+//
+// Weighted operation mixes for RunBenchmark
+//
+// By default a benchmark times every operation type over the same number of
+// ciphertexts. A mix like {"add": 7, "multiply": 2, "rotate": 1} instead runs
+// num_operations operations in those proportions, so the numbers look like an
+// actual workload. The schedule is deterministic (smooth weighted round-robin):
+// the same request always runs the same interleaved sequence, and the counts
+// per operation are as close to the weights as whole numbers allow.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::deadline_passed;
+use crate::he_service::OperationTiming;
+
+// Every operation a mix can name, in the order results are reported
+const MIX_OPERATIONS: [&str; 3] = ["add", "multiply", "rotate"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationMix {
+    // Only operations with a positive weight, in MIX_OPERATIONS order
+    weights: Vec<(&'static str, f64)>,
+}
+
+impl OperationMix {
+    /// Validate a request's mix against the operations a library supports.
+    /// An empty map means no mix (`None`).
+    pub fn parse(mix: &HashMap<String, f64>, supported: &[&str]) -> Result<Option<Self>, String> {
+        if mix.is_empty() {
+            return Ok(None);
+        }
+
+        for (name, &weight) in mix {
+            if !MIX_OPERATIONS.contains(&name.as_str()) {
+                return Err(format!(
+                    "Unknown operation '{}' in operation_mix (expected one of {})",
+                    name,
+                    MIX_OPERATIONS.join(", ")
+                ));
+            }
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!("Weight for '{}' must be a non-negative number, got {}", name, weight));
+            }
+            if weight > 0.0 && !supported.contains(&name.as_str()) {
+                return Err(format!("'{}' is not supported here (supported: {})", name, supported.join(", ")));
+            }
+        }
+
+        let weights: Vec<_> = MIX_OPERATIONS
+            .iter()
+            .filter_map(|&op| mix.get(op).filter(|&&w| w > 0.0).map(|&w| (op, w)))
+            .collect();
+        if weights.is_empty() {
+            return Err("operation_mix needs at least one positive weight".to_string());
+        }
+        Ok(Some(OperationMix { weights }))
+    }
+
+    pub fn includes(&self, op: &str) -> bool {
+        self.weights.iter().any(|&(name, _)| name == op)
+    }
+
+    // Index into `weights` for each of the `count` operations
+    fn schedule(&self, count: usize) -> Vec<usize> {
+        let total: f64 = self.weights.iter().map(|&(_, w)| w).sum();
+        let mut current = vec![0.0; self.weights.len()];
+
+        (0..count)
+            .map(|_| {
+                for (credit, &(_, weight)) in current.iter_mut().zip(&self.weights) {
+                    *credit += weight;
+                }
+                // First maximum wins ties, so the order is stable
+                let pick = (1..current.len()).fold(0, |best, i| if current[i] > current[best] { i } else { best });
+                current[pick] -= total;
+                pick
+            })
+            .collect()
+    }
+}
+
+/// Run `count` operations following `mix` and time each kind separately.
+/// `run` gets the operation name and the operation's position in the sequence.
+pub fn run_mix(
+    mix: &OperationMix,
+    count: usize,
+    deadline: Instant,
+    mut run: impl FnMut(&'static str, usize),
+) -> Vec<OperationTiming> {
+    let mut totals = vec![(0i32, Duration::ZERO); mix.weights.len()];
+
+    for (i, pick) in mix.schedule(count).into_iter().enumerate() {
+        if deadline_passed(deadline) { break; }
+        let start = Instant::now();
+        run(mix.weights[pick].0, i);
+        totals[pick].0 += 1;
+        totals[pick].1 += start.elapsed();
+    }
+
+    mix.weights
+        .iter()
+        .zip(totals)
+        .map(|(&(operation, _), (samples, time))| {
+            let total_time_ms = time.as_secs_f64() * 1000.0;
+            OperationTiming {
+                operation: operation.to_string(),
+                samples,
+                total_time_ms,
+                avg_time_ms: if samples > 0 { total_time_ms / samples as f64 } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_follows_weights() {
+        let request = HashMap::from([("add".to_string(), 7.0), ("multiply".to_string(), 3.0)]);
+        let mix = OperationMix::parse(&request, &["add", "multiply"]).unwrap().unwrap();
+
+        let schedule = mix.schedule(20);
+        assert_eq!(schedule.iter().filter(|&&i| i == 0).count(), 14);
+        assert_eq!(schedule.iter().filter(|&&i| i == 1).count(), 6);
+        // Interleaved, not 14 adds followed by 6 multiplies
+        assert!(schedule[..10].contains(&1));
+
+        let rotate = HashMap::from([("rotate".to_string(), 1.0)]);
+        assert!(OperationMix::parse(&rotate, &["add", "multiply"]).is_err());
+        let unknown = HashMap::from([("divide".to_string(), 1.0)]);
+        assert!(OperationMix::parse(&unknown, &MIX_OPERATIONS).is_err());
+        assert_eq!(OperationMix::parse(&HashMap::new(), &MIX_OPERATIONS), Ok(None));
+    }
+}
@@ -0,0 +1,161 @@
+// grpc_server/src/ratelimit.rs
+//
+// Per-client token buckets for HEService requests
+//
+// Key generation and multiplication are expensive, so one client sending
+// requests as fast as it can starves everyone else. Each client gets a bucket
+// of `burst` tokens that refills at `rate` per second; a request takes one
+// token, and with the bucket empty it is RESOURCE_EXHAUSTED before a handler
+// runs. Clients are told apart by their bearer token (once AuthInterceptor has
+// accepted it) or, without one, by peer IP address.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+// Past this many tracked clients, buckets that have refilled completely are
+// forgotten (a new bucket starts full, so nothing changes for them)
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    enabled: bool,
+    rate_per_sec: f64,
+    burst: f64,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// `rate_per_sec` sustained requests per client, up to `burst` at once
+    pub fn new(rate_per_sec: f64, burst: u32) -> Self {
+        RateLimiter {
+            enabled: true,
+            rate_per_sec: rate_per_sec.max(0.0),
+            burst: f64::from(burst.max(1)),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// No limit (the default when GRPC_RATE_LIMIT is unset)
+    pub fn disabled() -> Self {
+        RateLimiter { enabled: false, ..RateLimiter::new(0.0, 1) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn rate_per_sec(&self) -> f64 {
+        self.rate_per_sec
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+
+    /// Take a token from `client`'s bucket at `now`; false if it is empty
+    pub fn try_acquire(&self, client: &str, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            let (rate, burst) = (self.rate_per_sec, self.burst);
+            buckets.retain(|_, b| b.tokens + now.saturating_duration_since(b.updated).as_secs_f64() * rate < burst);
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket { tokens: self.burst, updated: now });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate_per_sec).min(self.burst);
+        bucket.updated = now.max(bucket.updated);
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+// Bearer token if there is one, else the peer's IP (all ports of one host share a bucket)
+fn client_identity(request: &Request<()>) -> String {
+    if let Some(token) = request
+        .metadata()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    {
+        return format!("token:{}", token);
+    }
+    match request.remote_addr() {
+        Some(addr) => format!("ip:{}", addr.ip()),
+        None => "unknown".to_string(),
+    }
+}
+
+impl Interceptor for RateLimiter {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if !self.enabled {
+            return Ok(request);
+        }
+        if !self.try_acquire(&client_identity(&request), Instant::now()) {
+            return Err(Status::resource_exhausted(format!(
+                "rate limit exceeded ({} requests/s, burst {}); retry later",
+                self.rate_per_sec, self.burst
+            )));
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bucket_rejects_past_the_burst_and_refills() {
+        let limiter = RateLimiter::new(10.0, 5);
+        let start = Instant::now();
+
+        // 20 requests in the same instant: the burst gets through, the rest don't
+        let accepted = (0..20).filter(|_| limiter.try_acquire("a", start)).count();
+        assert_eq!(accepted, 5);
+
+        // 10/s: one more token every 100 ms
+        assert!(!limiter.try_acquire("a", start + Duration::from_millis(50)));
+        assert!(limiter.try_acquire("a", start + Duration::from_millis(150)));
+        assert!(!limiter.try_acquire("a", start + Duration::from_millis(160)));
+
+        // Requests at 20/s for a second: the full bucket, then about every
+        // other one as it refills
+        let accepted = (0..20)
+            .filter(|i| limiter.try_acquire("a", start + Duration::from_millis(1000 + 50 * i)))
+            .count();
+        assert!((10..=14).contains(&accepted), "{} accepted", accepted);
+
+        // Other clients have their own buckets
+        assert!(limiter.try_acquire("b", start));
+    }
+
+    #[test]
+    fn test_interceptor_keys_by_bearer_token() {
+        let mut limiter = RateLimiter::new(0.0, 1);
+        let request = |token: &str| {
+            let mut request = Request::new(());
+            request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+            request
+        };
+
+        assert!(limiter.call(request("alice")).is_ok());
+        let status = limiter.call(request("alice")).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert!(limiter.call(request("bob")).is_ok());
+
+        let mut disabled = RateLimiter::disabled();
+        assert!((0..10).all(|_| disabled.call(request("alice")).is_ok()));
+    }
+}
@@ -0,0 +1,282 @@
+// grpc_server/src/session.rs
+//
+// Session storage with one lock per session
+//
+// The map itself is behind an RwLock that is only held for lookups and
+// inserts, never while an HE operation runs. Each session has its own async
+// Mutex, which a handler holds for the whole RPC: operations on one session
+// run one at a time (so stored ciphertexts keep their order), while different
+// sessions proceed in parallel.
+//
+// The lock makes concurrent requests safe but not predictable: two clients
+// sharing a session can't tell whose ciphertext landed first. So every change
+// to the stored ciphertexts bumps the session's version, and a request may name
+// the version it expects, to be refused if someone else got there first.
+
+use crate::keys::ImportedKeys;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::Mutex;
+
+// Request nonces remembered per session before the oldest are forgotten
+const NONCE_WINDOW: usize = 1024;
+
+// Session configuration - stores parameters needed to recreate SEAL/HELib context
+// This is Send + Sync safe: primitive types, plus imported keys behind Arcs
+#[derive(Clone)]
+pub struct SessionConfig {
+    pub library: String,
+    pub poly_modulus_degree: u64,
+    pub plain_modulus: u64,
+    // The client's own keys (SEAL only), used instead of the pooled context's.
+    // The session then has no secret key on the server.
+    pub keys: Option<ImportedKeys>,
+    // (ciphertext id, values), in the order they were stored
+    pub ciphertext_values: Vec<(String, Vec<i64>)>,
+    // Nonces of the requests already accepted, to refuse replays
+    pub seen_nonces: NonceWindow,
+    // Bumped by every change to ciphertext_values; starts at 1
+    pub version: u64,
+}
+
+impl SessionConfig {
+    pub fn new(library: String, poly_modulus_degree: u64, plain_modulus: u64) -> Self {
+        SessionConfig {
+            library,
+            poly_modulus_degree,
+            plain_modulus,
+            keys: None,
+            ciphertext_values: Vec::new(),
+            seen_nonces: NonceWindow::default(),
+            version: 1,
+        }
+    }
+
+    /// A SEAL session under keys the client generated and sent
+    pub fn with_imported_keys(poly_modulus_degree: u64, plain_modulus: u64, keys: ImportedKeys) -> Self {
+        SessionConfig { keys: Some(keys), ..Self::new("SEAL".to_string(), poly_modulus_degree, plain_modulus) }
+    }
+
+    /// Store a ciphertext's values and return the new version
+    pub fn store(&mut self, ciphertext_id: String, values: Vec<i64>) -> u64 {
+        self.ciphertext_values.push((ciphertext_id, values));
+        self.version += 1;
+        self.version
+    }
+}
+
+// The most recent NONCE_WINDOW nonces of a session. Clients send increasing
+// nonces, so once the window is full the oldest is dropped and everything at or
+// below it is refused too: a replay can't slip in after being forgotten.
+#[derive(Clone, Default)]
+pub struct NonceWindow {
+    seen: BTreeSet<u64>,
+    floor: u64,
+}
+
+impl NonceWindow {
+    /// Remember `nonce`; false if it was already used (or is too old to tell)
+    pub fn insert(&mut self, nonce: u64) -> bool {
+        if nonce <= self.floor || !self.seen.insert(nonce) {
+            return false;
+        }
+        if self.seen.len() > NONCE_WINDOW {
+            if let Some(oldest) = self.seen.pop_first() {
+                self.floor = oldest;
+            }
+        }
+        true
+    }
+}
+
+pub type SharedSession = Arc<Mutex<SessionConfig>>;
+
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, SharedSession>>,
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, session_id: String, session: SessionConfig) {
+        self.sessions
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(session_id, Arc::new(Mutex::new(session)));
+    }
+
+    /// Drop a session; handlers still holding its handle finish normally
+    pub fn remove(&self, session_id: &str) -> bool {
+        self.sessions
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(session_id)
+            .is_some()
+    }
+
+    /// Handle to one session; lock it for as long as the operation needs it
+    pub fn get(&self, session_id: &str) -> Option<SharedSession> {
+        self.sessions
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(session_id)
+            .cloned()
+    }
+
+    /// Drop every session (server shutdown) and say how many there were
+    pub fn clear(&self) -> usize {
+        let mut sessions = self.sessions.write().unwrap_or_else(PoisonError::into_inner);
+        let count = sessions.len();
+        sessions.clear();
+        count
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.sessions.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // Stand-in for an HE operation: holds the session for a fixed time
+    const OP_TIME: Duration = Duration::from_millis(20);
+
+    fn session() -> SessionConfig {
+        SessionConfig::new("SEAL".to_string(), 8192, 1032193)
+    }
+
+    #[test]
+    fn test_nonce_window_refuses_replays_and_forgotten_nonces() {
+        let mut nonces = NonceWindow::default();
+        assert!(nonces.insert(5));
+        assert!(!nonces.insert(5));
+        // Out of order is fine while the window still covers it
+        assert!(nonces.insert(3));
+
+        for nonce in 6..6 + NONCE_WINDOW as u64 {
+            assert!(nonces.insert(nonce));
+        }
+        // 3 and 5 have been forgotten, but still can't be reused
+        assert!(!nonces.insert(3));
+        assert!(!nonces.insert(4));
+        assert!(!nonces.insert(5));
+        assert_eq!(nonces.seen.len(), NONCE_WINDOW);
+    }
+
+    // One operation on each of `sessions` sessions, all at once, through the store
+    async fn per_session_locks(sessions: usize) -> Duration {
+        let store = Arc::new(SessionStore::new());
+        for i in 0..sessions {
+            store.insert(i.to_string(), session());
+        }
+
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..sessions)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                tokio::spawn(async move {
+                    let session = store.get(&i.to_string()).unwrap();
+                    let mut session = session.lock().await;
+                    tokio::time::sleep(OP_TIME).await;
+                    session.ciphertext_values.push((i.to_string(), vec![i as i64]));
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        start.elapsed()
+    }
+
+    // The same workload with the whole map behind one lock
+    async fn single_lock(sessions: usize) -> Duration {
+        let store = Arc::new(Mutex::new(HashMap::new()));
+        for i in 0..sessions {
+            store.lock().await.insert(i.to_string(), session());
+        }
+
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..sessions)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                tokio::spawn(async move {
+                    let mut sessions = store.lock().await;
+                    tokio::time::sleep(OP_TIME).await;
+                    let session = sessions.get_mut(&i.to_string()).unwrap();
+                    session.ciphertext_values.push((i.to_string(), vec![i as i64]));
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        start.elapsed()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sessions_run_in_parallel() {
+        let fine = per_session_locks(50).await;
+        let coarse = single_lock(50).await;
+
+        // 50 x 20ms back to back is a second; in parallel it is about one OP_TIME
+        assert!(coarse >= OP_TIME * 50);
+        assert!(fine * 10 < coarse, "per-session locks took {:?}, single lock {:?}", fine, coarse);
+    }
+
+    #[tokio::test]
+    async fn test_operations_within_a_session_are_serialized() {
+        let store = Arc::new(SessionStore::new());
+        store.insert("s".to_string(), session());
+
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..5)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                tokio::spawn(async move {
+                    let session = store.get("s").unwrap();
+                    let mut session = session.lock().await;
+                    tokio::time::sleep(OP_TIME).await;
+                    session.store(i.to_string(), vec![i]);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert!(start.elapsed() >= OP_TIME * 5);
+        let session = store.get("s").unwrap();
+        assert_eq!(session.lock().await.ciphertext_values.len(), 5);
+        assert_eq!(session.lock().await.version, 6);
+        assert_eq!(store.len(), 1);
+        assert!(store.get("missing").is_none());
+
+        assert!(store.remove("s"));
+        assert!(!store.remove("s"));
+        assert_eq!(store.len(), 0);
+    }
+
+    // Micro-benchmark: cargo test --release throughput -- --ignored --nocapture
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore]
+    async fn throughput_by_session_count() {
+        println!("{:>8} {:>16} {:>16}", "sessions", "single lock/s", "per-session/s");
+        for sessions in [1, 5, 10, 25, 50] {
+            let coarse = single_lock(sessions).await;
+            let fine = per_session_locks(sessions).await;
+            println!(
+                "{:>8} {:>16.1} {:>16.1}",
+                sessions,
+                sessions as f64 / coarse.as_secs_f64(),
+                sessions as f64 / fine.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,222 @@
+// grpc_server/src/stats.rs
+//
+// Repeated benchmark runs (BenchmarkRequest.repetitions)
+//
+// One run's timings move by several percent with CPU frequency, cache state and
+// whatever else the machine is doing, so a single RunBenchmark can't tell a
+// real difference between two libraries from noise. With repetitions the
+// benchmark runs K times and every timing is reported as a mean with a 95%
+// confidence interval. K is small, so the interval uses Student's t rather than
+// the normal 1.96.
+
+use std::time::Instant;
+
+use crate::he_service::{BenchmarkResponse, MetricSummary, OperationTiming};
+use crate::{deadline_passed, with_per_slot_costs};
+
+// Upper bound on BenchmarkRequest.repetitions
+pub const MAX_REPETITIONS: i32 = 100;
+
+// Two-sided 95% critical values of Student's t for 1..=30 degrees of freedom
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+    2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086,
+    2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+];
+
+// Beyond the table, round down to the nearest tabulated df (a slightly wider,
+// conservative interval)
+fn t_critical_95(df: usize) -> f64 {
+    match df {
+        0 => f64::NAN,
+        1..=30 => T_95[df - 1],
+        31..=39 => 2.042,
+        40..=59 => 2.021,
+        60..=119 => 2.000,
+        _ => 1.980,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    /// Sample standard deviation (n - 1); 0 for a single sample
+    pub std_dev: f64,
+    pub ci95_low: f64,
+    pub ci95_high: f64,
+}
+
+/// Mean, spread and 95% confidence interval of the mean. A single sample gives
+/// a zero-width interval.
+pub fn summarize(samples: &[f64]) -> Summary {
+    let n = samples.len();
+    let mean = samples.iter().sum::<f64>() / n.max(1) as f64;
+    if n < 2 {
+        return Summary { mean, std_dev: 0.0, ci95_low: mean, ci95_high: mean };
+    }
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+    let std_dev = variance.sqrt();
+    let half_width = t_critical_95(n - 1) * std_dev / (n as f64).sqrt();
+    Summary { mean, std_dev, ci95_low: mean - half_width, ci95_high: mean + half_width }
+}
+
+type Field = fn(&mut BenchmarkResponse) -> &mut f64;
+
+// The timings summarized, as (metric name, field)
+const METRICS: [(&str, Field); 7] = [
+    ("key_gen", |r| &mut r.key_gen_time_ms),
+    ("encoding", |r| &mut r.encoding_time_ms),
+    ("encryption", |r| &mut r.encryption_time_ms),
+    ("addition", |r| &mut r.addition_time_ms),
+    ("multiplication", |r| &mut r.multiplication_time_ms),
+    ("decryption", |r| &mut r.decryption_time_ms),
+    ("total", |r| &mut r.total_time_ms),
+];
+
+/// Failed runs come from failed_benchmark and never timed anything
+pub fn run_failed(response: &BenchmarkResponse) -> bool {
+    response.total_time_ms == 0.0
+}
+
+/// Run a benchmark `repetitions` times and report each timing as the mean over
+/// the runs, with a `MetricSummary` per metric
+///
+/// No new run starts once `deadline` has passed; the summaries cover the runs
+/// that completed. The first failed run is returned as is. Sizes, slots and the
+/// operation mix's sample counts are the same in every run and are taken from
+/// the first. A single repetition is one plain run, without summaries.
+pub fn run_benchmark_repeated(
+    repetitions: usize,
+    deadline: Instant,
+    mut run: impl FnMut() -> BenchmarkResponse,
+) -> BenchmarkResponse {
+    if repetitions <= 1 {
+        return run();
+    }
+
+    let mut runs: Vec<BenchmarkResponse> = Vec::with_capacity(repetitions);
+    while runs.len() < repetitions && (runs.is_empty() || !deadline_passed(deadline)) {
+        let response = run();
+        if run_failed(&response) {
+            return response;
+        }
+        runs.push(response);
+    }
+
+    let mut summaries = Vec::with_capacity(METRICS.len());
+    let mut mean = runs[0].clone();
+    for (metric, field) in METRICS {
+        let samples: Vec<f64> = runs.iter_mut().map(|r| *field(r)).collect();
+        let summary = summarize(&samples);
+        summaries.push(MetricSummary {
+            metric: metric.to_string(),
+            mean_ms: summary.mean,
+            std_dev_ms: summary.std_dev,
+            ci95_low_ms: summary.ci95_low,
+            ci95_high_ms: summary.ci95_high,
+            runs: runs.len() as i32,
+        });
+        *field(&mut mean) = summary.mean;
+    }
+    mean.operation_timings = mean_operation_timings(&runs);
+    mean.status = format!("{} (mean of {} runs)", mean.status, runs.len());
+    mean.repetitions = runs.len() as i32;
+    mean.summaries = summaries;
+    let slots = mean.slots_per_ciphertext as usize;
+    with_per_slot_costs(mean, slots)
+}
+
+// Same operations and sample counts in every run; average the times
+fn mean_operation_timings(runs: &[BenchmarkResponse]) -> Vec<OperationTiming> {
+    let n = runs.len() as f64;
+    runs[0]
+        .operation_timings
+        .iter()
+        .enumerate()
+        .map(|(i, timing)| {
+            let total: f64 = runs.iter().filter_map(|r| r.operation_timings.get(i)).map(|t| t.total_time_ms).sum();
+            let avg: f64 = runs.iter().filter_map(|r| r.operation_timings.get(i)).map(|t| t.avg_time_ms).sum();
+            OperationTiming {
+                operation: timing.operation.clone(),
+                samples: timing.samples,
+                total_time_ms: total / n,
+                avg_time_ms: avg / n,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_summary_matches_hand_computed_interval() {
+        let summary = summarize(&[10.0, 12.0, 14.0, 16.0, 18.0]);
+        assert_eq!(summary.mean, 14.0);
+        // sqrt(40 / 4)
+        assert!((summary.std_dev - 10f64.sqrt()).abs() < 1e-12);
+        // t(4) = 2.776, half width 2.776 * sqrt(10) / sqrt(5)
+        let half_width = 2.776 * 2f64.sqrt();
+        assert!((summary.ci95_low - (14.0 - half_width)).abs() < 1e-9);
+        assert!((summary.ci95_high - (14.0 + half_width)).abs() < 1e-9);
+
+        let single = summarize(&[3.0]);
+        assert_eq!((single.ci95_low, single.mean, single.ci95_high), (3.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_ten_runs_give_an_interval_inside_the_spread_of_the_runs() {
+        // Noisy timings around 20 ms
+        let runs = [19.2, 21.5, 20.1, 18.7, 22.3, 20.8, 19.9, 21.1, 20.4, 19.6];
+        let mut next = runs.iter();
+        let response = run_benchmark_repeated(10, Instant::now() + Duration::from_secs(60), || BenchmarkResponse {
+            total_time_ms: *next.next().unwrap(),
+            ..Default::default()
+        });
+
+        let total = response.summaries.iter().find(|s| s.metric == "total").unwrap();
+        let mean = runs.iter().sum::<f64>() / 10.0;
+        assert!((total.mean_ms - mean).abs() < 1e-9);
+        assert!(total.ci95_low_ms < mean && mean < total.ci95_high_ms, "{:?}", total);
+        // The mean is far better known than any one run
+        let (min, max) = (18.7, 22.3);
+        assert!(min < total.ci95_low_ms && total.ci95_high_ms < max, "{:?}", total);
+    }
+
+    #[test]
+    fn test_repeated_runs_report_means_and_stop_at_the_first_failure() {
+        let mut calls = 0;
+        let response = run_benchmark_repeated(4, Instant::now() + Duration::from_secs(60), || {
+            calls += 1;
+            BenchmarkResponse {
+                addition_time_ms: calls as f64,
+                total_time_ms: 10.0 * calls as f64,
+                slots_per_ciphertext: 2,
+                status: "done".to_string(),
+                ..Default::default()
+            }
+        });
+        assert_eq!(calls, 4);
+        assert_eq!(response.repetitions, 4);
+        assert_eq!(response.addition_time_ms, 2.5);
+        assert_eq!(response.addition_ns_per_slot, 1_250_000.0);
+        assert_eq!(response.status, "done (mean of 4 runs)");
+        let total = response.summaries.iter().find(|s| s.metric == "total").unwrap();
+        assert_eq!((total.mean_ms, total.runs), (25.0, 4));
+        assert!(total.ci95_low_ms < 25.0 && total.ci95_high_ms > 25.0);
+
+        let mut calls = 0;
+        let response = run_benchmark_repeated(4, Instant::now() + Duration::from_secs(60), || {
+            calls += 1;
+            if calls == 2 {
+                return crate::failed_benchmark("broken".to_string());
+            }
+            BenchmarkResponse { total_time_ms: 1.0, ..Default::default() }
+        });
+        assert_eq!(calls, 2);
+        assert_eq!(response.status, "broken");
+        assert!(response.summaries.is_empty());
+    }
+}
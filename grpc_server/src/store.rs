@@ -0,0 +1,229 @@
+// grpc_server/src/store.rs
+//
+// Persistent benchmark history backed by SQLite
+//
+// A self-contained alternative to a time-series database: every benchmark run is
+// one row, with the timings, library, parameters and timestamp as plain columns.
+// The schema is created the first time a store is opened.
+
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::he_service::BenchmarkResponse;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS benchmark_runs (
+        id                     INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp_ms           INTEGER NOT NULL,
+        library                TEXT    NOT NULL,
+        parameters             TEXT    NOT NULL,
+        num_operations         INTEGER NOT NULL,
+        status                 TEXT    NOT NULL,
+        key_gen_time_ms        REAL    NOT NULL,
+        encoding_time_ms       REAL    NOT NULL,
+        encryption_time_ms     REAL    NOT NULL,
+        addition_time_ms       REAL    NOT NULL,
+        multiplication_time_ms REAL    NOT NULL,
+        decryption_time_ms     REAL    NOT NULL,
+        total_time_ms          REAL    NOT NULL,
+        plaintext_bytes        INTEGER NOT NULL,
+        ciphertext_bytes       INTEGER NOT NULL,
+        expansion_ratio        REAL    NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_benchmark_runs_library_time
+        ON benchmark_runs (library, timestamp_ms);
+";
+
+/// Describes the run a BenchmarkResponse came from (BenchmarkResponse itself
+/// doesn't say which library or parameters were used)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunMetadata {
+    pub library: String,
+    pub parameters: String,
+    pub num_operations: i32,
+    /// Milliseconds since the Unix epoch
+    pub timestamp_ms: i64,
+}
+
+impl RunMetadata {
+    /// Metadata stamped with the current time
+    pub fn new(library: &str, parameters: &str, num_operations: i32) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        RunMetadata {
+            library: library.to_string(),
+            parameters: parameters.to_string(),
+            num_operations,
+            timestamp_ms,
+        }
+    }
+}
+
+/// One row read back from the store
+// The server itself only counts rows at startup; the fields are for callers reading history
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct StoredBenchmark {
+    pub id: i64,
+    pub metadata: RunMetadata,
+    pub result: BenchmarkResponse,
+}
+
+pub struct BenchmarkStore {
+    conn: Connection,
+}
+
+impl BenchmarkStore {
+    /// Open (or create) a database file
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Throwaway store that lives only as long as this value
+    #[cfg(test)]
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(BenchmarkStore { conn })
+    }
+
+    /// Save one benchmark run, returning its row id
+    pub fn save(&self, result: &BenchmarkResponse, metadata: &RunMetadata) -> rusqlite::Result<i64> {
+        self.conn.execute(
+            "INSERT INTO benchmark_runs (
+                timestamp_ms, library, parameters, num_operations, status,
+                key_gen_time_ms, encoding_time_ms, encryption_time_ms, addition_time_ms,
+                multiplication_time_ms, decryption_time_ms, total_time_ms,
+                plaintext_bytes, ciphertext_bytes, expansion_ratio
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![
+                metadata.timestamp_ms,
+                metadata.library,
+                metadata.parameters,
+                metadata.num_operations,
+                result.status,
+                result.key_gen_time_ms,
+                result.encoding_time_ms,
+                result.encryption_time_ms,
+                result.addition_time_ms,
+                result.multiplication_time_ms,
+                result.decryption_time_ms,
+                result.total_time_ms,
+                result.plaintext_bytes,
+                result.ciphertext_bytes,
+                result.expansion_ratio,
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// All runs for a library, oldest first
+    pub fn query_by_library(&self, library: &str) -> rusqlite::Result<Vec<StoredBenchmark>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, timestamp_ms, library, parameters, num_operations, status,
+                    key_gen_time_ms, encoding_time_ms, encryption_time_ms, addition_time_ms,
+                    multiplication_time_ms, decryption_time_ms, total_time_ms,
+                    plaintext_bytes, ciphertext_bytes, expansion_ratio
+             FROM benchmark_runs
+             WHERE library = ?1
+             ORDER BY timestamp_ms ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![library], |row| {
+            Ok(StoredBenchmark {
+                id: row.get(0)?,
+                metadata: RunMetadata {
+                    timestamp_ms: row.get(1)?,
+                    library: row.get(2)?,
+                    parameters: row.get(3)?,
+                    num_operations: row.get(4)?,
+                },
+                result: BenchmarkResponse {
+                    status: row.get(5)?,
+                    key_gen_time_ms: row.get(6)?,
+                    encoding_time_ms: row.get(7)?,
+                    encryption_time_ms: row.get(8)?,
+                    addition_time_ms: row.get(9)?,
+                    multiplication_time_ms: row.get(10)?,
+                    decryption_time_ms: row.get(11)?,
+                    total_time_ms: row.get(12)?,
+                    plaintext_bytes: row.get(13)?,
+                    ciphertext_bytes: row.get(14)?,
+                    expansion_ratio: row.get(15)?,
+                    // The per-operation mix breakdown and the per-slot costs
+                    // aren't kept in the history
+                    operation_timings: Vec::new(),
+                    ..Default::default()
+                },
+            })
+        })?;
+
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(total_time_ms: f64) -> BenchmarkResponse {
+        BenchmarkResponse {
+            status: "Success".to_string(),
+            total_time_ms,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(library: &str, timestamp_ms: i64) -> RunMetadata {
+        RunMetadata {
+            timestamp_ms,
+            ..RunMetadata::new(library, "poly_modulus_degree=8192", 10)
+        }
+    }
+
+    #[test]
+    fn test_save_and_query_ordered_by_timestamp() {
+        let store = BenchmarkStore::open_in_memory().unwrap();
+
+        // Inserted out of order on purpose
+        store.save(&run(30.0), &metadata("SEAL", 3_000)).unwrap();
+        store.save(&run(10.0), &metadata("SEAL", 1_000)).unwrap();
+        store.save(&run(99.0), &metadata("HELib", 2_000)).unwrap();
+        store.save(&run(20.0), &metadata("SEAL", 2_000)).unwrap();
+
+        let seal_runs = store.query_by_library("SEAL").unwrap();
+        let timestamps: Vec<i64> = seal_runs.iter().map(|r| r.metadata.timestamp_ms).collect();
+        let totals: Vec<f64> = seal_runs.iter().map(|r| r.result.total_time_ms).collect();
+
+        assert_eq!(timestamps, vec![1_000, 2_000, 3_000]);
+        assert_eq!(totals, vec![10.0, 20.0, 30.0]);
+        assert!(seal_runs.iter().all(|r| r.metadata.library == "SEAL"));
+
+        assert_eq!(store.query_by_library("HELib").unwrap().len(), 1);
+        assert!(store.query_by_library("OpenFHE").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_schema_created_once_per_file() {
+        let path = std::env::temp_dir().join(format!("he-bench-{}.db", uuid::Uuid::new_v4()));
+
+        let id = BenchmarkStore::open(&path).unwrap()
+            .save(&run(5.0), &metadata("OpenFHE", 1_000)).unwrap();
+
+        // Re-opening must keep existing rows
+        let runs = BenchmarkStore::open(&path).unwrap().query_by_library("OpenFHE").unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].id, id);
+        assert_eq!(runs[0].metadata.parameters, "poly_modulus_degree=8192");
+
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,101 @@
+// grpc_server/src/window.rs
+//
+// Rolling-window aggregation over a stream of encrypted counts (SEAL)
+//
+// Unlike the other RPCs, which re-encrypt stored values under a shared context
+// on every call, a window has to keep real ciphertexts between calls, so it owns
+// a SEAL context (and its keys) for the lifetime of the session. Encryptors/decryptors borrow the context, so
+// they are created per call rather than stored alongside it.
+
+use he_benchmark::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor, Evaluator};
+use std::collections::VecDeque;
+
+// Most recent entries kept per session; older ones are dropped
+pub const WINDOW_CAPACITY: usize = 256;
+
+pub struct EncryptedWindow {
+    entries: VecDeque<Ciphertext>,
+    capacity: usize,
+    encoder: BatchEncoder,
+    context: Context,
+}
+
+// SAFETY: the SEAL objects are plain heap allocations owned exclusively by this
+// struct (no thread-local state), and the server only touches a window while
+// holding its Mutex, so moving it between blocking threads is fine.
+unsafe impl Send for EncryptedWindow {}
+
+impl EncryptedWindow {
+    pub fn new(poly_modulus_degree: u64, plain_modulus: u64, capacity: usize) -> Result<Self, String> {
+        let context = Context::new(poly_modulus_degree, plain_modulus)
+            .map_err(|e| format!("Failed to create context: {}", e))?;
+        let encoder = BatchEncoder::new(&context).map_err(|e| format!("Failed to create encoder: {}", e))?;
+
+        Ok(EncryptedWindow {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            encoder,
+            context,
+        })
+    }
+
+    /// Encrypt each timestep's count and append it, evicting the oldest entries when full
+    pub fn push(&mut self, counts: &[i64]) -> Result<(), String> {
+        let encryptor = Encryptor::new(&self.context).map_err(|e| format!("Failed to create encryptor: {}", e))?;
+
+        for &count in counts {
+            let plain = self.encoder.encode(&[count]).map_err(|e| format!("Encoding failed: {}", e))?;
+            let cipher = encryptor.encrypt(&plain).map_err(|e| format!("Encryption failed: {}", e))?;
+
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back(cipher);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Homomorphic sum of the most recent `k` entries (fewer if not that many are stored).
+    /// Returns the decrypted total and how many entries went into it.
+    pub fn sum_last(&self, k: usize) -> Result<(i64, usize), String> {
+        let used = k.min(self.entries.len());
+        if used == 0 {
+            return Ok((0, 0));
+        }
+
+        let evaluator = Evaluator::new(&self.context).map_err(|e| format!("Failed to create evaluator: {}", e))?;
+        let encryptor = Encryptor::new(&self.context).map_err(|e| format!("Failed to create encryptor: {}", e))?;
+        let decryptor = Decryptor::new(&self.context).map_err(|e| format!("Failed to create decryptor: {}", e))?;
+
+        // Start from an encryption of zero so every entry is just one add
+        let zero = self.encoder.encode(&[0]).map_err(|e| format!("Encoding failed: {}", e))?;
+        let mut total = encryptor.encrypt(&zero).map_err(|e| format!("Encryption failed: {}", e))?;
+        for cipher in self.entries.iter().skip(self.entries.len() - used) {
+            total = evaluator.add(&total, cipher).map_err(|e| format!("Addition failed: {}", e))?;
+        }
+
+        let plain = decryptor.decrypt(&total).map_err(|e| format!("Decryption failed: {}", e))?;
+        let decoded = self.encoder.decode(&plain).map_err(|e| format!("Decoding failed: {}", e))?;
+
+        Ok((decoded.first().copied().unwrap_or(0), used))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let mut window = EncryptedWindow::new(8192, 1032193, 3).unwrap();
+        window.push(&[100, 1, 2, 3]).unwrap();
+
+        // 100 was evicted
+        assert_eq!(window.len(), 3);
+        assert_eq!(window.sum_last(10).unwrap(), (6, 3));
+    }
+}
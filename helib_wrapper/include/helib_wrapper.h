@@ -29,6 +29,19 @@ HElibContext* helib_create_context(
 
 void helib_destroy_context(HElibContext* ctx);
 
+/// Parameters the context was built from, plus what HElib derived from them:
+/// SIMD slots per plaintext, estimated security level (bits) and the number of
+/// primes in the modulus chain. Returns 0 on success, -1 on failure.
+int helib_context_info(
+    HElibContext* ctx,
+    unsigned long* m,
+    unsigned long* p,
+    unsigned long* r,
+    long* nslots,
+    double* security_bits,
+    long* num_primes
+);
+
 // Key Management
 /// Generate secret key
 HElibSecretKey* helib_generate_secret_key(HElibContext* ctx);
@@ -82,11 +95,42 @@ HElibCiphertext* helib_subtract(
     HElibCiphertext* b
 );
 
+/// Homomorphic squaring: result = a * a
+/// Cheaper and less noisy than helib_multiply(a, a)
+HElibCiphertext* helib_square(HElibCiphertext* a);
+
+/// Homomorphic exponentiation: result = a ^ exponent (square-and-multiply)
+/// @return NULL if exponent is 0
+HElibCiphertext* helib_power(
+    HElibCiphertext* a,
+    unsigned long exponent
+);
+
 // Utility Functions
 
+/// Serialized size of a ciphertext in bytes (0 on failure)
+size_t helib_ciphertext_byte_count(HElibCiphertext* cipher);
+
 /// Get noise budget (for debugging)
 int helib_noise_budget(HElibSecretKey* sk, HElibCiphertext* cipher);
 
+/// Depth metrics for comparing against SEAL: noise budget (bits), primes left in
+/// the ciphertext's modulus (level), primes in the full chain, and the bit size of
+/// the current modulus. Returns 0 on success, -1 on failure.
+int helib_ciphertext_stats(
+    HElibSecretKey* sk,
+    HElibCiphertext* cipher,
+    int* noise_budget_bits,
+    long* level,
+    long* total_levels,
+    double* capacity_bits
+);
+
+// Library Version
+/// HElib version this wrapper was built against, e.g. "2.3.0"
+/// @return Static string (don't free)
+const char* helib_version(void);
+
 #ifdef __cplusplus
 }
 #endif
@@ -1,8 +1,11 @@
 #include "../include/helib_wrapper.h"
 #include <helib/helib.h>
+#include <helib/version.h>
 #include <NTL/ZZX.h>
 #include <memory>
 #include <iostream>
+#include <sstream>
+#include <cmath>
 
 using namespace helib;
 using namespace std;
@@ -64,6 +67,34 @@ extern "C" void helib_destroy_context(HElibContext* ctx) {
     if (ctx) delete ctx;
 }
 
+extern "C" int helib_context_info(
+    HElibContext* ctx,
+    unsigned long* m,
+    unsigned long* p,
+    unsigned long* r,
+    long* nslots,
+    double* security_bits,
+    long* num_primes
+) {
+    try {
+        if (!ctx || !ctx->context) return -1;
+        if (!m || !p || !r || !nslots || !security_bits || !num_primes) return -1;
+
+        const Context& context = *ctx->context;
+        *m = context.getM();
+        *p = context.getP();
+        *r = context.getR();
+        // What the EncryptedArray (SIMD) encoder packs into one plaintext
+        *nslots = context.getNSlots();
+        *security_bits = context.securityLevel();
+        *num_primes = context.numPrimes();
+
+        return 0;
+    } catch (...) {
+        return -1;
+    }
+}
+
 // Key Management Implementation
 extern "C" HElibSecretKey* helib_generate_secret_key(HElibContext* ctx) {
     try {
@@ -257,7 +288,77 @@ extern "C" HElibCiphertext* helib_subtract(
     }
 }
 
+extern "C" HElibCiphertext* helib_square(HElibCiphertext* a) {
+    try {
+        if (!a || !a->ctxt) return nullptr;
+        
+        HElibCiphertext* result = new HElibCiphertext();
+        
+        // Copy and square in place (one tensor product + relinearization)
+        result->ctxt = make_unique<Ctxt>(*a->ctxt);
+        result->ctxt->square();
+        
+        return result;
+        
+    } catch (const exception& e) {
+        cerr << "Squaring failed: " << e.what() << endl;
+        return nullptr;
+    }
+}
+
+extern "C" HElibCiphertext* helib_power(
+    HElibCiphertext* a,
+    unsigned long exponent
+) {
+    try {
+        // x^0 would need a fresh encryption of 1, which needs the public key
+        if (!a || !a->ctxt || exponent == 0) return nullptr;
+        
+        // Square-and-multiply: walk the exponent bits from least significant,
+        // squaring the base each step and multiplying it in when the bit is set.
+        // Depth is ~log2(exponent) instead of exponent - 1.
+        Ctxt base(*a->ctxt);
+        unique_ptr<Ctxt> acc;
+        while (exponent > 0) {
+            if (exponent & 1) {
+                if (!acc) {
+                    acc = make_unique<Ctxt>(base);
+                } else {
+                    acc->multiplyBy(base);
+                }
+            }
+            exponent >>= 1;
+            if (exponent > 0) {
+                base.square();
+            }
+        }
+        
+        HElibCiphertext* result = new HElibCiphertext();
+        result->ctxt = std::move(acc);
+        
+        return result;
+        
+    } catch (const exception& e) {
+        cerr << "Exponentiation failed: " << e.what() << endl;
+        return nullptr;
+    }
+}
+
 // Utility Functions Implementation
+extern "C" size_t helib_ciphertext_byte_count(HElibCiphertext* cipher) {
+    try {
+        if (!cipher || !cipher->ctxt) return 0;
+        
+        // Serialize to a buffer and measure it (HElib has no size query)
+        stringstream ss;
+        cipher->ctxt->writeTo(ss);
+        return ss.str().size();
+        
+    } catch (...) {
+        return 0;
+    }
+}
+
 extern "C" int helib_noise_budget(
     HElibSecretKey* sk,
     HElibCiphertext* cipher
@@ -273,4 +374,37 @@ extern "C" int helib_noise_budget(
     } catch (...) {
         return -1;
     }
-}
\ No newline at end of file
+}
+
+extern "C" int helib_ciphertext_stats(
+    HElibSecretKey* sk,
+    HElibCiphertext* cipher,
+    int* noise_budget_bits,
+    long* level,
+    long* total_levels,
+    double* capacity_bits
+) {
+    try {
+        if (!sk || !sk->secretKey || !cipher || !cipher->ctxt) return -1;
+        if (!noise_budget_bits || !level || !total_levels || !capacity_bits) return -1;
+
+        const Ctxt& ctxt = *cipher->ctxt;
+        const Context& context = ctxt.getContext();
+
+        *noise_budget_bits = static_cast<int>(ctxt.capacity());
+        // HElib drops primes from the ciphertext modulus as noise grows, so the
+        // number left is the equivalent of SEAL's chain index
+        *level = ctxt.getPrimeSet().card();
+        *total_levels = context.getCtxtPrimes().card();
+        *capacity_bits = context.logOfProduct(ctxt.getPrimeSet()) / log(2.0);
+
+        return 0;
+    } catch (...) {
+        return -1;
+    }
+}
+
+// Library Version
+extern "C" const char* helib_version(void) {
+    return helib::version::asString;
+}
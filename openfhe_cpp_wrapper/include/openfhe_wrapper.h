@@ -25,9 +25,40 @@ OpenFHEContext* openfhe_create_bfv_context(
     uint32_t multiplicative_depth
 );
 
+/// Create a new OpenFHE BGV context
+/// @param ring_dimension: Polynomial ring dimension (0 lets OpenFHE pick the smallest secure one)
+/// @param plaintext_modulus: Plaintext modulus (e.g., 65537)
+/// @param multiplicative_depth: Multiplicative depth (e.g., 2)
+/// @return Pointer to context or NULL on failure
+OpenFHEContext* openfhe_create_bgv_context(
+    uint64_t ring_dimension,
+    uint64_t plaintext_modulus,
+    uint32_t multiplicative_depth
+);
+
 /// Destroy context and free memory
 void openfhe_destroy_context(OpenFHEContext* ctx);
 
+/// Whether a context uses BGV (false for BFV)
+bool openfhe_context_is_bgv(OpenFHEContext* ctx);
+
+/// Multiplicative depth the context was created with. Not serialized: a loaded
+/// BGV context reads it back from its modulus chain, a loaded BFV context returns 0
+uint32_t openfhe_context_mult_depth(OpenFHEContext* ctx);
+
+/// Get serialized (binary) size of a crypto context
+/// @return Size in bytes, or 0 on failure
+size_t openfhe_context_byte_count(OpenFHEContext* ctx);
+
+/// Serialize a crypto context (parameters only, no keys)
+/// @param output: Buffer of at least openfhe_context_byte_count() bytes
+/// @return Bytes written, or 0 on failure
+size_t openfhe_context_save(OpenFHEContext* ctx, uint8_t* output, size_t output_size);
+
+/// Deserialize a crypto context written by openfhe_context_save
+/// @return Pointer to context or NULL on failure
+OpenFHEContext* openfhe_context_load(const uint8_t* data, size_t data_size);
+
 // Key Management
 /// Generate public/private k ey pair
 /// @param ctx: OpenFHE context
@@ -37,6 +68,50 @@ OpenFHEKeyPair* openfhe_generate_keypair(OpenFHEContext* ctx);
 /// Destroy key pair and free memory
 void openfhe_destroy_keypair(OpenFHEKeyPair* keypair);
 
+// Key Serialization (binary)
+/// Get serialized size of a key pair's public key
+/// @return Size in bytes, or 0 on failure
+size_t openfhe_public_key_byte_count(OpenFHEKeyPair* keypair);
+
+/// Serialize a key pair's public key
+/// @param output: Buffer of at least openfhe_public_key_byte_count() bytes
+/// @return Bytes written, or 0 on failure
+size_t openfhe_public_key_save(OpenFHEKeyPair* keypair, uint8_t* output, size_t output_size);
+
+/// Get serialized size of a key pair's secret key
+/// @return Size in bytes, or 0 on failure (including a key pair without one)
+size_t openfhe_secret_key_byte_count(OpenFHEKeyPair* keypair);
+
+/// Serialize a key pair's secret key
+/// @param output: Buffer of at least openfhe_secret_key_byte_count() bytes
+/// @return Bytes written, or 0 on failure
+size_t openfhe_secret_key_save(OpenFHEKeyPair* keypair, uint8_t* output, size_t output_size);
+
+/// Rebuild a key pair from keys written by openfhe_public_key_save / openfhe_secret_key_save
+/// @param secret_data: NULL for a public-only key pair (can encrypt, not decrypt)
+/// @return Pointer to key pair or NULL on failure (including keys from another context)
+OpenFHEKeyPair* openfhe_keypair_load(
+    OpenFHEContext* ctx,
+    const uint8_t* public_data,
+    size_t public_size,
+    const uint8_t* secret_data,
+    size_t secret_size
+);
+
+/// Get serialized size of the context's relinearization (EvalMult) keys
+/// @return Size in bytes, or 0 on failure
+size_t openfhe_eval_mult_keys_byte_count(OpenFHEContext* ctx);
+
+/// Serialize the context's relinearization (EvalMult) keys
+/// @param output: Buffer of at least openfhe_eval_mult_keys_byte_count() bytes
+/// @return Bytes written, or 0 on failure
+size_t openfhe_eval_mult_keys_save(OpenFHEContext* ctx, uint8_t* output, size_t output_size);
+
+/// Load relinearization keys written by openfhe_eval_mult_keys_save, so
+/// ciphertexts under this context can be multiplied
+/// @return true on success, false on failure
+bool openfhe_eval_mult_keys_load(OpenFHEContext* ctx, const uint8_t* data, size_t data_size);
+
 // Plaintext Operations
 
 /// Create plaintext from integer vector
@@ -91,6 +166,29 @@ OpenFHEPlaintext* openfhe_decrypt(
 /// Destroy ciphertext and free memory
 void openfhe_destroy_ciphertext(OpenFHECiphertext* cipher);
 
+/// Get serialized (binary) size of a ciphertext
+/// @param cipher: Ciphertext to measure
+/// @return Size in bytes, or 0 on failure
+size_t openfhe_ciphertext_byte_count(OpenFHECiphertext* cipher);
+
+/// Multiplicative levels a ciphertext has used (0 when fresh)
+/// @return Levels, or 0 on failure
+uint32_t openfhe_ciphertext_level(OpenFHECiphertext* cipher);
+
+/// Serialize a ciphertext (binary)
+/// @param output: Buffer of at least openfhe_ciphertext_byte_count() bytes
+/// @return Bytes written, or 0 on failure
+size_t openfhe_ciphertext_save(OpenFHECiphertext* cipher, uint8_t* output, size_t output_size);
+
+/// Deserialize a ciphertext written by openfhe_ciphertext_save
+/// @param ctx: Context the ciphertext was created under (or one loaded from its serialization)
+/// @return Pointer to ciphertext or NULL on failure (including a context mismatch)
+OpenFHECiphertext* openfhe_ciphertext_load(
+    OpenFHEContext* ctx,
+    const uint8_t* data,
+    size_t data_size
+);
+
 // Homomorphic Operations
 /// Add two ciphertexts
 /// @param ctx: OpenFHE context
@@ -127,6 +225,43 @@ OpenFHECiphertext* openfhe_subtract(
     OpenFHECiphertext* b
 );
 
+// Threshold (n-out-of-n) Keys
+/// Generate one party's key share
+/// @param lead: NULL for the lead party, otherwise the lead's share
+/// @return Pointer to key share (secret share + public share) or NULL on failure
+OpenFHEKeyPair* openfhe_threshold_gen_share(OpenFHEContext* ctx, OpenFHEKeyPair* lead);
+
+/// Sum every party's public share into the joint public key
+/// @return Key pair holding only the joint public key, or NULL on failure
+OpenFHEKeyPair* openfhe_threshold_combine_shares(
+    OpenFHEContext* ctx,
+    OpenFHEKeyPair* const* shares,
+    size_t count
+);
+
+/// One party's partial decryption of a ciphertext under the joint key
+/// @param lead: true for the lead party's share
+/// @return Pointer to the partial decryption or NULL on failure
+OpenFHECiphertext* openfhe_threshold_partial_decrypt(
+    OpenFHEContext* ctx,
+    OpenFHEKeyPair* share,
+    OpenFHECiphertext* cipher,
+    bool lead
+);
+
+/// Recover the plaintext from every party's partial decryption
+/// @return Pointer to plaintext or NULL on failure
+OpenFHEPlaintext* openfhe_threshold_combine_partials(
+    OpenFHEContext* ctx,
+    OpenFHECiphertext* const* partials,
+    size_t count
+);
+
+// Library Version
+/// OpenFHE version this wrapper was built against, e.g. "1.2.0"
+/// @return Static string (don't free)
+const char* openfhe_version(void);
+
 // Error Handling
 /// Get last error message
 /// @return Error message string (valid until next call)
@@ -8,20 +8,31 @@
 #include "openfhe/pke/openfhe.h"
 #include "openfhe/pke/scheme/bfvrns/gen-cryptocontext-bfvrns-params.h"
 #include "openfhe/pke/scheme/bfvrns/gen-cryptocontext-bfvrns.h"
+#include "openfhe/pke/scheme/bgvrns/gen-cryptocontext-bgvrns-params.h"
+#include "openfhe/pke/scheme/bgvrns/gen-cryptocontext-bgvrns.h"
 #include "openfhe/pke/encoding/plaintext.h"  
 #include "openfhe/pke/ciphertext.h"                  
+#include "openfhe/pke/ciphertext-ser.h"
+#include "openfhe/pke/cryptocontext-ser.h"
+#include "openfhe/pke/key/key-ser.h"
+// Register the scheme-specific parameter types with the serializer
+#include "openfhe/pke/scheme/bfvrns/bfvrns-ser.h"
+#include "openfhe/pke/scheme/bgvrns/bgvrns-ser.h"
 
 // Standard headers
 #include <string>
 #include <memory>
 #include <vector>
 #include <cstring>
+#include <sstream>
+#include <stdexcept>
 
 using namespace lbcrypto;
 
 // Internal Structures
 struct OpenFHEContext {
     CryptoContext<DCRTPoly> cryptoContext;
+    uint32_t multiplicativeDepth = 0;  // As created; see openfhe_context_load
 };
 
 struct OpenFHEKeyPair {
@@ -67,10 +78,12 @@ extern "C" OpenFHEContext* openfhe_create_bfv_context(
         cryptoContext->Enable(PKE);
         cryptoContext->Enable(KEYSWITCH);
         cryptoContext->Enable(LEVELEDSHE);
+        cryptoContext->Enable(MULTIPARTY);
         
         // Allocate and return
         OpenFHEContext* ctx = new OpenFHEContext();
         ctx->cryptoContext = cryptoContext;
+        ctx->multiplicativeDepth = multiplicative_depth;
         
         set_error("");
         return ctx;
@@ -81,12 +94,144 @@ extern "C" OpenFHEContext* openfhe_create_bfv_context(
     }
 }
 
+extern "C" OpenFHEContext* openfhe_create_bgv_context(
+    uint64_t ring_dimension,
+    uint64_t plaintext_modulus,
+    uint32_t multiplicative_depth
+) {
+    try {
+        // Same shape as BFV; BGV keeps the noise small by switching modulus
+        // after each multiplication instead of scaling the message up
+        CCParams<CryptoContextBGVRNS> parameters;
+        parameters.SetPlaintextModulus(plaintext_modulus);
+        parameters.SetMultiplicativeDepth(multiplicative_depth);
+        if (ring_dimension != 0) {
+            parameters.SetRingDim(ring_dimension);
+        }
+        
+        CryptoContext<DCRTPoly> cryptoContext = GenCryptoContext(parameters);
+        
+        cryptoContext->Enable(PKE);
+        cryptoContext->Enable(KEYSWITCH);
+        cryptoContext->Enable(LEVELEDSHE);
+        cryptoContext->Enable(MULTIPARTY);
+        
+        OpenFHEContext* ctx = new OpenFHEContext();
+        ctx->cryptoContext = cryptoContext;
+        ctx->multiplicativeDepth = multiplicative_depth;
+        
+        set_error("");
+        return ctx;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to create BGV context: ") + e.what());
+        return nullptr;
+    }
+}
+
 extern "C" void openfhe_destroy_context(OpenFHEContext* ctx) {
     if (ctx) {
         delete ctx;
     }
 }
 
+extern "C" bool openfhe_context_is_bgv(OpenFHEContext* ctx) {
+    if (!ctx) {
+        return false;
+    }
+    return std::dynamic_pointer_cast<CryptoParametersBGVRNS>(ctx->cryptoContext->GetCryptoParameters()) != nullptr;
+}
+
+extern "C" uint32_t openfhe_context_mult_depth(OpenFHEContext* ctx) {
+    if (!ctx) {
+        return 0;
+    }
+    return ctx->multiplicativeDepth;
+}
+
+// Copies `serialized` into output if it fits; returns its size, or 0 if it doesn't
+static size_t copy_serialized(const std::string& serialized, uint8_t* output, size_t output_size) {
+    if (serialized.size() > output_size) {
+        set_error("Output buffer too small");
+        return 0;
+    }
+    std::memcpy(output, serialized.data(), serialized.size());
+    set_error("");
+    return serialized.size();
+}
+
+extern "C" size_t openfhe_context_byte_count(OpenFHEContext* ctx) {
+    if (!ctx) {
+        set_error("Invalid context");
+        return 0;
+    }
+    
+    try {
+        std::stringstream ss;
+        Serial::Serialize(ctx->cryptoContext, ss, SerType::BINARY);
+        
+        set_error("");
+        return ss.str().size();
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize context: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" size_t openfhe_context_save(OpenFHEContext* ctx, uint8_t* output, size_t output_size) {
+    if (!ctx || !output) {
+        set_error("Invalid parameters");
+        return 0;
+    }
+    
+    try {
+        std::stringstream ss;
+        Serial::Serialize(ctx->cryptoContext, ss, SerType::BINARY);
+        return copy_serialized(ss.str(), output, output_size);
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize context: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" OpenFHEContext* openfhe_context_load(const uint8_t* data, size_t data_size) {
+    if (!data) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        std::stringstream ss(std::string(reinterpret_cast<const char*>(data), data_size));
+        CryptoContext<DCRTPoly> cryptoContext;
+        Serial::Deserialize(cryptoContext, ss, SerType::BINARY);
+        if (!cryptoContext) {
+            set_error("Deserialized context is empty");
+            return nullptr;
+        }
+        
+        // OpenFHE reuses an already loaded context with the same parameters,
+        // so ciphertexts from it deserialize against this one
+        OpenFHEContext* ctx = new OpenFHEContext();
+        ctx->cryptoContext = cryptoContext;
+        // The depth isn't serialized. BGV's modulus chain has one prime per
+        // level plus the base prime, so it can be read back from there; BFV's
+        // prime count follows its noise estimate instead and says nothing
+        if (openfhe_context_is_bgv(ctx)) {
+            size_t towers = cryptoContext->GetElementParams()->GetParams().size();
+            ctx->multiplicativeDepth = towers > 0 ? towers - 1 : 0;
+        }
+        
+        set_error("");
+        return ctx;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to deserialize context: ") + e.what());
+        return nullptr;
+    }
+}
+
 // Key Management Implementation
 extern "C" OpenFHEKeyPair* openfhe_generate_keypair(OpenFHEContext* ctx) {
     if (!ctx) {
@@ -128,6 +273,198 @@ extern "C" void openfhe_destroy_keypair(OpenFHEKeyPair* keypair) {
     }
 }
 
+// Key Serialization Implementation
+template <typename T>
+static std::string serialize_binary(const T& object) {
+    std::stringstream ss;
+    Serial::Serialize(object, ss, SerType::BINARY);
+    return ss.str();
+}
+
+// Deserialize a key and check it belongs to ctx; nullptr (with the error set) if not
+template <typename Key>
+static Key load_key(OpenFHEContext* ctx, const uint8_t* data, size_t data_size, const char* what) {
+    std::stringstream ss(std::string(reinterpret_cast<const char*>(data), data_size));
+    Key key;
+    Serial::Deserialize(key, ss, SerType::BINARY);
+    if (!key) {
+        set_error(std::string("Deserialized ") + what + " is empty");
+        return nullptr;
+    }
+    if (key->GetCryptoContext() != ctx->cryptoContext) {
+        set_error(std::string(what) + " was serialized under a different crypto context");
+        return nullptr;
+    }
+    return key;
+}
+
+extern "C" size_t openfhe_public_key_byte_count(OpenFHEKeyPair* keypair) {
+    if (!keypair || !keypair->keyPair.publicKey) {
+        set_error("Invalid key pair");
+        return 0;
+    }
+    
+    try {
+        size_t size = serialize_binary(keypair->keyPair.publicKey).size();
+        set_error("");
+        return size;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize public key: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" size_t openfhe_public_key_save(OpenFHEKeyPair* keypair, uint8_t* output, size_t output_size) {
+    if (!keypair || !keypair->keyPair.publicKey || !output) {
+        set_error("Invalid parameters");
+        return 0;
+    }
+    
+    try {
+        return copy_serialized(serialize_binary(keypair->keyPair.publicKey), output, output_size);
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize public key: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" size_t openfhe_secret_key_byte_count(OpenFHEKeyPair* keypair) {
+    if (!keypair || !keypair->keyPair.secretKey) {
+        set_error("Key pair has no secret key");
+        return 0;
+    }
+    
+    try {
+        size_t size = serialize_binary(keypair->keyPair.secretKey).size();
+        set_error("");
+        return size;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize secret key: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" size_t openfhe_secret_key_save(OpenFHEKeyPair* keypair, uint8_t* output, size_t output_size) {
+    if (!keypair || !keypair->keyPair.secretKey || !output) {
+        set_error("Invalid parameters");
+        return 0;
+    }
+    
+    try {
+        return copy_serialized(serialize_binary(keypair->keyPair.secretKey), output, output_size);
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize secret key: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" OpenFHEKeyPair* openfhe_keypair_load(
+    OpenFHEContext* ctx,
+    const uint8_t* public_data,
+    size_t public_size,
+    const uint8_t* secret_data,
+    size_t secret_size
+) {
+    if (!ctx || !public_data) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        KeyPair<DCRTPoly> keyPair;
+        keyPair.publicKey = load_key<PublicKey<DCRTPoly>>(ctx, public_data, public_size, "Public key");
+        if (!keyPair.publicKey) {
+            return nullptr;
+        }
+        if (secret_data) {
+            keyPair.secretKey = load_key<PrivateKey<DCRTPoly>>(ctx, secret_data, secret_size, "Secret key");
+            if (!keyPair.secretKey) {
+                return nullptr;
+            }
+        }
+        
+        OpenFHEKeyPair* kp = new OpenFHEKeyPair();
+        kp->keyPair = keyPair;
+        kp->ctx = ctx;
+        
+        set_error("");
+        return kp;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to deserialize key pair: ") + e.what());
+        return nullptr;
+    }
+}
+
+// Every EvalMult key registered for this context (one per key pair generated under it)
+static std::string serialize_eval_mult_keys(OpenFHEContext* ctx) {
+    std::stringstream ss;
+    if (!CryptoContextImpl<DCRTPoly>::SerializeEvalMultKey(ss, SerType::BINARY, ctx->cryptoContext)) {
+        throw std::runtime_error("no EvalMult keys for this context");
+    }
+    return ss.str();
+}
+
+extern "C" size_t openfhe_eval_mult_keys_byte_count(OpenFHEContext* ctx) {
+    if (!ctx) {
+        set_error("Invalid context");
+        return 0;
+    }
+    
+    try {
+        size_t size = serialize_eval_mult_keys(ctx).size();
+        set_error("");
+        return size;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize EvalMult keys: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" size_t openfhe_eval_mult_keys_save(OpenFHEContext* ctx, uint8_t* output, size_t output_size) {
+    if (!ctx || !output) {
+        set_error("Invalid parameters");
+        return 0;
+    }
+    
+    try {
+        return copy_serialized(serialize_eval_mult_keys(ctx), output, output_size);
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize EvalMult keys: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" bool openfhe_eval_mult_keys_load(OpenFHEContext* ctx, const uint8_t* data, size_t data_size) {
+    if (!ctx || !data) {
+        set_error("Invalid parameters");
+        return false;
+    }
+    
+    try {
+        // The keys register themselves with the context matching their
+        // parameters, which is ctx once it has been loaded
+        std::stringstream ss(std::string(reinterpret_cast<const char*>(data), data_size));
+        if (!CryptoContextImpl<DCRTPoly>::DeserializeEvalMultKey(ss, SerType::BINARY)) {
+            set_error("Failed to deserialize EvalMult keys");
+            return false;
+        }
+        
+        set_error("");
+        return true;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to deserialize EvalMult keys: ") + e.what());
+        return false;
+    }
+}
+
 // Plaintext Operations Implementation
 extern "C" OpenFHEPlaintext* openfhe_create_plaintext(
     OpenFHEContext* ctx,
@@ -236,6 +573,12 @@ extern "C" OpenFHEPlaintext* openfhe_decrypt(
         return nullptr;
     }
     
+    // The joint key from threshold key generation has no secret half
+    if (!keypair->keyPair.secretKey) {
+        set_error("Key pair has no secret key (a joint threshold key needs partial decryption)");
+        return nullptr;
+    }
+    
     try {
         // Decrypt using secret key
         Plaintext result;
@@ -264,6 +607,94 @@ extern "C" void openfhe_destroy_ciphertext(OpenFHECiphertext* cipher) {
     }
 }
 
+extern "C" size_t openfhe_ciphertext_byte_count(OpenFHECiphertext* cipher) {
+    if (!cipher) {
+        set_error("Invalid ciphertext");
+        return 0;
+    }
+    
+    try {
+        // Serialize to a binary buffer and measure it
+        std::stringstream ss;
+        Serial::Serialize(cipher->ciphertext, ss, SerType::BINARY);
+        
+        set_error("");
+        return ss.str().size();
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize ciphertext: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" uint32_t openfhe_ciphertext_level(OpenFHECiphertext* cipher) {
+    if (!cipher || !cipher->ciphertext) {
+        set_error("Invalid ciphertext");
+        return 0;
+    }
+    
+    // GetLevel counts the moduli already switched away; a product whose
+    // rescale is still pending (OpenFHE rescales lazily, before the next
+    // multiplication) has noise scale degree 2 and has used one more
+    auto& ct = cipher->ciphertext;
+    set_error("");
+    return static_cast<uint32_t>(ct->GetLevel() + ct->GetNoiseScaleDeg() - 1);
+}
+
+extern "C" size_t openfhe_ciphertext_save(OpenFHECiphertext* cipher, uint8_t* output, size_t output_size) {
+    if (!cipher || !output) {
+        set_error("Invalid parameters");
+        return 0;
+    }
+    
+    try {
+        std::stringstream ss;
+        Serial::Serialize(cipher->ciphertext, ss, SerType::BINARY);
+        return copy_serialized(ss.str(), output, output_size);
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to serialize ciphertext: ") + e.what());
+        return 0;
+    }
+}
+
+extern "C" OpenFHECiphertext* openfhe_ciphertext_load(
+    OpenFHEContext* ctx,
+    const uint8_t* data,
+    size_t data_size
+) {
+    if (!ctx || !data) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        std::stringstream ss(std::string(reinterpret_cast<const char*>(data), data_size));
+        Ciphertext<DCRTPoly> ciphertext;
+        Serial::Deserialize(ciphertext, ss, SerType::BINARY);
+        if (!ciphertext) {
+            set_error("Deserialized ciphertext is empty");
+            return nullptr;
+        }
+        // The ciphertext names the parameters it was made under; they must be ours
+        if (ciphertext->GetCryptoContext() != ctx->cryptoContext) {
+            set_error("Ciphertext was serialized under a different crypto context");
+            return nullptr;
+        }
+        
+        OpenFHECiphertext* cipher = new OpenFHECiphertext();
+        cipher->ciphertext = ciphertext;
+        cipher->ctx = ctx;
+        
+        set_error("");
+        return cipher;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to deserialize ciphertext: ") + e.what());
+        return nullptr;
+    }
+}
+
 // Homomorphic Operations Implementation
 extern "C" OpenFHECiphertext* openfhe_eval_add(
     OpenFHECiphertext* ct1,
@@ -352,4 +783,134 @@ extern "C" OpenFHECiphertext* openfhe_eval_sub(
         set_error(std::string("EvalSub failed: ") + e.what());
         return nullptr;
     }
-}
\ No newline at end of file
+}
+
+// Threshold (n-out-of-n) Keys Implementation
+// Star topology: the lead's KeyGen fixes the common random polynomial, every
+// other party generates a fresh share against it, and the public halves are
+// summed into the joint key. No party ever sees another's secret share.
+extern "C" OpenFHEKeyPair* openfhe_threshold_gen_share(OpenFHEContext* ctx, OpenFHEKeyPair* lead) {
+    if (!ctx) {
+        set_error("Invalid context");
+        return nullptr;
+    }
+    
+    try {
+        KeyPair<DCRTPoly> keyPair = lead
+            ? ctx->cryptoContext->MultipartyKeyGen(lead->keyPair.publicKey, false, true)
+            : ctx->cryptoContext->KeyGen();
+        if (!keyPair.good()) {
+            set_error("Key share generation failed");
+            return nullptr;
+        }
+        
+        OpenFHEKeyPair* kp = new OpenFHEKeyPair();
+        kp->keyPair = keyPair;
+        kp->ctx = ctx;
+        
+        set_error("");
+        return kp;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to generate key share: ") + e.what());
+        return nullptr;
+    }
+}
+
+extern "C" OpenFHEKeyPair* openfhe_threshold_combine_shares(
+    OpenFHEContext* ctx,
+    OpenFHEKeyPair* const* shares,
+    size_t count
+) {
+    if (!ctx || !shares || count == 0) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        PublicKey<DCRTPoly> joint = shares[0]->keyPair.publicKey;
+        for (size_t i = 1; i < count; i++) {
+            joint = ctx->cryptoContext->MultiAddPubKeys(joint, shares[i]->keyPair.publicKey, joint->GetKeyTag());
+        }
+        
+        // Public half only: encrypting works, decrypting needs every party
+        OpenFHEKeyPair* kp = new OpenFHEKeyPair();
+        kp->keyPair.publicKey = joint;
+        kp->ctx = ctx;
+        
+        set_error("");
+        return kp;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Failed to combine key shares: ") + e.what());
+        return nullptr;
+    }
+}
+
+extern "C" OpenFHECiphertext* openfhe_threshold_partial_decrypt(
+    OpenFHEContext* ctx,
+    OpenFHEKeyPair* share,
+    OpenFHECiphertext* cipher,
+    bool lead
+) {
+    if (!ctx || !share || !cipher || !share->keyPair.secretKey) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        std::vector<Ciphertext<DCRTPoly>> in = {cipher->ciphertext};
+        auto partials = lead
+            ? ctx->cryptoContext->MultipartyDecryptLead(in, share->keyPair.secretKey)
+            : ctx->cryptoContext->MultipartyDecryptMain(in, share->keyPair.secretKey);
+        
+        OpenFHECiphertext* out = new OpenFHECiphertext();
+        out->ciphertext = partials.at(0);
+        out->ctx = ctx;
+        
+        set_error("");
+        return out;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Partial decryption failed: ") + e.what());
+        return nullptr;
+    }
+}
+
+extern "C" OpenFHEPlaintext* openfhe_threshold_combine_partials(
+    OpenFHEContext* ctx,
+    OpenFHECiphertext* const* partials,
+    size_t count
+) {
+    if (!ctx || !partials || count == 0) {
+        set_error("Invalid parameters");
+        return nullptr;
+    }
+    
+    try {
+        std::vector<Ciphertext<DCRTPoly>> in;
+        for (size_t i = 0; i < count; i++) {
+            in.push_back(partials[i]->ciphertext);
+        }
+        
+        Plaintext result;
+        ctx->cryptoContext->MultipartyDecryptFusion(in, &result);
+        
+        OpenFHEPlaintext* plain = new OpenFHEPlaintext();
+        plain->plaintext = result;
+        
+        set_error("");
+        return plain;
+        
+    } catch (const std::exception& e) {
+        set_error(std::string("Combining partial decryptions failed: ") + e.what());
+        return nullptr;
+    }
+}
+
+// Library Version
+extern "C" const char* openfhe_version(void) {
+    // Built once; GetOpenFHEVersion returns a temporary
+    static const std::string version = GetOpenFHEVersion();
+    return version.c_str();
+}
@@ -0,0 +1,177 @@
+// A running sum of BFV ciphertexts, kept encrypted between additions
+//
+// For streaming aggregation (bed availability, outbreak counts): each new
+// encrypted value is one homomorphic addition into the sum, nothing earlier is
+// re-fetched. Additions grow BFV noise slowly (about one bit per doubling of
+// the number of terms), but a long-lived sum still runs out eventually, and
+// this SEAL setup has no bootstrapping. The refresh is the key holder
+// decrypting the sum and encrypting it again.
+//
+// `needs_refresh` measures the noise budget when given a decryptor. Without one
+// (a --no-decrypt server) it can only estimate, by counting additions since the
+// last refresh.
+
+use crate::seal::{Ciphertext, Context, Decryptor, Encryptor, Evaluator, Plaintext, Result, Scheme, SealError};
+
+/// Budget (bits) below which a measured sum should be refreshed
+pub const REFRESH_MARGIN_BITS: u32 = 10;
+
+/// Additions after which an unmeasured sum is assumed to need a refresh:
+/// about 20 bits of noise growth, half of a fresh degree-8192 budget
+pub const DEFAULT_REFRESH_AFTER: u64 = 1 << 20;
+
+pub struct EncryptedAccumulator<'a> {
+    context: &'a Context,
+    evaluator: Evaluator<'a>,
+    sum: Ciphertext,
+    count: u64,
+    additions_since_refresh: u64,
+    refresh_after: u64,
+}
+
+/// An accumulator's sum and counters without the borrowed context, for
+/// owners that keep it between calls (`into_state` / `from_state`)
+pub struct AccumulatorState {
+    pub sum: Ciphertext,
+    pub count: u64,
+    pub additions_since_refresh: u64,
+}
+
+impl<'a> EncryptedAccumulator<'a> {
+    /// A sum starting at an encryption of zero (BFV only)
+    pub fn new(context: &'a Context) -> Result<Self> {
+        if context.scheme() != Scheme::Bfv {
+            return Err(SealError::InvalidParameter(format!(
+                "EncryptedAccumulator needs a BFV context, got {}",
+                context.scheme()
+            )));
+        }
+        let zero = Encryptor::new(context)?.encrypt(&Plaintext::from_hex("0")?)?;
+        Self::from_state(context, AccumulatorState { sum: zero, count: 0, additions_since_refresh: 0 })
+    }
+
+    /// Continue a sum saved with `into_state`
+    pub fn from_state(context: &'a Context, state: AccumulatorState) -> Result<Self> {
+        crate::seal::check_same_context(context, &[&state.sum])?;
+        Ok(EncryptedAccumulator {
+            context,
+            evaluator: Evaluator::new(context)?,
+            sum: state.sum,
+            count: state.count,
+            additions_since_refresh: state.additions_since_refresh,
+            refresh_after: DEFAULT_REFRESH_AFTER,
+        })
+    }
+
+    /// Estimate that a refresh is due after this many additions (when no
+    /// decryptor is available to measure)
+    pub fn with_refresh_after(mut self, additions: u64) -> Self {
+        self.refresh_after = additions.max(1);
+        self
+    }
+
+    /// Add `cipher` into the sum
+    pub fn add(&mut self, cipher: &Ciphertext) -> Result<()> {
+        self.sum = self.evaluator.add(&self.sum, cipher)?;
+        self.count += 1;
+        self.additions_since_refresh += 1;
+        Ok(())
+    }
+
+    /// The encrypted sum so far
+    pub fn value(&self) -> &Ciphertext {
+        &self.sum
+    }
+
+    /// Ciphertexts added since the accumulator was created
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Whether the sum should be refreshed before many more additions
+    ///
+    /// With a decryptor: its noise budget is below `REFRESH_MARGIN_BITS`.
+    /// Without: `with_refresh_after` additions since the last refresh (an
+    /// estimate that assumes fresh operands).
+    pub fn needs_refresh(&self, decryptor: Option<&Decryptor>) -> Result<bool> {
+        match decryptor {
+            Some(decryptor) => Ok(decryptor.invariant_noise_budget(&self.sum)? < REFRESH_MARGIN_BITS),
+            None => Ok(self.additions_since_refresh >= self.refresh_after),
+        }
+    }
+
+    /// Replace the sum with a fresh encryption of the same value
+    pub fn refresh(&mut self, decryptor: &Decryptor, encryptor: &Encryptor) -> Result<()> {
+        self.sum = encryptor.encrypt(&decryptor.decrypt(&self.sum)?)?;
+        self.additions_since_refresh = 0;
+        Ok(())
+    }
+
+    /// The context the sum lives in
+    pub fn context(&self) -> &'a Context {
+        self.context
+    }
+
+    pub fn into_state(self) -> AccumulatorState {
+        AccumulatorState {
+            sum: self.sum,
+            count: self.count,
+            additions_since_refresh: self.additions_since_refresh,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::BatchEncoder;
+
+    #[test]
+    fn test_hundred_ones_sum_to_hundred() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let one = encoder.encode(&[1]).unwrap();
+
+        let mut accumulator = EncryptedAccumulator::new(&ctx).unwrap();
+        for _ in 0..100 {
+            accumulator.add(&encryptor.encrypt(&one).unwrap()).unwrap();
+        }
+
+        assert_eq!(accumulator.count(), 100);
+        let total = encoder.decode(&decryptor.decrypt(accumulator.value()).unwrap()).unwrap();
+        assert_eq!(total[0], 100);
+        assert!(!accumulator.needs_refresh(Some(&decryptor)).unwrap());
+    }
+
+    #[test]
+    fn test_refresh_estimate_without_a_key() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let seven = encryptor.encrypt(&encoder.encode(&[7]).unwrap()).unwrap();
+
+        let mut accumulator = EncryptedAccumulator::new(&ctx).unwrap().with_refresh_after(3);
+        for _ in 0..3 {
+            assert!(!accumulator.needs_refresh(None).unwrap());
+            accumulator.add(&seven).unwrap();
+        }
+        assert!(accumulator.needs_refresh(None).unwrap());
+
+        // Refreshing keeps the value and restarts the estimate; the state
+        // round trip keeps both counters
+        accumulator.refresh(&decryptor, &encryptor).unwrap();
+        let mut accumulator = EncryptedAccumulator::from_state(&ctx, accumulator.into_state()).unwrap().with_refresh_after(3);
+        assert!(!accumulator.needs_refresh(None).unwrap());
+        accumulator.add(&seven).unwrap();
+        assert_eq!(accumulator.count(), 4);
+        assert_eq!(encoder.decode(&decryptor.decrypt(accumulator.value()).unwrap()).unwrap()[0], 28);
+
+        let other = Context::new(8192, 1032193).unwrap();
+        assert!(accumulator.add(&Encryptor::new(&other).unwrap().encrypt(&encoder.encode(&[1]).unwrap()).unwrap()).is_err());
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(matches!(EncryptedAccumulator::new(&ckks), Err(SealError::InvalidParameter(_))));
+    }
+}
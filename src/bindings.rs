@@ -44,6 +44,41 @@ pub struct SEALGaloisKeys {
     _private: [u8; 0],
 }
 
+#[repr(C)]
+pub struct SEALEvaluator {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALCKKSEncoder {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALRelinKeys {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALSecretKey {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALPublicKey {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALKSwitchKeys {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+pub struct SEALKeyGenerator {
+    _private: [u8; 0],
+}
+
 // FFI Function Declarations
 unsafe extern "C" {
     // Context management - Initialize the encryption environment
@@ -54,8 +89,32 @@ unsafe extern "C" {
         coeff_modulus_size: usize, // Modulus sizes
         plain_modulus: c_ulonglong, // Internal structures used for key generation and encryption
     ) -> *mut SEALContext;
+    pub fn seal_create_batching_context(
+        poly_modulus_degree: u64,
+        coeff_modulus: *const u64,
+        coeff_modulus_size: usize,
+        plain_modulus_bits: i32,
+    ) -> *mut SEALContext;
+    pub fn seal_create_context_from_primes(
+        poly_modulus_degree: u64,
+        primes: *const u64,
+        primes_size: usize,
+        plain_modulus: u64,
+    ) -> *mut SEALContext;
+    pub fn seal_create_public_context(
+        poly_modulus_degree: u64,
+        coeff_modulus: *const u64,
+        coeff_modulus_size: usize,
+        plain_modulus: u64,
+        public_key: *const u8,
+        public_key_size: usize,
+    ) -> *mut SEALContext;
+    pub fn seal_context_has_secret_key(ctx: *mut SEALContext) -> i32;
     
     pub fn seal_destroy_context(ctx: *mut SEALContext);
+    pub fn seal_context_plain_modulus(ctx: *mut SEALContext) -> u64;
+    pub fn seal_context_poly_modulus_degree(ctx: *mut SEALContext) -> u64;
+    pub fn seal_context_params_id(ctx: *mut SEALContext, out: *mut u64);
     
     // Encryptor
     pub fn seal_create_encryptor(
@@ -79,6 +138,9 @@ unsafe extern "C" {
     pub fn seal_create_plaintext(hex_string: *const c_char) -> *mut SEALPlaintext;
     pub fn seal_destroy_plaintext(plain: *mut SEALPlaintext);
     pub fn seal_plaintext_to_string(plain: *mut SEALPlaintext) -> *const c_char;
+    pub fn seal_plaintext_save_size(plain: *mut SEALPlaintext) -> usize;
+    pub fn seal_plaintext_save(plain: *mut SEALPlaintext, output: *mut u8, output_size: usize) -> usize;
+    pub fn seal_plaintext_load(ctx: *mut SEALContext, data: *const u8, data_size: usize) -> *mut SEALPlaintext;
     
     // Encryption/Decryption
     pub fn seal_encrypt(
@@ -96,8 +158,14 @@ unsafe extern "C" {
     // Ciphertext inspection (NEW!)
     pub fn seal_ciphertext_size(cipher: *mut SEALCiphertext) -> usize;
     pub fn seal_ciphertext_coeff_count(cipher: *mut SEALCiphertext) -> u64;
+    pub fn seal_ciphertext_coeff_modulus_size(cipher: *mut SEALCiphertext) -> usize;
     pub fn seal_ciphertext_byte_count(cipher: *mut SEALCiphertext) -> usize;
     pub fn seal_ciphertext_info(cipher: *mut SEALCiphertext) -> *const c_char;
+    pub fn seal_ciphertext_scale(cipher: *mut SEALCiphertext) -> f64;
+    pub fn seal_ciphertext_estimated_remaining_levels(ctx: *mut SEALContext, cipher: *mut SEALCiphertext) -> i32;
+    pub fn seal_ciphertext_chain_index(ctx: *mut SEALContext, cipher: *mut SEALCiphertext) -> i32;
+    pub fn seal_ciphertext_save(cipher: *mut SEALCiphertext, output: *mut u8, output_size: usize) -> usize;
+    pub fn seal_ciphertext_load(ctx: *mut SEALContext, data: *const u8, data_size: usize) -> *mut SEALCiphertext;
     
     // Homomorphic operations
     pub fn seal_add(
@@ -106,6 +174,12 @@ unsafe extern "C" {
         b: *mut SEALCiphertext,
     ) -> *mut SEALCiphertext;
     
+    pub fn seal_sub(
+        ctx: *mut SEALContext,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+    
     pub fn seal_multiply(
         ctx: *mut SEALContext,
         a: *mut SEALCiphertext,
@@ -130,6 +204,7 @@ unsafe extern "C" {
     
     // Galois keys
     pub fn seal_generate_galois_keys(ctx: *mut SEALContext) -> *mut SEALGaloisKeys;
+    pub fn seal_galois_keys_params_id(keys: *mut SEALGaloisKeys, out: *mut u64);
     pub fn seal_destroy_galois_keys(keys: *mut SEALGaloisKeys);
     pub fn seal_rotate_rows(
         ctx: *mut SEALContext,
@@ -137,4 +212,198 @@ unsafe extern "C" {
         steps: i32,
         galois_keys: *mut SEALGaloisKeys,
     ) -> *mut SEALCiphertext;
-}
\ No newline at end of file
+
+    // Evaluator
+    pub fn seal_create_evaluator(ctx: *mut SEALContext) -> *mut SEALEvaluator;
+    pub fn seal_destroy_evaluator(eval: *mut SEALEvaluator);
+    pub fn seal_evaluator_add(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_sub(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_multiply(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_multiply_optimized(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_multiply_plain(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        plain: *mut SEALPlaintext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_add_plain(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        plain: *mut SEALPlaintext,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_add_plain_scalar(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        scalar: i64,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_add_plain_scalar_ckks(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        scalar: f64,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_mask_slot(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        index: usize,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_rotate_rows(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        steps: i32,
+        galois_keys: *mut SEALGaloisKeys,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_rotate_columns(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        galois_keys: *mut SEALGaloisKeys,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_rotate_vector(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        steps: i32,
+        galois_keys: *mut SEALGaloisKeys,
+    ) -> *mut SEALCiphertext;
+
+    // Keys and key switching
+    pub fn seal_get_secret_key(ctx: *mut SEALContext) -> *mut SEALSecretKey;
+    pub fn seal_destroy_secret_key(key: *mut SEALSecretKey);
+    pub fn seal_get_public_key(ctx: *mut SEALContext) -> *mut SEALPublicKey;
+    pub fn seal_destroy_public_key(key: *mut SEALPublicKey);
+    pub fn seal_public_key_byte_count(key: *mut SEALPublicKey) -> usize;
+    pub fn seal_public_key_save(key: *mut SEALPublicKey, output: *mut u8, output_size: usize) -> usize;
+    pub fn seal_secret_key_byte_count(key: *mut SEALSecretKey) -> usize;
+    pub fn seal_relin_keys_byte_count(keys: *mut SEALRelinKeys) -> usize;
+    pub fn seal_galois_keys_byte_count(keys: *mut SEALGaloisKeys) -> usize;
+    pub fn seal_relin_keys_save(keys: *mut SEALRelinKeys, output: *mut u8, output_size: usize) -> usize;
+    pub fn seal_relin_keys_load(ctx: *mut SEALContext, data: *const u8, data_size: usize) -> *mut SEALRelinKeys;
+    pub fn seal_galois_keys_save(keys: *mut SEALGaloisKeys, output: *mut u8, output_size: usize) -> usize;
+    pub fn seal_galois_keys_load(ctx: *mut SEALContext, data: *const u8, data_size: usize) -> *mut SEALGaloisKeys;
+    pub fn seal_keygen_create_seeded(ctx: *mut SEALContext, seed: *const u8, seed_size: usize) -> *mut SEALKeyGenerator;
+    pub fn seal_keygen_secret_key(keygen: *mut SEALKeyGenerator) -> *mut SEALSecretKey;
+    pub fn seal_keygen_public_key(keygen: *mut SEALKeyGenerator) -> *mut SEALPublicKey;
+    pub fn seal_keygen_destroy(keygen: *mut SEALKeyGenerator);
+    pub fn seal_verify_key_pair(
+        ctx: *mut SEALContext,
+        pk: *mut SEALPublicKey,
+        sk: *mut SEALSecretKey,
+    ) -> i32;
+    pub fn seal_generate_kswitch_keys(
+        from: *mut SEALSecretKey,
+        to: *mut SEALPublicKey,
+    ) -> *mut SEALKSwitchKeys;
+    pub fn seal_destroy_kswitch_keys(keys: *mut SEALKSwitchKeys);
+    pub fn seal_evaluator_key_switch(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        switch_keys: *mut SEALKSwitchKeys,
+    ) -> *mut SEALCiphertext;
+
+    // CKKS
+    pub fn seal_create_ckks_context(
+        poly_modulus_degree: u64,
+        coeff_modulus: *const u64,
+        coeff_modulus_size: usize,
+    ) -> *mut SEALContext;
+    // scheme: 0 = BFV, 1 = CKKS; security_bits: 128, 192 or 256
+    pub fn seal_create_context_with_security(
+        scheme: u8,
+        poly_modulus_degree: u64,
+        coeff_modulus: *const u64,
+        coeff_modulus_size: usize,
+        plain_modulus: u64,
+        security_bits: i32,
+    ) -> *mut SEALContext;
+    pub fn seal_create_ckks_encoder(ctx: *mut SEALContext, scale: f64) -> *mut SEALCKKSEncoder;
+    pub fn seal_destroy_ckks_encoder(encoder: *mut SEALCKKSEncoder);
+    pub fn seal_ckks_encode(
+        encoder: *mut SEALCKKSEncoder,
+        values: *const f64,
+        values_size: usize,
+    ) -> *mut SEALPlaintext;
+    pub fn seal_ckks_decode(
+        encoder: *mut SEALCKKSEncoder,
+        plain: *mut SEALPlaintext,
+        output: *mut f64,
+        output_size: *mut usize,
+    );
+    pub fn seal_ckks_encode_complex(
+        encoder: *mut SEALCKKSEncoder,
+        values: *const f64,
+        count: usize,
+    ) -> *mut SEALPlaintext;
+    pub fn seal_ckks_decode_complex(
+        encoder: *mut SEALCKKSEncoder,
+        plain: *mut SEALPlaintext,
+        output: *mut f64,
+        output_size: *mut usize,
+    );
+    pub fn seal_ckks_slot_count(encoder: *mut SEALCKKSEncoder) -> usize;
+
+    // Relinearization keys
+    pub fn seal_generate_relin_keys(ctx: *mut SEALContext) -> *mut SEALRelinKeys;
+    pub fn seal_destroy_relin_keys(keys: *mut SEALRelinKeys);
+    pub fn seal_relin_keys_params_id(keys: *mut SEALRelinKeys, out: *mut u64);
+
+    pub fn seal_evaluator_compare_gt(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        threshold: f64,
+        iterations: u32,
+        relin_keys: *mut SEALRelinKeys,
+    ) -> *mut SEALCiphertext;
+
+    pub fn seal_evaluator_max_approx(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+        iterations: u32,
+        relin_keys: *mut SEALRelinKeys,
+    ) -> *mut SEALCiphertext;
+
+    pub fn seal_invariant_noise_budget(
+        decryptor: *mut SEALDecryptor,
+        ciphertext: *mut SEALCiphertext,
+    ) -> i32;
+    pub fn seal_evaluator_relinearize(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+        relin_keys: *mut SEALRelinKeys,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_rescale_to_next(
+        eval: *mut SEALEvaluator,
+        cipher: *mut SEALCiphertext,
+    ) -> *mut SEALCiphertext;
+
+    // CKKS scale/level matching
+    pub fn seal_evaluator_add_auto(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+        max_scale_ratio: f64,
+    ) -> *mut SEALCiphertext;
+    pub fn seal_evaluator_multiply_auto(
+        eval: *mut SEALEvaluator,
+        a: *mut SEALCiphertext,
+        b: *mut SEALCiphertext,
+        relin_keys: *mut SEALRelinKeys,
+        max_scale_ratio: f64,
+    ) -> *mut SEALCiphertext;
+
+    // Library version (static string)
+    pub fn seal_version() -> *const c_char;
+}
@@ -0,0 +1,157 @@
+// Trace how a SEAL circuit uses up its modulus chain
+//
+// Noise exhaustion usually shows up as a wrong answer several operations after
+// the real mistake (a missing rescale, one multiplication too many for the
+// primes chosen). ModulusChainTracker records where each intermediate
+// ciphertext sits in the chain, so the step that burned a level is visible.
+
+use crate::seal::{Ciphertext, Context, Result, Scheme};
+use std::fmt::Write;
+
+/// Where one intermediate ciphertext sits in the modulus chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainStep {
+    pub operation: String,
+    /// 0 = last level; fresh ciphertexts have the highest index
+    pub chain_index: usize,
+    /// Primes left in the ciphertext's coefficient modulus
+    pub primes_remaining: usize,
+    /// log2 of the CKKS scale (None for BFV)
+    pub scale_bits: Option<f64>,
+}
+
+pub struct ModulusChainTracker<'a> {
+    context: &'a Context,
+    steps: Vec<ChainStep>,
+}
+
+impl<'a> ModulusChainTracker<'a> {
+    pub fn new(context: &'a Context) -> Self {
+        ModulusChainTracker { context, steps: Vec::new() }
+    }
+
+    /// Record `cipher` as the result of `operation`
+    pub fn record(&mut self, operation: &str, cipher: &Ciphertext) -> Result<()> {
+        let chain_index = cipher.chain_index(self.context)?;
+        let scale_bits = (self.context.scheme() == Scheme::Ckks).then(|| cipher.scale().log2());
+        self.steps.push(ChainStep {
+            operation: operation.to_string(),
+            chain_index,
+            primes_remaining: cipher.coeff_modulus_size(),
+            scale_bits,
+        });
+        Ok(())
+    }
+
+    /// Steps in the order they were recorded
+    pub fn steps(&self) -> &[ChainStep] {
+        &self.steps
+    }
+
+    /// Aligned text table, one row per step
+    pub fn to_table(&self) -> String {
+        let width = self.steps.iter().map(|s| s.operation.len()).max().unwrap_or(0).max("operation".len());
+        let mut out = format!(
+            "{:>4}  {:<width$}  {:>11}  {:>6}  {:>10}\n",
+            "step", "operation", "chain index", "primes", "scale bits"
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{:>4}  {:<width$}  {:>11}  {:>6}  {:>10}",
+                i,
+                step.operation,
+                step.chain_index,
+                step.primes_remaining,
+                step.scale_bits.map_or("-".to_string(), |b| format!("{:.1}", b))
+            );
+        }
+        out
+    }
+
+    /// CSV with a header row; the scale column is empty for BFV
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("step,operation,chain_index,primes_remaining,scale_bits\n");
+        for (i, step) in self.steps.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{}",
+                i,
+                csv_field(&step.operation),
+                step.chain_index,
+                step.primes_remaining,
+                step.scale_bits.map_or(String::new(), |b| format!("{:.2}", b))
+            );
+        }
+        out
+    }
+}
+
+// Quote a CSV field if it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::{BatchEncoder, CKKSEncoder, Encryptor, Evaluator, RelinKeys};
+
+    #[test]
+    fn test_chain_shrinks_one_prime_per_rescale() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let mut tracker = ModulusChainTracker::new(&ctx);
+
+        let x = encryptor.encrypt(&encoder.encode(&[1.5]).unwrap()).unwrap();
+        tracker.record("encrypt", &x).unwrap();
+        let x2 = evaluator.relinearize(&evaluator.multiply(&x, &x).unwrap(), &relin_keys).unwrap();
+        tracker.record("multiply", &x2).unwrap();
+        let x2 = evaluator.rescale_to_next(&x2).unwrap();
+        tracker.record("rescale", &x2).unwrap();
+        let x4 = evaluator.relinearize(&evaluator.multiply(&x2, &x2).unwrap(), &relin_keys).unwrap();
+        tracker.record("multiply", &x4).unwrap();
+        let x4 = evaluator.rescale_to_next(&x4).unwrap();
+        tracker.record("rescale", &x4).unwrap();
+
+        // Three data primes (the last 60-bit one is the special prime)
+        let primes: Vec<_> = tracker.steps().iter().map(|s| s.primes_remaining).collect();
+        assert_eq!(primes, vec![3, 3, 2, 2, 1]);
+        let indices: Vec<_> = tracker.steps().iter().map(|s| s.chain_index).collect();
+        assert_eq!(indices, vec![2, 2, 1, 1, 0]);
+        // Multiplying doubles the scale, rescaling brings it back
+        let scale = |i: usize| tracker.steps()[i].scale_bits.unwrap();
+        assert!((scale(1) - 80.0).abs() < 0.1 && (scale(2) - 40.0).abs() < 0.1);
+
+        // Nothing left to drop at the last level
+        assert!(evaluator.rescale_to_next(&x4).is_err());
+
+        let table = tracker.to_table();
+        assert_eq!(table.lines().count(), 6);
+        assert!(table.lines().nth(3).unwrap().contains("rescale"), "{}", table);
+        let csv = tracker.to_csv();
+        assert_eq!(csv.lines().next(), Some("step,operation,chain_index,primes_remaining,scale_bits"));
+        assert!(csv.lines().nth(5).unwrap().starts_with("4,rescale,0,1,"), "{}", csv);
+    }
+
+    #[test]
+    fn test_bfv_steps_have_no_scale() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let cipher = Encryptor::new(&ctx).unwrap().encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let mut tracker = ModulusChainTracker::new(&ctx);
+        tracker.record("encrypt, fresh", &cipher).unwrap();
+
+        assert_eq!(tracker.steps()[0].scale_bits, None);
+        // [36, 36, 37]: two data primes, the 37-bit one is special
+        assert!(tracker.to_csv().ends_with("0,\"encrypt, fresh\",1,2,\n"), "{}", tracker.to_csv());
+        assert!(Evaluator::new(&ctx).unwrap().rescale_to_next(&cipher).is_err());
+    }
+}
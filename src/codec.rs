@@ -0,0 +1,104 @@
+// Integer encoding for categorical fields (diagnosis codes, blood types, ...)
+//
+// HE schemes only see integers, so a categorical value has to become one before
+// encryption and be mapped back after decryption. CategoricalCodec fixes that
+// mapping for a vocabulary; both sides of a computation must build it from the
+// same vocabulary in the same order.
+//
+// Codes start at 1: an unused (zero) slot never decodes as a real category.
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoricalCodec {
+    categories: Vec<String>,
+    codes: HashMap<String, i64>,
+}
+
+impl CategoricalCodec {
+    /// Codec for `vocabulary`, coded 1, 2, 3, ... in order
+    pub fn new<S: AsRef<str>>(vocabulary: &[S]) -> Result<Self, CodecError> {
+        let mut codes = HashMap::with_capacity(vocabulary.len());
+        for (i, category) in vocabulary.iter().enumerate() {
+            let category = category.as_ref();
+            if codes.insert(category.to_string(), i as i64 + 1).is_some() {
+                return Err(CodecError::DuplicateCategory(category.to_string()));
+            }
+        }
+        Ok(CategoricalCodec {
+            categories: vocabulary.iter().map(|c| c.as_ref().to_string()).collect(),
+            codes,
+        })
+    }
+
+    pub fn encode(&self, category: &str) -> Result<i64, CodecError> {
+        self.codes
+            .get(category)
+            .copied()
+            .ok_or_else(|| CodecError::UnknownCategory(category.to_string()))
+    }
+
+    pub fn decode(&self, value: i64) -> Result<String, CodecError> {
+        value
+            .checked_sub(1)
+            .and_then(|i| usize::try_from(i).ok())
+            .and_then(|i| self.categories.get(i))
+            .cloned()
+            .ok_or(CodecError::UnknownValue(value))
+    }
+
+    /// Number of categories (the largest code)
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodecError {
+    UnknownCategory(String),
+    UnknownValue(i64),
+    DuplicateCategory(String),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::UnknownCategory(category) => write!(f, "Category {:?} is not in the vocabulary", category),
+            CodecError::UnknownValue(value) => write!(f, "{} is not the code of any category", value),
+            CodecError::DuplicateCategory(category) => write!(f, "Category {:?} appears twice in the vocabulary", category),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOOD_TYPES: [&str; 8] = ["O-", "O+", "A-", "A+", "B-", "B+", "AB-", "AB+"];
+
+    #[test]
+    fn test_round_trip_is_identity() {
+        let codec = CategoricalCodec::new(&BLOOD_TYPES).unwrap();
+        assert_eq!(codec.len(), 8);
+        for category in BLOOD_TYPES {
+            assert_eq!(codec.decode(codec.encode(category).unwrap()).unwrap(), category);
+        }
+        assert_eq!(codec.encode("O-"), Ok(1));
+
+        assert_eq!(codec.encode("C+"), Err(CodecError::UnknownCategory("C+".to_string())));
+        for value in [0, 9, -1, i64::MIN] {
+            assert_eq!(codec.decode(value), Err(CodecError::UnknownValue(value)));
+        }
+        assert_eq!(
+            CategoricalCodec::new(&["A", "B", "A"]),
+            Err(CodecError::DuplicateCategory("A".to_string()))
+        );
+    }
+}
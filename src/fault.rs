@@ -0,0 +1,81 @@
+// Make chosen operations fail on demand, for resilience tests
+//
+// Only built with the "fault-injection" feature; without it the hooks compile
+// to nothing. `fail_nth("multiply", 3, err)` makes the third "multiply" from
+// now return `err` instead of running; each armed fault fires once. Injectors
+// belong to one Evaluator, Decryptor or server, never to the process, so tests
+// running in parallel don't trip each other's faults.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+struct Fault<E> {
+    remaining: usize,
+    error: E,
+}
+
+pub struct FaultInjector<E> {
+    faults: Mutex<HashMap<String, Fault<E>>>,
+}
+
+impl<E> Default for FaultInjector<E> {
+    fn default() -> Self {
+        FaultInjector { faults: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<E> FaultInjector<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail the `n`th (1-based) `operation` from now with `error`, replacing
+    /// any fault already armed for it
+    pub fn fail_nth(&self, operation: &str, n: usize, error: E) {
+        assert!(n > 0, "n is 1-based");
+        self.faults
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(operation.to_string(), Fault { remaining: n, error });
+    }
+
+    /// Disarm every fault
+    pub fn clear(&self) {
+        self.faults.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    /// Count one `operation`; the error if this is the one armed to fail
+    pub fn check(&self, operation: &str) -> Result<(), E> {
+        let mut faults = self.faults.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(fault) = faults.get_mut(operation) else {
+            return Ok(());
+        };
+        fault.remaining -= 1;
+        if fault.remaining > 0 {
+            return Ok(());
+        }
+        Err(faults.remove(operation).map(|fault| fault.error).expect("fault was just found"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fails_only_the_nth_call() {
+        let faults = FaultInjector::new();
+        faults.fail_nth("decrypt", 3, "boom");
+
+        assert_eq!(faults.check("decrypt"), Ok(()));
+        assert_eq!(faults.check("encrypt"), Ok(()));
+        assert_eq!(faults.check("decrypt"), Ok(()));
+        assert_eq!(faults.check("decrypt"), Err("boom"));
+        // Fired once, then disarmed
+        assert_eq!(faults.check("decrypt"), Ok(()));
+
+        faults.fail_nth("add", 1, "never");
+        faults.clear();
+        assert_eq!(faults.check("add"), Ok(()));
+    }
+}
@@ -0,0 +1,171 @@
+// Startup self-test: one tiny encrypt/decrypt round trip per compiled-in library
+//
+// A mismatched shared library (e.g. the wrong libseal-4.1 on the rpath) otherwise
+// only shows up deep inside the first real operation, with a confusing message.
+
+use std::error::Error;
+
+/// Outcome of one library's self-test
+pub type SelfTestResult = std::result::Result<(), Box<dyn Error + Send + Sync>>;
+
+#[cfg(any(feature = "seal", feature = "helib", feature = "openfhe"))]
+const PROBE: i64 = 42;
+
+type Check = (&'static str, fn() -> SelfTestResult);
+type Version = (&'static str, fn() -> String);
+
+/// For each compiled-in library: create a small context, encrypt 42 and decrypt it
+pub fn self_test() -> Vec<(String, SelfTestResult)> {
+    let checks: Vec<Check> = vec![
+        #[cfg(feature = "seal")]
+        ("SEAL", seal_round_trip),
+        #[cfg(feature = "helib")]
+        ("HElib", helib_round_trip),
+        #[cfg(feature = "openfhe")]
+        ("OpenFHE", openfhe_round_trip),
+    ];
+
+    checks
+        .into_iter()
+        .map(|(library, check)| (library.to_string(), check()))
+        .collect()
+}
+
+/// (library, version) for each compiled-in library, as built into its wrapper
+pub fn library_versions() -> Vec<(String, String)> {
+    let versions: Vec<Version> = vec![
+        #[cfg(feature = "seal")]
+        ("SEAL", crate::seal::seal_version),
+        #[cfg(feature = "helib")]
+        ("HElib", crate::helib::helib_version),
+        #[cfg(feature = "openfhe")]
+        ("OpenFHE", crate::open_fhe_lib::openfhe_version),
+    ];
+
+    versions
+        .into_iter()
+        .map(|(library, version)| (library.to_string(), version()))
+        .collect()
+}
+
+/// (library, error) for each backend this build was made without: the error
+/// its stub module returns from every constructor
+pub fn not_compiled() -> Vec<(String, String)> {
+    let missing: Vec<(&str, String)> = vec![
+        #[cfg(not(feature = "helib"))]
+        ("HElib", crate::helib::HElibError::NotCompiled.to_string()),
+        #[cfg(not(feature = "openfhe"))]
+        ("OpenFHE", crate::open_fhe_lib::OpenFHEError::NotCompiled.to_string()),
+    ];
+
+    missing
+        .into_iter()
+        .map(|(library, error)| (library.to_string(), error))
+        .collect()
+}
+
+/// Run `self_test` and print one line per library. Returns an error naming the
+/// libraries that failed.
+pub fn print_self_test() -> std::result::Result<(), String> {
+    let mut failed = Vec::new();
+
+    for (library, outcome) in self_test() {
+        match outcome {
+            Ok(()) => println!("  ✓ {} self-test passed", library),
+            Err(e) => {
+                println!("  ✗ {} self-test FAILED: {}", library, e);
+                failed.push(library);
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("self-test failed for {} (check the installed library versions)", failed.join(", ")))
+    }
+}
+
+#[cfg(any(feature = "seal", feature = "helib", feature = "openfhe"))]
+fn check_probe(library: &str, decrypted: i64) -> SelfTestResult {
+    if decrypted != PROBE {
+        return Err(format!("{} decrypted {} to {}", library, PROBE, decrypted).into());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "seal")]
+fn seal_round_trip() -> SelfTestResult {
+    use crate::seal::{BatchEncoder, Context, ContextPreset, Decryptor, Encryptor};
+
+    let context = Context::from_preset(ContextPreset::FastLowDepth)?;
+    let encoder = BatchEncoder::new(&context)?;
+    let cipher = Encryptor::new(&context)?.encrypt(&encoder.encode(&[PROBE])?)?;
+    let decoded = encoder.decode(&Decryptor::new(&context)?.decrypt(&cipher)?)?;
+
+    check_probe("SEAL", decoded.first().copied().unwrap_or_default())
+}
+
+#[cfg(feature = "helib")]
+fn helib_round_trip() -> SelfTestResult {
+    use crate::helib::{HEContext, HEPlaintext, HESecretKey};
+
+    // p = 257 so that 42 survives (the benchmarks use p = 2)
+    let context = HEContext::new(4095, 257, 1)?;
+    let secret_key = HESecretKey::generate(&context)?;
+    let cipher = secret_key.public_key()?.encrypt(&HEPlaintext::new(&context, PROBE)?)?;
+
+    check_probe("HElib", secret_key.decrypt(&cipher)?.value())
+}
+
+#[cfg(feature = "openfhe")]
+fn openfhe_round_trip() -> SelfTestResult {
+    use crate::open_fhe_lib::{OpenFHECiphertext, OpenFHEContext, OpenFHEKeyPair, OpenFHEPlaintext};
+
+    let context = OpenFHEContext::new_bfv(65537, 1)?;
+    let keypair = OpenFHEKeyPair::generate(&context)?;
+    let plain = OpenFHEPlaintext::from_vec(&context, &[PROBE])?;
+    let cipher = OpenFHECiphertext::encrypt(&context, &keypair, &plain)?;
+    let decoded = cipher.decrypt(&context, &keypair)?.to_vec()?;
+
+    check_probe("OpenFHE", decoded.first().copied().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_linked_library_passes_self_test() {
+        let results = self_test();
+        let expected = [cfg!(feature = "seal"), cfg!(feature = "helib"), cfg!(feature = "openfhe")]
+            .iter()
+            .filter(|&&enabled| enabled)
+            .count();
+        assert_eq!(results.len(), expected);
+
+        for (library, outcome) in results {
+            assert!(outcome.is_ok(), "{} self-test failed: {:?}", library, outcome.err());
+        }
+    }
+
+    #[test]
+    fn test_left_out_libraries_are_reported_not_compiled() {
+        let missing = not_compiled();
+        assert_eq!(missing.len() + self_test().len(), 2 + usize::from(cfg!(feature = "seal")));
+        for (library, error) in missing {
+            assert!(error.contains("not compiled in"), "{}: {}", library, error);
+        }
+    }
+
+    #[test]
+    fn test_every_linked_library_reports_a_version() {
+        let versions = library_versions();
+        assert_eq!(versions.len(), self_test().len());
+        for (library, version) in versions {
+            // "major.minor..." at least
+            assert!(version.split('.').count() >= 2, "{} version {:?}", library, version);
+            assert!(version.split('.').next().unwrap().parse::<u32>().is_ok(), "{} version {:?}", library, version);
+        }
+    }
+}
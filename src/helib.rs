@@ -11,6 +11,8 @@ pub enum HElibError {
     EncryptionFailed,
     DecryptionFailed,
     OperationFailed,
+    /// Gave up waiting on a call (see `watchdog::helib_with_deadline`)
+    TimedOut(std::time::Duration),
 }
 
 // Implement Display for HElibError
@@ -22,6 +24,7 @@ impl std::fmt::Display for HElibError {
             HElibError::EncryptionFailed => write!(f, "Encryption operation failed"),
             HElibError::DecryptionFailed => write!(f, "Decryption operation failed"),
             HElibError::OperationFailed => write!(f, "HElib operation failed"),
+            HElibError::TimedOut(limit) => write!(f, "HElib call did not finish within {:?}", limit),
         }
     }
 }
@@ -31,6 +34,11 @@ impl std::error::Error for HElibError {}
 
 pub type Result<T> = std::result::Result<T, HElibError>;
 
+/// Version of the HElib library the wrapper was built against, e.g. "2.3.0"
+pub fn helib_version() -> String {
+    unsafe { std::ffi::CStr::from_ptr(ffi!(helib_bindings::helib_version())) }.to_string_lossy().into_owned()
+}
+
 // Context
 pub struct HEContext {
     ptr: NonNull<helib_bindings::HElibContext>,
@@ -45,19 +53,72 @@ impl HEContext {
     /// - r: Lifting (typically 1)
     pub fn new(m: u64, p: u64, r: u64) -> Result<Self> {
         let ptr = unsafe {
-            helib_bindings::helib_create_context(m, p, r)
+            ffi!(helib_bindings::helib_create_context(m, p, r))
         };
         
         NonNull::new(ptr)
             .map(|ptr| HEContext { ptr })
             .ok_or(HElibError::NullPointer)
     }
+
+    /// The (m, p, r) this context was built from and what HElib derived from them
+    pub fn info(&self) -> Result<HEContextInfo> {
+        let (mut m, mut p, mut r) = (0, 0, 0);
+        let mut nslots = 0;
+        let mut security_bits = 0.0;
+        let mut num_primes = 0;
+
+        let status = unsafe {
+            ffi!(helib_bindings::helib_context_info(
+                self.ptr.as_ptr(),
+                &mut m,
+                &mut p,
+                &mut r,
+                &mut nslots,
+                &mut security_bits,
+                &mut num_primes,
+            ))
+        };
+        if status != 0 {
+            return Err(HElibError::OperationFailed);
+        }
+
+        Ok(HEContextInfo {
+            m,
+            p,
+            r,
+            nslots: nslots as usize,
+            security_bits,
+            num_primes: num_primes as usize,
+        })
+    }
 }
 
+/// HElib context parameters, comparable to SEAL's poly modulus degree / slot count
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HEContextInfo {
+    /// Cyclotomic index; the ring has degree phi(m)
+    pub m: u64,
+    /// Plaintext prime, raised to the power `r`
+    pub p: u64,
+    pub r: u64,
+    /// Values one plaintext packs with SIMD encoding: phi(m) / ord_m(p)
+    pub nslots: usize,
+    /// HElib's estimate of the security level in bits
+    pub security_bits: f64,
+    /// Primes in the modulus chain (including the special primes for key switching)
+    pub num_primes: usize,
+}
+
+// SAFETY: an HElib Context is only read once constructed (encryption and key
+// generation take it by const reference), so it can be shared between threads
+unsafe impl Send for HEContext {}
+unsafe impl Sync for HEContext {}
+
 impl Drop for HEContext {
     fn drop(&mut self) {
         unsafe {
-            helib_bindings::helib_destroy_context(self.ptr.as_ptr());
+            ffi!(helib_bindings::helib_destroy_context(self.ptr.as_ptr()));
         }
     }
 }
@@ -70,7 +131,7 @@ pub struct HESecretKey {
 impl HESecretKey {
     pub fn generate(context: &HEContext) -> Result<Self> {
         let ptr = unsafe {
-            helib_bindings::helib_generate_secret_key(context.ptr.as_ptr())
+            ffi!(helib_bindings::helib_generate_secret_key(context.ptr.as_ptr()))
         };
         
         NonNull::new(ptr)
@@ -80,7 +141,7 @@ impl HESecretKey {
     
     pub fn public_key(&self) -> Result<HEPublicKey> {
         let ptr = unsafe {
-            helib_bindings::helib_get_public_key(self.ptr.as_ptr())
+            ffi!(helib_bindings::helib_get_public_key(self.ptr.as_ptr()))
         };
         
         NonNull::new(ptr)
@@ -90,10 +151,10 @@ impl HESecretKey {
     
     pub fn decrypt(&self, ciphertext: &HECiphertext) -> Result<HEPlaintext> {
         let ptr = unsafe {
-            helib_bindings::helib_decrypt(
+            ffi!(helib_bindings::helib_decrypt(
                 self.ptr.as_ptr(),
                 ciphertext.ptr.as_ptr(),
-            )
+            ))
         };
         
         NonNull::new(ptr)
@@ -103,18 +164,64 @@ impl HESecretKey {
     
     pub fn noise_budget(&self, ciphertext: &HECiphertext) -> i32 {
         unsafe {
-            helib_bindings::helib_noise_budget(
+            ffi!(helib_bindings::helib_noise_budget(
                 self.ptr.as_ptr(),
                 ciphertext.ptr.as_ptr(),
-            )
+            ))
         }
     }
+    
+    /// Noise budget together with the ciphertext's position in the modulus chain
+    pub fn ciphertext_stats(&self, ciphertext: &HECiphertext) -> Result<CiphertextStats> {
+        let mut noise_budget_bits = 0;
+        let mut level = 0;
+        let mut total_levels = 0;
+        let mut capacity = 0.0;
+        
+        let status = unsafe {
+            ffi!(helib_bindings::helib_ciphertext_stats(
+                self.ptr.as_ptr(),
+                ciphertext.ptr.as_ptr(),
+                &mut noise_budget_bits,
+                &mut level,
+                &mut total_levels,
+                &mut capacity,
+            ))
+        };
+        if status != 0 {
+            return Err(HElibError::OperationFailed);
+        }
+        
+        Ok(CiphertextStats {
+            noise_budget_bits,
+            level: level as usize,
+            total_levels: total_levels as usize,
+            capacity,
+        })
+    }
+}
+
+/// Depth metrics of one ciphertext, comparable to SEAL's noise budget / chain index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CiphertextStats {
+    /// Bits of noise budget left (same value as `noise_budget`)
+    pub noise_budget_bits: i32,
+    /// Primes still in the ciphertext modulus; drops as multiplications consume depth
+    pub level: usize,
+    /// Primes in the full chain (the level of a fresh ciphertext)
+    pub total_levels: usize,
+    /// Bit size of the current ciphertext modulus
+    pub capacity: f64,
 }
 
+// SAFETY: the key owns its C++ object outright (besides the pointer into its
+// context, which is Sync); handing it to another thread moves that ownership
+unsafe impl Send for HESecretKey {}
+
 impl Drop for HESecretKey {
     fn drop(&mut self) {
         unsafe {
-            helib_bindings::helib_destroy_secret_key(self.ptr.as_ptr());
+            ffi!(helib_bindings::helib_destroy_secret_key(self.ptr.as_ptr()));
         }
     }
 }
@@ -127,10 +234,10 @@ pub struct HEPublicKey {
 impl HEPublicKey {
     pub fn encrypt(&self, plaintext: &HEPlaintext) -> Result<HECiphertext> {
         let ptr = unsafe {
-            helib_bindings::helib_encrypt(
+            ffi!(helib_bindings::helib_encrypt(
                 self.ptr.as_ptr(),
                 plaintext.ptr.as_ptr(),
-            )
+            ))
         };
         
         NonNull::new(ptr)
@@ -139,10 +246,13 @@ impl HEPublicKey {
     }
 }
 
+// SAFETY: as for HESecretKey (the PubKey it points to lives in the secret key)
+unsafe impl Send for HEPublicKey {}
+
 impl Drop for HEPublicKey {
     fn drop(&mut self) {
         unsafe {
-            helib_bindings::helib_destroy_public_key(self.ptr.as_ptr());
+            ffi!(helib_bindings::helib_destroy_public_key(self.ptr.as_ptr()));
         }
     }
 }
@@ -155,10 +265,10 @@ pub struct HEPlaintext {
 impl HEPlaintext {
     pub fn new(context: &HEContext, value: i64) -> Result<Self> {
         let ptr = unsafe {
-            helib_bindings::helib_create_plaintext(
+            ffi!(helib_bindings::helib_create_plaintext(
                 context.ptr.as_ptr(),
                 value,
-            )
+            ))
         };
         
         NonNull::new(ptr)
@@ -168,7 +278,7 @@ impl HEPlaintext {
     
     pub fn value(&self) -> i64 {
         unsafe {
-            helib_bindings::helib_plaintext_to_long(self.ptr.as_ptr())
+            ffi!(helib_bindings::helib_plaintext_to_long(self.ptr.as_ptr()))
         }
     }
 }
@@ -176,12 +286,21 @@ impl HEPlaintext {
 impl Drop for HEPlaintext {
     fn drop(&mut self) {
         unsafe {
-            helib_bindings::helib_destroy_plaintext(self.ptr.as_ptr());
+            ffi!(helib_bindings::helib_destroy_plaintext(self.ptr.as_ptr()));
         }
     }
 }
 
 // Ciphertext
+//
+// Operations need no evaluator: each HElib Ctxt points at its Context and the
+// public key it was encrypted under, and that is where the evaluation state
+// lives. The Context builds its EncryptedArray (slot encoding) and modulus
+// chain once, in HEContext::new; the key-switching matrices for relinearization
+// are generated once, in HESecretKey::generate. An add or multiply reuses both,
+// so the setup worth caching is those two calls, not anything per operation.
+// The server keeps them in its KeyCache; examples/helib_setup_cache.rs compares
+// a multiply with and without that cache.
 pub struct HECiphertext {
     ptr: NonNull<helib_bindings::HElibCiphertext>,
 }
@@ -190,10 +309,10 @@ impl HECiphertext {
     /// Homomorphic addition
     pub fn add(&self, other: &HECiphertext) -> Result<HECiphertext> {
         let ptr = unsafe {
-            helib_bindings::helib_add(
+            ffi!(helib_bindings::helib_add(
                 self.ptr.as_ptr(),
                 other.ptr.as_ptr(),
-            )
+            ))
         };
         
         NonNull::new(ptr)
@@ -204,10 +323,10 @@ impl HECiphertext {
     /// Homomorphic multiplication
     pub fn multiply(&self, other: &HECiphertext) -> Result<HECiphertext> {
         let ptr = unsafe {
-            helib_bindings::helib_multiply(
+            ffi!(helib_bindings::helib_multiply(
                 self.ptr.as_ptr(),
                 other.ptr.as_ptr(),
-            )
+            ))
         };
         
         NonNull::new(ptr)
@@ -218,10 +337,46 @@ impl HECiphertext {
     /// Homomorphic subtraction
     pub fn subtract(&self, other: &HECiphertext) -> Result<HECiphertext> {
         let ptr = unsafe {
-            helib_bindings::helib_subtract(
+            ffi!(helib_bindings::helib_subtract(
                 self.ptr.as_ptr(),
                 other.ptr.as_ptr(),
-            )
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| HECiphertext { ptr })
+            .ok_or(HElibError::OperationFailed)
+    }
+    
+    /// Get the total size in bytes when serialized
+    pub fn byte_count(&self) -> usize {
+        unsafe {
+            ffi!(helib_bindings::helib_ciphertext_byte_count(self.ptr.as_ptr()))
+        }
+    }
+    
+    /// Homomorphic squaring (uses less noise budget than multiply(self, self))
+    pub fn square(&self) -> Result<HECiphertext> {
+        let ptr = unsafe {
+            ffi!(helib_bindings::helib_square(self.ptr.as_ptr()))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| HECiphertext { ptr })
+            .ok_or(HElibError::OperationFailed)
+    }
+    
+    /// Homomorphic exponentiation via square-and-multiply (depth ~log2(exponent))
+    /// 
+    /// exponent must be at least 1. The plaintext modulus p has to be large enough
+    /// to hold the result, otherwise it wraps around mod p.
+    pub fn power(&self, exponent: u32) -> Result<HECiphertext> {
+        if exponent == 0 {
+            return Err(HElibError::InvalidParameter);
+        }
+        
+        let ptr = unsafe {
+            ffi!(helib_bindings::helib_power(self.ptr.as_ptr(), exponent as std::os::raw::c_ulong))
         };
         
         NonNull::new(ptr)
@@ -233,7 +388,94 @@ impl HECiphertext {
 impl Drop for HECiphertext {
     fn drop(&mut self) {
         unsafe {
-            helib_bindings::helib_destroy_ciphertext(self.ptr.as_ptr());
+            ffi!(helib_bindings::helib_destroy_ciphertext(self.ptr.as_ptr()));
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // p = 257 so small powers don't wrap around
+    fn setup() -> (HEContext, HESecretKey) {
+        let context = HEContext::new(4095, 257, 1).unwrap();
+        let secret_key = HESecretKey::generate(&context).unwrap();
+        (context, secret_key)
+    }
+
+    #[test]
+    fn test_power() {
+        let (context, secret_key) = setup();
+        let public_key = secret_key.public_key().unwrap();
+
+        let three = public_key.encrypt(&HEPlaintext::new(&context, 3).unwrap()).unwrap();
+        let cubed = three.power(3).unwrap();
+
+        assert_eq!(secret_key.decrypt(&cubed).unwrap().value(), 27);
+        assert!(matches!(three.power(0), Err(HElibError::InvalidParameter)));
+    }
+
+    #[test]
+    fn test_square_noise_budget() {
+        let (context, secret_key) = setup();
+        let public_key = secret_key.public_key().unwrap();
+
+        let five = public_key.encrypt(&HEPlaintext::new(&context, 5).unwrap()).unwrap();
+        let squared = five.square().unwrap();
+        let multiplied = five.multiply(&five).unwrap();
+
+        assert_eq!(secret_key.decrypt(&squared).unwrap().value(), 25);
+        assert!(secret_key.noise_budget(&squared) >= secret_key.noise_budget(&multiplied));
+    }
+
+    #[test]
+    fn test_context_info_reports_slots() {
+        let (context, _) = setup();
+        let info = context.info().unwrap();
+        assert_eq!((info.m, info.p, info.r), (4095, 257, 1));
+
+        // m = 4095 = 9 * 5 * 7 * 13, so phi(m) = 6 * 4 * 6 * 12 = 1728. 257 has
+        // order lcm(6, 4, 6, 6) = 12 mod m, which leaves 1728 / 12 slots.
+        let phi: u64 = (1..4095).filter(|&k| gcd(k, 4095) == 1).count() as u64;
+        let order = (1..).find(|&k| mod_pow(257, k, 4095) == 1).unwrap();
+        assert_eq!(info.nslots as u64, phi / order);
+        assert_eq!(info.nslots, 144);
+
+        assert!(info.security_bits > 0.0);
+        assert!(info.num_primes > 0);
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+        (0..exponent).fold(1, |acc, _| acc * base % modulus)
+    }
+
+    #[test]
+    fn test_ciphertext_stats_track_depth() {
+        let (context, secret_key) = setup();
+        let public_key = secret_key.public_key().unwrap();
+
+        let mut cipher = public_key.encrypt(&HEPlaintext::new(&context, 2).unwrap()).unwrap();
+        let fresh = secret_key.ciphertext_stats(&cipher).unwrap();
+        assert_eq!(fresh.level, fresh.total_levels);
+        assert_eq!(fresh.noise_budget_bits, secret_key.noise_budget(&cipher));
+
+        // 2 -> 4 -> 16 -> 256
+        let mut previous = fresh;
+        for _ in 0..3 {
+            cipher = cipher.square().unwrap();
+            let stats = secret_key.ciphertext_stats(&cipher).unwrap();
+
+            assert!(stats.noise_budget_bits < previous.noise_budget_bits);
+            assert!(stats.level <= previous.level);
+            assert!(stats.capacity <= previous.capacity);
+            previous = stats;
+        }
+        assert!(previous.level < fresh.level);
+        assert_eq!(secret_key.decrypt(&cipher).unwrap().value(), 256);
+    }
+}
@@ -1,6 +1,6 @@
 //! Raw FFI bindings to HElib C wrapper
 
-use std::os::raw::c_int;
+use std::os::raw::{c_char, c_int};
 
 // Opaque Types
 #[repr(C)]
@@ -38,6 +38,16 @@ unsafe extern "C" {
     ) -> *mut HElibContext;
     
     pub fn helib_destroy_context(ctx: *mut HElibContext);
+
+    pub fn helib_context_info(
+        ctx: *mut HElibContext,
+        m: *mut std::os::raw::c_ulong,
+        p: *mut std::os::raw::c_ulong,
+        r: *mut std::os::raw::c_ulong,
+        nslots: *mut std::os::raw::c_long,
+        security_bits: *mut f64,
+        num_primes: *mut std::os::raw::c_long,
+    ) -> c_int;
     
     // Key management
     pub fn helib_generate_secret_key(ctx: *mut HElibContext) -> *mut HElibSecretKey;
@@ -84,9 +94,30 @@ unsafe extern "C" {
         b: *mut HElibCiphertext,
     ) -> *mut HElibCiphertext;
     
+    pub fn helib_square(a: *mut HElibCiphertext) -> *mut HElibCiphertext;
+    
+    pub fn helib_power(
+        a: *mut HElibCiphertext,
+        exponent: std::os::raw::c_ulong,
+    ) -> *mut HElibCiphertext;
+    
     // Utilities
+    pub fn helib_ciphertext_byte_count(cipher: *mut HElibCiphertext) -> usize;
+    
     pub fn helib_noise_budget(
         sk: *mut HElibSecretKey,
         cipher: *mut HElibCiphertext,
     ) -> c_int;
+
+    pub fn helib_ciphertext_stats(
+        sk: *mut HElibSecretKey,
+        cipher: *mut HElibCiphertext,
+        noise_budget_bits: *mut c_int,
+        level: *mut std::os::raw::c_long,
+        total_levels: *mut std::os::raw::c_long,
+        capacity_bits: *mut f64,
+    ) -> c_int;
+
+    // Library version (static string)
+    pub fn helib_version() -> *const c_char;
 }
\ No newline at end of file
@@ -0,0 +1,162 @@
+//! Stand-in for the HElib wrapper when the crate is built without `--features helib`
+//!
+//! Same names and signatures as `helib.rs`, so code written against HElib still
+//! compiles in a SEAL-only build. The constructors fail at runtime with
+//! `HElibError::NotCompiled`; nothing else can be reached, since no value of
+//! these types can exist.
+
+use std::convert::Infallible;
+
+#[derive(Debug)]
+pub enum HElibError {
+    NotCompiled,
+}
+
+impl std::fmt::Display for HElibError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HElibError::NotCompiled => write!(f, "HElib support not compiled in; rebuild with --features helib"),
+        }
+    }
+}
+
+impl std::error::Error for HElibError {}
+
+pub type Result<T> = std::result::Result<T, HElibError>;
+
+/// Empty: no HElib is linked into this build
+pub fn helib_version() -> String {
+    String::new()
+}
+
+pub struct HEContext {
+    never: Infallible,
+}
+
+impl HEContext {
+    pub fn new(_m: u64, _p: u64, _r: u64) -> Result<Self> {
+        Err(HElibError::NotCompiled)
+    }
+
+    pub fn new_with_deadline(_m: u64, _p: u64, _r: u64, _limit: std::time::Duration) -> Result<Self> {
+        Err(HElibError::NotCompiled)
+    }
+
+    pub fn info(&self) -> Result<HEContextInfo> {
+        match self.never {}
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HEContextInfo {
+    pub m: u64,
+    pub p: u64,
+    pub r: u64,
+    pub nslots: usize,
+    pub security_bits: f64,
+    pub num_primes: usize,
+}
+
+pub struct HESecretKey {
+    never: Infallible,
+}
+
+impl HESecretKey {
+    pub fn generate(_context: &HEContext) -> Result<Self> {
+        Err(HElibError::NotCompiled)
+    }
+
+    pub fn generate_with_deadline(_context: &std::sync::Arc<HEContext>, _limit: std::time::Duration) -> Result<Self> {
+        Err(HElibError::NotCompiled)
+    }
+
+    pub fn public_key(&self) -> Result<HEPublicKey> {
+        match self.never {}
+    }
+
+    pub fn decrypt(&self, _ciphertext: &HECiphertext) -> Result<HEPlaintext> {
+        match self.never {}
+    }
+
+    pub fn noise_budget(&self, _ciphertext: &HECiphertext) -> i32 {
+        match self.never {}
+    }
+
+    pub fn ciphertext_stats(&self, _ciphertext: &HECiphertext) -> Result<CiphertextStats> {
+        match self.never {}
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CiphertextStats {
+    pub noise_budget_bits: i32,
+    pub level: usize,
+    pub total_levels: usize,
+    pub capacity: f64,
+}
+
+pub struct HEPublicKey {
+    never: Infallible,
+}
+
+impl HEPublicKey {
+    pub fn encrypt(&self, _plaintext: &HEPlaintext) -> Result<HECiphertext> {
+        match self.never {}
+    }
+}
+
+pub struct HEPlaintext {
+    never: Infallible,
+}
+
+impl HEPlaintext {
+    pub fn new(_context: &HEContext, _value: i64) -> Result<Self> {
+        Err(HElibError::NotCompiled)
+    }
+
+    pub fn value(&self) -> i64 {
+        match self.never {}
+    }
+}
+
+pub struct HECiphertext {
+    never: Infallible,
+}
+
+impl HECiphertext {
+    pub fn add(&self, _other: &HECiphertext) -> Result<HECiphertext> {
+        match self.never {}
+    }
+
+    pub fn multiply(&self, _other: &HECiphertext) -> Result<HECiphertext> {
+        match self.never {}
+    }
+
+    pub fn subtract(&self, _other: &HECiphertext) -> Result<HECiphertext> {
+        match self.never {}
+    }
+
+    pub fn byte_count(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn square(&self) -> Result<HECiphertext> {
+        match self.never {}
+    }
+
+    pub fn power(&self, _exponent: u32) -> Result<HECiphertext> {
+        match self.never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors_report_helib_not_compiled() {
+        let err = HEContext::new(4095, 2, 1).err().unwrap();
+        assert_eq!(err.to_string(), "HElib support not compiled in; rebuild with --features helib");
+        assert!(helib_version().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! Integrity tags for serialized SEAL ciphertexts
+//!
+//! HE ciphertexts are malleable by design: a flipped bit doesn't make decryption
+//! fail, it just decrypts to garbage. `AuthenticatedCiphertext` keeps the
+//! serialized ciphertext together with an HMAC-SHA256 tag under a MAC key the
+//! parties share (e.g. one per session), and refuses to load anything whose tag
+//! doesn't match.
+
+use crate::seal::{check_same_context, Ciphertext, Context, Result, SealError};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length of the HMAC-SHA256 tag in bytes
+pub const TAG_LEN: usize = 32;
+
+pub struct AuthenticatedCiphertext<'a> {
+    context: &'a Context,
+    data: Vec<u8>,
+    tag: [u8; TAG_LEN],
+}
+
+impl<'a> AuthenticatedCiphertext<'a> {
+    /// Serialize `ciphertext` and tag it with `mac_key`
+    pub fn new(context: &'a Context, ciphertext: &Ciphertext, mac_key: &[u8]) -> Result<Self> {
+        check_same_context(context, &[ciphertext])?;
+
+        let data = ciphertext.to_bytes()?;
+        let tag = new_mac(mac_key)?.chain_update(&data).finalize().into_bytes().into();
+
+        Ok(AuthenticatedCiphertext { context, data, tag })
+    }
+
+    /// Parse the output of `to_bytes` (the tag is only checked by `verify_and_load`)
+    pub fn from_bytes(context: &'a Context, bytes: &[u8]) -> Result<Self> {
+        if bytes.len() <= TAG_LEN {
+            return Err(SealError::InvalidParameter(format!(
+                "authenticated ciphertext needs more than {} bytes, got {}",
+                TAG_LEN,
+                bytes.len()
+            )));
+        }
+
+        let (data, tag) = bytes.split_at(bytes.len() - TAG_LEN);
+        Ok(AuthenticatedCiphertext {
+            context,
+            data: data.to_vec(),
+            tag: tag.try_into().expect("split at TAG_LEN"),
+        })
+    }
+
+    /// Serialized ciphertext followed by the tag
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.data.len() + TAG_LEN);
+        bytes.extend_from_slice(&self.data);
+        bytes.extend_from_slice(&self.tag);
+        bytes
+    }
+
+    /// Check the tag (in constant time), then deserialize the ciphertext
+    ///
+    /// Fails with `IntegrityCheckFailed` if the data or tag was modified or the key
+    /// is wrong; nothing is handed to SEAL in that case.
+    pub fn verify_and_load(&self, mac_key: &[u8]) -> Result<Ciphertext> {
+        new_mac(mac_key)?
+            .chain_update(&self.data)
+            .verify_slice(&self.tag)
+            .map_err(|_| SealError::IntegrityCheckFailed)?;
+
+        Ciphertext::from_bytes(self.context, &self.data)
+    }
+}
+
+fn new_mac(mac_key: &[u8]) -> Result<HmacSha256> {
+    if mac_key.is_empty() {
+        return Err(SealError::InvalidParameter("MAC key must not be empty".to_string()));
+    }
+    HmacSha256::new_from_slice(mac_key).map_err(|e| SealError::InvalidParameter(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::{BatchEncoder, ContextPreset, Decryptor, Encryptor};
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let ctx = Context::from_preset(ContextPreset::FastLowDepth).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let key = b"session mac key";
+
+        let cipher = encryptor.encrypt(&encoder.encode(&[7, 8, 9]).unwrap()).unwrap();
+        let bytes = AuthenticatedCiphertext::new(&ctx, &cipher, key).unwrap().to_bytes();
+
+        let loaded = AuthenticatedCiphertext::from_bytes(&ctx, &bytes).unwrap().verify_and_load(key).unwrap();
+        let decoded = encoder.decode(&decryptor.decrypt(&loaded).unwrap()).unwrap();
+        assert_eq!(&decoded[..3], &[7, 8, 9]);
+
+        // One flipped bit in the ciphertext body
+        let mut tampered = bytes.clone();
+        tampered[bytes.len() / 2] ^= 0x01;
+        let result = AuthenticatedCiphertext::from_bytes(&ctx, &tampered).unwrap().verify_and_load(key);
+        assert!(matches!(result, Err(SealError::IntegrityCheckFailed)));
+
+        // Wrong key
+        let result = AuthenticatedCiphertext::from_bytes(&ctx, &bytes).unwrap().verify_and_load(b"other key");
+        assert!(matches!(result, Err(SealError::IntegrityCheckFailed)));
+
+        assert!(AuthenticatedCiphertext::from_bytes(&ctx, &bytes[..TAG_LEN]).is_err());
+    }
+}
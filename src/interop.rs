@@ -0,0 +1,215 @@
+// Run one BFV computation through several backends and compare the results
+//
+// SEAL and OpenFHE both implement BFV: on the same inputs and plaintext modulus
+// they must decrypt to exactly the same slots. CrossBackendHarness runs an
+// operation sequence through every backend it was given and checks each result
+// against the first backend's, slot by slot (with ResultVerifier). Backends may
+// be given different plaintext moduli; a test uses that to make sure a real
+// disagreement gets reported.
+
+use crate::verify::{ResultVerifier, VerificationError};
+use std::fmt;
+
+/// Inputs every backend starts from: the computation begins with `input` and
+/// combines the running result with `operand` once per operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlainData {
+    pub input: Vec<i64>,
+    pub operand: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossOp {
+    Add,
+    Subtract,
+    /// Relinearized where the backend needs it
+    Multiply,
+}
+
+// Encrypts, runs the operations and returns the decrypted first `input.len()` slots
+type Backend = Box<dyn Fn(&PlainData, &[CrossOp]) -> Result<Vec<i64>, String>>;
+
+#[derive(Default)]
+pub struct CrossBackendHarness {
+    backends: Vec<(String, Backend)>,
+}
+
+impl CrossBackendHarness {
+    /// No backends yet; add them with the `with_*` methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// SEAL BFV with batching (`plain_modulus` must be a batching prime)
+    #[cfg(feature = "seal")]
+    pub fn with_seal_bfv(mut self, poly_modulus_degree: u64, plain_modulus: u64) -> Self {
+        self.backends.push((
+            format!("SEAL BFV (t = {})", plain_modulus),
+            Box::new(move |data, ops| {
+                run_seal(poly_modulus_degree, plain_modulus, data, ops).map_err(|e| e.to_string())
+            }),
+        ));
+        self
+    }
+
+    /// OpenFHE BFV; `multiplicative_depth` has to cover the `Multiply`s run
+    #[cfg(feature = "openfhe")]
+    pub fn with_openfhe_bfv(mut self, plain_modulus: u64, multiplicative_depth: u32) -> Self {
+        self.backends.push((
+            format!("OpenFHE BFV (t = {})", plain_modulus),
+            Box::new(move |data, ops| {
+                run_openfhe(plain_modulus, multiplicative_depth, data, ops).map_err(|e| e.to_string())
+            }),
+        ));
+        self
+    }
+
+    /// Names of the backends, in the order they run
+    pub fn backends(&self) -> Vec<&str> {
+        self.backends.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Run `ops` on `data` through every backend; the result they all agree on
+    ///
+    /// Every backend is compared with the first one, exactly.
+    pub fn run(&self, data: &PlainData, ops: &[CrossOp]) -> Result<Vec<i64>, CrossBackendError> {
+        if data.input.is_empty() || data.input.len() != data.operand.len() {
+            return Err(CrossBackendError::InvalidInput(format!(
+                "input and operand must be non-empty and the same length, got {} and {}",
+                data.input.len(),
+                data.operand.len()
+            )));
+        }
+        let Some(((reference_name, reference_backend), others)) = self.backends.split_first() else {
+            return Err(CrossBackendError::NoBackends);
+        };
+
+        let failed = |backend: &str, message: String| CrossBackendError::BackendFailed {
+            backend: backend.to_string(),
+            message,
+        };
+        let expected = reference_backend(data, ops).map_err(|e| failed(reference_name, e))?;
+        for (name, backend) in others {
+            let got = backend(data, ops).map_err(|e| failed(name, e))?;
+            ResultVerifier::exact()
+                .verify(&got, || expected.clone())
+                .map_err(|error| CrossBackendError::Disagreement {
+                    reference: reference_name.clone(),
+                    backend: name.clone(),
+                    error,
+                })?;
+        }
+        Ok(expected)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CrossBackendError {
+    NoBackends,
+    InvalidInput(String),
+    /// A backend couldn't run the computation at all
+    BackendFailed { backend: String, message: String },
+    /// `backend` decrypted something other than `reference` (the first backend)
+    Disagreement { reference: String, backend: String, error: VerificationError },
+}
+
+impl fmt::Display for CrossBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossBackendError::NoBackends => write!(f, "No backends to compare"),
+            CrossBackendError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            CrossBackendError::BackendFailed { backend, message } => write!(f, "{} failed: {}", backend, message),
+            CrossBackendError::Disagreement { reference, backend, error } => {
+                write!(f, "{} disagrees with {} (the reference): {}", backend, reference, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CrossBackendError {}
+
+#[cfg(feature = "seal")]
+fn run_seal(poly_modulus_degree: u64, plain_modulus: u64, data: &PlainData, ops: &[CrossOp]) -> crate::seal::Result<Vec<i64>> {
+    use crate::seal::{BatchEncoder, Context, Decryptor, Encryptor, Evaluator, RelinKeys};
+
+    let ctx = Context::new(poly_modulus_degree, plain_modulus)?;
+    let encoder = BatchEncoder::new(&ctx)?;
+    let encryptor = Encryptor::new(&ctx)?;
+    let evaluator = Evaluator::new(&ctx)?;
+    let relin_keys = RelinKeys::generate(&ctx)?;
+
+    let mut result = encryptor.encrypt(&encoder.encode(&data.input)?)?;
+    let operand = encryptor.encrypt(&encoder.encode(&data.operand)?)?;
+    for op in ops {
+        result = match op {
+            CrossOp::Add => evaluator.add(&result, &operand)?,
+            CrossOp::Subtract => evaluator.sub(&result, &operand)?,
+            CrossOp::Multiply => evaluator.relinearize(&evaluator.multiply(&result, &operand)?, &relin_keys)?,
+        };
+    }
+
+    let mut decoded = encoder.decode(&Decryptor::new(&ctx)?.decrypt(&result)?)?;
+    decoded.truncate(data.input.len());
+    Ok(decoded)
+}
+
+#[cfg(feature = "openfhe")]
+fn run_openfhe(plain_modulus: u64, multiplicative_depth: u32, data: &PlainData, ops: &[CrossOp]) -> crate::open_fhe_lib::Result<Vec<i64>> {
+    use crate::open_fhe_lib::{OpenFHECiphertext, OpenFHEContext, OpenFHEKeyPair, OpenFHEPlaintext};
+
+    let ctx = OpenFHEContext::new_bfv(plain_modulus, multiplicative_depth)?;
+    let keypair = OpenFHEKeyPair::generate(&ctx)?;
+    let encrypt = |values: &[i64]| OpenFHECiphertext::encrypt(&ctx, &keypair, &OpenFHEPlaintext::from_vec(&ctx, values)?);
+
+    let mut result = encrypt(&data.input)?;
+    let operand = encrypt(&data.operand)?;
+    for op in ops {
+        result = match op {
+            CrossOp::Add => result.add(&ctx, &operand)?,
+            CrossOp::Subtract => result.subtract(&ctx, &operand)?,
+            CrossOp::Multiply => result.multiply(&ctx, &keypair, &operand)?,
+        };
+    }
+
+    let mut decoded = result.decrypt(&ctx, &keypair)?.to_vec()?;
+    decoded.truncate(data.input.len());
+    Ok(decoded)
+}
+
+#[cfg(all(test, feature = "seal", feature = "openfhe"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_openfhe_agree_on_bfv() {
+        let harness = CrossBackendHarness::new().with_seal_bfv(8192, 65537).with_openfhe_bfv(65537, 2);
+        assert_eq!(harness.backends(), vec!["SEAL BFV (t = 65537)", "OpenFHE BFV (t = 65537)"]);
+
+        let data = PlainData { input: vec![1, 2, 3, 4, 5, -6], operand: vec![7, 8, 9, 10, 11, 12] };
+        let ops = [CrossOp::Add, CrossOp::Multiply, CrossOp::Subtract];
+        let result = harness.run(&data, &ops).unwrap();
+
+        let expected: Vec<i64> = data.input.iter().zip(&data.operand).map(|(&x, &y)| (x + y) * y - y).collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_divergent_plain_modulus_is_reported() {
+        // 300 * 300 = 90000 fits t = 1032193 but wraps to 24463 mod 65537
+        let harness = CrossBackendHarness::new().with_seal_bfv(8192, 1032193).with_openfhe_bfv(65537, 2);
+        let data = PlainData { input: vec![300, 1], operand: vec![300, 1] };
+
+        let err = harness.run(&data, &[CrossOp::Multiply]).unwrap_err();
+        let CrossBackendError::Disagreement { backend, error: VerificationError::Mismatch { mismatches, .. }, .. } = &err else {
+            panic!("expected a disagreement, got {:?}", err);
+        };
+        assert_eq!(backend, "OpenFHE BFV (t = 65537)");
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!((mismatches[0].slot, mismatches[0].got.as_str()), (0, "24463"));
+        assert!(err.to_string().contains("slot 0: expected 90000, got 24463"), "{}", err);
+
+        assert_eq!(CrossBackendHarness::new().run(&data, &[]), Err(CrossBackendError::NoBackends));
+        let uneven = PlainData { input: vec![1, 2], operand: vec![1] };
+        assert!(matches!(harness.run(&uneven, &[]), Err(CrossBackendError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,134 @@
+// Encrypted square matrices for linear layers (CKKS)
+//
+// The matrix is stored by generalized diagonals: ciphertext i holds
+// M[j][(j + i) % dim] in slot j. Then M·v is the sum over i of
+// diagonal_i * rotate(v, i), slot by slot (the diagonal method of Halevi and
+// Shoup): dim rotations and dim multiplications, with no per-row sums.
+//
+// Slot rotations wrap around all slot_count slots, not the first dim, so the
+// vector is first copied into slots dim..2*dim. Rotating that by i < dim puts
+// v[(j + i) % dim] in slot j for every j < dim. The products are summed before
+// relinearizing and rescaling once, so the whole product costs one level.
+
+use crate::seal::{
+    check_same_context, CKKSEncoder, Ciphertext, Context, Encryptor, Evaluator, GaloisKeys, RelinKeys, Result,
+    Scheme, SealError,
+};
+
+pub struct EncryptedMatrix<'a> {
+    context: &'a Context,
+    // diagonals[i] holds M[j][(j + i) % dim] in slot j < dim, zeros after
+    diagonals: Vec<Ciphertext>,
+}
+
+impl<'a> EncryptedMatrix<'a> {
+    /// Encrypt a dim x dim matrix given as rows, one ciphertext per diagonal
+    pub fn encrypt(context: &'a Context, encryptor: &Encryptor<'_>, encoder: &CKKSEncoder, rows: &[Vec<f64>]) -> Result<Self> {
+        let dim = rows.len();
+        if rows.iter().any(|row| row.len() != dim) {
+            return Err(SealError::InvalidParameter(format!("EncryptedMatrix needs a square matrix, got {} rows", dim)));
+        }
+
+        let diagonals = (0..dim)
+            .map(|i| {
+                let diagonal: Vec<f64> = (0..dim).map(|j| rows[j][(j + i) % dim]).collect();
+                encryptor.encrypt(&encoder.encode(&diagonal)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_diagonals(context, diagonals)
+    }
+
+    /// A matrix whose diagonals were encrypted elsewhere: `diagonals[i]` must
+    /// hold M[j][(j + i) % dim] in slot j and zeros in every slot from dim on
+    pub fn from_diagonals(context: &'a Context, diagonals: Vec<Ciphertext>) -> Result<Self> {
+        if context.scheme() != Scheme::Ckks {
+            return Err(SealError::InvalidParameter(format!(
+                "EncryptedMatrix needs a CKKS context, got {}",
+                context.scheme()
+            )));
+        }
+        check_same_context(context, &diagonals.iter().collect::<Vec<_>>())?;
+        if diagonals.is_empty() || diagonals.len() * 2 > context.slot_count() {
+            return Err(SealError::InvalidParameter(format!(
+                "EncryptedMatrix dimension must be between 1 and {} (half the slots), got {}",
+                context.slot_count() / 2,
+                diagonals.len()
+            )));
+        }
+        Ok(EncryptedMatrix { context, diagonals })
+    }
+
+    /// Rows (and columns)
+    pub fn dim(&self) -> usize {
+        self.diagonals.len()
+    }
+
+    pub fn diagonals(&self) -> &[Ciphertext] {
+        &self.diagonals
+    }
+
+    /// Encrypted M·v
+    ///
+    /// `vec` holds v in slots 0..dim and zeros after, at the same level and scale
+    /// as the diagonals, with at least one level left. The result holds M·v in
+    /// slots 0..dim, one level down at about the input scale.
+    pub fn matvec(&self, vec: &Ciphertext, galois: &GaloisKeys, keys: &RelinKeys) -> Result<Ciphertext> {
+        check_same_context(self.context, &[vec])?;
+        if vec.chain_index(self.context)? < 1 {
+            return Err(SealError::InvalidParameter("matvec needs at least 1 remaining level".to_string()));
+        }
+
+        let evaluator = Evaluator::new(self.context)?;
+        let dim = self.dim();
+        let replicated = evaluator.add(vec, &evaluator.rotate_vector(vec, -(dim as i32), galois)?)?;
+
+        let mut sum = evaluator.multiply(&self.diagonals[0], &replicated)?;
+        for (i, diagonal) in self.diagonals.iter().enumerate().skip(1) {
+            let rotated = evaluator.rotate_vector(&replicated, i as i32, galois)?;
+            sum = evaluator.add(&sum, &evaluator.multiply(diagonal, &rotated)?)?;
+        }
+        evaluator.rescale_to_next(&evaluator.relinearize(&sum, keys)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::Decryptor;
+
+    #[test]
+    fn test_matvec_matches_plaintext_product() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let galois_keys = GaloisKeys::generate(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        let rows = vec![
+            vec![1.0, 2.0, 0.0, -1.0],
+            vec![0.5, 0.0, 3.0, 1.0],
+            vec![-2.0, 1.0, 1.0, 0.0],
+            vec![0.0, 0.25, -0.5, 4.0],
+        ];
+        let v = [1.5, -2.0, 0.5, 3.0];
+        let expected: Vec<f64> = rows.iter().map(|row| row.iter().zip(&v).map(|(m, x)| m * x).sum()).collect();
+
+        let matrix = EncryptedMatrix::encrypt(&ctx, &encryptor, &encoder, &rows).unwrap();
+        assert_eq!(matrix.dim(), 4);
+        let cipher = encryptor.encrypt(&encoder.encode(&v).unwrap()).unwrap();
+        let product = matrix.matvec(&cipher, &galois_keys, &relin_keys).unwrap();
+
+        let decoded = encoder.decode(&decryptor.decrypt(&product).unwrap()).unwrap();
+        for (got, want) in decoded.iter().zip(&expected) {
+            assert!((got - want).abs() < 1e-3, "expected {:?}, got {:?}", expected, &decoded[..4]);
+        }
+
+        // A ragged matrix is rejected before anything is encrypted
+        let ragged = vec![vec![1.0, 2.0], vec![3.0]];
+        assert!(matches!(
+            EncryptedMatrix::encrypt(&ctx, &encryptor, &encoder, &ragged),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+}
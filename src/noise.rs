@@ -0,0 +1,195 @@
+// Noise budget checks after every evaluator operation
+//
+// A BFV ciphertext whose noise budget reaches zero decrypts to garbage, and
+// nothing says so until the decryption. NoiseMonitor runs the same operations
+// as an Evaluator, measures the result's invariant noise budget and warns as
+// soon as it drops below a safety margin, while there is still room to stop or
+// refresh the ciphertext (this SEAL setup has no bootstrapping).
+//
+// Measuring needs the secret key. Without a decryptor the monitor is a plain
+// evaluator: nothing is checked and nothing warns.
+
+use crate::seal::{Ciphertext, Context, Decryptor, Evaluator, GaloisKeys, Plaintext, RelinKeys, Result, Scheme, SealError};
+
+/// Default margin: about one more multiplication's worth of budget at degree 8192
+pub const DEFAULT_NOISE_MARGIN_BITS: u32 = 30;
+
+/// A result whose budget fell below the margin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoiseWarning {
+    /// Evaluator method that produced the ciphertext
+    pub operation: &'static str,
+    pub budget_bits: u32,
+    pub margin_bits: u32,
+}
+
+type WarningCallback<'a> = Box<dyn Fn(&NoiseWarning) + 'a>;
+
+pub struct NoiseMonitor<'a> {
+    evaluator: Evaluator<'a>,
+    decryptor: Option<&'a Decryptor<'a>>,
+    margin_bits: u32,
+    on_low_budget: Option<WarningCallback<'a>>,
+}
+
+impl<'a> NoiseMonitor<'a> {
+    /// A monitor with the default margin and no decryptor yet (BFV only: CKKS
+    /// has no invariant noise budget)
+    pub fn new(context: &'a Context) -> Result<Self> {
+        if context.scheme() != Scheme::Bfv {
+            return Err(SealError::InvalidParameter(format!(
+                "NoiseMonitor needs a BFV context, got {}",
+                context.scheme()
+            )));
+        }
+        Ok(NoiseMonitor {
+            evaluator: Evaluator::new(context)?,
+            decryptor: None,
+            margin_bits: DEFAULT_NOISE_MARGIN_BITS,
+            on_low_budget: None,
+        })
+    }
+
+    /// Measure results with this decryptor's secret key
+    pub fn with_decryptor(mut self, decryptor: &'a Decryptor<'a>) -> Self {
+        self.decryptor = Some(decryptor);
+        self
+    }
+
+    /// Warn once a result has fewer than `bits` of budget left
+    pub fn with_margin(mut self, bits: u32) -> Self {
+        self.margin_bits = bits;
+        self
+    }
+
+    /// Call `callback` for each low-budget result instead of `tracing::warn!`
+    pub fn on_low_budget(mut self, callback: impl Fn(&NoiseWarning) + 'a) -> Self {
+        self.on_low_budget = Some(Box::new(callback));
+        self
+    }
+
+    /// The wrapped evaluator, for operations the monitor doesn't cover (unchecked)
+    pub fn evaluator(&self) -> &Evaluator<'a> {
+        &self.evaluator
+    }
+
+    pub fn margin_bits(&self) -> u32 {
+        self.margin_bits
+    }
+
+    /// Measure `cipher` and warn if it is below the margin, as if `operation`
+    /// had just produced it. None without a decryptor.
+    pub fn check(&self, operation: &'static str, cipher: &Ciphertext) -> Result<Option<u32>> {
+        let Some(decryptor) = self.decryptor else { return Ok(None) };
+        let budget_bits = decryptor.invariant_noise_budget(cipher)?;
+        if budget_bits < self.margin_bits {
+            let warning = NoiseWarning { operation, budget_bits, margin_bits: self.margin_bits };
+            match &self.on_low_budget {
+                Some(callback) => callback(&warning),
+                None => tracing::warn!(
+                    operation,
+                    budget_bits,
+                    margin_bits = self.margin_bits,
+                    "noise budget below safety margin"
+                ),
+            }
+        }
+        Ok(Some(budget_bits))
+    }
+
+    // Check an operation's result on the way out
+    fn checked(&self, operation: &'static str, result: Result<Ciphertext>) -> Result<Ciphertext> {
+        let cipher = result?;
+        self.check(operation, &cipher)?;
+        Ok(cipher)
+    }
+
+    pub fn add(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.checked("add", self.evaluator.add(a, b))
+    }
+
+    pub fn sub(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.checked("sub", self.evaluator.sub(a, b))
+    }
+
+    pub fn multiply(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.checked("multiply", self.evaluator.multiply(a, b))
+    }
+
+    pub fn relinearize(&self, cipher: &Ciphertext, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        self.checked("relinearize", self.evaluator.relinearize(cipher, relin_keys))
+    }
+
+    pub fn multiply_plain(&self, cipher: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        self.checked("multiply_plain", self.evaluator.multiply_plain(cipher, plain))
+    }
+
+    pub fn add_plain(&self, cipher: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        self.checked("add_plain", self.evaluator.add_plain(cipher, plain))
+    }
+
+    pub fn rotate_rows(&self, cipher: &Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        self.checked("rotate_rows", self.evaluator.rotate_rows(cipher, steps, galois_keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::{BatchEncoder, Encryptor};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_high_margin_warns_several_multiplications_before_exhaustion() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let warnings = RefCell::new(Vec::new());
+        let monitor = NoiseMonitor::new(&ctx)
+            .unwrap()
+            .with_decryptor(&decryptor)
+            .with_margin(90)
+            .on_low_budget(|w| warnings.borrow_mut().push(w.clone()));
+
+        // Square until the budget is gone, noting the first warning
+        let mut x = encryptor.encrypt(&encoder.encode(&[2]).unwrap()).unwrap();
+        let mut first_warning = None;
+        let mut exhausted = None;
+        for step in 0..10 {
+            let squared = monitor.multiply(&x, &x).unwrap();
+            x = monitor.relinearize(&squared, &relin_keys).unwrap();
+            if first_warning.is_none() && !warnings.borrow().is_empty() {
+                first_warning = Some(step);
+            }
+            if decryptor.invariant_noise_budget(&x).unwrap() == 0 {
+                exhausted = Some(step);
+                break;
+            }
+        }
+
+        let (first_warning, exhausted) = (first_warning.unwrap(), exhausted.unwrap());
+        assert!(exhausted >= first_warning + 2, "warned at {}, exhausted at {}", first_warning, exhausted);
+        let warnings = warnings.borrow();
+        assert_eq!(warnings[0].operation, "multiply");
+        assert!(warnings.iter().all(|w| w.budget_bits < 90 && w.margin_bits == 90), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_without_a_decryptor_nothing_is_measured() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let cipher = Encryptor::new(&ctx).unwrap().encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let monitor = NoiseMonitor::new(&ctx)
+            .unwrap()
+            .with_margin(u32::MAX)
+            .on_low_budget(|w| panic!("unexpected warning {:?}", w));
+
+        monitor.add(&cipher, &cipher).unwrap();
+        assert_eq!(monitor.check("add", &cipher).unwrap(), None);
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(matches!(NoiseMonitor::new(&ckks), Err(SealError::InvalidParameter(_))));
+    }
+}
@@ -34,8 +34,25 @@ unsafe extern "C" {
         multiplicative_depth: c_uint,
     ) -> *mut OpenFHEContext;
     
+    pub fn openfhe_create_bgv_context(
+        ring_dimension: c_ulonglong,
+        plaintext_modulus: c_ulonglong,
+        multiplicative_depth: c_uint,
+    ) -> *mut OpenFHEContext;
+    
     pub fn openfhe_destroy_context(ctx: *mut OpenFHEContext);
     
+    pub fn openfhe_context_is_bgv(ctx: *mut OpenFHEContext) -> bool;
+    
+    pub fn openfhe_context_mult_depth(ctx: *mut OpenFHEContext) -> c_uint;
+    
+    // Context serialization (parameters only)
+    pub fn openfhe_context_byte_count(ctx: *mut OpenFHEContext) -> usize;
+    
+    pub fn openfhe_context_save(ctx: *mut OpenFHEContext, output: *mut u8, output_size: usize) -> usize;
+    
+    pub fn openfhe_context_load(data: *const u8, data_size: usize) -> *mut OpenFHEContext;
+    
     // Key management
     pub fn openfhe_generate_keypair(
         ctx: *mut OpenFHEContext,
@@ -43,6 +60,22 @@ unsafe extern "C" {
     
     pub fn openfhe_destroy_keypair(keypair: *mut OpenFHEKeyPair);
     
+    // Key serialization
+    pub fn openfhe_public_key_byte_count(keypair: *mut OpenFHEKeyPair) -> usize;
+    pub fn openfhe_public_key_save(keypair: *mut OpenFHEKeyPair, output: *mut u8, output_size: usize) -> usize;
+    pub fn openfhe_secret_key_byte_count(keypair: *mut OpenFHEKeyPair) -> usize;
+    pub fn openfhe_secret_key_save(keypair: *mut OpenFHEKeyPair, output: *mut u8, output_size: usize) -> usize;
+    pub fn openfhe_keypair_load(
+        ctx: *mut OpenFHEContext,
+        public_data: *const u8,
+        public_size: usize,
+        secret_data: *const u8,
+        secret_size: usize,
+    ) -> *mut OpenFHEKeyPair;
+    pub fn openfhe_eval_mult_keys_byte_count(ctx: *mut OpenFHEContext) -> usize;
+    pub fn openfhe_eval_mult_keys_save(ctx: *mut OpenFHEContext, output: *mut u8, output_size: usize) -> usize;
+    pub fn openfhe_eval_mult_keys_load(ctx: *mut OpenFHEContext, data: *const u8, data_size: usize) -> bool;
+    
     // Plaintext operations
     pub fn openfhe_create_plaintext(
         ctx: *mut OpenFHEContext,
@@ -73,6 +106,18 @@ unsafe extern "C" {
     
     pub fn openfhe_destroy_ciphertext(cipher: *mut OpenFHECiphertext);
     
+    pub fn openfhe_ciphertext_byte_count(cipher: *mut OpenFHECiphertext) -> usize;
+    
+    pub fn openfhe_ciphertext_level(cipher: *mut OpenFHECiphertext) -> c_uint;
+    
+    pub fn openfhe_ciphertext_save(cipher: *mut OpenFHECiphertext, output: *mut u8, output_size: usize) -> usize;
+    
+    pub fn openfhe_ciphertext_load(
+        ctx: *mut OpenFHEContext,
+        data: *const u8,
+        data_size: usize,
+    ) -> *mut OpenFHECiphertext;
+    
     // Homomorphic operations
     pub fn openfhe_eval_add(
         a: *mut OpenFHECiphertext,
@@ -89,6 +134,34 @@ unsafe extern "C" {
         b: *mut OpenFHECiphertext,
     ) -> *mut OpenFHECiphertext;
     
+    // Threshold (n-out-of-n) keys
+    pub fn openfhe_threshold_gen_share(
+        ctx: *mut OpenFHEContext,
+        lead: *mut OpenFHEKeyPair,
+    ) -> *mut OpenFHEKeyPair;
+    
+    pub fn openfhe_threshold_combine_shares(
+        ctx: *mut OpenFHEContext,
+        shares: *const *mut OpenFHEKeyPair,
+        count: usize,
+    ) -> *mut OpenFHEKeyPair;
+    
+    pub fn openfhe_threshold_partial_decrypt(
+        ctx: *mut OpenFHEContext,
+        share: *mut OpenFHEKeyPair,
+        cipher: *mut OpenFHECiphertext,
+        lead: bool,
+    ) -> *mut OpenFHECiphertext;
+    
+    pub fn openfhe_threshold_combine_partials(
+        ctx: *mut OpenFHEContext,
+        partials: *const *mut OpenFHECiphertext,
+        count: usize,
+    ) -> *mut OpenFHEPlaintext;
+    
+    // Library version (static string)
+    pub fn openfhe_version() -> *const c_char;
+
     // Error handling
     pub fn openfhe_get_last_error() -> *const c_char;
 }
\ No newline at end of file
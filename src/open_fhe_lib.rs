@@ -33,6 +33,11 @@ impl std::error::Error for OpenFHEError {}
 
 pub type Result<T> = std::result::Result<T, OpenFHEError>;
 
+/// Version of the OpenFHE library the wrapper was built against, e.g. "1.2.0"
+pub fn openfhe_version() -> String {
+    unsafe { CStr::from_ptr(open_fhe_binding::openfhe_version()) }.to_string_lossy().into_owned()
+}
+
 /// Get last error from OpenFHE
 fn get_last_error() -> String {
     unsafe {
@@ -46,9 +51,43 @@ fn get_last_error() -> String {
     }
 }
 
+// Run one of the wrapper's byte_count/save pairs into a Vec
+fn save_bytes(byte_count: impl FnOnce() -> usize, save: impl FnOnce(*mut u8, usize) -> usize) -> Result<Vec<u8>> {
+    let size = byte_count();
+    if size == 0 {
+        return Err(OpenFHEError::Unknown(get_last_error()));
+    }
+    
+    let mut bytes = vec![0u8; size];
+    let written = save(bytes.as_mut_ptr(), bytes.len());
+    if written == 0 {
+        return Err(OpenFHEError::Unknown(get_last_error()));
+    }
+    
+    bytes.truncate(written);
+    Ok(bytes)
+}
+
+// Which scheme a context was created for (used to label benchmark results)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenFHEScheme {
+    Bfv,
+    Bgv,
+}
+
+impl std::fmt::Display for OpenFHEScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bfv => write!(f, "BFV"),
+            Self::Bgv => write!(f, "BGV"),
+        }
+    }
+}
+
 // Context (owns OpenFHE crypto context)
 pub struct OpenFHEContext {
     ptr: NonNull<open_fhe_binding::OpenFHEContext>,
+    scheme: OpenFHEScheme,
 }
 
 impl OpenFHEContext {
@@ -63,10 +102,89 @@ impl OpenFHEContext {
         };
         
         NonNull::new(ptr)
-            .map(|ptr| OpenFHEContext { ptr })
+            .map(|ptr| OpenFHEContext { ptr, scheme: OpenFHEScheme::Bfv })
+            .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
+    }
+    
+    /// Create a new OpenFHE BGV context (same operations as BFV)
+    /// 
+    /// # Parameters
+    /// - poly_modulus_degree: Ring dimension, a power of two (0 lets OpenFHE pick
+    ///   the smallest one that is secure for the depth)
+    /// - plain_modulus: Plaintext modulus, must be 1 mod 2*poly_modulus_degree
+    ///   for packing (e.g., 65537)
+    /// - mult_depth: Multiplicative depth (e.g., 2)
+    pub fn new_bgv(poly_modulus_degree: u64, plain_modulus: u64, mult_depth: u32) -> Result<Self> {
+        if poly_modulus_degree != 0 && !poly_modulus_degree.is_power_of_two() {
+            return Err(OpenFHEError::InvalidParameter);
+        }
+        
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_create_bgv_context(poly_modulus_degree, plain_modulus, mult_depth)
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| OpenFHEContext { ptr, scheme: OpenFHEScheme::Bgv })
             .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
     }
     
+    /// Scheme this context was created for
+    pub fn scheme(&self) -> OpenFHEScheme {
+        self.scheme
+    }
+    
+    /// Multiplicative depth the context was created with: the levels a fresh
+    /// ciphertext has. OpenFHE doesn't serialize it, so a BGV context from
+    /// `from_bytes` reads it back from its modulus chain and a BFV one reports 0.
+    pub fn multiplicative_depth(&self) -> usize {
+        unsafe { open_fhe_binding::openfhe_context_mult_depth(self.ptr.as_ptr()) as usize }
+    }
+    
+    /// Serialize the crypto context (parameters only, no keys) in OpenFHE's
+    /// binary format, for a peer that needs it to load ciphertexts
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        save_bytes(
+            || unsafe { open_fhe_binding::openfhe_context_byte_count(self.ptr.as_ptr()) },
+            |output, size| unsafe { open_fhe_binding::openfhe_context_save(self.ptr.as_ptr(), output, size) },
+        )
+    }
+    
+    /// Load a crypto context written by `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_context_load(data.as_ptr(), data.len())
+        };
+        
+        let ptr = NonNull::new(ptr).ok_or_else(|| OpenFHEError::Unknown(get_last_error()))?;
+        let scheme = if unsafe { open_fhe_binding::openfhe_context_is_bgv(ptr.as_ptr()) } {
+            OpenFHEScheme::Bgv
+        } else {
+            OpenFHEScheme::Bfv
+        };
+        Ok(OpenFHEContext { ptr, scheme })
+    }
+    
+    /// Serialize the relinearization keys `OpenFHEKeyPair::generate` created
+    /// under this context, which `multiply` needs
+    pub fn eval_mult_keys_to_bytes(&self) -> Result<Vec<u8>> {
+        save_bytes(
+            || unsafe { open_fhe_binding::openfhe_eval_mult_keys_byte_count(self.ptr.as_ptr()) },
+            |output, size| unsafe { open_fhe_binding::openfhe_eval_mult_keys_save(self.ptr.as_ptr(), output, size) },
+        )
+    }
+    
+    /// Load relinearization keys written by `eval_mult_keys_to_bytes`, so
+    /// ciphertexts under this (loaded) context can be multiplied
+    pub fn load_eval_mult_keys(&self, data: &[u8]) -> Result<()> {
+        let loaded = unsafe {
+            open_fhe_binding::openfhe_eval_mult_keys_load(self.ptr.as_ptr(), data.as_ptr(), data.len())
+        };
+        if !loaded {
+            return Err(OpenFHEError::Unknown(get_last_error()));
+        }
+        Ok(())
+    }
+    
     /// Get raw pointer (for internal use)
     pub(crate) fn as_ptr(&self) -> *mut open_fhe_binding::OpenFHEContext {
         self.ptr.as_ptr()
@@ -102,6 +220,45 @@ impl OpenFHEKeyPair {
             .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
     }
     
+    /// Serialize the public key (binary), for a peer that only encrypts
+    pub fn public_key_to_bytes(&self) -> Result<Vec<u8>> {
+        save_bytes(
+            || unsafe { open_fhe_binding::openfhe_public_key_byte_count(self.ptr.as_ptr()) },
+            |output, size| unsafe { open_fhe_binding::openfhe_public_key_save(self.ptr.as_ptr(), output, size) },
+        )
+    }
+    
+    /// Serialize the secret key (binary). Fails for a key pair without one
+    /// (public-only, or a joint threshold key).
+    pub fn secret_key_to_bytes(&self) -> Result<Vec<u8>> {
+        save_bytes(
+            || unsafe { open_fhe_binding::openfhe_secret_key_byte_count(self.ptr.as_ptr()) },
+            |output, size| unsafe { open_fhe_binding::openfhe_secret_key_save(self.ptr.as_ptr(), output, size) },
+        )
+    }
+    
+    /// Rebuild a key pair from `public_key_to_bytes` (and optionally
+    /// `secret_key_to_bytes`) output. Without the secret key it can encrypt
+    /// but not decrypt.
+    ///
+    /// The keys must come from `context` or the context it was loaded from.
+    pub fn from_bytes(context: &OpenFHEContext, public_key: &[u8], secret_key: Option<&[u8]>) -> Result<Self> {
+        let (secret_ptr, secret_len) = secret_key.map_or((std::ptr::null(), 0), |key| (key.as_ptr(), key.len()));
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_keypair_load(
+                context.as_ptr(),
+                public_key.as_ptr(),
+                public_key.len(),
+                secret_ptr,
+                secret_len,
+            )
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| OpenFHEKeyPair { ptr })
+            .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
+    }
+    
     /// Get raw pointer (for internal use)
     pub(crate) fn as_ptr(&self) -> *mut open_fhe_binding::OpenFHEKeyPair {
         self.ptr.as_ptr()
@@ -221,6 +378,45 @@ impl OpenFHECiphertext {
             .ok_or(OpenFHEError::DecryptionFailed)
     }
     
+    /// Get the total size in bytes when serialized (binary format)
+    pub fn byte_count(&self) -> usize {
+        unsafe {
+            open_fhe_binding::openfhe_ciphertext_byte_count(self.ptr.as_ptr())
+        }
+    }
+    
+    /// Multiplicative levels used so far (0 when fresh), from OpenFHE's own
+    /// bookkeeping: moduli already switched away plus a rescale still pending
+    /// on a product. BGV drops a modulus per level, so this is its tower depth.
+    pub fn level(&self) -> usize {
+        unsafe { open_fhe_binding::openfhe_ciphertext_level(self.ptr.as_ptr()) as usize }
+    }
+    
+    /// Multiplications still possible before the context's depth runs out
+    pub fn remaining_levels(&self, context: &OpenFHEContext) -> usize {
+        context.multiplicative_depth().saturating_sub(self.level())
+    }
+    
+    /// Serialize with OpenFHE's binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        save_bytes(
+            || self.byte_count(),
+            |output, size| unsafe { open_fhe_binding::openfhe_ciphertext_save(self.ptr.as_ptr(), output, size) },
+        )
+    }
+    
+    /// Load a ciphertext written by `to_bytes` under the same crypto context
+    /// (the original, or one loaded with `OpenFHEContext::from_bytes`)
+    pub fn from_bytes(context: &OpenFHEContext, data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_ciphertext_load(context.as_ptr(), data.as_ptr(), data.len())
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| OpenFHECiphertext { ptr })
+            .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
+    }
+    
     /// Add two ciphertexts homomorphically
     pub fn add(&self, _context: &OpenFHEContext, other: &OpenFHECiphertext) -> Result<OpenFHECiphertext> {
     let ptr = unsafe {
@@ -277,6 +473,133 @@ impl Drop for OpenFHECiphertext {
     }
 }
 
+// Threshold (n-out-of-n) keys
+//
+// For parties that must compute on each other's data without any one of them
+// able to decrypt it: each holds a secret share, encryption uses the joint
+// public key, and decrypting takes a partial decryption from every party.
+// OpenFHE only: SEAL has no multi-party primitives.
+//
+// The joint key has no relinearization key (building one is a further
+// interactive round), so ciphertexts under it support addition and
+// subtraction but not `multiply`.
+pub struct ThresholdKeyGen<'a> {
+    context: &'a OpenFHEContext,
+}
+
+/// One party's secret share and public share
+pub struct KeyShare {
+    ptr: NonNull<open_fhe_binding::OpenFHEKeyPair>,
+    lead: bool,
+}
+
+/// One party's contribution to decrypting a ciphertext under the joint key;
+/// reveals nothing on its own
+pub struct PartialDecryption {
+    ptr: NonNull<open_fhe_binding::OpenFHECiphertext>,
+}
+
+impl<'a> ThresholdKeyGen<'a> {
+    pub fn new(context: &'a OpenFHEContext) -> Self {
+        ThresholdKeyGen { context }
+    }
+    
+    /// Generate one party's share
+    ///
+    /// The lead party passes `None`; everyone else passes the lead's share,
+    /// whose public half fixes the randomness all shares have to agree on.
+    pub fn gen_share(&self, lead: Option<&KeyShare>) -> Result<KeyShare> {
+        if lead.is_some_and(|lead| !lead.lead) {
+            return Err(OpenFHEError::InvalidParameter);
+        }
+        
+        let lead_ptr = lead.map_or(std::ptr::null_mut(), |lead| lead.ptr.as_ptr());
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_threshold_gen_share(self.context.as_ptr(), lead_ptr)
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| KeyShare { ptr, lead: lead.is_none() })
+            .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
+    }
+    
+    /// Joint public key from every party's share, for `OpenFHECiphertext::encrypt`
+    ///
+    /// The returned key pair has no secret half: `decrypt` with it fails.
+    pub fn combine_shares(&self, shares: &[&KeyShare]) -> Result<OpenFHEKeyPair> {
+        if shares.iter().filter(|share| share.lead).count() != 1 {
+            return Err(OpenFHEError::InvalidParameter);
+        }
+        
+        let ptrs: Vec<_> = shares.iter().map(|share| share.ptr.as_ptr()).collect();
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_threshold_combine_shares(self.context.as_ptr(), ptrs.as_ptr(), ptrs.len())
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| OpenFHEKeyPair { ptr })
+            .ok_or_else(|| OpenFHEError::Unknown(get_last_error()))
+    }
+    
+    /// Plaintext from every party's partial decryption
+    ///
+    /// With any party missing the result is meaningless (or an error); nothing
+    /// here can tell which.
+    pub fn combine_partials(&self, partials: &[PartialDecryption]) -> Result<OpenFHEPlaintext> {
+        if partials.is_empty() {
+            return Err(OpenFHEError::InvalidParameter);
+        }
+        
+        let ptrs: Vec<_> = partials.iter().map(|partial| partial.ptr.as_ptr()).collect();
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_threshold_combine_partials(self.context.as_ptr(), ptrs.as_ptr(), ptrs.len())
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| OpenFHEPlaintext { ptr })
+            .ok_or(OpenFHEError::DecryptionFailed)
+    }
+}
+
+impl KeyShare {
+    /// This party's partial decryption of `cipher` (encrypted under the joint key)
+    pub fn partial_decrypt(&self, context: &OpenFHEContext, cipher: &OpenFHECiphertext) -> Result<PartialDecryption> {
+        let ptr = unsafe {
+            open_fhe_binding::openfhe_threshold_partial_decrypt(
+                context.as_ptr(),
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                self.lead,
+            )
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| PartialDecryption { ptr })
+            .ok_or(OpenFHEError::DecryptionFailed)
+    }
+    
+    /// Whether this is the lead party's share
+    pub fn is_lead(&self) -> bool {
+        self.lead
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        unsafe {
+            open_fhe_binding::openfhe_destroy_keypair(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl Drop for PartialDecryption {
+    fn drop(&mut self) {
+        unsafe {
+            open_fhe_binding::openfhe_destroy_ciphertext(self.ptr.as_ptr());
+        }
+    }
+}
+
 // Tests
 #[cfg(test)]
 mod tests {
@@ -309,4 +632,160 @@ mod tests {
         let result = decrypted.to_vec().unwrap();
         assert_eq!(&result[..5], &values[..]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bgv_round_trip_and_depth_two() {
+        let ctx = OpenFHEContext::new_bgv(8192, 65537, 2).unwrap();
+        assert_eq!(ctx.scheme(), OpenFHEScheme::Bgv);
+        let keypair = OpenFHEKeyPair::generate(&ctx).unwrap();
+        
+        let encrypt = |values: &[i64]| {
+            let plaintext = OpenFHEPlaintext::from_vec(&ctx, values).unwrap();
+            OpenFHECiphertext::encrypt(&ctx, &keypair, &plaintext).unwrap()
+        };
+        let decrypt = |cipher: &OpenFHECiphertext, n: usize| {
+            cipher.decrypt(&ctx, &keypair).unwrap().to_vec().unwrap()[..n].to_vec()
+        };
+        
+        let a = encrypt(&[1, 2, 3, 4]);
+        let b = encrypt(&[5, 6, 7, 8]);
+        assert_eq!(decrypt(&a, 4), vec![1, 2, 3, 4]);
+        assert_eq!(decrypt(&a.add(&ctx, &b).unwrap(), 4), vec![6, 8, 10, 12]);
+        
+        // (a * b) * a uses both levels
+        let ab = a.multiply(&ctx, &keypair, &b).unwrap();
+        let aba = ab.multiply(&ctx, &keypair, &a).unwrap();
+        assert_eq!(decrypt(&aba, 4), vec![5, 24, 63, 128]);
+        
+        assert!(matches!(OpenFHEContext::new_bgv(1000, 65537, 2), Err(OpenFHEError::InvalidParameter)));
+    }
+
+    #[test]
+    fn test_bgv_remaining_levels_drop_with_each_multiplication() {
+        let ctx = OpenFHEContext::new_bgv(8192, 65537, 3).unwrap();
+        assert_eq!(ctx.multiplicative_depth(), 3);
+        let keypair = OpenFHEKeyPair::generate(&ctx).unwrap();
+        let plaintext = OpenFHEPlaintext::from_vec(&ctx, &[2, 3]).unwrap();
+        let fresh = OpenFHECiphertext::encrypt(&ctx, &keypair, &plaintext).unwrap();
+        assert_eq!(fresh.level(), 0);
+        assert_eq!(fresh.remaining_levels(&ctx), 3);
+
+        // Adding costs no level
+        assert_eq!(fresh.add(&ctx, &fresh).unwrap().remaining_levels(&ctx), 3);
+
+        let mut x = fresh.multiply(&ctx, &keypair, &fresh).unwrap();
+        let mut remaining = vec![x.remaining_levels(&ctx)];
+        for _ in 0..2 {
+            x = x.multiply(&ctx, &keypair, &fresh).unwrap();
+            remaining.push(x.remaining_levels(&ctx));
+        }
+        assert_eq!(remaining, vec![2, 1, 0]);
+        assert_eq!(x.decrypt(&ctx, &keypair).unwrap().to_vec().unwrap()[..2], [16, 81]);
+
+        // The depth survives serialization for BGV
+        let loaded = OpenFHEContext::from_bytes(&ctx.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.multiplicative_depth(), 3);
+    }
+
+    #[test]
+    fn test_ciphertext_bytes_round_trip() {
+        let ctx = OpenFHEContext::new_bfv(65537, 2).unwrap();
+        let keypair = OpenFHEKeyPair::generate(&ctx).unwrap();
+        
+        let values = vec![7, -3, 12, 0, 42];
+        let plaintext = OpenFHEPlaintext::from_vec(&ctx, &values).unwrap();
+        let ciphertext = OpenFHECiphertext::encrypt(&ctx, &keypair, &plaintext).unwrap();
+        
+        let bytes = ciphertext.to_bytes().unwrap();
+        assert_eq!(bytes.len(), ciphertext.byte_count());
+        
+        // What a receiving server does: load the context, then the ciphertext
+        let received_ctx = OpenFHEContext::from_bytes(&ctx.to_bytes().unwrap()).unwrap();
+        assert_eq!(received_ctx.scheme(), OpenFHEScheme::Bfv);
+        let loaded = OpenFHECiphertext::from_bytes(&received_ctx, &bytes).unwrap();
+        
+        let decrypted = loaded.decrypt(&ctx, &keypair).unwrap().to_vec().unwrap();
+        assert_eq!(&decrypted[..values.len()], &values[..]);
+        
+        assert!(OpenFHECiphertext::from_bytes(&ctx, &bytes[..bytes.len() / 2]).is_err());
+        assert!(OpenFHECiphertext::from_bytes(&ctx, b"not a ciphertext").is_err());
+    }
+    
+    #[test]
+    fn test_serialized_context_and_public_key_encrypt_for_the_original_secret_key() {
+        let ctx = OpenFHEContext::new_bfv(65537, 2).unwrap();
+        let keypair = OpenFHEKeyPair::generate(&ctx).unwrap();
+        let context_bytes = ctx.to_bytes().unwrap();
+        let public_key = keypair.public_key_to_bytes().unwrap();
+        let eval_keys = ctx.eval_mult_keys_to_bytes().unwrap();
+        
+        // A client holding only the serialized context and keys squares its values
+        let values = vec![3, -4, 5];
+        let cipher_bytes = {
+            let client_ctx = OpenFHEContext::from_bytes(&context_bytes).unwrap();
+            client_ctx.load_eval_mult_keys(&eval_keys).unwrap();
+            let client_keys = OpenFHEKeyPair::from_bytes(&client_ctx, &public_key, None).unwrap();
+            assert!(client_keys.secret_key_to_bytes().is_err());
+            
+            let plaintext = OpenFHEPlaintext::from_vec(&client_ctx, &values).unwrap();
+            let cipher = OpenFHECiphertext::encrypt(&client_ctx, &client_keys, &plaintext).unwrap();
+            assert!(cipher.decrypt(&client_ctx, &client_keys).is_err());
+            cipher.multiply(&client_ctx, &client_keys, &cipher).unwrap().to_bytes().unwrap()
+        };
+        
+        let received = OpenFHECiphertext::from_bytes(&ctx, &cipher_bytes).unwrap();
+        let decrypted = received.decrypt(&ctx, &keypair).unwrap().to_vec().unwrap();
+        assert_eq!(&decrypted[..values.len()], &[9, 16, 25]);
+        
+        // The secret key survives a round trip too
+        let secret_key = keypair.secret_key_to_bytes().unwrap();
+        let restored = OpenFHEKeyPair::from_bytes(&ctx, &public_key, Some(&secret_key)).unwrap();
+        let decrypted = received.decrypt(&ctx, &restored).unwrap().to_vec().unwrap();
+        assert_eq!(&decrypted[..values.len()], &[9, 16, 25]);
+        
+        assert!(OpenFHEKeyPair::from_bytes(&ctx, &public_key[..public_key.len() / 2], None).is_err());
+        let other_ctx = OpenFHEContext::new_bfv(65537, 3).unwrap();
+        assert!(OpenFHEKeyPair::from_bytes(&other_ctx, &public_key, None).is_err());
+    }
+    
+    #[test]
+    fn test_three_party_threshold_sum() {
+        let ctx = OpenFHEContext::new_bfv(65537, 2).unwrap();
+        let keygen = ThresholdKeyGen::new(&ctx);
+        
+        // Three hospitals; none of them ever holds the whole secret key
+        let lead = keygen.gen_share(None).unwrap();
+        let second = keygen.gen_share(Some(&lead)).unwrap();
+        let third = keygen.gen_share(Some(&lead)).unwrap();
+        assert!(lead.is_lead() && !second.is_lead());
+        assert!(keygen.gen_share(Some(&second)).is_err());
+        let joint = keygen.combine_shares(&[&lead, &second, &third]).unwrap();
+        
+        let encrypt = |values: &[i64]| {
+            let plaintext = OpenFHEPlaintext::from_vec(&ctx, values).unwrap();
+            OpenFHECiphertext::encrypt(&ctx, &joint, &plaintext).unwrap()
+        };
+        let sum = encrypt(&[10, 20, 30])
+            .add(&ctx, &encrypt(&[1, 2, 3])).unwrap()
+            .add(&ctx, &encrypt(&[100, 200, 300])).unwrap();
+        
+        // The joint key can't decrypt on its own
+        assert!(sum.decrypt(&ctx, &joint).is_err());
+        
+        let partials: Vec<_> = [&lead, &second, &third]
+            .iter()
+            .map(|share| share.partial_decrypt(&ctx, &sum).unwrap())
+            .collect();
+        let plain = keygen.combine_partials(&partials).unwrap();
+        assert_eq!(&plain.to_vec().unwrap()[..3], &[111, 222, 333]);
+        
+        // Two of three parties learn nothing useful
+        let missing_one: Vec<_> = [&lead, &second]
+            .iter()
+            .map(|share| share.partial_decrypt(&ctx, &sum).unwrap())
+            .collect();
+        if let Ok(plain) = keygen.combine_partials(&missing_one) {
+            assert_ne!(&plain.to_vec().unwrap()[..3], &[111, 222, 333]);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Stand-in for the OpenFHE wrapper when the crate is built without `--features openfhe`
+//!
+//! Mirrors `open_fhe_lib.rs` name for name. Context creation fails at runtime
+//! with `OpenFHEError::NotCompiled`, and everything else needs a context first.
+
+use std::convert::Infallible;
+
+#[derive(Debug)]
+pub enum OpenFHEError {
+    NotCompiled,
+}
+
+impl std::fmt::Display for OpenFHEError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotCompiled => write!(f, "OpenFHE support not compiled in; rebuild with --features openfhe"),
+        }
+    }
+}
+
+impl std::error::Error for OpenFHEError {}
+
+pub type Result<T> = std::result::Result<T, OpenFHEError>;
+
+/// Empty: no OpenFHE is linked into this build
+pub fn openfhe_version() -> String {
+    String::new()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenFHEScheme {
+    Bfv,
+    Bgv,
+}
+
+impl std::fmt::Display for OpenFHEScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bfv => write!(f, "BFV"),
+            Self::Bgv => write!(f, "BGV"),
+        }
+    }
+}
+
+pub struct OpenFHEContext {
+    never: Infallible,
+}
+
+impl OpenFHEContext {
+    pub fn new_bfv(_plaintext_modulus: u64, _multiplicative_depth: u32) -> Result<Self> {
+        Err(OpenFHEError::NotCompiled)
+    }
+
+    pub fn new_bgv(_poly_modulus_degree: u64, _plain_modulus: u64, _mult_depth: u32) -> Result<Self> {
+        Err(OpenFHEError::NotCompiled)
+    }
+
+    pub fn scheme(&self) -> OpenFHEScheme {
+        match self.never {}
+    }
+
+    pub fn multiplicative_depth(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self.never {}
+    }
+
+    pub fn from_bytes(_data: &[u8]) -> Result<Self> {
+        Err(OpenFHEError::NotCompiled)
+    }
+
+    pub fn eval_mult_keys_to_bytes(&self) -> Result<Vec<u8>> {
+        match self.never {}
+    }
+
+    pub fn load_eval_mult_keys(&self, _data: &[u8]) -> Result<()> {
+        match self.never {}
+    }
+}
+
+pub struct OpenFHEKeyPair {
+    never: Infallible,
+}
+
+impl OpenFHEKeyPair {
+    pub fn generate(context: &OpenFHEContext) -> Result<Self> {
+        match context.never {}
+    }
+
+    pub fn public_key_to_bytes(&self) -> Result<Vec<u8>> {
+        match self.never {}
+    }
+
+    pub fn secret_key_to_bytes(&self) -> Result<Vec<u8>> {
+        match self.never {}
+    }
+
+    pub fn from_bytes(context: &OpenFHEContext, _public_key: &[u8], _secret_key: Option<&[u8]>) -> Result<Self> {
+        match context.never {}
+    }
+}
+
+pub struct OpenFHEPlaintext {
+    never: Infallible,
+}
+
+impl OpenFHEPlaintext {
+    pub fn from_vec(context: &OpenFHEContext, _values: &[i64]) -> Result<Self> {
+        match context.never {}
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<i64>> {
+        match self.never {}
+    }
+}
+
+pub struct OpenFHECiphertext {
+    never: Infallible,
+}
+
+impl OpenFHECiphertext {
+    pub fn encrypt(context: &OpenFHEContext, _keypair: &OpenFHEKeyPair, _plaintext: &OpenFHEPlaintext) -> Result<Self> {
+        match context.never {}
+    }
+
+    pub fn decrypt(&self, _context: &OpenFHEContext, _keypair: &OpenFHEKeyPair) -> Result<OpenFHEPlaintext> {
+        match self.never {}
+    }
+
+    pub fn byte_count(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn level(&self) -> usize {
+        match self.never {}
+    }
+
+    pub fn remaining_levels(&self, _context: &OpenFHEContext) -> usize {
+        match self.never {}
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self.never {}
+    }
+
+    pub fn from_bytes(context: &OpenFHEContext, _data: &[u8]) -> Result<Self> {
+        match context.never {}
+    }
+
+    pub fn add(&self, _context: &OpenFHEContext, _other: &OpenFHECiphertext) -> Result<OpenFHECiphertext> {
+        match self.never {}
+    }
+
+    pub fn multiply(
+        &self,
+        _context: &OpenFHEContext,
+        _keypair: &OpenFHEKeyPair,
+        _other: &OpenFHECiphertext,
+    ) -> Result<OpenFHECiphertext> {
+        match self.never {}
+    }
+
+    pub fn subtract(&self, _context: &OpenFHEContext, _other: &OpenFHECiphertext) -> Result<OpenFHECiphertext> {
+        match self.never {}
+    }
+}
+
+pub struct ThresholdKeyGen<'a> {
+    context: &'a OpenFHEContext,
+}
+
+pub struct KeyShare {
+    never: Infallible,
+}
+
+pub struct PartialDecryption {
+    _never: Infallible,
+}
+
+impl<'a> ThresholdKeyGen<'a> {
+    pub fn new(context: &'a OpenFHEContext) -> Self {
+        ThresholdKeyGen { context }
+    }
+
+    pub fn gen_share(&self, _lead: Option<&KeyShare>) -> Result<KeyShare> {
+        match self.context.never {}
+    }
+
+    pub fn combine_shares(&self, _shares: &[&KeyShare]) -> Result<OpenFHEKeyPair> {
+        match self.context.never {}
+    }
+
+    pub fn combine_partials(&self, _partials: &[PartialDecryption]) -> Result<OpenFHEPlaintext> {
+        match self.context.never {}
+    }
+}
+
+impl KeyShare {
+    pub fn partial_decrypt(&self, _context: &OpenFHEContext, _cipher: &OpenFHECiphertext) -> Result<PartialDecryption> {
+        match self.never {}
+    }
+
+    pub fn is_lead(&self) -> bool {
+        match self.never {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_creation_reports_openfhe_not_compiled() {
+        let err = OpenFHEContext::new_bfv(65537, 2).err().unwrap();
+        assert_eq!(err.to_string(), "OpenFHE support not compiled in; rebuild with --features openfhe");
+        assert!(OpenFHEContext::new_bgv(8192, 65537, 2).is_err());
+    }
+}
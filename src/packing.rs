@@ -0,0 +1,194 @@
+// Packing variable-length rows into BFV slots
+//
+// One row per plaintext wastes most of the slots when rows are short. The
+// packer lays rows end to end instead, several per plaintext, and records
+// where each one went so the rows can be cut back out after decryption.
+//
+// Placement is first-fit decreasing: longest rows first, each into the first
+// plaintext with room. Bin packing is NP-hard, so this isn't always the fewest
+// plaintexts, but it never uses more than 11/9 of the optimum plus one, and a
+// row is never split across plaintexts (a split row can't be operated on
+// slot-wise in one ciphertext).
+//
+// Slots past the packed rows are zero. Operations that move data between slots
+// (rotations) mix neighbouring rows; slot-wise addition and multiplication
+// keep each row in place.
+
+use crate::seal::{BatchEncoder, Plaintext, Result, SealError};
+
+pub struct SlotPacker {
+    slot_count: usize,
+}
+
+/// Where one row went: `len` slots of plaintext `plaintext`, from `offset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowPlacement {
+    pub plaintext: usize,
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// Placement of every row, in the order the rows were given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackingLayout {
+    slot_count: usize,
+    rows: Vec<RowPlacement>,
+    plaintexts: usize,
+}
+
+impl PackingLayout {
+    pub fn rows(&self) -> &[RowPlacement] {
+        &self.rows
+    }
+
+    pub fn plaintext_count(&self) -> usize {
+        self.plaintexts
+    }
+
+    /// Cut the rows back out of decoded slot vectors, one per plaintext
+    pub fn unpack_slots(&self, slots: &[Vec<i64>]) -> Result<Vec<Vec<i64>>> {
+        if slots.len() != self.plaintexts {
+            return Err(SealError::InvalidParameter(format!(
+                "layout packs {} plaintexts, got {}",
+                self.plaintexts,
+                slots.len()
+            )));
+        }
+        self.rows
+            .iter()
+            .map(|row| {
+                if row.len == 0 {
+                    return Ok(Vec::new());
+                }
+                slots[row.plaintext]
+                    .get(row.offset..row.offset + row.len)
+                    .map(<[i64]>::to_vec)
+                    .ok_or_else(|| {
+                        SealError::InvalidParameter(format!(
+                            "plaintext {} has {} slots, the layout needs {}",
+                            row.plaintext,
+                            slots[row.plaintext].len(),
+                            row.offset + row.len
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+impl SlotPacker {
+    /// Pack into the first `slot_count` slots of each plaintext
+    pub fn new(slot_count: usize) -> Result<Self> {
+        if slot_count == 0 {
+            return Err(SealError::InvalidParameter("SlotPacker needs at least 1 slot".to_string()));
+        }
+        Ok(SlotPacker { slot_count })
+    }
+
+    pub fn slot_count(&self) -> usize {
+        self.slot_count
+    }
+
+    /// Place rows of these lengths without encoding anything
+    pub fn layout(&self, row_lengths: &[usize]) -> Result<PackingLayout> {
+        if let Some((i, len)) = row_lengths.iter().enumerate().find(|(_, len)| **len > self.slot_count) {
+            return Err(SealError::InvalidParameter(format!(
+                "row {} has {} values, more than the {} slots of a plaintext",
+                i, len, self.slot_count
+            )));
+        }
+
+        // Longest first; the stable sort keeps equal rows in their given order
+        let mut order: Vec<usize> = (0..row_lengths.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(row_lengths[i]));
+
+        let mut used: Vec<usize> = Vec::new();
+        let mut rows = vec![RowPlacement { plaintext: 0, offset: 0, len: 0 }; row_lengths.len()];
+        for i in order {
+            let len = row_lengths[i];
+            if len == 0 {
+                continue;
+            }
+            let plaintext = match used.iter().position(|used| used + len <= self.slot_count) {
+                Some(plaintext) => plaintext,
+                None => {
+                    used.push(0);
+                    used.len() - 1
+                }
+            };
+            rows[i] = RowPlacement { plaintext, offset: used[plaintext], len };
+            used[plaintext] += len;
+        }
+        Ok(PackingLayout { slot_count: self.slot_count, rows, plaintexts: used.len() })
+    }
+
+    /// Encode the rows into as few plaintexts as the layout allows
+    pub fn pack(&self, encoder: &BatchEncoder, rows: &[Vec<i64>]) -> Result<(Vec<Plaintext>, PackingLayout)> {
+        if self.slot_count > encoder.slot_count() {
+            return Err(SealError::InvalidParameter(format!(
+                "SlotPacker has {} slots, the encoder only {}",
+                self.slot_count,
+                encoder.slot_count()
+            )));
+        }
+
+        let layout = self.layout(&rows.iter().map(Vec::len).collect::<Vec<_>>())?;
+        let mut slots = vec![vec![0i64; self.slot_count]; layout.plaintexts];
+        for (row, placement) in rows.iter().zip(&layout.rows) {
+            slots[placement.plaintext][placement.offset..placement.offset + placement.len].copy_from_slice(row);
+        }
+        let plaintexts = slots.iter().map(|slots| encoder.encode(slots)).collect::<Result<Vec<_>>>()?;
+        Ok((plaintexts, layout))
+    }
+
+    /// Recover the rows from (decrypted) plaintexts packed with `layout`
+    pub fn unpack(&self, encoder: &BatchEncoder, layout: &PackingLayout, plaintexts: &[Plaintext]) -> Result<Vec<Vec<i64>>> {
+        if layout.slot_count != self.slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "layout was made for {} slots, this packer has {}",
+                layout.slot_count, self.slot_count
+            )));
+        }
+        let slots = plaintexts.iter().map(|plain| encoder.decode(plain)).collect::<Result<Vec<_>>>()?;
+        layout.unpack_slots(&slots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::{Context, Decryptor, Encryptor};
+
+    #[test]
+    fn test_ragged_rows_round_trip_through_encryption() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+
+        let rows = vec![vec![1, 2, 3], vec![-4, 5, 6, 7, 8], vec![9, -10]];
+        let packer = SlotPacker::new(8).unwrap();
+        let (plaintexts, layout) = packer.pack(&encoder, &rows).unwrap();
+
+        // The 5 and the 3 fill the first plaintext exactly; the 2 needs a second
+        assert_eq!(layout.plaintext_count(), 2);
+        assert_eq!(
+            layout.rows(),
+            [
+                RowPlacement { plaintext: 0, offset: 5, len: 3 },
+                RowPlacement { plaintext: 0, offset: 0, len: 5 },
+                RowPlacement { plaintext: 1, offset: 0, len: 2 },
+            ]
+        );
+
+        let decrypted: Vec<_> = plaintexts
+            .iter()
+            .map(|plain| decryptor.decrypt(&encryptor.encrypt(plain).unwrap()).unwrap())
+            .collect();
+        assert_eq!(packer.unpack(&encoder, &layout, &decrypted).unwrap(), rows);
+
+        // A row that can't fit anywhere is refused up front
+        assert!(matches!(packer.layout(&[3, 9]), Err(SealError::InvalidParameter(_))));
+        assert_eq!(packer.layout(&[0, 0]).unwrap().plaintext_count(), 0);
+    }
+}
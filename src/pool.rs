@@ -0,0 +1,97 @@
+//! Shared SEAL contexts keyed by parameter set
+//!
+//! Building a context generates the NTT tables and keys, which takes tens of
+//! milliseconds. `ContextPool` builds each parameter set once and hands out
+//! `Arc<Context>` clones. Evicting a parameter set only drops the pool's handle;
+//! the context itself is freed when the last clone is gone.
+
+use crate::seal::{Context, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+// (poly_modulus_degree, plain_modulus, coeff_modulus_bits)
+type ContextKey = (u64, u64, Vec<u64>);
+
+#[derive(Default)]
+pub struct ContextPool {
+    contexts: Mutex<HashMap<ContextKey, Arc<Context>>>,
+}
+
+impl ContextPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared BFV context for these parameters, built on first use
+    ///
+    /// The pool stays locked while a missing context is built, so two callers
+    /// asking for the same new parameters don't both pay for it.
+    pub fn get(&self, poly_modulus_degree: u64, plain_modulus: u64, coeff_modulus_bits: &[u64]) -> Result<Arc<Context>> {
+        let key = (poly_modulus_degree, plain_modulus, coeff_modulus_bits.to_vec());
+        let mut contexts = self.contexts.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(context) = contexts.get(&key) {
+            return Ok(Arc::clone(context));
+        }
+
+        let context = Arc::new(
+            Context::builder()
+                .poly_modulus_degree(poly_modulus_degree)
+                .plain_modulus(plain_modulus)
+                .coeff_modulus_bits(coeff_modulus_bits)
+                .build()?,
+        );
+        contexts.insert(key, Arc::clone(&context));
+        Ok(context)
+    }
+
+    /// Drop the pool's handle for these parameters; returns whether there was one.
+    /// The next `get` builds a fresh context (with fresh keys).
+    pub fn evict(&self, poly_modulus_degree: u64, plain_modulus: u64, coeff_modulus_bits: &[u64]) -> bool {
+        let key = (poly_modulus_degree, plain_modulus, coeff_modulus_bits.to_vec());
+        self.contexts.lock().unwrap_or_else(PoisonError::into_inner).remove(&key).is_some()
+    }
+
+    /// Number of parameter sets currently cached
+    pub fn len(&self) -> usize {
+        self.contexts.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seal::{BatchEncoder, Decryptor, Encryptor};
+
+    const COEFF_BITS: [u64; 3] = [36, 36, 37];
+
+    #[test]
+    fn test_same_parameters_share_a_context() {
+        let pool = ContextPool::new();
+
+        let first = pool.get(4096, 40961, &COEFF_BITS).unwrap();
+        let second = pool.get(4096, 40961, &COEFF_BITS).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(Arc::strong_count(&first), 3); // two handles + the pool's
+
+        let other = pool.get(8192, 1032193, &COEFF_BITS).unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(pool.len(), 2);
+
+        // Eviction rebuilds on the next get, while old handles keep working
+        assert!(pool.evict(4096, 40961, &COEFF_BITS));
+        assert_eq!(Arc::strong_count(&first), 2);
+        let rebuilt = pool.get(4096, 40961, &COEFF_BITS).unwrap();
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+
+        drop(second);
+        let encoder = BatchEncoder::new(&first).unwrap();
+        let cipher = Encryptor::new(&first).unwrap().encrypt(&encoder.encode(&[42]).unwrap()).unwrap();
+        let decoded = encoder.decode(&Decryptor::new(&first).unwrap().decrypt(&cipher).unwrap()).unwrap();
+        assert_eq!(decoded[0], 42);
+    }
+}
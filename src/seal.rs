@@ -0,0 +1,3780 @@
+//! Safe Rust wrapper for SEAL homomorphic encryption library
+//! 
+//! This module provides a safe, idiomatic Rust interface to Microsoft SEAL.
+
+use crate::bindings; // low-level FFI bindings (the C function definitions) that connect to C++ wrapper
+use std::ffi::{CStr, CString}; // CStr and CString convert between Rust strings and C strings.
+use std::marker::PhantomData;
+use std::ptr::NonNull; // NonNull safely wraps raw pointers that should never be null.
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(debug_assertions)]
+use std::collections::BTreeSet;
+#[cfg(debug_assertions)]
+use std::sync::{Mutex, PoisonError};
+
+pub use num_complex::Complex;
+
+// Error Types
+#[derive(Debug)]
+pub enum SealError {
+    // Defines all possible errors might encounter
+    NullPointer,
+    InvalidParameter(String),
+    EncryptionFailed,
+    DecryptionFailed,
+    OperationFailed,
+    IntegrityCheckFailed, // an authenticated ciphertext's tag didn't match
+    NoiseExhausted, // a BFV ciphertext's noise budget is 0, so it would decrypt to garbage
+    DecryptionMismatch, // two decryptions of one ciphertext disagreed (see Decryptor::decrypt_verified)
+    TimedOut(std::time::Duration), // a watchdog stopped waiting for an FFI call (see watchdog.rs)
+    // Rust’s Result<T, SealError> then makes it safe to handle errors using ?.
+}
+
+
+// Implement Display for SealError
+// Makes SealError printable and compatible with Rust’s standard Result and ? error-handling syntax
+impl std::fmt::Display for SealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SealError::NullPointer => write!(f, "Null pointer returned from SEAL"),
+            SealError::InvalidParameter(msg) => write!(f, "Invalid parameter provided: {}", msg),
+            SealError::EncryptionFailed => write!(f, "Encryption operation failed"),
+            SealError::DecryptionFailed => write!(f, "Decryption operation failed"),
+            SealError::OperationFailed => write!(f, "SEAL operation failed"),
+            SealError::IntegrityCheckFailed => write!(f, "Ciphertext failed its integrity check"),
+            SealError::NoiseExhausted => write!(f, "Noise budget exhausted: the ciphertext no longer decrypts correctly"),
+            SealError::DecryptionMismatch => write!(f, "Decryption mismatch: two decryptions of the same ciphertext disagreed"),
+            SealError::TimedOut(limit) => write!(f, "SEAL operation timed out after {:?}", limit),
+        }
+    }
+}
+
+// Implement Error trait for SealError
+impl std::error::Error for SealError {}
+
+pub type Result<T> = std::result::Result<T, SealError>;
+
+// Every Context gets a unique id, and every Ciphertext remembers the id of the
+// context it was made under. Mixing ciphertexts from two contexts either makes SEAL
+// throw (different parameters) or silently decrypts to garbage (same parameters,
+// different keys), so we check the ids before calling into C++.
+static NEXT_CONTEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// Debug builds also remember which contexts are still alive. A Ciphertext doesn't
+// borrow its Context (Encryptor, Decryptor and Evaluator do, so the borrow checker
+// covers them), and one that outlives it should fail loudly at its next use rather
+// than reach SEAL next to some other context's keys. Compiled out in release.
+#[cfg(debug_assertions)]
+static LIVE_CONTEXTS: Mutex<BTreeSet<u64>> = Mutex::new(BTreeSet::new());
+
+fn new_context_id() -> u64 {
+    let id = NEXT_CONTEXT_ID.fetch_add(1, Ordering::Relaxed);
+    #[cfg(debug_assertions)]
+    LIVE_CONTEXTS.lock().unwrap_or_else(PoisonError::into_inner).insert(id);
+    id
+}
+
+#[cfg(debug_assertions)]
+fn release_context_id(id: u64) {
+    LIVE_CONTEXTS.lock().unwrap_or_else(PoisonError::into_inner).remove(&id);
+}
+
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+fn release_context_id(_id: u64) {}
+
+// Deterministic Miller-Rabin: these bases decide primality for every u64
+fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    if let Some(&base) = BASES.iter().find(|&&base| n.is_multiple_of(base)) {
+        return n == base;
+    }
+
+    let mul_mod = |a: u64, b: u64| (u128::from(a) * u128::from(b) % u128::from(n)) as u64;
+    let pow_mod = |mut base: u64, mut exp: u64| {
+        let mut result = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul_mod(result, base);
+            }
+            base = mul_mod(base, base);
+            exp >>= 1;
+        }
+        result
+    };
+
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    BASES.iter().all(|&base| {
+        let mut x = pow_mod(base, odd);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..shift).any(|_| {
+            x = mul_mod(x, x);
+            x == n - 1
+        })
+    })
+}
+
+// Context (owns SEAL context and keys; only a public key for `with_public_key`)
+pub struct Context {
+    // store only a pointer to the C++ object, but wrapped in NonNull to ensure it’s valid
+    ptr: NonNull<bindings::SEALContext>,
+    id: u64,
+    scheme: Scheme,
+}
+
+/// Encryption scheme a Context was built for. Encoders and scheme-specific
+/// evaluator operations check it up front instead of failing inside SEAL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Exact integer arithmetic modulo the plain modulus (BatchEncoder)
+    Bfv,
+    /// Approximate arithmetic on real numbers (CKKSEncoder)
+    Ckks,
+}
+
+impl std::fmt::Display for Scheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scheme::Bfv => write!(f, "BFV"),
+            Scheme::Ckks => write!(f, "CKKS"),
+        }
+    }
+}
+
+/// Identifies a parameter set: equal for contexts built with the same scheme,
+/// degree, coefficient and plain moduli, whatever their keys
+///
+/// This is SEAL's `parms_id`, a 256-bit hash of the parameters. Unlike
+/// `Context::id` it says nothing about keys, so it is the key for caching
+/// anything derived from parameters alone (never from keys).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParamsId([u64; 4]);
+
+impl std::fmt::Display for ParamsId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for word in self.0 {
+            write!(f, "{:016x}", word)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================
+// Context Presets
+// ============================================
+/// Vetted BFV parameter sets, so callers don't need to pick magic numbers
+///
+/// All presets use SEAL's default coefficient modulus for their degree, which
+/// meets 128-bit security (HomomorphicEncryption.org standard), and a batching-friendly
+/// plain modulus (prime, = 1 mod 2 * degree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextPreset {
+    /// degree 4096, 109-bit modulus, plain modulus 40961 (16 bits).
+    /// Multiplicative depth ~1, 4096 slots. Additions and a single multiply.
+    FastLowDepth,
+    /// degree 8192, 218-bit modulus, plain modulus 1032193 (20 bits).
+    /// Multiplicative depth ~3, 8192 slots. Typical medical statistics
+    /// (sums, means, weighted scores).
+    BalancedMedical,
+    /// degree 16384, 438-bit modulus, plain modulus 786433 (20 bits).
+    /// Multiplicative depth ~8, 16384 slots. Polynomial activations and
+    /// multi-layer models; noticeably slower and larger.
+    DeepML,
+}
+
+impl ContextPreset {
+    /// (poly_modulus_degree, coeff_modulus bit sizes, plain_modulus)
+    pub fn parameters(self) -> (u64, &'static [u64], u64) {
+        match self {
+            ContextPreset::FastLowDepth => (4096, &[36, 36, 37], 40961),
+            ContextPreset::BalancedMedical => (8192, &[43, 43, 44, 44, 44], 1032193),
+            ContextPreset::DeepML => (16384, &[48, 48, 48, 49, 49, 49, 49, 49, 49], 786433),
+        }
+    }
+}
+
+impl Context {
+    /// Create a new SEAL context with BFV scheme
+    /// 
+    /// # Parameters
+    /// - poly_modulus_degree: Polynomial modulus degree (e.g., 4096, 8192)
+    /// - plain_modulus: Plaintext modulus for BFV. `BatchEncoder` only works if it
+    ///   is a prime congruent to 1 mod 2 * poly_modulus_degree (1032193 is, for
+    ///   degrees up to 8192); `new_batching` picks one
+    pub fn new(poly_modulus_degree: u64, plain_modulus: u64) -> Result<Self> {
+        // Standard coefficient modulus for given poly degree
+        let coeff_modulus = [36, 36, 37]; // bits per prime (109 bits total)
+        Self::with_coeff_modulus(poly_modulus_degree, &coeff_modulus, plain_modulus)
+    }
+
+    /// Create a BFV context with a batching-friendly plain modulus
+    ///
+    /// SEAL picks a `plain_modulus_bits`-bit prime congruent to 1 mod
+    /// 2 * poly_modulus_degree, so `BatchEncoder` always works. Read the chosen
+    /// prime back with `plain_modulus()`. Such a prime needs more bits than
+    /// log2(2 * poly_modulus_degree); SEAL allows at most 60.
+    pub fn new_batching(poly_modulus_degree: u64, plain_modulus_bits: u32) -> Result<Self> {
+        let coeff_modulus: [u64; 3] = [36, 36, 37]; // same as Context::new
+        if !(2..=60).contains(&plain_modulus_bits) {
+            return Err(SealError::InvalidParameter(format!(
+                "plain_modulus_bits must be between 2 and 60, got {}",
+                plain_modulus_bits
+            )));
+        }
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_batching_context(
+                poly_modulus_degree,
+                coeff_modulus.as_ptr(),
+                coeff_modulus.len(),
+                plain_modulus_bits as i32,
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme: Scheme::Bfv })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "no {}-bit batching prime for poly_modulus_degree {}",
+                plain_modulus_bits, poly_modulus_degree
+            )))
+    }
+
+    /// Create a BFV context from a named parameter preset
+    pub fn from_preset(preset: ContextPreset) -> Result<Self> {
+        let (poly_modulus_degree, coeff_modulus, plain_modulus) = preset.parameters();
+        Self::with_coeff_modulus(poly_modulus_degree, coeff_modulus, plain_modulus)
+    }
+
+    /// Create a BFV context whose coefficient modulus is exactly `primes`, e.g.
+    /// to reproduce a paper's parameters down to the prime
+    ///
+    /// Each prime must be NTT-compatible: prime, at most 60 bits, and congruent
+    /// to 1 mod 2 * poly_modulus_degree. `InvalidParameter` names the first one
+    /// that isn't; SEAL's own checks (distinct primes, security bound for the
+    /// total size) come after.
+    pub fn with_explicit_primes(poly_modulus_degree: u64, primes: &[u64], plain_modulus: u64) -> Result<Self> {
+        if !poly_modulus_degree.is_power_of_two() {
+            return Err(SealError::InvalidParameter(format!(
+                "poly_modulus_degree must be a power of two, got {}",
+                poly_modulus_degree
+            )));
+        }
+        if primes.is_empty() {
+            return Err(SealError::InvalidParameter("with_explicit_primes needs at least one prime".to_string()));
+        }
+        let modulus = 2 * poly_modulus_degree;
+        for &prime in primes {
+            if prime >= 1 << 60 || !is_prime(prime) {
+                return Err(SealError::InvalidParameter(format!(
+                    "coefficient modulus {} is not a prime of at most 60 bits",
+                    prime
+                )));
+            }
+            if prime % modulus != 1 {
+                return Err(SealError::InvalidParameter(format!(
+                    "coefficient modulus prime {} is not 1 mod {} (2 * poly_modulus_degree), so not NTT-compatible",
+                    prime, modulus
+                )));
+            }
+        }
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_context_from_primes(
+                poly_modulus_degree,
+                primes.as_ptr(),
+                primes.len(),
+                plain_modulus,
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme: Scheme::Bfv })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "SEAL rejected primes {:?} for degree {} with plain modulus {}",
+                primes, poly_modulus_degree, plain_modulus
+            )))
+    }
+
+    fn with_coeff_modulus(poly_modulus_degree: u64, coeff_modulus: &[u64], plain_modulus: u64) -> Result<Self> {
+        // Calls C++ seal_create_context function via FFI (marked unsafe because it’s a raw pointer)
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_context(
+                poly_modulus_degree,
+                coeff_modulus.as_ptr(),
+                coeff_modulus.len(),
+                plain_modulus,
+            ))
+        };
+        // If the pointer returned from C++ is valid, store it inside a Context.
+        // If it’s null, return a NullPointer error.
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme: Scheme::Bfv })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// A BFV context that can encrypt and evaluate but holds no secret key:
+    /// only `public_key` (from `PublicKey::to_bytes` on a context with the same
+    /// parameters) is loaded, and no key generator is created. Decryptors, the
+    /// secret key and evaluation keys can't be made from it; load the key
+    /// owner's with `RelinKeys::from_bytes` / `GaloisKeys::from_bytes`.
+    ///
+    /// Same coefficient modulus as `new`. InvalidParameter if `public_key`
+    /// isn't a valid key for these parameters.
+    pub fn with_public_key(poly_modulus_degree: u64, plain_modulus: u64, public_key: &[u8]) -> Result<Self> {
+        let coeff_modulus = [36, 36, 37]; // same as Context::new
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_public_context(
+                poly_modulus_degree,
+                coeff_modulus.as_ptr(),
+                coeff_modulus.len(),
+                plain_modulus,
+                public_key.as_ptr(),
+                public_key.len(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme: Scheme::Bfv })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "not a valid public key for degree {}, plain modulus {}",
+                poly_modulus_degree, plain_modulus
+            )))
+    }
+
+    /// False for a context made by `with_public_key`
+    pub fn has_secret_key(&self) -> bool {
+        unsafe { ffi!(bindings::seal_context_has_secret_key(self.ptr.as_ptr())) == 1 }
+    }
+
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
+    }
+
+    /// Unique per Context, and so per secret key: two contexts with the same
+    /// parameters still have different ids
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Same for every context with these parameters (see `ParamsId`)
+    pub fn params_id(&self) -> ParamsId {
+        let mut words = [0u64; 4];
+        unsafe {
+            ffi!(bindings::seal_context_params_id(self.ptr.as_ptr(), words.as_mut_ptr()));
+        }
+        ParamsId(words)
+    }
+
+    /// InvalidParameter unless this context uses `scheme`; `what` names the caller
+    fn require_scheme(&self, scheme: Scheme, what: &str) -> Result<()> {
+        if self.scheme != scheme {
+            return Err(SealError::InvalidParameter(format!(
+                "{} needs a {} context, got {}",
+                what, scheme, self.scheme
+            )));
+        }
+        Ok(())
+    }
+
+    /// Plaintext modulus t (0 for CKKS contexts)
+    pub fn plain_modulus(&self) -> u64 {
+        unsafe {
+            ffi!(bindings::seal_context_plain_modulus(self.ptr.as_ptr()))
+        }
+    }
+
+    /// Polynomial modulus degree N
+    pub fn poly_modulus_degree(&self) -> u64 {
+        unsafe {
+            ffi!(bindings::seal_context_poly_modulus_degree(self.ptr.as_ptr()))
+        }
+    }
+
+    /// How many values one plaintext holds: N for BFV (a 2 x N/2 matrix of
+    /// integers), N/2 for CKKS (one real or complex number per slot). Matches
+    /// `BatchEncoder::slot_count` / `CKKSEncoder::slot_count` for this context.
+    pub fn slot_count(&self) -> usize {
+        let degree = self.poly_modulus_degree() as usize;
+        match self.scheme {
+            Scheme::Bfv => degree,
+            Scheme::Ckks => degree / 2,
+        }
+    }
+
+    /// Create a new SEAL context with the CKKS scheme (approximate real numbers)
+    ///
+    /// # Parameters
+    /// - poly_modulus_degree: Polynomial modulus degree (e.g., 8192, 16384)
+    /// - coeff_modulus_bits: bit size of each prime, e.g. [60, 40, 40, 60].
+    ///   Every middle prime is one multiplication level; they should match the
+    ///   encoder scale (40 bits for a 2^40 scale)
+    pub fn new_ckks(poly_modulus_degree: u64, coeff_modulus_bits: &[u64]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_ckks_context(
+                poly_modulus_degree,
+                coeff_modulus_bits.as_ptr(),
+                coeff_modulus_bits.len(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme: Scheme::Ckks })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "SEAL rejected CKKS parameters (degree {}, primes {:?})",
+                poly_modulus_degree, coeff_modulus_bits
+            )))
+    }
+
+    /// Start building a context with named, validated parameters
+    pub fn builder() -> ContextBuilder {
+        ContextBuilder::default()
+    }
+
+    /// Smallest CKKS context that supports `depth` multiplications (each
+    /// followed by a rescale) with about `precision_bits` bits of precision
+    ///
+    /// The scale primes get `precision_bits + CKKS_AUTO_NOISE_BITS` bits, the
+    /// first and special primes up to 20 more (the integer part of the values).
+    /// Encode at `Context::ckks_auto_scale(precision_bits)`. InvalidParameter if
+    /// no degree up to 32768 fits the chain at `security`.
+    pub fn ckks_auto(depth: u32, precision_bits: u32, security: SecurityLevel) -> Result<Self> {
+        if !(1..=CKKS_AUTO_MAX_PRECISION_BITS).contains(&precision_bits) {
+            return Err(SealError::InvalidParameter(format!(
+                "ckks_auto: precision_bits must be between 1 and {}, got {}",
+                CKKS_AUTO_MAX_PRECISION_BITS, precision_bits
+            )));
+        }
+        let scale_bits = u64::from(precision_bits + CKKS_AUTO_NOISE_BITS);
+        let outer_bits = (scale_bits + 20).min(60);
+        let mut coeff_modulus_bits = vec![outer_bits];
+        coeff_modulus_bits.extend(std::iter::repeat_n(scale_bits, depth as usize));
+        coeff_modulus_bits.push(outer_bits);
+        let total_bits: u64 = coeff_modulus_bits.iter().sum();
+
+        let degree = [1024, 2048, 4096, 8192, 16384, 32768]
+            .into_iter()
+            .find(|&n| security.max_coeff_modulus_bits(n).is_some_and(|max| total_bits <= max))
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "ckks_auto: depth {} at {} bits of precision needs {} coefficient modulus bits, \
+                 more than any degree allows at {:?}",
+                depth, precision_bits, total_bits, security
+            )))?;
+
+        Context::builder()
+            .scheme(Scheme::Ckks)
+            .poly_modulus_degree(degree)
+            .coeff_modulus_bits(&coeff_modulus_bits)
+            .security_level(security)
+            .build()
+    }
+
+    /// Encoder scale for a `ckks_auto` context with this `precision_bits`
+    pub fn ckks_auto_scale(precision_bits: u32) -> f64 {
+        2f64.powi((precision_bits + CKKS_AUTO_NOISE_BITS) as i32)
+    }
+}
+
+/// Bits of the CKKS scale `Context::ckks_auto` sets aside for encoding and
+/// rescaling noise, on top of the requested precision
+pub const CKKS_AUTO_NOISE_BITS: u32 = 20;
+// Leaves the scale primes at most 50 bits, so the integer part keeps 10
+const CKKS_AUTO_MAX_PRECISION_BITS: u32 = 30;
+
+// ============================================
+// Context Builder
+// ============================================
+/// Security level the coefficient modulus has to meet
+/// (HomomorphicEncryption.org standard, classical attacks)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SecurityLevel {
+    #[default]
+    TC128,
+    TC192,
+    TC256,
+}
+
+impl SecurityLevel {
+    fn bits(self) -> i32 {
+        match self {
+            SecurityLevel::TC128 => 128,
+            SecurityLevel::TC192 => 192,
+            SecurityLevel::TC256 => 256,
+        }
+    }
+
+    /// Largest total coefficient modulus (in bits) SEAL allows for this degree
+    fn max_coeff_modulus_bits(self, poly_modulus_degree: u64) -> Option<u64> {
+        let table: [u64; 6] = match self {
+            SecurityLevel::TC128 => [27, 54, 109, 218, 438, 881],
+            SecurityLevel::TC192 => [19, 37, 75, 152, 305, 611],
+            SecurityLevel::TC256 => [14, 29, 58, 118, 237, 476],
+        };
+        [1024, 2048, 4096, 8192, 16384, 32768]
+            .iter()
+            .position(|&n| n == poly_modulus_degree)
+            .map(|i| table[i])
+    }
+}
+
+/// Named alternative to the positional `Context` constructors
+///
+/// ```ignore
+/// let ctx = Context::builder()
+///     .poly_modulus_degree(8192)
+///     .plain_modulus(1032193)
+///     .coeff_modulus_bits(&[43, 43, 44, 44, 44])
+///     .build()?;
+/// ```
+///
+/// The scheme defaults to BFV and the security level to 128 bits. A BFV context
+/// without `coeff_modulus_bits` gets the same primes as `Context::new`; CKKS has
+/// no sensible default and needs them set.
+#[derive(Debug, Clone, Default)]
+pub struct ContextBuilder {
+    poly_modulus_degree: Option<u64>,
+    plain_modulus: Option<u64>,
+    coeff_modulus_bits: Option<Vec<u64>>,
+    scheme: Option<Scheme>,
+    security_level: SecurityLevel,
+}
+
+impl ContextBuilder {
+    pub fn poly_modulus_degree(mut self, poly_modulus_degree: u64) -> Self {
+        self.poly_modulus_degree = Some(poly_modulus_degree);
+        self
+    }
+
+    /// BFV only
+    pub fn plain_modulus(mut self, plain_modulus: u64) -> Self {
+        self.plain_modulus = Some(plain_modulus);
+        self
+    }
+
+    /// Bit size of each coefficient modulus prime
+    pub fn coeff_modulus_bits(mut self, bits: &[u64]) -> Self {
+        self.coeff_modulus_bits = Some(bits.to_vec());
+        self
+    }
+
+    pub fn scheme(mut self, scheme: Scheme) -> Self {
+        self.scheme = Some(scheme);
+        self
+    }
+
+    pub fn security_level(mut self, security_level: SecurityLevel) -> Self {
+        self.security_level = security_level;
+        self
+    }
+
+    /// Check the combination and create the context
+    pub fn build(&self) -> Result<Context> {
+        let invalid = |msg: String| Err(SealError::InvalidParameter(msg));
+        let scheme = self.scheme.unwrap_or(Scheme::Bfv);
+
+        let Some(poly_modulus_degree) = self.poly_modulus_degree else {
+            return invalid("ContextBuilder: poly_modulus_degree is required".to_string());
+        };
+        let Some(max_bits) = self.security_level.max_coeff_modulus_bits(poly_modulus_degree) else {
+            return invalid(format!(
+                "ContextBuilder: poly_modulus_degree must be a power of two from 1024 to 32768, got {}",
+                poly_modulus_degree
+            ));
+        };
+
+        let plain_modulus = match (scheme, self.plain_modulus) {
+            (Scheme::Bfv, None) => return invalid("ContextBuilder: plain_modulus is required for BFV".to_string()),
+            (Scheme::Bfv, Some(t)) if t < 2 => {
+                return invalid(format!("ContextBuilder: plain_modulus must be at least 2, got {}", t));
+            }
+            (Scheme::Bfv, Some(t)) => t,
+            (Scheme::Ckks, Some(_)) => {
+                return invalid("ContextBuilder: CKKS has no plain_modulus; remove .plain_modulus()".to_string());
+            }
+            (Scheme::Ckks, None) => 0,
+        };
+
+        let coeff_modulus_bits = match (&self.coeff_modulus_bits, scheme) {
+            (Some(bits), _) => bits.clone(),
+            (None, Scheme::Bfv) => vec![36, 36, 37],
+            (None, Scheme::Ckks) => {
+                return invalid("ContextBuilder: coeff_modulus_bits is required for CKKS (e.g. [60, 40, 40, 60])".to_string());
+            }
+        };
+        if coeff_modulus_bits.is_empty() || coeff_modulus_bits.iter().any(|&b| b == 0 || b > 60) {
+            return invalid(format!(
+                "ContextBuilder: coeff_modulus_bits needs at least one prime of 1-60 bits, got {:?}",
+                coeff_modulus_bits
+            ));
+        }
+        let total_bits: u64 = coeff_modulus_bits.iter().sum();
+        if total_bits > max_bits {
+            return invalid(format!(
+                "ContextBuilder: {} coefficient modulus bits exceed the {}-bit maximum for degree {} at {:?}",
+                total_bits, max_bits, poly_modulus_degree, self.security_level
+            ));
+        }
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_context_with_security(
+                match scheme {
+                    Scheme::Bfv => 0,
+                    Scheme::Ckks => 1,
+                },
+                poly_modulus_degree,
+                coeff_modulus_bits.as_ptr(),
+                coeff_modulus_bits.len(),
+                plain_modulus,
+                self.security_level.bits(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Context { ptr, id: new_context_id(), scheme })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "SEAL rejected {} parameters (degree {}, primes {:?}, plain modulus {})",
+                scheme, poly_modulus_degree, coeff_modulus_bits, plain_modulus
+            )))
+    }
+}
+
+impl Context {
+    /// Copy of this context's secret key (e.g. to generate KSwitchKeys)
+    pub fn secret_key(&self) -> Result<SecretKey> {
+        let ptr = unsafe { ffi!(bindings::seal_get_secret_key(self.ptr.as_ptr())) };
+
+        NonNull::new(ptr)
+            .map(|ptr| SecretKey { ptr, context_id: self.id })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Copy of this context's public key
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let ptr = unsafe { ffi!(bindings::seal_get_public_key(self.ptr.as_ptr())) };
+
+        NonNull::new(ptr)
+            .map(|ptr| PublicKey { ptr, context_id: self.id })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Whether `public_key` and `secret_key` form a key pair: a random value
+    /// encrypted under one must decrypt unchanged with the other. A cheap check
+    /// for corrupted or mixed-up keys before a long computation.
+    ///
+    /// InvalidParameter if either key was made for different parameters.
+    pub fn verify_key_pair(&self, public_key: &PublicKey, secret_key: &SecretKey) -> Result<bool> {
+        let status = unsafe {
+            ffi!(bindings::seal_verify_key_pair(self.ptr.as_ptr(), public_key.ptr.as_ptr(), secret_key.ptr.as_ptr()))
+        };
+        match status {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(SealError::InvalidParameter(
+                "the keys were made for different encryption parameters than this context".to_string(),
+            )),
+        }
+    }
+}
+
+// When the Rust Context goes out of scope, 
+// it automatically calls the C++ function to free memory — so the user can’t forget
+impl Drop for Context {
+    fn drop(&mut self) {
+        release_context_id(self.id);
+        unsafe {
+            ffi!(bindings::seal_destroy_context(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// SAFETY: after construction the SEAL context and keys are only read, and the
+// wrapper serializes key generation (relin/galois keys) behind a mutex, so a
+// Context can be shared between threads (e.g. through a ContextPool)
+unsafe impl Send for Context {}
+unsafe impl Sync for Context {}
+
+// ============================================
+// Encryptor
+// ============================================
+// Represents the C++ Encryptor object (handles encryption).
+// The lifetime ties it to its Context: the C++ encryptor was built from the
+// context's keys, so the borrow checker must not let the Context be dropped first.
+pub struct Encryptor<'a> {
+    ptr: NonNull<bindings::SEALEncryptor>,
+    context_id: u64,
+    _context: PhantomData<&'a Context>,
+}
+
+// Creates an encryptor using the existing SEAL context.
+impl<'a> Encryptor<'a> {
+    pub fn new(context: &'a Context) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_encryptor(
+                context.ptr.as_ptr(),
+                std::ptr::null(),
+                0,
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| Encryptor { ptr, context_id: context.id, _context: PhantomData })
+            .ok_or(SealError::NullPointer)
+    }
+    
+    pub fn encrypt(&self, plaintext: &Plaintext) -> Result<Ciphertext> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_encrypt(
+                self.ptr.as_ptr(),
+                plaintext.ptr.as_ptr(),
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context_id })
+            .ok_or(SealError::EncryptionFailed)
+    }
+}
+
+impl Drop for Encryptor<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_encryptor(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Decryptor
+// ============================================
+// Same lifetime rule as Encryptor (holds the context's secret key)
+pub struct Decryptor<'a> {
+    ptr: NonNull<bindings::SEALDecryptor>,
+    scheme: Scheme,
+    context: &'a Context,
+    #[cfg(feature = "fault-injection")]
+    faults: crate::fault::FaultInjector<Plaintext>,
+}
+
+/// A decrypted and decoded ciphertext, in the form its scheme and encoding give
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecryptedValue {
+    /// BFV without batching: the plaintext's constant coefficient (`Plaintext::from_decimal`)
+    Integer(i64),
+    /// Batched BFV, one value per slot
+    IntegerVec(Vec<i64>),
+    /// CKKS, one (approximate) value per slot
+    RealVec(Vec<f64>),
+}
+
+impl<'a> Decryptor<'a> {
+    pub fn new(context: &'a Context) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_decryptor(
+                context.ptr.as_ptr(),
+                std::ptr::null(),
+                0,
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| Decryptor {
+                ptr,
+                scheme: context.scheme,
+                context,
+                #[cfg(feature = "fault-injection")]
+                faults: crate::fault::FaultInjector::new(),
+            })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Arm corrupted decryptions: the armed "error" is the plaintext a
+    /// "decrypt" returns in place of the real one, the way a transient fault
+    /// would go unnoticed
+    #[cfg(feature = "fault-injection")]
+    pub fn faults(&self) -> &crate::fault::FaultInjector<Plaintext> {
+        &self.faults
+    }
+    
+    /// Decrypt, refusing BFV ciphertexts whose noise budget has run out
+    ///
+    /// With no budget left SEAL still "decrypts", to wrong values; this returns
+    /// `NoiseExhausted` instead. CKKS has no budget (its results are approximate
+    /// anyway), so CKKS ciphertexts are always decrypted.
+    pub fn decrypt(&self, ciphertext: &Ciphertext) -> Result<Plaintext> {
+        ciphertext.assert_context_alive();
+        if self.scheme == Scheme::Bfv {
+            let budget = unsafe {
+                ffi!(bindings::seal_invariant_noise_budget(self.ptr.as_ptr(), ciphertext.ptr.as_ptr()))
+            };
+            match budget {
+                0 => return Err(SealError::NoiseExhausted),
+                // SEAL couldn't even measure it: not a ciphertext for this key
+                b if b < 0 => return Err(SealError::DecryptionFailed),
+                _ => {}
+            }
+        }
+        self.decrypt_unchecked(ciphertext)
+    }
+
+    /// Decrypt without the noise budget check, which costs about as much as the
+    /// decryption itself. For timing raw decryption; anything else should use `decrypt`.
+    pub fn decrypt_unchecked(&self, ciphertext: &Ciphertext) -> Result<Plaintext> {
+        ciphertext.assert_context_alive();
+        #[cfg(feature = "fault-injection")]
+        if let Err(corrupted) = self.faults.check("decrypt") {
+            return Ok(corrupted);
+        }
+        let ptr = unsafe {
+            ffi!(bindings::seal_decrypt(
+                self.ptr.as_ptr(),
+                ciphertext.ptr.as_ptr(),
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or(SealError::DecryptionFailed)
+    }
+
+    /// Decrypt twice and fail with `DecryptionMismatch` unless both agree
+    ///
+    /// Decryption is deterministic, so two runs differ only if a transient
+    /// fault (or a tampered process) changed one of them. Costs two decryptions
+    /// plus serializing both plaintexts to compare them.
+    pub fn decrypt_verified(&self, ciphertext: &Ciphertext) -> Result<Plaintext> {
+        let first = self.decrypt(ciphertext)?;
+        let second = self.decrypt(ciphertext)?;
+        if first.to_bytes()? != second.to_bytes()? {
+            return Err(SealError::DecryptionMismatch);
+        }
+        Ok(first)
+    }
+
+    /// Decrypt (with the noise budget check) and decode the way the context's
+    /// scheme calls for, so callers don't have to pick an encoder themselves
+    pub fn decrypt_typed(&self, ciphertext: &Ciphertext) -> Result<DecryptedValue> {
+        let plain = self.decrypt(ciphertext)?;
+        match self.scheme {
+            Scheme::Ckks => CKKSEncoder::new(self.context, ciphertext.scale())?
+                .decode(&plain)
+                .map(DecryptedValue::RealVec),
+            // A BatchEncoder can only be built when the parameters allow batching
+            Scheme::Bfv => match BatchEncoder::new(self.context) {
+                Ok(encoder) => encoder.decode(&plain).map(DecryptedValue::IntegerVec),
+                Err(_) => constant_value(&plain, self.context.plain_modulus()).map(DecryptedValue::Integer),
+            },
+        }
+    }
+
+    /// Remaining noise budget in bits (BFV). At 0 the ciphertext no longer decrypts correctly.
+    pub fn invariant_noise_budget(&self, ciphertext: &Ciphertext) -> Result<u32> {
+        ciphertext.assert_context_alive();
+        let budget = unsafe {
+            ffi!(bindings::seal_invariant_noise_budget(self.ptr.as_ptr(), ciphertext.ptr.as_ptr()))
+        };
+
+        u32::try_from(budget).map_err(|_| SealError::OperationFailed)
+    }
+}
+
+impl Drop for Decryptor<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_decryptor(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// The constant coefficient of an unbatched BFV plaintext, read back in the
+// signed form Plaintext::from_decimal stores (p + v for negative v)
+fn constant_value(plain: &Plaintext, plain_modulus: u64) -> Result<i64> {
+    let hex = plain.to_string()?;
+    if hex.contains('x') {
+        return Err(SealError::InvalidParameter(format!(
+            "plaintext '{}' is a polynomial, not a single integer",
+            hex
+        )));
+    }
+    let value = u64::from_str_radix(hex.trim(), 16).map_err(|_| SealError::OperationFailed)?;
+    if value > plain_modulus / 2 {
+        Ok(value as i64 - plain_modulus as i64)
+    } else {
+        Ok(value as i64)
+    }
+}
+
+// ============================================
+// Batch Encoder
+// ============================================
+// Values are signed and stored in balanced form: with plain modulus p, a slot
+// holds v in [-(p-1)/2, (p-1)/2], negatives as p + v.
+pub struct BatchEncoder {
+    ptr: NonNull<bindings::SEALBatchEncoder>,
+    plain_modulus: u64,
+}
+
+impl BatchEncoder {
+    pub fn new(context: &Context) -> Result<Self> {
+        context.require_scheme(Scheme::Bfv, "BatchEncoder")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_batch_encoder(context.ptr.as_ptr()))
+        };
+        
+        // SEAL's only reason to refuse: batching isn't enabled by these parameters
+        NonNull::new(ptr)
+            .map(|ptr| BatchEncoder { ptr, plain_modulus: context.plain_modulus() })
+            .ok_or_else(|| SealError::InvalidParameter(format!(
+                "plain modulus {} doesn't support batching at poly_modulus_degree {} \
+                 (it must be a prime congruent to 1 mod {}); see Context::new_batching",
+                context.plain_modulus(),
+                context.poly_modulus_degree(),
+                2 * context.poly_modulus_degree()
+            )))
+    }
+    
+    /// Largest magnitude a slot can hold: (p - 1) / 2
+    pub fn max_value(&self) -> i64 {
+        ((self.plain_modulus - 1) / 2) as i64
+    }
+
+    /// Encode a vector of signed integers into a plaintext
+    ///
+    /// Returns `InvalidParameter` for more values than slots, or a value outside
+    /// `[-max_value(), max_value()]` (it would wrap around modulo p).
+    pub fn encode(&self, values: &[i64]) -> Result<Plaintext> {
+        let slot_count = self.slot_count();
+        if values.len() > slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "{} values don't fit in {} slots",
+                values.len(),
+                slot_count
+            )));
+        }
+
+        let max = self.max_value();
+        if let Some((i, v)) = values.iter().enumerate().find(|(_, v)| v.unsigned_abs() > max as u64) {
+            return Err(SealError::InvalidParameter(format!(
+                "value {} at index {} is outside [-{}, {}]",
+                v, i, max, max
+            )));
+        }
+
+        // SEAL's signed encode maps v < 0 to p + v, and decode maps back
+        let ptr = unsafe {
+            ffi!(bindings::seal_batch_encode(
+                self.ptr.as_ptr(),
+                values.as_ptr(),
+                values.len(),
+            ))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+    
+    /// Decode a plaintext back to vector of integers
+    pub fn decode(&self, plain: &Plaintext) -> Result<Vec<i64>> {
+        let mut output = vec![0i64; self.slot_count()];
+        let mut output_size = output.len();
+        
+        unsafe {
+            ffi!(bindings::seal_batch_decode(
+                self.ptr.as_ptr(),
+                plain.ptr.as_ptr(),
+                output.as_mut_ptr(),
+                &mut output_size,
+            ));
+        }
+        
+        output.truncate(output_size);
+        Ok(output)
+    }
+    
+    pub fn slot_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_get_slot_count(self.ptr.as_ptr())) }
+    }
+}
+
+impl Drop for BatchEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_batch_encoder(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// CKKS Encoder
+// ============================================
+// Encodes real numbers as fixed-point values scaled by `scale` (e.g. 2^40)
+pub struct CKKSEncoder {
+    ptr: NonNull<bindings::SEALCKKSEncoder>,
+}
+
+impl CKKSEncoder {
+    pub fn new(context: &Context, scale: f64) -> Result<Self> {
+        context.require_scheme(Scheme::Ckks, "CKKSEncoder")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_ckks_encoder(context.ptr.as_ptr(), scale))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| CKKSEncoder { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Encode a vector of real numbers into a plaintext
+    ///
+    /// Returns `InvalidParameter` for more values than slots.
+    pub fn encode(&self, values: &[f64]) -> Result<Plaintext> {
+        let slot_count = self.slot_count();
+        if values.len() > slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "{} values don't fit in {} slots",
+                values.len(),
+                slot_count
+            )));
+        }
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_ckks_encode(self.ptr.as_ptr(), values.as_ptr(), values.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Decode a plaintext back to (approximate) real numbers
+    pub fn decode(&self, plain: &Plaintext) -> Result<Vec<f64>> {
+        let mut output = vec![0f64; self.slot_count()];
+        let mut output_size = output.len();
+
+        unsafe {
+            ffi!(bindings::seal_ckks_decode(
+                self.ptr.as_ptr(),
+                plain.ptr.as_ptr(),
+                output.as_mut_ptr(),
+                &mut output_size,
+            ));
+        }
+
+        output.truncate(output_size);
+        Ok(output)
+    }
+
+    /// Encode complex numbers, one per slot (`slot_count()` = degree / 2 of them)
+    ///
+    /// `encode` is the same with every imaginary part zero.
+    pub fn encode_complex(&self, values: &[Complex<f64>]) -> Result<Plaintext> {
+        let slot_count = self.slot_count();
+        if values.len() > slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "{} values don't fit in {} slots",
+                values.len(),
+                slot_count
+            )));
+        }
+
+        // Complex<f64> is #[repr(C)] { re, im }: the slice already is the
+        // interleaved layout the wrapper expects
+        let ptr = unsafe {
+            ffi!(bindings::seal_ckks_encode_complex(self.ptr.as_ptr(), values.as_ptr().cast::<f64>(), values.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Decode a plaintext back to (approximate) complex numbers, one per slot
+    pub fn decode_complex(&self, plain: &Plaintext) -> Result<Vec<Complex<f64>>> {
+        let mut output = vec![Complex::new(0f64, 0f64); self.slot_count()];
+        let mut output_size = output.len();
+
+        unsafe {
+            ffi!(bindings::seal_ckks_decode_complex(
+                self.ptr.as_ptr(),
+                plain.ptr.as_ptr(),
+                output.as_mut_ptr().cast::<f64>(),
+                &mut output_size,
+            ));
+        }
+
+        output.truncate(output_size);
+        Ok(output)
+    }
+
+    pub fn slot_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_ckks_slot_count(self.ptr.as_ptr())) }
+    }
+}
+
+impl Drop for CKKSEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_ckks_encoder(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Relinearization Keys
+// ============================================
+// Shrink a product back to a normal size-2 ciphertext
+pub struct RelinKeys {
+    ptr: NonNull<bindings::SEALRelinKeys>,
+}
+
+impl RelinKeys {
+    pub fn generate(context: &Context) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_generate_relin_keys(context.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| RelinKeys { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Parameters the keys were generated for; equals the context's `params_id`
+    pub fn params_id(&self) -> ParamsId {
+        let mut words = [0u64; 4];
+        unsafe {
+            ffi!(bindings::seal_relin_keys_params_id(self.ptr.as_ptr(), words.as_mut_ptr()));
+        }
+        ParamsId(words)
+    }
+
+    /// Size in bytes when serialized (an upper bound)
+    pub fn byte_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_relin_keys_byte_count(self.ptr.as_ptr())) }
+    }
+
+    /// Serialize with SEAL's binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![0u8; self.byte_count()];
+        let written = unsafe {
+            ffi!(bindings::seal_relin_keys_save(self.ptr.as_ptr(), bytes.as_mut_ptr(), bytes.len()))
+        };
+        if written == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+
+    /// Load keys written by `to_bytes` for `context`'s parameters
+    pub fn from_bytes(context: &Context, data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_relin_keys_load(context.ptr.as_ptr(), data.as_ptr(), data.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| RelinKeys { ptr })
+            .ok_or_else(|| SealError::InvalidParameter("not valid relinearization keys for this context".to_string()))
+    }
+}
+
+impl Drop for RelinKeys {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_relin_keys(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// SAFETY: evaluation keys are never modified after generation; the evaluator
+// only reads them, so they can be shared between threads
+unsafe impl Send for RelinKeys {}
+unsafe impl Sync for RelinKeys {}
+
+// ============================================
+// Galois Keys
+// ============================================
+pub struct GaloisKeys {
+    ptr: NonNull<bindings::SEALGaloisKeys>,
+}
+
+impl GaloisKeys {
+    pub fn generate(context: &Context) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_generate_galois_keys(context.ptr.as_ptr()))
+        };
+        
+        NonNull::new(ptr)
+            .map(|ptr| GaloisKeys { ptr })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Parameters the keys were generated for; equals the context's `params_id`
+    pub fn params_id(&self) -> ParamsId {
+        let mut words = [0u64; 4];
+        unsafe {
+            ffi!(bindings::seal_galois_keys_params_id(self.ptr.as_ptr(), words.as_mut_ptr()));
+        }
+        ParamsId(words)
+    }
+
+    /// Size in bytes when serialized (an upper bound); one key per rotation step,
+    /// so usually by far the largest key material
+    pub fn byte_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_galois_keys_byte_count(self.ptr.as_ptr())) }
+    }
+
+    /// Serialize with SEAL's binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![0u8; self.byte_count()];
+        let written = unsafe {
+            ffi!(bindings::seal_galois_keys_save(self.ptr.as_ptr(), bytes.as_mut_ptr(), bytes.len()))
+        };
+        if written == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+
+    /// Load keys written by `to_bytes` for `context`'s parameters
+    pub fn from_bytes(context: &Context, data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_galois_keys_load(context.ptr.as_ptr(), data.as_ptr(), data.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| GaloisKeys { ptr })
+            .ok_or_else(|| SealError::InvalidParameter("not valid Galois keys for this context".to_string()))
+    }
+}
+
+impl Drop for GaloisKeys {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_galois_keys(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// SAFETY: as for RelinKeys
+unsafe impl Send for GaloisKeys {}
+unsafe impl Sync for GaloisKeys {}
+
+// ============================================
+// Bootstrapping Keys
+// ============================================
+/// Keys for CKKS bootstrapping, which SEAL does not implement
+///
+/// No SEAL parameter set supports bootstrapping, so this type has no values:
+/// `generate` always fails with an explanation, and `Evaluator::bootstrap`
+/// (which needs one) can never be reached with a ciphertext. Deep CKKS
+/// circuits have to fit the coefficient modulus chain, or be refreshed by the
+/// key holder decrypting and re-encrypting.
+pub enum BootstrapKeys {}
+
+impl BootstrapKeys {
+    pub fn generate(context: &Context) -> Result<Self> {
+        context.require_scheme(Scheme::Ckks, "bootstrapping")?;
+        Err(SealError::InvalidParameter(format!(
+            "this context (degree {}) can't be bootstrapped: SEAL has no CKKS bootstrapping; \
+             use a longer coefficient modulus chain or refresh by decrypting and re-encrypting",
+            context.poly_modulus_degree()
+        )))
+    }
+}
+
+// ============================================
+// Library Version
+// ============================================
+/// Version of the SEAL library the wrapper was built against, e.g. "4.1.1"
+pub fn seal_version() -> String {
+    unsafe { CStr::from_ptr(ffi!(bindings::seal_version())) }.to_string_lossy().into_owned()
+}
+
+// ============================================
+// Secret / Public Keys
+// ============================================
+// Standalone copies of a Context's keys. Each remembers which context it came from.
+pub struct SecretKey {
+    ptr: NonNull<bindings::SEALSecretKey>,
+    context_id: u64,
+}
+
+impl SecretKey {
+    /// Size in bytes when serialized (an upper bound)
+    pub fn byte_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_secret_key_byte_count(self.ptr.as_ptr())) }
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_secret_key(self.ptr.as_ptr()));
+        }
+    }
+}
+
+pub struct PublicKey {
+    ptr: NonNull<bindings::SEALPublicKey>,
+    context_id: u64,
+}
+
+impl PublicKey {
+    /// Size in bytes when serialized (an upper bound; `to_bytes` may be shorter)
+    pub fn byte_count(&self) -> usize {
+        unsafe { ffi!(bindings::seal_public_key_byte_count(self.ptr.as_ptr())) }
+    }
+
+    /// Serialize with SEAL's binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![0u8; self.byte_count()];
+        let written = unsafe {
+            ffi!(bindings::seal_public_key_save(self.ptr.as_ptr(), bytes.as_mut_ptr(), bytes.len()))
+        };
+        if written == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+}
+
+impl Drop for PublicKey {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_public_key(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Seeded Key Generation
+// ============================================
+// A Context generates its own keys from the OS RNG. KeyGenerator::with_seed
+// makes a separate key pair for the same parameters from a fixed seed, so
+// tests can pin key material and compare serialized keys across runs.
+pub struct KeyGenerator {
+    ptr: NonNull<bindings::SEALKeyGenerator>,
+    // A new secret key, so an id of its own (see Context::id), live until the
+    // generator is dropped
+    context_id: u64,
+}
+
+impl KeyGenerator {
+    /// Deterministic keys for `context`'s parameters: the same seed always gives
+    /// byte-identical keys
+    ///
+    /// TEST ONLY. Anyone who knows the seed knows the secret key, and every
+    /// random value is drawn from the same seeded stream. Never use these keys
+    /// for real data.
+    pub fn with_seed(context: &Context, seed: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_keygen_create_seeded(context.ptr.as_ptr(), seed.as_ptr(), seed.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| KeyGenerator { ptr, context_id: new_context_id() })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    pub fn secret_key(&self) -> Result<SecretKey> {
+        let ptr = unsafe { ffi!(bindings::seal_keygen_secret_key(self.ptr.as_ptr())) };
+
+        NonNull::new(ptr)
+            .map(|ptr| SecretKey { ptr, context_id: self.context_id })
+            .ok_or(SealError::NullPointer)
+    }
+
+    pub fn public_key(&self) -> Result<PublicKey> {
+        let ptr = unsafe { ffi!(bindings::seal_keygen_public_key(self.ptr.as_ptr())) };
+
+        NonNull::new(ptr)
+            .map(|ptr| PublicKey { ptr, context_id: self.context_id })
+            .ok_or(SealError::NullPointer)
+    }
+}
+
+impl Drop for KeyGenerator {
+    fn drop(&mut self) {
+        release_context_id(self.context_id);
+        unsafe {
+            ffi!(bindings::seal_keygen_destroy(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Key Switching Keys
+// ============================================
+// Moves ciphertexts from one context's secret key to another's
+// (e.g. hospital A's key -> a shared regional key)
+pub struct KSwitchKeys {
+    ptr: NonNull<bindings::SEALKSwitchKeys>,
+    from_context_id: u64,
+    to_context_id: u64,
+}
+
+impl KSwitchKeys {
+    /// Generate keys that switch ciphertexts encrypted under `from` to `to`
+    ///
+    /// Only the target's public key is needed, so the data owner can produce these
+    /// without learning the other party's secret key. Both contexts must use the
+    /// same encryption parameters.
+    pub fn generate(from: &SecretKey, to: &PublicKey) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_generate_kswitch_keys(from.ptr.as_ptr(), to.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| KSwitchKeys {
+                ptr,
+                from_context_id: from.context_id,
+                to_context_id: to.context_id,
+            })
+            .ok_or_else(|| SealError::InvalidParameter(
+                "key switching needs two keys with the same encryption parameters".to_string(),
+            ))
+    }
+}
+
+impl Drop for KSwitchKeys {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_kswitch_keys(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Rotation
+// ============================================
+pub fn rotate_rows(
+    context: &Context,
+    cipher: &Ciphertext,
+    steps: i32,
+    galois_keys: &GaloisKeys,
+) -> Result<Ciphertext> {
+    check_same_context(context, &[cipher])?;
+
+    let ptr = unsafe {
+        ffi!(bindings::seal_rotate_rows(
+            context.ptr.as_ptr(),
+            cipher.ptr.as_ptr(),
+            steps,
+            galois_keys.ptr.as_ptr(),
+        ))
+    };
+    
+    NonNull::new(ptr)
+        .map(|ptr| Ciphertext { ptr, context_id: context.id })
+        .ok_or(SealError::OperationFailed)
+}
+
+// ============================================
+// Plaintext
+// ============================================
+pub struct Plaintext {
+    ptr: NonNull<bindings::SEALPlaintext>,
+}
+
+/// Slots `Plaintext::decoded_string` shows before the ellipsis
+pub const DECODED_STRING_SLOTS: usize = 5;
+
+impl Plaintext {
+    /// Parse SEAL's polynomial syntax, e.g. "142" or "1x^2 + 3Fx^1 + 7" (hex coefficients)
+    ///
+    /// Returns `InvalidParameter` naming the malformed term.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        for term in hex.split(" + ") {
+            let (coeff, power) = match term.split_once("x^") {
+                Some((coeff, power)) => (coeff, Some(power)),
+                None => (term, None),
+            };
+            let valid = !coeff.is_empty()
+                && coeff.chars().all(|c| c.is_ascii_hexdigit())
+                && power.is_none_or(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+            if !valid {
+                return Err(SealError::InvalidParameter(format!(
+                    "malformed term '{}' in plaintext polynomial '{}'",
+                    term, hex
+                )));
+            }
+        }
+
+        let c_hex = CString::new(hex)
+            .map_err(|_| SealError::InvalidParameter("hex string contains a NUL byte".to_string()))?;
+        
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_plaintext(c_hex.as_ptr()))
+        };
+        
+        // Well-formed terms SEAL still refuses (e.g. powers out of order)
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or_else(|| SealError::InvalidParameter(format!("SEAL rejected plaintext polynomial '{}'", hex)))
+    }
+
+    /// Constant plaintext from a base-10 integer, e.g. "322" or "-5"
+    ///
+    /// Negative values are stored as plain_modulus + value, so the context must be
+    /// BFV and |value| must be below its plain modulus.
+    pub fn from_decimal(context: &Context, s: &str) -> Result<Self> {
+        context.require_scheme(Scheme::Bfv, "Plaintext::from_decimal")?;
+        let plain_modulus = context.plain_modulus() as i128;
+
+        let value: i128 = s.trim().parse().map_err(|_| {
+            SealError::InvalidParameter(format!("'{}' is not a decimal integer", s))
+        })?;
+        if value.abs() >= plain_modulus {
+            return Err(SealError::InvalidParameter(format!(
+                "{} is out of range for plain modulus {}",
+                value, plain_modulus
+            )));
+        }
+
+        let reduced = if value < 0 { plain_modulus + value } else { value };
+        Self::from_hex(&format!("{:X}", reduced))
+    }
+    
+    pub fn to_string(&self) -> Result<String> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_plaintext_to_string(self.ptr.as_ptr()))
+        };
+        
+        if ptr.is_null() {
+            return Err(SealError::NullPointer);
+        }
+        
+        let c_str = unsafe { CStr::from_ptr(ptr) };
+        Ok(c_str.to_string_lossy().into_owned())
+    }
+
+    /// The slot values as a list, e.g. "[10, 20, 30, 40, 50, ...]"
+    ///
+    /// For batched plaintexts, where `to_string`'s polynomial says nothing about
+    /// the values encoded. Only the first `DECODED_STRING_SLOTS` slots are shown.
+    pub fn decoded_string(&self, encoder: &BatchEncoder) -> Result<String> {
+        let values = encoder.decode(self)?;
+        let shown: Vec<String> = values.iter().take(DECODED_STRING_SLOTS).map(|v| v.to_string()).collect();
+        let ellipsis = if values.len() > DECODED_STRING_SLOTS { ", ..." } else { "" };
+        Ok(format!("[{}{}]", shown.join(", "), ellipsis))
+    }
+
+    /// Serialize with SEAL's binary format (lossless, unlike `to_string`)
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let capacity = unsafe {
+            ffi!(bindings::seal_plaintext_save_size(self.ptr.as_ptr()))
+        };
+        if capacity == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        let mut bytes = vec![0u8; capacity];
+        let written = unsafe {
+            ffi!(bindings::seal_plaintext_save(self.ptr.as_ptr(), bytes.as_mut_ptr(), bytes.len()))
+        };
+        if written == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+
+    /// Load a plaintext written by `to_bytes` under the same encryption parameters
+    pub fn from_bytes(context: &Context, data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_plaintext_load(context.ptr.as_ptr(), data.as_ptr(), data.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Plaintext { ptr })
+            .ok_or_else(|| SealError::InvalidParameter("not a valid plaintext for this context".to_string()))
+    }
+}
+
+impl Drop for Plaintext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_plaintext(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Ciphertext
+// ============================================
+/// Where a ciphertext's bytes go: `num_polys` polynomials of `coeffs_per_poly`
+/// coefficients, each stored as one 64-bit word per prime still in the modulus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FootprintReport {
+    pub num_polys: usize,
+    pub coeffs_per_poly: u64,
+    pub bytes_per_coeff: usize,
+    /// Serialized size (`byte_count`), an upper bound that includes headers
+    pub total_bytes: usize,
+}
+
+impl FootprintReport {
+    /// Size of the polynomial data alone (what the ciphertext holds in memory)
+    pub fn poly_bytes(&self) -> usize {
+        self.num_polys * self.coeffs_per_poly as usize * self.bytes_per_coeff
+    }
+}
+
+pub struct Ciphertext {
+    ptr: NonNull<bindings::SEALCiphertext>,
+    context_id: u64, // id of the Context this ciphertext was created under
+}
+
+impl Ciphertext {
+    // Panics if this ciphertext's Context has been dropped (debug builds only)
+    #[cfg(debug_assertions)]
+    fn assert_context_alive(&self) {
+        let alive = LIVE_CONTEXTS.lock().unwrap_or_else(PoisonError::into_inner).contains(&self.context_id);
+        assert!(
+            alive,
+            "context already dropped: ciphertext from context #{} used after its Context",
+            self.context_id
+        );
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    fn assert_context_alive(&self) {}
+
+    /// Get the number of polynomials in the ciphertext (usually 2 for fresh encryptions)
+    pub fn size(&self) -> usize {
+        unsafe {
+            ffi!(bindings::seal_ciphertext_size(self.ptr.as_ptr()))
+        }
+    }
+    
+    /// Get the polynomial modulus degree (number of coefficients per polynomial)
+    pub fn coeff_count(&self) -> u64 {
+        unsafe {
+            ffi!(bindings::seal_ciphertext_coeff_count(self.ptr.as_ptr()))
+        }
+    }
+
+    /// Number of primes left in the coefficient modulus (one fewer per rescale / mod switch)
+    pub fn coeff_modulus_size(&self) -> usize {
+        unsafe {
+            ffi!(bindings::seal_ciphertext_coeff_modulus_size(self.ptr.as_ptr()))
+        }
+    }
+
+    /// `byte_count` broken down by polynomial and by prime, e.g. to see how
+    /// much relinearizing or dropping a level saves
+    pub fn memory_footprint(&self) -> FootprintReport {
+        FootprintReport {
+            num_polys: self.size(),
+            coeffs_per_poly: self.coeff_count(),
+            bytes_per_coeff: self.coeff_modulus_size() * std::mem::size_of::<u64>(),
+            total_bytes: self.byte_count(),
+        }
+    }
+    
+    /// Get the total size in bytes when serialized
+    pub fn byte_count(&self) -> usize {
+        unsafe {
+            ffi!(bindings::seal_ciphertext_byte_count(self.ptr.as_ptr()))
+        }
+    }
+    
+    /// Serialize with SEAL's binary format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![0u8; self.byte_count()];
+        let written = unsafe {
+            ffi!(bindings::seal_ciphertext_save(self.ptr.as_ptr(), bytes.as_mut_ptr(), bytes.len()))
+        };
+        if written == 0 {
+            return Err(SealError::OperationFailed);
+        }
+
+        bytes.truncate(written);
+        Ok(bytes)
+    }
+
+    /// Load a ciphertext written by `to_bytes` under the same encryption parameters
+    ///
+    /// Safe on untrusted input: anything malformed, or valid for other parameters,
+    /// is an `InvalidParameter` error (see fuzz/ for the harness that checks this).
+    pub fn from_bytes(context: &Context, data: &[u8]) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_ciphertext_load(context.ptr.as_ptr(), data.as_ptr(), data.len()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: context.id })
+            .ok_or_else(|| SealError::InvalidParameter("not a valid ciphertext for this context".to_string()))
+    }
+
+    /// CKKS fixed-point scale (1.0 for BFV ciphertexts)
+    pub fn scale(&self) -> f64 {
+        unsafe {
+            ffi!(bindings::seal_ciphertext_scale(self.ptr.as_ptr()))
+        }
+    }
+
+    /// Rough number of further multiplications this ciphertext can take, worked out
+    /// without the secret key (unlike `Decryptor::invariant_noise_budget`), e.g. for
+    /// a `--no-decrypt` server deciding when to ask the client for a refresh
+    ///
+    /// CKKS counts the primes left in the modulus chain; BFV counts multiplications
+    /// against the budget its parameters allow. Only multiplications done through
+    /// this crate are counted (a ciphertext from `from_bytes` looks fresh), so treat
+    /// it as a scheduling hint, not a guarantee. A product never scores higher than
+    /// its operands. Ciphertexts from another context get 0.
+    pub fn estimated_remaining_levels(&self, context: &Context) -> usize {
+        if check_same_context(context, &[self]).is_err() {
+            return 0;
+        }
+        let levels = unsafe {
+            ffi!(bindings::seal_ciphertext_estimated_remaining_levels(context.ptr.as_ptr(), self.ptr.as_ptr()))
+        };
+        levels.max(0) as usize
+    }
+
+    /// Position of this ciphertext's level in the modulus chain: the highest
+    /// index for fresh ciphertexts, 0 once every droppable prime is gone
+    pub fn chain_index(&self, context: &Context) -> Result<usize> {
+        check_same_context(context, &[self])?;
+        let index = unsafe {
+            ffi!(bindings::seal_ciphertext_chain_index(context.ptr.as_ptr(), self.ptr.as_ptr()))
+        };
+        usize::try_from(index).map_err(|_| SealError::OperationFailed)
+    }
+
+    /// True once the ciphertext is at the last level of the modulus chain
+    /// (chain index 0). A CKKS ciphertext there can't be multiplied: the product
+    /// could never be rescaled. False for ciphertexts from another context.
+    pub fn is_final_level(&self, context: &Context) -> bool {
+        matches!(self.chain_index(context), Ok(0))
+    }
+
+    /// Get a human-readable summary of the ciphertext
+    pub fn info(&self) -> Result<String> {
+        let c_str = unsafe {
+            let ptr = ffi!(bindings::seal_ciphertext_info(self.ptr.as_ptr()));
+            if ptr.is_null() {
+                return Err(SealError::NullPointer);
+            }
+            CStr::from_ptr(ptr)
+        };
+        
+        c_str.to_str()
+            .map(|s| s.to_owned())
+            .map_err(|_| SealError::OperationFailed)
+    }
+}
+
+impl Drop for Ciphertext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_ciphertext(self.ptr.as_ptr()));
+        }
+    }
+}
+
+// ============================================
+// Homomorphic Operations
+// ============================================
+// Fails with InvalidParameter if any operand came from a different Context (and,
+// in debug builds, panics first if that Context is gone)
+pub(crate) fn check_same_context(context: &Context, operands: &[&Ciphertext]) -> Result<()> {
+    operands.iter().for_each(|c| c.assert_context_alive());
+    match operands.iter().find(|c| c.context_id != context.id) {
+        Some(c) => Err(SealError::InvalidParameter(format!(
+            "ciphertext belongs to context #{} but the operation uses context #{}",
+            c.context_id, context.id
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub fn add(context: &Context, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+    check_same_context(context, &[a, b])?;
+
+    let ptr = unsafe {
+        ffi!(bindings::seal_add(
+            context.ptr.as_ptr(),
+            a.ptr.as_ptr(),
+            b.ptr.as_ptr(),
+        ))
+    };
+    
+    NonNull::new(ptr)
+        .map(|ptr| Ciphertext { ptr, context_id: context.id })
+        .ok_or(SealError::OperationFailed)
+}
+
+pub fn sub(context: &Context, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+    check_same_context(context, &[a, b])?;
+
+    let ptr = unsafe {
+        ffi!(bindings::seal_sub(
+            context.ptr.as_ptr(),
+            a.ptr.as_ptr(),
+            b.ptr.as_ptr(),
+        ))
+    };
+    
+    NonNull::new(ptr)
+        .map(|ptr| Ciphertext { ptr, context_id: context.id })
+        .ok_or(SealError::OperationFailed)
+}
+
+pub fn multiply(context: &Context, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+    check_same_context(context, &[a, b])?;
+
+    let ptr = unsafe {
+        ffi!(bindings::seal_multiply(
+            context.ptr.as_ptr(),
+            a.ptr.as_ptr(),
+            b.ptr.as_ptr(),
+        ))
+    };
+    
+    NonNull::new(ptr)
+        .map(|ptr| Ciphertext { ptr, context_id: context.id })
+        .ok_or(SealError::OperationFailed)
+}
+
+// ============================================
+// Evaluator
+// ============================================
+// Keeps one SEAL Evaluator alive for a context, so chains of operations
+// (like the rotate-and-sum in dot_product) don't rebuild it every step.
+pub struct Evaluator<'a> {
+    ptr: NonNull<bindings::SEALEvaluator>,
+    context: &'a Context,
+    #[cfg(feature = "fault-injection")]
+    faults: crate::fault::FaultInjector<SealError>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(context: &'a Context) -> Result<Self> {
+        let ptr = unsafe {
+            ffi!(bindings::seal_create_evaluator(context.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Evaluator {
+                ptr,
+                context,
+                #[cfg(feature = "fault-injection")]
+                faults: crate::fault::FaultInjector::new(),
+            })
+            .ok_or(SealError::NullPointer)
+    }
+
+    /// Arm failures for this evaluator's operations, by method name
+    #[cfg(feature = "fault-injection")]
+    pub fn faults(&self) -> &crate::fault::FaultInjector<SealError> {
+        &self.faults
+    }
+
+    // Fails if a test armed a fault for `operation`
+    #[cfg(feature = "fault-injection")]
+    fn inject(&self, operation: &str) -> Result<()> {
+        self.faults.check(operation)
+    }
+
+    #[cfg(not(feature = "fault-injection"))]
+    #[inline(always)]
+    fn inject(&self, _operation: &str) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn add(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        check_same_context(self.context, &[a, b])?;
+
+        self.inject("add")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_add(self.ptr.as_ptr(), a.ptr.as_ptr(), b.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    pub fn sub(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        check_same_context(self.context, &[a, b])?;
+
+        self.inject("sub")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_sub(self.ptr.as_ptr(), a.ptr.as_ptr(), b.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Fails with InvalidParameter on a CKKS ciphertext at the final level
+    /// (see `Ciphertext::is_final_level`) instead of producing garbage. BFV
+    /// products stay at the top level, so only the noise budget limits them.
+    pub fn multiply(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        check_same_context(self.context, &[a, b])?;
+        if self.context.scheme == Scheme::Ckks && (a.is_final_level(self.context) || b.is_final_level(self.context)) {
+            return Err(SealError::InvalidParameter("ciphertext at final level, cannot multiply".to_string()));
+        }
+
+        self.inject("multiply")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_multiply(self.ptr.as_ptr(), a.ptr.as_ptr(), b.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// BFV multiply that first mod-switches both operands to a cheaper level
+    ///
+    /// Each operand goes down the modulus chain as long as the smaller modulus can
+    /// still hold its estimated noise budget (the keyless model behind
+    /// `estimated_remaining_levels`), so the switch costs only rounding noise.
+    /// Both then meet at the higher of the two levels. Fresh ciphertexts don't move;
+    /// deeper in a circuit the product is computed over fewer primes, which is
+    /// faster. Operands at different levels are fine (plain `multiply` rejects them).
+    pub fn multiply_optimized(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Bfv, "multiply_optimized")?;
+        check_same_context(self.context, &[a, b])?;
+
+        self.inject("multiply_optimized")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_multiply_optimized(self.ptr.as_ptr(), a.ptr.as_ptr(), b.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Shrink a product back to size 2 (keeps further multiplications cheap and less noisy)
+    pub fn relinearize(&self, cipher: &Ciphertext, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("relinearize")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_relinearize(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                relin_keys.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Divide a CKKS ciphertext by the last prime in its chain, bringing a
+    /// product's scale back down at the cost of one level
+    ///
+    /// Fails at the last level, where there is no prime left to drop.
+    pub fn rescale_to_next(&self, cipher: &Ciphertext) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "rescale_to_next")?;
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("rescale_to_next")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_rescale_to_next(self.ptr.as_ptr(), cipher.ptr.as_ptr()))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Multiply a ciphertext by a plaintext (slot-wise when both are batch encoded)
+    pub fn multiply_plain(&self, cipher: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("multiply_plain")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_multiply_plain(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                plain.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Add a plaintext to a ciphertext (slot-wise when both are batch encoded)
+    ///
+    /// For CKKS the plaintext has to be encoded at the ciphertext's scale, e.g. a
+    /// bias added to a `multiply_plain` product needs the scale squared.
+    pub fn add_plain(&self, cipher: &Ciphertext, plain: &Plaintext) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("add_plain")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_add_plain(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                plain.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Add `scalar` to every slot of a batched BFV ciphertext (e.g. a bias term)
+    ///
+    /// Same as `add_plain` with the scalar repeated in all slots, without building
+    /// that vector. Slots past the encoded values get the scalar too.
+    pub fn add_plain_scalar(&self, cipher: &Ciphertext, scalar: i64) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Bfv, "add_plain_scalar")?;
+        check_same_context(self.context, &[cipher])?;
+
+        let max = ((self.context.plain_modulus() - 1) / 2) as i64;
+        if scalar.unsigned_abs() > max as u64 {
+            return Err(SealError::InvalidParameter(format!(
+                "scalar {} is outside [-{}, {}]",
+                scalar, max, max
+            )));
+        }
+
+        self.inject("add_plain_scalar")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_add_plain_scalar(self.ptr.as_ptr(), cipher.ptr.as_ptr(), scalar))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// `add_plain_scalar` for CKKS
+    ///
+    /// The scalar is encoded at the ciphertext's level and scale, so unlike
+    /// `add_plain` there is no plaintext scale to get right.
+    pub fn add_plain_scalar_ckks(&self, cipher: &Ciphertext, scalar: f64) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "add_plain_scalar_ckks")?;
+        check_same_context(self.context, &[cipher])?;
+
+        if !scalar.is_finite() {
+            return Err(SealError::InvalidParameter(format!("scalar {} is not finite", scalar)));
+        }
+
+        self.inject("add_plain_scalar_ckks")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_add_plain_scalar_ckks(self.ptr.as_ptr(), cipher.ptr.as_ptr(), scalar))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Keep slot `index` and zero all the others, e.g. to isolate the answer a
+    /// rotate-and-sum left in slot 0 (where every other slot holds partial sums)
+    ///
+    /// Multiplies by a one-hot plaintext mask. For BFV that costs some noise
+    /// budget; for CKKS one level, with the result rescaled back to the input's
+    /// scale, so it fails on a ciphertext at the final level.
+    pub fn mask_slot(&self, cipher: &Ciphertext, index: usize) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+        let slot_count = self.context.slot_count();
+        if index >= slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "slot {} is out of range ({} slots)",
+                index, slot_count
+            )));
+        }
+        if self.context.scheme == Scheme::Ckks && cipher.is_final_level(self.context) {
+            return Err(SealError::InvalidParameter("ciphertext at final level, cannot mask".to_string()));
+        }
+
+        self.inject("mask_slot")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_mask_slot(self.ptr.as_ptr(), cipher.ptr.as_ptr(), index))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Rotate both batching rows left by `steps` (negative rotates right)
+    pub fn rotate_rows(&self, cipher: &Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("rotate_rows")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_rotate_rows(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                steps,
+                galois_keys.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Swap the two batching rows
+    pub fn rotate_columns(&self, cipher: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("rotate_columns")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_rotate_columns(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                galois_keys.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Encrypted dot product: sum(cipher[i] * weights[i]) (BFV, batch encoded input)
+    ///
+    /// The weights are batch encoded and multiplied slot-wise, then every slot is
+    /// folded together with log2(slot_count) rotations. The total ends up in slot 0
+    /// (in fact in every slot).
+    ///
+    /// # Parameters
+    /// - cipher: batch-encoded encrypted vector
+    /// - weights: plaintext weights, at most slot_count of them (missing ones are 0)
+    /// - galois_keys: rotation keys from the same context
+    pub fn dot_product(&self, cipher: &Ciphertext, weights: &[i64], galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let encoder = BatchEncoder::new(self.context)?;
+        let slot_count = encoder.slot_count();
+        if weights.is_empty() || weights.len() > slot_count {
+            return Err(SealError::InvalidParameter(format!(
+                "dot_product needs 1..={} weights, got {}",
+                slot_count,
+                weights.len()
+            )));
+        }
+
+        let plain_weights = encoder.encode(weights)?;
+        let mut acc = self.multiply_plain(cipher, &plain_weights)?;
+
+        // Slots form a 2 x (slot_count / 2) matrix. Rotating by 1, 2, 4, ...
+        // and adding sums each row into every one of its slots...
+        let row_size = slot_count / 2;
+        let mut step = 1;
+        while step < row_size {
+            let rotated = self.rotate_rows(&acc, step as i32, galois_keys)?;
+            acc = self.add(&acc, &rotated)?;
+            step *= 2;
+        }
+
+        // ...then swapping the rows and adding combines the two row sums
+        let swapped = self.rotate_columns(&acc, galois_keys)?;
+        self.add(&acc, &swapped)
+    }
+
+    /// Rotate all CKKS slots left by `steps` (negative rotates right)
+    pub fn rotate_vector(&self, cipher: &Ciphertext, steps: i32, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "rotate_vector")?;
+        check_same_context(self.context, &[cipher])?;
+
+        self.inject("rotate_vector")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_rotate_vector(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                steps,
+                galois_keys.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Encrypted mean of one value per ciphertext (CKKS)
+    ///
+    /// Each ciphertext carries a single value, e.g. one clinic's count, in any slot
+    /// with the others zero. They are added, every slot is folded together with
+    /// log2(slot_count) rotations, and the total is multiplied by the public 1/N.
+    /// The mean ends up in slot 0 (in fact in every slot), at the input scale
+    /// squared since nothing is rescaled.
+    pub fn mean(&self, ciphers: &[Ciphertext], galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "mean")?;
+        let Some((first, rest)) = ciphers.split_first() else {
+            return Err(SealError::InvalidParameter("mean needs at least one ciphertext".to_string()));
+        };
+        check_same_context(self.context, &ciphers.iter().collect::<Vec<_>>())?;
+
+        let mut sum: Option<Ciphertext> = None;
+        for cipher in rest {
+            sum = Some(self.add(sum.as_ref().unwrap_or(first), cipher)?);
+        }
+        let sum = sum.as_ref().unwrap_or(first);
+        let total = self.sum_slots(sum, galois_keys)?;
+
+        // Encoded at the top level, so the inputs must not have been rescaled
+        let encoder = CKKSEncoder::new(self.context, total.scale())?;
+        let reciprocal = encoder.encode(&vec![1.0 / ciphers.len() as f64; encoder.slot_count()])?;
+        self.multiply_plain(&total, &reciprocal)
+    }
+
+    /// Encrypted population variance across all slots of one CKKS ciphertext:
+    /// mean(x^2) - mean(x)^2, in slot 0 (in fact in every slot)
+    ///
+    /// Every slot counts, including unused ones (which hold 0), so fill the vector
+    /// or scale the result by slot_count / n yourself. For the standard deviation,
+    /// take the square root after decrypting.
+    ///
+    /// # Depth
+    /// Two levels: the input must be fresh (at the top of the modulus chain, where
+    /// the constants are encoded) with at least two middle primes below it, e.g.
+    /// `[60, 40, 40, 60]`. The result is at the input level minus two.
+    ///
+    /// x / sqrt(N) squared and x / N summed then squared both take one
+    /// multiply_plain and one multiply, so the two terms land at the same level and
+    /// exactly the same scale and subtract without any scale fixing.
+    pub fn variance(&self, cipher: &Ciphertext, galois_keys: &GaloisKeys, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "variance")?;
+        check_same_context(self.context, &[cipher])?;
+        if cipher.chain_index(self.context)? < 2 {
+            return Err(SealError::InvalidParameter("variance needs at least 2 remaining levels".to_string()));
+        }
+
+        let encoder = CKKSEncoder::new(self.context, cipher.scale())?;
+        let n = encoder.slot_count() as f64;
+        let constant = |value: f64| encoder.encode(&vec![value; encoder.slot_count()]);
+        let square = |c: &Ciphertext| self.rescale_to_next(&self.relinearize(&self.multiply(c, c)?, relin_keys)?);
+
+        // mean(x^2) = sum((x / sqrt(N))^2)
+        let scaled = self.rescale_to_next(&self.multiply_plain(cipher, &constant(1.0 / n.sqrt())?)?)?;
+        let mean_of_squares = self.sum_slots(&square(&scaled)?, galois_keys)?;
+
+        // mean(x)^2 = (sum(x / N))^2
+        let scaled = self.rescale_to_next(&self.multiply_plain(cipher, &constant(1.0 / n)?)?)?;
+        let mean = self.sum_slots(&scaled, galois_keys)?;
+        let square_of_mean = square(&mean)?;
+
+        self.sub(&mean_of_squares, &square_of_mean)
+    }
+
+    /// Refresh a CKKS ciphertext back to the top of the modulus chain
+    ///
+    /// Unreachable with SEAL: see `BootstrapKeys`, which can't be generated.
+    pub fn bootstrap(&self, _cipher: &Ciphertext, keys: &BootstrapKeys) -> Result<Ciphertext> {
+        match *keys {}
+    }
+
+    // Total of all CKKS slots, in every slot: rotating by 1, 2, 4, ... and adding
+    // folds the whole vector together in log2(slot_count) steps
+    fn sum_slots(&self, cipher: &Ciphertext, galois_keys: &GaloisKeys) -> Result<Ciphertext> {
+        let slot_count = CKKSEncoder::new(self.context, cipher.scale())?.slot_count();
+        let mut acc = self.add(cipher, &self.rotate_vector(cipher, 1, galois_keys)?)?;
+        let mut step = 2;
+        while step < slot_count {
+            let rotated = self.rotate_vector(&acc, step as i32, galois_keys)?;
+            acc = self.add(&acc, &rotated)?;
+            step *= 2;
+        }
+        Ok(acc)
+    }
+}
+
+impl Evaluator<'_> {
+    /// Re-encrypt `cipher` under the target key of `switch_keys`
+    ///
+    /// The result belongs to the target context and decrypts with its Decryptor.
+    /// `cipher` must have size 2 (relinearize products first).
+    pub fn key_switch(&self, cipher: &Ciphertext, switch_keys: &KSwitchKeys) -> Result<Ciphertext> {
+        cipher.assert_context_alive();
+        if cipher.context_id != switch_keys.from_context_id {
+            return Err(SealError::InvalidParameter(format!(
+                "ciphertext belongs to context #{} but the switch keys start from context #{}",
+                cipher.context_id, switch_keys.from_context_id
+            )));
+        }
+
+        self.inject("key_switch")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_key_switch(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                switch_keys.ptr.as_ptr(),
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: switch_keys.to_context_id })
+            .ok_or(SealError::OperationFailed)
+    }
+}
+
+impl Evaluator<'_> {
+    /// Encrypted "cipher > threshold" for CKKS: ~1.0 above the threshold, ~0.0 below
+    ///
+    /// HE has no native comparison, so this evaluates a polynomial approximation of
+    /// sign(x - threshold): f(x) = (3x - x^3) / 2 composed with itself k times, where
+    /// k = floor(log3(degree)) (so the effective degree is the largest power of 3 that
+    /// is <= `degree`). Values must be scaled so that |x - threshold| <= 1; outside
+    /// that range the polynomial diverges.
+    ///
+    /// Accuracy depends on the gap d = |x - threshold| and the degree. Worst-case
+    /// distance of the result from 0 / 1:
+    ///
+    /// | degree | levels | d = 0.1 | d = 0.2 | d = 0.3 |
+    /// |--------|--------|---------|---------|---------|
+    /// | 9      | 4      | 0.39    | 0.28    | 0.19    |
+    /// | 27     | 6      | 0.34    | 0.20    | 0.10    |
+    /// | 81     | 8      | 0.26    | 0.10    | 0.03    |
+    /// | 243    | 10     | 0.17    | 0.03    | < 0.01  |
+    ///
+    /// Each factor of 3 in degree costs two levels (middle primes of the coefficient
+    /// modulus), so the context must be built with enough of them.
+    pub fn compare_gt(
+        &self,
+        cipher: &Ciphertext,
+        threshold: f64,
+        degree: u32,
+        keys: &RelinKeys,
+    ) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "compare_gt")?;
+        check_same_context(self.context, &[cipher])?;
+
+        let mut iterations = 0;
+        let mut effective_degree = 1u64;
+        while effective_degree * 3 <= degree as u64 {
+            effective_degree *= 3;
+            iterations += 1;
+        }
+        if iterations == 0 {
+            return Err(SealError::InvalidParameter(format!(
+                "compare_gt needs degree >= 3, got {}",
+                degree
+            )));
+        }
+
+        self.inject("compare_gt")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_compare_gt(
+                self.ptr.as_ptr(),
+                cipher.ptr.as_ptr(),
+                threshold,
+                iterations,
+                keys.ptr.as_ptr(),
+            ))
+        };
+
+        // Null means too few levels for this degree
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// Encrypted histogram (CKKS): how many slots fall into each public bin
+    ///
+    /// Returns one ciphertext per bin [bin_edges[i], bin_edges[i + 1]), holding the
+    /// count in every slot. For each edge, `compare_gt` gives a step function that is
+    /// ~1 above the edge; summed over the slots that is the number of values above
+    /// the edge, and a bin's count is the difference of its two edges' sums. Values
+    /// outside [first edge, last edge) count towards no bin.
+    ///
+    /// The comparison needs |value - edge| <= 1 for every edge, so the edges may
+    /// span at most 1.0 and the values have to be scaled into that range. The
+    /// polynomial degree is the largest the ciphertext's remaining levels allow
+    /// (two levels per factor of 3), capped at 243. The counts end up at the last
+    /// level, so the first coefficient modulus prime needs room for slot_count times
+    /// the scale (e.g. 60 bits for 8192 slots at a 2^33 scale).
+    ///
+    /// # Accuracy
+    /// A value at distance d from its nearest edge contributes this much error to
+    /// each of the two step functions around it:
+    ///
+    /// | degree | levels | d = 0.1 | d = 0.2 | d = 0.25 | d = 0.3 |
+    /// |--------|--------|---------|---------|----------|---------|
+    /// | 27     | 6      | 0.34    | 0.20    | 0.14     | 0.10    |
+    /// | 81     | 8      | 0.26    | 0.10    | 0.05     | 0.03    |
+    /// | 243    | 10     | 0.17    | 0.03    | < 0.01   | < 0.01  |
+    ///
+    /// Every slot is counted, including unused ones (which hold 0), and the errors
+    /// add up: a count is off by roughly slot_count times the per-value error. Keep
+    /// values away from the edges (narrow bins make that impossible: with n bins
+    /// no value is further than 0.5 / n from an edge), use as many levels as you
+    /// can afford, and treat the result as an estimate, not an exact count.
+    pub fn histogram(
+        &self,
+        cipher: &Ciphertext,
+        bin_edges: &[f64],
+        keys: &RelinKeys,
+        galois_keys: &GaloisKeys,
+    ) -> Result<Vec<Ciphertext>> {
+        self.context.require_scheme(Scheme::Ckks, "histogram")?;
+        check_same_context(self.context, &[cipher])?;
+
+        if bin_edges.len() < 2
+            || bin_edges.iter().any(|edge| !edge.is_finite())
+            || bin_edges.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(SealError::InvalidParameter(format!(
+                "histogram needs at least two increasing bin edges, got {:?}",
+                bin_edges
+            )));
+        }
+        let span = bin_edges[bin_edges.len() - 1] - bin_edges[0];
+        if span > 1.0 {
+            return Err(SealError::InvalidParameter(format!(
+                "histogram bin edges may span at most 1.0, got {}; scale the values down",
+                span
+            )));
+        }
+
+        let iterations = (cipher.estimated_remaining_levels(self.context) / 2).min(MAX_HISTOGRAM_ITERATIONS);
+        if iterations == 0 {
+            return Err(SealError::InvalidParameter(
+                "histogram needs at least 2 remaining levels".to_string(),
+            ));
+        }
+        let degree = 3u32.pow(iterations as u32);
+
+        // Number of values above each edge, in every slot
+        let above = bin_edges
+            .iter()
+            .map(|&edge| {
+                let step = self.compare_gt(cipher, edge, degree, keys)?;
+                self.sum_slots(&step, galois_keys)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        above.windows(2).map(|pair| self.sub(&pair[0], &pair[1])).collect()
+    }
+
+    /// Encrypted slot-wise maximum of several CKKS ciphertexts (approximate)
+    ///
+    /// Uses max(a, b) = (a + b) / 2 + |a - b| / 2, where |a - b| = sqrt((a - b)^2)
+    /// comes from `iterations` steps of Wilkes' square root iteration, and reduces
+    /// the ciphertexts pairwise in a tree. Values must lie in an interval of width
+    /// 1 (e.g. [0, 1]) so that |a - b| <= 1; scale them down first.
+    ///
+    /// The result never overshoots: each max lands between the mean of its two
+    /// inputs and the true max, and converges slowly for close inputs. Worst-case
+    /// error of one max for a gap d = |a - b|:
+    ///
+    /// | iterations | levels | d = 0.05 | d = 0.1 | d = 0.2 | d = 0.5 |
+    /// |------------|--------|----------|---------|---------|---------|
+    /// | 2          | 5      | 0.022    | 0.039   | 0.057   | 0.033   |
+    /// | 3          | 7      | 0.021    | 0.034   | 0.039   | 0.006   |
+    /// | 4          | 9      | 0.019    | 0.026   | 0.020   | < 0.001 |
+    /// | 6          | 13     | 0.012    | 0.008   | < 0.001 | < 0.001 |
+    ///
+    /// Each tree round costs 2 * iterations + 1 levels, so N ciphertexts need
+    /// ceil(log2(N)) times that many middle primes. All ciphertexts must have the
+    /// same scale; the result has it too.
+    pub fn max_approx(&self, ciphers: &[Ciphertext], keys: &RelinKeys, iterations: u32) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "max_approx")?;
+        check_same_context(self.context, &ciphers.iter().collect::<Vec<_>>())?;
+
+        if ciphers.len() < 2 {
+            return Err(SealError::InvalidParameter(format!(
+                "max_approx needs at least two ciphertexts, got {}",
+                ciphers.len()
+            )));
+        }
+        if iterations == 0 {
+            return Err(SealError::InvalidParameter("max_approx needs at least one iteration".to_string()));
+        }
+
+        let mut pairs = ciphers.chunks_exact(2);
+        let mut round = pairs
+            .by_ref()
+            .map(|pair| self.max_pair(&pair[0], &pair[1], keys, iterations))
+            .collect::<Result<Vec<_>>>()?;
+        if let [odd] = pairs.remainder() {
+            let last = round.pop().expect("at least one pair");
+            round.push(self.max_pair(&last, odd, keys, iterations)?);
+        }
+
+        while round.len() > 1 {
+            let mut next = Vec::with_capacity(round.len().div_ceil(2));
+            let mut remaining = round.into_iter();
+            while let Some(a) = remaining.next() {
+                match remaining.next() {
+                    Some(b) => next.push(self.max_pair(&a, &b, keys, iterations)?),
+                    None => next.push(a),
+                }
+            }
+            round = next;
+        }
+        Ok(round.pop().expect("at least one ciphertext"))
+    }
+
+    fn max_pair(&self, a: &Ciphertext, b: &Ciphertext, keys: &RelinKeys, iterations: u32) -> Result<Ciphertext> {
+        self.inject("max_approx")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_max_approx(
+                self.ptr.as_ptr(),
+                a.ptr.as_ptr(),
+                b.ptr.as_ptr(),
+                iterations,
+                keys.ptr.as_ptr(),
+            ))
+        };
+
+        // Null means mismatched scales or too few levels left
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+}
+
+// Degree 3^5 = 243; beyond that the extra levels buy little accuracy
+const MAX_HISTOGRAM_ITERATIONS: usize = 5;
+
+// Largest scale mismatch add_auto/multiply_auto will round away. Rescaling divides
+// by primes that are only close to the scale, so scales drift by far less than
+// this; a bigger gap means the operands were encoded with different scales.
+const MAX_SCALE_DRIFT: f64 = 1.001;
+
+impl Evaluator<'_> {
+    /// CKKS add without manual level/scale bookkeeping
+    ///
+    /// An operand whose scale is far larger than the other's (a product that was
+    /// never rescaled) is rescaled first, then the operand at the higher level is
+    /// mod-switched down to the lower one, and small scale drift is rounded away.
+    /// Fails if the scales still differ by more than rounding can fix.
+    pub fn add_auto(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "add_auto")?;
+        check_same_context(self.context, &[a, b])?;
+
+        self.inject("add_auto")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_add_auto(
+                self.ptr.as_ptr(),
+                a.ptr.as_ptr(),
+                b.ptr.as_ptr(),
+                MAX_SCALE_DRIFT,
+            ))
+        };
+
+        // Null means scales that can't be matched
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+
+    /// CKKS multiply that aligns the operands like `add_auto`, then relinearizes
+    /// and rescales the product (unless it is already at the last level)
+    pub fn multiply_auto(&self, a: &Ciphertext, b: &Ciphertext, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Ckks, "multiply_auto")?;
+        check_same_context(self.context, &[a, b])?;
+
+        self.inject("multiply_auto")?;
+
+        let ptr = unsafe {
+            ffi!(bindings::seal_evaluator_multiply_auto(
+                self.ptr.as_ptr(),
+                a.ptr.as_ptr(),
+                b.ptr.as_ptr(),
+                relin_keys.ptr.as_ptr(),
+                MAX_SCALE_DRIFT,
+            ))
+        };
+
+        NonNull::new(ptr)
+            .map(|ptr| Ciphertext { ptr, context_id: self.context.id })
+            .ok_or(SealError::OperationFailed)
+    }
+}
+
+impl Evaluator<'_> {
+    /// Encryptions of `x^1, x^2, ..., x^max_degree` (BFV or CKKS), relinearized
+    ///
+    /// Every power is computed, one multiply each (`max_degree - 1` in total), by
+    /// binary splitting: `x^k` is `x^h * x^(k-h)` with `h` the largest power of
+    /// two below `k`. The powers of two come from repeated squaring, and every
+    /// other power multiplies one of them by a lower power already built.
+    ///
+    /// # Depth
+    /// `ceil(log2(max_degree))` levels: 2 for degree 4, 3 for degree 8. CKKS
+    /// products are rescaled and aligned like `multiply_auto`, so the input needs
+    /// that many levels left; `x^k` ends up `ceil(log2(k))` levels below it. BFV
+    /// has no levels to check, but each level of depth costs noise budget.
+    pub fn power_basis(&self, cipher: &Ciphertext, max_degree: u32, keys: &RelinKeys) -> Result<Vec<Ciphertext>> {
+        check_same_context(self.context, &[cipher])?;
+        if max_degree == 0 {
+            return Err(SealError::InvalidParameter("power_basis needs max_degree of at least 1".to_string()));
+        }
+        let depth = max_degree.next_power_of_two().trailing_zeros() as usize;
+        if self.context.scheme == Scheme::Ckks && cipher.chain_index(self.context)? < depth {
+            return Err(SealError::InvalidParameter(format!(
+                "power_basis to degree {} needs {} remaining levels, the input has {}",
+                max_degree,
+                depth,
+                cipher.chain_index(self.context)?
+            )));
+        }
+
+        // x^1 is a copy of the input, which Ciphertext can only make by round trip
+        let mut powers = vec![Ciphertext::from_bytes(self.context, &cipher.to_bytes()?)?];
+        for k in 2..=max_degree as usize {
+            let high = 1 << (usize::BITS - 1 - (k - 1).leading_zeros());
+            let (a, b) = (&powers[high - 1], &powers[k - high - 1]);
+            let power = match self.context.scheme {
+                Scheme::Ckks => self.multiply_auto(a, b, keys)?,
+                Scheme::Bfv => self.relinearize(&self.multiply(a, b)?, keys)?,
+            };
+            powers.push(power);
+        }
+        Ok(powers)
+    }
+}
+
+// Element-wise arithmetic on BatchEncoder slot vectors
+//
+// Slot i of the result is computed from slot i of each operand only; nothing
+// moves between slots (that takes rotations). Slots past the encoded values hold
+// zeros, so they stay zero through add and multiply.
+impl Evaluator<'_> {
+    /// `a[i] + b[i]` for every slot (BFV)
+    pub fn vec_add(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Bfv, "vec_add")?;
+        self.add(a, b)
+    }
+
+    /// `a[i] - b[i]` for every slot (BFV)
+    pub fn vec_sub(&self, a: &Ciphertext, b: &Ciphertext) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Bfv, "vec_sub")?;
+        self.sub(a, b)
+    }
+
+    /// `a[i] * b[i]` for every slot (BFV), relinearized back to size 2
+    ///
+    /// Like every slot operation this is modulo the plain modulus: products past
+    /// `BatchEncoder::max_value` wrap around.
+    pub fn vec_mul(&self, a: &Ciphertext, b: &Ciphertext, relin_keys: &RelinKeys) -> Result<Ciphertext> {
+        self.context.require_scheme(Scheme::Bfv, "vec_mul")?;
+        let product = self.multiply(a, b)?;
+        self.relinearize(&product, relin_keys)
+    }
+}
+
+impl Drop for Evaluator<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi!(bindings::seal_destroy_evaluator(self.ptr.as_ptr()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dot_product() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let galois = GaloisKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let x = encryptor.encrypt(&encoder.encode(&[1, 2, 3, 4]).unwrap()).unwrap();
+        let result = evaluator.dot_product(&x, &[1, 1, 1, 1], &galois).unwrap();
+
+        let decoded = encoder.decode(&decryptor.decrypt(&result).unwrap()).unwrap();
+        assert_eq!(decoded[0], 10);
+    }
+
+    #[test]
+    fn test_mask_slot_keeps_only_the_reduced_answer() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let galois = GaloisKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        // The reduction leaves the total in every slot; the mask keeps slot 0's
+        let x = encryptor.encrypt(&encoder.encode(&[1, 2, 3, 4]).unwrap()).unwrap();
+        let reduced = evaluator.dot_product(&x, &[1, 1, 1, 1], &galois).unwrap();
+        let masked = evaluator.mask_slot(&reduced, 0).unwrap();
+        let decoded = encoder.decode(&decryptor.decrypt(&masked).unwrap()).unwrap();
+        assert_eq!(decoded[0], 10);
+        assert!(decoded[1..].iter().all(|&v| v == 0));
+        assert!(matches!(evaluator.mask_slot(&reduced, 8192), Err(SealError::InvalidParameter(_))));
+
+        // CKKS: one level down, same scale
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ckks, 2f64.powi(40)).unwrap();
+        let evaluator = Evaluator::new(&ckks).unwrap();
+        let cipher = Encryptor::new(&ckks).unwrap().encrypt(&encoder.encode(&[1.5, 2.5, 3.5]).unwrap()).unwrap();
+        let masked = evaluator.mask_slot(&cipher, 1).unwrap();
+        assert_eq!(masked.chain_index(&ckks).unwrap(), cipher.chain_index(&ckks).unwrap() - 1);
+        assert!((masked.scale() - cipher.scale()).abs() < 1.0);
+        let decoded = encoder.decode(&Decryptor::new(&ckks).unwrap().decrypt(&masked).unwrap()).unwrap();
+        assert!((decoded[1] - 2.5).abs() < 1e-3, "{:?}", &decoded[..3]);
+        assert!(decoded.iter().enumerate().all(|(i, v)| i == 1 || v.abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_dot_product_rejects_too_many_weights() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let galois = GaloisKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let x = encryptor.encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let weights = vec![1i64; encoder.slot_count() + 1];
+        assert!(matches!(
+            evaluator.dot_product(&x, &weights, &galois),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_batch_encoder_signed_values() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let max = encoder.max_value();
+        assert_eq!(max, (1032193 - 1) / 2);
+
+        let values = [-3, 5, -1, max, -max];
+        let decoded = encoder.decode(&encoder.encode(&values).unwrap()).unwrap();
+        assert_eq!(&decoded[..values.len()], &values);
+
+        for out_of_range in [max + 1, -max - 1, i64::MIN] {
+            assert!(matches!(encoder.encode(&[1, out_of_range]), Err(SealError::InvalidParameter(_))));
+        }
+        assert!(matches!(
+            encoder.encode(&vec![0; encoder.slot_count() + 1]),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_new_batching_picks_a_batching_prime() {
+        let ctx = Context::new_batching(4096, 20).unwrap();
+        let t = ctx.plain_modulus();
+        assert_eq!(64 - t.leading_zeros(), 20);
+        assert_eq!(t % (2 * 4096), 1);
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let values = [7, -3, 1000];
+        assert_eq!(&encoder.decode(&encoder.encode(&values).unwrap()).unwrap()[..3], &values);
+
+        // A power of two is never a batching prime
+        let footgun = Context::new(4096, 1024).unwrap();
+        assert!(matches!(
+            BatchEncoder::new(&footgun),
+            Err(SealError::InvalidParameter(msg)) if msg.contains("new_batching")
+        ));
+
+        // 13 bits can't hold a prime congruent to 1 mod 8192
+        assert!(matches!(Context::new_batching(4096, 13), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(Context::new_batching(4096, 61), Err(SealError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_vec_ops_are_element_wise() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        let a = encryptor.encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap();
+        let b = encryptor.encrypt(&encoder.encode(&[4, 5, 6]).unwrap()).unwrap();
+        let decode = |cipher: &Ciphertext| encoder.decode(&decryptor.decrypt(cipher).unwrap()).unwrap()[..4].to_vec();
+
+        let product = evaluator.vec_mul(&a, &b, &relin_keys).unwrap();
+        assert_eq!(product.size(), 2);
+        assert_eq!(decode(&product), vec![4, 10, 18, 0]);
+        assert_eq!(decode(&evaluator.vec_add(&a, &b).unwrap()), vec![5, 7, 9, 0]);
+        assert_eq!(decode(&evaluator.vec_sub(&a, &b).unwrap()), vec![-3, -3, -3, 0]);
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(matches!(
+            Evaluator::new(&ckks).unwrap().vec_add(&a, &b),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_reports_noise_exhaustion() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        // Keep multiplying by 1 so the right answer never changes
+        let one = encryptor.encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let mut cipher = encryptor.encrypt(&encoder.encode(&[5]).unwrap()).unwrap();
+        let mut exhausted = false;
+        for _ in 0..16 {
+            let product = evaluator.multiply(&cipher, &one).unwrap();
+            cipher = evaluator.relinearize(&product, &relin_keys).unwrap();
+            match decryptor.decrypt(&cipher) {
+                Ok(plain) => assert_eq!(encoder.decode(&plain).unwrap()[0], 5),
+                Err(SealError::NoiseExhausted) => {
+                    exhausted = true;
+                    break;
+                }
+                Err(e) => panic!("unexpected error: {}", e),
+            }
+        }
+        assert!(exhausted, "16 multiplications should exhaust a 109-bit modulus");
+        assert_eq!(decryptor.invariant_noise_budget(&cipher).unwrap(), 0);
+        // The unchecked path still hands back whatever SEAL produces
+        assert!(decryptor.decrypt_unchecked(&cipher).is_ok());
+    }
+
+    #[test]
+    fn test_decrypt_typed_follows_the_scheme() {
+        let bfv = Context::new(8192, 1032193).unwrap();
+        let cipher = Encryptor::new(&bfv).unwrap()
+            .encrypt(&BatchEncoder::new(&bfv).unwrap().encode(&[3, -4, 5]).unwrap()).unwrap();
+        match Decryptor::new(&bfv).unwrap().decrypt_typed(&cipher).unwrap() {
+            DecryptedValue::IntegerVec(values) => assert_eq!(values[..3], [3, -4, 5]),
+            other => panic!("expected IntegerVec, got {:?}", other),
+        }
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ckks, 2f64.powi(40)).unwrap();
+        let cipher = Encryptor::new(&ckks).unwrap().encrypt(&encoder.encode(&[1.5, -2.25]).unwrap()).unwrap();
+        match Decryptor::new(&ckks).unwrap().decrypt_typed(&cipher).unwrap() {
+            DecryptedValue::RealVec(values) => {
+                assert!((values[0] - 1.5).abs() < 1e-6 && (values[1] + 2.25).abs() < 1e-6, "{:?}", &values[..2]);
+            }
+            other => panic!("expected RealVec, got {:?}", other),
+        }
+
+        // 1024 isn't a batching prime, so the plaintext is a single constant
+        let scalar = Context::new(4096, 1024).unwrap();
+        let cipher = Encryptor::new(&scalar).unwrap()
+            .encrypt(&Plaintext::from_decimal(&scalar, "-5").unwrap()).unwrap();
+        assert_eq!(Decryptor::new(&scalar).unwrap().decrypt_typed(&cipher).unwrap(), DecryptedValue::Integer(-5));
+    }
+
+    #[test]
+    fn test_params_id_follows_parameters_not_keys() {
+        use std::collections::HashSet;
+
+        let a = Context::new(8192, 1032193).unwrap();
+        let b = Context::new(8192, 1032193).unwrap();
+        assert_ne!(a.id(), b.id());
+        assert_eq!(a.params_id(), b.params_id());
+        assert_eq!(
+            Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap().params_id(),
+            Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap().params_id()
+        );
+
+        let distinct: HashSet<ParamsId> = [
+            a.params_id(),
+            Context::new(8192, 786433).unwrap().params_id(),
+            Context::new(4096, 40961).unwrap().params_id(),
+            Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap().params_id(),
+            Context::from_preset(ContextPreset::BalancedMedical).unwrap().params_id(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(distinct.len(), 5);
+        assert_eq!(a.params_id().to_string().len(), 64);
+    }
+
+    #[test]
+    fn test_verify_key_pair_catches_mismatched_keys() {
+        let a = Context::new(4096, 40961).unwrap();
+        let b = Context::new(4096, 40961).unwrap();
+        assert!(a.verify_key_pair(&a.public_key().unwrap(), &a.secret_key().unwrap()).unwrap());
+        // Same parameters, different secret key
+        assert!(!a.verify_key_pair(&a.public_key().unwrap(), &b.secret_key().unwrap()).unwrap());
+        assert!(!a.verify_key_pair(&b.public_key().unwrap(), &a.secret_key().unwrap()).unwrap());
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let other_ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(ckks.verify_key_pair(&ckks.public_key().unwrap(), &ckks.secret_key().unwrap()).unwrap());
+        assert!(!ckks.verify_key_pair(&ckks.public_key().unwrap(), &other_ckks.secret_key().unwrap()).unwrap());
+
+        // Keys for other parameters can't even be tried
+        assert!(matches!(
+            a.verify_key_pair(&ckks.public_key().unwrap(), &ckks.secret_key().unwrap()),
+            Err(SealError::InvalidParameter(_))
+        ));
+
+        // Evaluation keys carry the parameters they were made for
+        assert_eq!(RelinKeys::generate(&a).unwrap().params_id(), a.params_id());
+        assert_eq!(GaloisKeys::generate(&a).unwrap().params_id(), b.params_id());
+        assert_ne!(RelinKeys::generate(&ckks).unwrap().params_id(), a.params_id());
+    }
+
+    #[test]
+    fn test_bootstrap_keys_fail_cleanly() {
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let err = BootstrapKeys::generate(&ckks).err().unwrap();
+        assert!(
+            matches!(&err, SealError::InvalidParameter(msg) if msg.contains("can't be bootstrapped")),
+            "{}", err
+        );
+
+        let bfv = Context::new(8192, 1032193).unwrap();
+        let err = BootstrapKeys::generate(&bfv).err().unwrap();
+        assert!(matches!(&err, SealError::InvalidParameter(msg) if msg.contains("CKKS")), "{}", err);
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[test]
+    fn test_injected_fault_fails_the_nth_operation() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let cipher = encryptor.encrypt(&encoder.encode(&[3]).unwrap()).unwrap();
+
+        evaluator.faults().fail_nth("add", 2, SealError::OperationFailed);
+        assert!(evaluator.add(&cipher, &cipher).is_ok());
+        assert!(matches!(evaluator.add(&cipher, &cipher), Err(SealError::OperationFailed)));
+        assert!(evaluator.add(&cipher, &cipher).is_ok());
+
+        // Composite operations fail through the primitive they call
+        evaluator.faults().fail_nth("relinearize", 1, SealError::InvalidParameter("injected".to_string()));
+        assert!(matches!(evaluator.vec_mul(&cipher, &cipher, &relin_keys), Err(SealError::InvalidParameter(_))));
+        assert!(evaluator.vec_mul(&cipher, &cipher, &relin_keys).is_ok());
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[test]
+    fn test_decrypt_verified_catches_a_corrupted_decryption() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let cipher = encryptor.encrypt(&encoder.encode(&[7, 8, 9]).unwrap()).unwrap();
+
+        let plain = decryptor.decrypt_verified(&cipher).unwrap();
+        assert_eq!(&encoder.decode(&plain).unwrap()[..3], [7, 8, 9]);
+
+        // The second of the two decryptions comes back wrong
+        decryptor.faults().fail_nth("decrypt", 2, encoder.encode(&[7, 8, 10]).unwrap());
+        assert!(matches!(decryptor.decrypt_verified(&cipher), Err(SealError::DecryptionMismatch)));
+        assert!(decryptor.decrypt_verified(&cipher).is_ok());
+    }
+
+    #[test]
+    fn test_slot_count_matches_degree() {
+        let bfv = Context::new(8192, 1032193).unwrap();
+        assert_eq!(bfv.poly_modulus_degree(), 8192);
+        assert_eq!(bfv.slot_count(), 8192);
+        assert_eq!(BatchEncoder::new(&bfv).unwrap().slot_count(), bfv.slot_count());
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ckks, 2f64.powi(40)).unwrap();
+        assert_eq!(ckks.slot_count(), 4096);
+        assert_eq!(encoder.slot_count(), ckks.slot_count());
+
+        assert!(encoder.encode(&vec![0.5; 4096]).is_ok());
+        let too_long = encoder.encode(&vec![0.5; 4097]);
+        assert!(matches!(too_long, Err(SealError::InvalidParameter(msg)) if msg.contains("4096 slots")));
+    }
+
+    #[test]
+    fn test_plaintext_from_decimal_matches_hex() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+
+        let decimal = Plaintext::from_decimal(&ctx, "322").unwrap();
+        assert_eq!(decimal.to_string().unwrap(), Plaintext::from_hex("142").unwrap().to_string().unwrap());
+        // -1 is p - 1 = 1032192 = 0xFC000
+        let negative = Plaintext::from_decimal(&ctx, "-1").unwrap();
+        assert_eq!(negative.to_string().unwrap(), "FC000");
+
+        for bad in ["12a", "", "1032193", "-1032193"] {
+            assert!(matches!(Plaintext::from_decimal(&ctx, bad), Err(SealError::InvalidParameter(_))), "{}", bad);
+        }
+        let err = Plaintext::from_hex("1x^2 + 3Gx^1 + 7").err().unwrap();
+        assert!(matches!(&err, SealError::InvalidParameter(msg) if msg.contains("'3Gx^1'")), "{}", err);
+    }
+
+    #[test]
+    fn test_decoded_string_shows_slot_values() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+
+        let plain = encoder.encode(&[10, 20, 30, 40, 50, 60, 70]).unwrap();
+        assert_eq!(plain.decoded_string(&encoder).unwrap(), "[10, 20, 30, 40, 50, ...]");
+        let negative = encoder.encode(&[-3, 7]).unwrap();
+        assert_eq!(negative.decoded_string(&encoder).unwrap(), "[-3, 7, 0, 0, 0, ...]");
+    }
+
+    #[test]
+    fn test_plaintext_bytes_round_trip() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let weights = encoder.encode(&[3, -1, 4, 1, -5]).unwrap();
+
+        let bytes = weights.to_bytes().unwrap();
+        let loaded = Plaintext::from_bytes(&ctx, &bytes).unwrap();
+
+        assert_eq!(loaded.to_bytes().unwrap(), bytes);
+        assert_eq!(&encoder.decode(&loaded).unwrap()[..5], &[3, -1, 4, 1, -5]);
+
+        // Truncated data is rejected rather than half-loaded
+        assert!(matches!(
+            Plaintext::from_bytes(&ctx, &bytes[..bytes.len() / 2]),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_memory_footprint_accounts_for_byte_count() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let fresh = encryptor.encrypt(&encoder.encode(&[1.0, 2.0]).unwrap()).unwrap();
+        let product = evaluator.multiply(&fresh, &fresh).unwrap();
+        let rescaled = evaluator.rescale_to_next(&product).unwrap();
+
+        let report = fresh.memory_footprint();
+        assert_eq!(
+            report,
+            FootprintReport { num_polys: 2, coeffs_per_poly: 8192, bytes_per_coeff: 24, total_bytes: fresh.byte_count() }
+        );
+        for cipher in [&fresh, &product, &rescaled] {
+            let report = cipher.memory_footprint();
+            // byte_count is a compression bound: the raw data plus a little
+            let raw = report.poly_bytes() as f64;
+            assert!(report.total_bytes as f64 >= raw, "{:?}", report);
+            assert!((report.total_bytes as f64) < raw * 1.01, "{:?}", report);
+        }
+
+        // A product has a third polynomial; rescaling drops a prime from each
+        assert_eq!(product.memory_footprint().num_polys, 3);
+        assert_eq!(rescaled.memory_footprint().bytes_per_coeff, 16);
+    }
+
+    #[test]
+    fn test_ciphertext_bytes_rejects_malformed_input() {
+        let ctx = Context::from_preset(ContextPreset::FastLowDepth).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+
+        let cipher = encryptor.encrypt(&encoder.encode(&[7, 8, 9]).unwrap()).unwrap();
+        let bytes = cipher.to_bytes().unwrap();
+        let loaded = Ciphertext::from_bytes(&ctx, &bytes).unwrap();
+        assert_eq!(&encoder.decode(&decryptor.decrypt(&loaded).unwrap()).unwrap()[..3], &[7, 8, 9]);
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] ^= 0xff; // magic number
+        let other_params = Context::from_preset(ContextPreset::BalancedMedical).unwrap();
+
+        for (data, ctx) in [
+            (&[][..], &ctx),
+            (&bytes[..16], &ctx),
+            (&corrupted[..], &ctx),
+            (&bytes[..], &other_params),
+        ] {
+            assert!(matches!(Ciphertext::from_bytes(ctx, data), Err(SealError::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn test_cross_context_add_is_rejected() {
+        let ctx_a = Context::new(8192, 1032193).unwrap();
+        let ctx_b = Context::new(8192, 1032193).unwrap();
+        let a = Encryptor::new(&ctx_a).unwrap()
+            .encrypt(&BatchEncoder::new(&ctx_a).unwrap().encode(&[1, 2, 3]).unwrap()).unwrap();
+        let b = Encryptor::new(&ctx_b).unwrap()
+            .encrypt(&BatchEncoder::new(&ctx_b).unwrap().encode(&[4, 5, 6]).unwrap()).unwrap();
+
+        assert!(matches!(add(&ctx_a, &a, &b), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(sub(&ctx_a, &a, &b), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(multiply(&ctx_b, &a, &b), Err(SealError::InvalidParameter(_))));
+
+        let evaluator = Evaluator::new(&ctx_b).unwrap();
+        assert!(matches!(evaluator.add(&a, &b), Err(SealError::InvalidParameter(_))));
+
+        // Same-context operations still work
+        assert!(add(&ctx_a, &a, &a).is_ok());
+    }
+
+    #[test]
+    fn test_key_switch_between_parties() {
+        // Hospital A encrypts under its own key, then hands the data to the region
+        let hospital = Context::new(8192, 1032193).unwrap();
+        let region = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&hospital).unwrap();
+
+        let values = vec![12, 7, 30, 1];
+        let cipher = Encryptor::new(&hospital).unwrap()
+            .encrypt(&encoder.encode(&values).unwrap()).unwrap();
+
+        let switch_keys = KSwitchKeys::generate(
+            &hospital.secret_key().unwrap(),
+            &region.public_key().unwrap(),
+        ).unwrap();
+        let switched = Evaluator::new(&region).unwrap().key_switch(&cipher, &switch_keys).unwrap();
+
+        let decrypted = Decryptor::new(&region).unwrap().decrypt(&switched).unwrap();
+        let decoded = BatchEncoder::new(&region).unwrap().decode(&decrypted).unwrap();
+        assert_eq!(&decoded[..values.len()], &values[..]);
+
+        // The switched ciphertext now belongs to the region's context
+        assert!(add(&region, &switched, &switched).is_ok());
+        assert!(add(&hospital, &switched, &cipher).is_err());
+    }
+
+    #[test]
+    fn test_compare_gt_ckks() {
+        // 8 middle 40-bit primes = 8 levels, enough for degree 81
+        let ctx = Context::new_ckks(16384, &[50, 40, 40, 40, 40, 40, 40, 40, 40, 50]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        // Case counts scaled to [0, 1] by the maximum expected count
+        let values = [0.9, 0.1, 0.85, 0.15];
+        let cipher = encryptor.encrypt(&encoder.encode(&values).unwrap()).unwrap();
+
+        let result = evaluator.compare_gt(&cipher, 0.5, 81, &relin_keys).unwrap();
+        let decoded = encoder.decode(&decryptor.decrypt(&result).unwrap()).unwrap();
+
+        let expected = [1.0, 0.0, 1.0, 0.0];
+        for (got, want) in decoded.iter().zip(expected) {
+            assert!((got - want).abs() < 0.05, "expected ~{}, got {}", want, got);
+        }
+
+        // Degree below 3 isn't a sign approximation at all
+        assert!(matches!(
+            evaluator.compare_gt(&cipher, 0.5, 2, &relin_keys),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_histogram_ckks() {
+        // 10 middle primes = 10 levels, enough for degree 243. The 60-bit first
+        // prime holds counts of up to 2^13 at a 2^33 scale.
+        let mut primes = vec![60];
+        primes.extend([33; 10]);
+        primes.push(48);
+        let ctx = Context::new_ckks(16384, &primes).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(33)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let galois_keys = GaloisKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        // Fully packed: three low readings for every high one
+        let slot_count = encoder.slot_count();
+        let values: Vec<f64> = (0..slot_count).map(|i| if i % 4 == 3 { 0.75 } else { 0.25 }).collect();
+        let cipher = encryptor.encrypt(&encoder.encode(&values).unwrap()).unwrap();
+
+        let bins = evaluator.histogram(&cipher, &[0.0, 0.5, 1.0], &relin_keys, &galois_keys).unwrap();
+        assert_eq!(bins.len(), 2);
+
+        let expected = [slot_count as f64 * 0.75, slot_count as f64 * 0.25];
+        for (bin, want) in bins.iter().zip(expected) {
+            let got = encoder.decode(&decryptor.decrypt(bin).unwrap()).unwrap()[0];
+            assert!((got - want).abs() < want * 0.05, "expected ~{}, got {}", want, got);
+        }
+
+        assert!(evaluator.histogram(&cipher, &[0.0, 2.0], &relin_keys, &galois_keys).is_err());
+        assert!(evaluator.histogram(&cipher, &[0.5, 0.5], &relin_keys, &galois_keys).is_err());
+    }
+
+    #[test]
+    fn test_max_approx_ckks() {
+        // Two tree rounds of 2 * 3 + 1 levels each; 35 + 14 * 26 + 35 bits fits 16384
+        let mut primes = vec![35];
+        primes.extend([26; 14]);
+        primes.push(35);
+        let ctx = Context::new_ckks(16384, &primes).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(26)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let keys = RelinKeys::generate(&ctx).unwrap();
+
+        // Counts scaled into [0, 1]
+        let ciphers: Vec<_> = [3.0, 9.0, 2.0, 7.0]
+            .iter()
+            .map(|&count: &f64| encryptor.encrypt(&encoder.encode(&[count / 10.0]).unwrap()).unwrap())
+            .collect();
+
+        let max = evaluator.max_approx(&ciphers, &keys, 3).unwrap();
+        let decoded = encoder.decode(&decryptor.decrypt(&max).unwrap()).unwrap()[0] * 10.0;
+        // The last round compares ~0.9 with ~0.7: per the table, up to 0.039 * 10 low
+        assert!((decoded - 9.0).abs() < 0.5, "max {} not close to 9", decoded);
+        assert!(decoded < 9.05, "max_approx should not overshoot, got {}", decoded);
+
+        assert!(matches!(evaluator.max_approx(&ciphers[..1], &keys, 3), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(evaluator.max_approx(&ciphers, &keys, 0), Err(SealError::InvalidParameter(_))));
+        // Three iterations fit twice, eight don't fit at all
+        assert!(matches!(evaluator.max_approx(&ciphers, &keys, 8), Err(SealError::OperationFailed)));
+    }
+
+    #[test]
+    fn test_scheme_tag_guards_encoders() {
+        let bfv = Context::new(8192, 1032193).unwrap();
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert_eq!(bfv.scheme(), Scheme::Bfv);
+        assert_eq!(Context::from_preset(ContextPreset::FastLowDepth).unwrap().scheme(), Scheme::Bfv);
+        assert_eq!(ckks.scheme(), Scheme::Ckks);
+
+        assert!(matches!(CKKSEncoder::new(&bfv, 2f64.powi(40)), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(BatchEncoder::new(&ckks), Err(SealError::InvalidParameter(_))));
+
+        // CKKS-only evaluator operations refuse BFV ciphertexts before reaching SEAL
+        let encoder = BatchEncoder::new(&bfv).unwrap();
+        let cipher = Encryptor::new(&bfv).unwrap().encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let evaluator = Evaluator::new(&bfv).unwrap();
+        let relin_keys = RelinKeys::generate(&bfv).unwrap();
+        assert!(matches!(evaluator.add_auto(&cipher, &cipher), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(
+            evaluator.compare_gt(&cipher, 0.5, 27, &relin_keys),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_auto_scale_matching_ckks() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let xs = [1.5, 2.0, -0.5];
+        let ys = [0.5, 3.0, 4.0];
+        let x = encryptor.encrypt(&encoder.encode(&xs).unwrap()).unwrap();
+        let y = encryptor.encrypt(&encoder.encode(&ys).unwrap()).unwrap();
+
+        // Every step mixes operands at different levels; a raw product (scale^2,
+        // never rescaled) is thrown in as well
+        let xy = evaluator.multiply_auto(&x, &y, &relin_keys).unwrap();
+        let sum = evaluator.add_auto(&xy, &x).unwrap();
+        let product = evaluator.multiply_auto(&sum, &y, &relin_keys).unwrap();
+        let raw = evaluator.multiply(&x, &x).unwrap();
+        let result = evaluator.add_auto(&product, &raw).unwrap();
+
+        let decoded = encoder.decode(&decryptor.decrypt(&result).unwrap()).unwrap();
+        for i in 0..xs.len() {
+            let want = (xs[i] * ys[i] + xs[i]) * ys[i] + xs[i] * xs[i];
+            assert!((decoded[i] - want).abs() < 0.01, "slot {}: expected {}, got {}", i, want, decoded[i]);
+        }
+
+        // Encoded at an unrelated scale: that's a bug, not drift
+        let small_encoder = CKKSEncoder::new(&ctx, 2f64.powi(20)).unwrap();
+        let z = encryptor.encrypt(&small_encoder.encode(&xs).unwrap()).unwrap();
+        assert!((z.scale() - 2f64.powi(20)).abs() < 1.0);
+        assert!(evaluator.add_auto(&x, &z).is_err());
+    }
+
+    #[test]
+    fn test_mean_ckks() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let galois_keys = GaloisKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let ciphers: Vec<_> = [2.0, 4.0, 6.0, 8.0]
+            .iter()
+            .map(|&v| encryptor.encrypt(&encoder.encode(&[v]).unwrap()).unwrap())
+            .collect();
+        let mean = evaluator.mean(&ciphers, &galois_keys).unwrap();
+
+        let decoded = encoder.decode(&decryptor.decrypt(&mean).unwrap()).unwrap();
+        assert!((decoded[0] - 5.0).abs() < 0.01, "expected 5, got {}", decoded[0]);
+        assert!(evaluator.mean(&[], &galois_keys).is_err());
+    }
+
+    #[test]
+    fn test_context_from_explicit_primes() {
+        // SEAL's own default primes for degree 8192 (CoeffModulus::BFVDefault)
+        let primes = [0x7fffffd8001, 0x7fffffc8001, 0xfffffffc001, 0xffffff6c001, 0xfffffebc001];
+        let ctx = Context::with_explicit_primes(8192, &primes, 1032193).unwrap();
+        assert_eq!(ctx.poly_modulus_degree(), 8192);
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let cipher = Encryptor::new(&ctx).unwrap().encrypt(&encoder.encode(&[7, 11]).unwrap()).unwrap();
+        let decoded = encoder.decode(&Decryptor::new(&ctx).unwrap().decrypt(&cipher).unwrap()).unwrap();
+        assert_eq!(&decoded[..2], &[7, 11]);
+
+        // 16385 = 5 * 29 * 113 is 1 mod 16384 but not prime; 0xffffee001 is a
+        // degree-4096 prime, 1 mod 8192 but not mod 16384
+        for offender in [16385, 0xffffee001] {
+            match Context::with_explicit_primes(8192, &[primes[0], offender], 1032193) {
+                Err(SealError::InvalidParameter(msg)) => assert!(msg.contains(&offender.to_string()), "{}", msg),
+                other => panic!("{} accepted: {:?}", offender, other.map(|_| ())),
+            }
+        }
+        assert!(is_prime(1032193) && is_prime((1 << 61) - 1) && !is_prime(3215031751));
+    }
+
+    #[test]
+    fn test_power_basis_ckks() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let x = encryptor.encrypt(&encoder.encode(&[2.0]).unwrap()).unwrap();
+        let powers = evaluator.power_basis(&x, 4, &relin_keys).unwrap();
+        for (power, want) in powers.iter().zip([2.0, 4.0, 8.0, 16.0]) {
+            let got = encoder.decode(&decryptor.decrypt(power).unwrap()).unwrap()[0];
+            assert!((got - want).abs() < 0.01, "expected {}, got {}", want, got);
+        }
+        // Degree 4 is two levels deep, which is all this chain has
+        assert_eq!(powers[3].chain_index(&ctx).unwrap(), 0);
+        assert!(matches!(evaluator.power_basis(&x, 5, &relin_keys), Err(SealError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_variance_ckks() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let galois_keys = GaloisKeys::generate(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        // Every slot counts, so repeat the sample to fill them: mean 5, variance 4
+        let sample = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let values: Vec<f64> = sample.iter().cycle().take(encoder.slot_count()).copied().collect();
+        let cipher = encryptor.encrypt(&encoder.encode(&values).unwrap()).unwrap();
+        let variance = evaluator.variance(&cipher, &galois_keys, &relin_keys).unwrap();
+
+        let decoded = encoder.decode(&decryptor.decrypt(&variance).unwrap()).unwrap();
+        assert!((decoded[0] - 4.0).abs() < 0.01, "expected 4, got {}", decoded[0]);
+
+        // Both levels used up: a second round has nothing left
+        assert!(matches!(
+            evaluator.variance(&variance, &galois_keys, &relin_keys),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_complex_round_trip_uses_every_slot() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        assert_eq!(encoder.slot_count(), 4096);
+
+        let values: Vec<_> = (0..encoder.slot_count())
+            .map(|i| Complex::new(i as f64 / 100.0, -(i as f64) / 7.0))
+            .collect();
+        let cipher = encryptor.encrypt(&encoder.encode_complex(&values).unwrap()).unwrap();
+        let decoded = encoder.decode_complex(&decryptor.decrypt(&cipher).unwrap()).unwrap();
+
+        assert_eq!(decoded.len(), values.len());
+        for (got, want) in decoded.iter().zip(&values) {
+            assert!((got - want).norm() < 1e-4, "expected {}, got {}", want, got);
+        }
+
+        // Real values come back with (approximately) zero imaginary parts
+        let plain = encoder.encode(&[1.5, -2.0]).unwrap();
+        let decoded = encoder.decode_complex(&plain).unwrap();
+        assert!((decoded[1] - Complex::new(-2.0, 0.0)).norm() < 1e-6);
+
+        let too_many = vec![Complex::new(1.0, 1.0); encoder.slot_count() + 1];
+        assert!(matches!(encoder.encode_complex(&too_many), Err(SealError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_estimated_remaining_levels_decrease_with_multiplication() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let fresh = encryptor.encrypt(&encoder.encode(&[2]).unwrap()).unwrap();
+        let mut cipher = evaluator.add(&fresh, &fresh).unwrap();
+        let mut levels = cipher.estimated_remaining_levels(&ctx);
+        assert_eq!(levels, fresh.estimated_remaining_levels(&ctx));
+        assert!(levels > 0);
+
+        while levels > 0 {
+            let product = evaluator.multiply(&cipher, &fresh).unwrap();
+            cipher = evaluator.relinearize(&product, &relin_keys).unwrap();
+            let next = cipher.estimated_remaining_levels(&ctx);
+            assert!(next < levels, "{} -> {} after a multiplication", levels, next);
+            levels = next;
+        }
+
+        // CKKS: each rescale uses up a prime
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let ckks_encoder = CKKSEncoder::new(&ckks, 2f64.powi(40)).unwrap();
+        let ckks_encryptor = Encryptor::new(&ckks).unwrap();
+        let ckks_relin = RelinKeys::generate(&ckks).unwrap();
+        let ckks_evaluator = Evaluator::new(&ckks).unwrap();
+        let x = ckks_encryptor.encrypt(&ckks_encoder.encode(&[1.5]).unwrap()).unwrap();
+        let xx = ckks_evaluator.multiply_auto(&x, &x, &ckks_relin).unwrap();
+        assert_eq!(x.estimated_remaining_levels(&ckks), 2);
+        assert_eq!(xx.estimated_remaining_levels(&ckks), 1);
+        assert_eq!(ckks_evaluator.multiply(&x, &x).unwrap().estimated_remaining_levels(&ckks), 1);
+
+        // Not from this context
+        assert_eq!(x.estimated_remaining_levels(&ctx), 0);
+    }
+
+    #[test]
+    fn test_seeded_key_generation_is_reproducible() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let first = KeyGenerator::with_seed(&ctx, b"regression seed").unwrap();
+        let second = KeyGenerator::with_seed(&ctx, b"regression seed").unwrap();
+        let public_bytes = first.public_key().unwrap().to_bytes().unwrap();
+        assert_eq!(public_bytes, second.public_key().unwrap().to_bytes().unwrap());
+
+        // Also across contexts with the same parameters
+        let other_ctx = Context::new(8192, 1032193).unwrap();
+        let third = KeyGenerator::with_seed(&other_ctx, b"regression seed").unwrap();
+        assert_eq!(public_bytes, third.public_key().unwrap().to_bytes().unwrap());
+
+        assert_ne!(public_bytes, KeyGenerator::with_seed(&ctx, b"another seed").unwrap().public_key().unwrap().to_bytes().unwrap());
+        assert_ne!(public_bytes, ctx.public_key().unwrap().to_bytes().unwrap());
+
+        // A working key pair, but not the context's own
+        assert!(ctx.verify_key_pair(&first.public_key().unwrap(), &second.secret_key().unwrap()).unwrap());
+        assert!(!ctx.verify_key_pair(&first.public_key().unwrap(), &ctx.secret_key().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_key_switch_to_and_from_a_seeded_key_pair() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let seeded = KeyGenerator::with_seed(&ctx, b"regression seed").unwrap();
+
+        let values = vec![4, 8, 15];
+        let cipher = Encryptor::new(&ctx).unwrap().encrypt(&encoder.encode(&values).unwrap()).unwrap();
+
+        // Over to the seeded key and back: the ciphertext in between carries the
+        // generator's id, which debug builds must count as alive
+        let to_seeded = KSwitchKeys::generate(&ctx.secret_key().unwrap(), &seeded.public_key().unwrap()).unwrap();
+        let switched = evaluator.key_switch(&cipher, &to_seeded).unwrap();
+        let back = KSwitchKeys::generate(&seeded.secret_key().unwrap(), &ctx.public_key().unwrap()).unwrap();
+        let restored = evaluator.key_switch(&switched, &back).unwrap();
+
+        let decoded = encoder.decode(&Decryptor::new(&ctx).unwrap().decrypt(&restored).unwrap()).unwrap();
+        assert_eq!(&decoded[..values.len()], &values[..]);
+    }
+
+    #[test]
+    fn test_multiply_at_the_final_ckks_level_is_rejected() {
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ctx, 2f64.powi(40)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        // x -> x^2 -> x^4 uses up both middle primes
+        let mut cipher = encryptor.encrypt(&encoder.encode(&[1.5]).unwrap()).unwrap();
+        for _ in 0..2 {
+            assert!(!cipher.is_final_level(&ctx));
+            let squared = evaluator.relinearize(&evaluator.multiply(&cipher, &cipher).unwrap(), &relin_keys).unwrap();
+            cipher = evaluator.rescale_to_next(&squared).unwrap();
+        }
+        assert!(cipher.is_final_level(&ctx));
+
+        match evaluator.multiply(&cipher, &cipher) {
+            Err(SealError::InvalidParameter(msg)) => assert_eq!(msg, "ciphertext at final level, cannot multiply"),
+            other => panic!("expected InvalidParameter, got {:?}", other.map(|_| ())),
+        }
+
+        // Never true for a ciphertext from another context
+        let other = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(!cipher.is_final_level(&other));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "context already dropped")]
+    fn test_ciphertext_outliving_its_context_panics_in_debug() {
+        // The context goes out of scope before its ciphertext
+        let cipher = {
+            let ctx = Context::new(8192, 1032193).unwrap();
+            let encoder = BatchEncoder::new(&ctx).unwrap();
+            Encryptor::new(&ctx).unwrap().encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap()
+        };
+
+        // Same parameters, different keys: without the check this decrypts garbage
+        let other = Context::new(8192, 1032193).unwrap();
+        let _ = Decryptor::new(&other).unwrap().decrypt(&cipher);
+    }
+
+    #[test]
+    fn test_public_context_evaluates_client_ciphertexts_without_a_secret_key() {
+        let client = Context::new(8192, 1032193).unwrap();
+        let public_key = client.public_key().unwrap().to_bytes().unwrap();
+        let relin_keys = RelinKeys::generate(&client).unwrap().to_bytes().unwrap();
+        let galois_keys = GaloisKeys::generate(&client).unwrap().to_bytes().unwrap();
+
+        let server = Context::with_public_key(8192, 1032193, &public_key).unwrap();
+        assert!(client.has_secret_key());
+        assert!(!server.has_secret_key());
+        assert!(server.secret_key().is_err());
+        assert!(Decryptor::new(&server).is_err());
+        assert!(RelinKeys::generate(&server).is_err());
+        assert!(GaloisKeys::generate(&server).is_err());
+
+        // Client encrypts, server squares, relinearizes and rotates with the
+        // client's keys, client decrypts
+        let encoder = BatchEncoder::new(&client).unwrap();
+        let cipher = Encryptor::new(&client).unwrap().encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap();
+        let loaded = Ciphertext::from_bytes(&server, &cipher.to_bytes().unwrap()).unwrap();
+        let evaluator = Evaluator::new(&server).unwrap();
+        let relin_keys = RelinKeys::from_bytes(&server, &relin_keys).unwrap();
+        let galois_keys = GaloisKeys::from_bytes(&server, &galois_keys).unwrap();
+        let squared = evaluator.relinearize(&evaluator.multiply(&loaded, &loaded).unwrap(), &relin_keys).unwrap();
+        let rotated = evaluator.rotate_rows(&squared, 1, &galois_keys).unwrap();
+
+        let decryptor = Decryptor::new(&client).unwrap();
+        for (cipher, want) in [(&squared, [1, 4, 9]), (&rotated, [4, 9, 0])] {
+            let back = Ciphertext::from_bytes(&client, &cipher.to_bytes().unwrap()).unwrap();
+            assert_eq!(&encoder.decode(&decryptor.decrypt(&back).unwrap()).unwrap()[..3], &want);
+        }
+
+        // The server can still encrypt under the client's key
+        let server_encoder = BatchEncoder::new(&server).unwrap();
+        let cipher = Encryptor::new(&server).unwrap().encrypt(&server_encoder.encode(&[5]).unwrap()).unwrap();
+        let back = Ciphertext::from_bytes(&client, &cipher.to_bytes().unwrap()).unwrap();
+        assert_eq!(encoder.decode(&decryptor.decrypt(&back).unwrap()).unwrap()[0], 5);
+
+        // Keys for other parameters are rejected
+        let other = Context::from_preset(ContextPreset::BalancedMedical).unwrap();
+        let other_key = other.public_key().unwrap().to_bytes().unwrap();
+        assert!(matches!(Context::with_public_key(8192, 1032193, &other_key), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(Context::with_public_key(8192, 1032193, &[]), Err(SealError::InvalidParameter(_))));
+        let other_relin = RelinKeys::generate(&other).unwrap().to_bytes().unwrap();
+        assert!(matches!(RelinKeys::from_bytes(&server, &other_relin), Err(SealError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_add_plain_scalar_broadcasts_to_every_slot() {
+        let ctx = Context::new(8192, 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let cipher = encryptor.encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap();
+        let biased = evaluator.add_plain_scalar(&cipher, 5).unwrap();
+        let decoded = encoder.decode(&decryptor.decrypt(&biased).unwrap()).unwrap();
+        assert_eq!(&decoded[..4], &[6, 7, 8, 5]);
+        assert!(matches!(evaluator.add_plain_scalar(&cipher, 1032193), Err(SealError::InvalidParameter(_))));
+
+        // CKKS: works on a rescaled product without matching its scale by hand
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encoder = CKKSEncoder::new(&ckks, 2f64.powi(40)).unwrap();
+        let evaluator = Evaluator::new(&ckks).unwrap();
+        let relin_keys = RelinKeys::generate(&ckks).unwrap();
+        let cipher = Encryptor::new(&ckks).unwrap().encrypt(&encoder.encode(&[1.0, 2.0, 3.0]).unwrap()).unwrap();
+        let squared = evaluator.relinearize(&evaluator.multiply(&cipher, &cipher).unwrap(), &relin_keys).unwrap();
+        let squared = evaluator.rescale_to_next(&squared).unwrap();
+        let biased = evaluator.add_plain_scalar_ckks(&squared, 0.5).unwrap();
+        let decoded = encoder.decode(&Decryptor::new(&ckks).unwrap().decrypt(&biased).unwrap()).unwrap();
+        for (got, want) in decoded.iter().zip([1.5, 4.5, 9.5, 0.5]) {
+            assert!((got - want).abs() < 1e-3, "{} vs {}", got, want);
+        }
+        assert!(matches!(evaluator.add_plain_scalar(&squared, 5), Err(SealError::InvalidParameter(_))));
+    }
+
+    // Squares (and relinearizes) an encryption of 1 until the noise budget runs out
+    fn multiplications_before_noise_exhaustion(preset: ContextPreset) -> u32 {
+        let ctx = Context::from_preset(preset).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+
+        let mut cipher = encryptor.encrypt(&encoder.encode(&[1]).unwrap()).unwrap();
+        let mut count = 0;
+        while count < 32 {
+            let product = evaluator.multiply(&cipher, &cipher).unwrap();
+            let next = evaluator.relinearize(&product, &relin_keys).unwrap();
+            if decryptor.invariant_noise_budget(&next).unwrap() == 0 {
+                break;
+            }
+            cipher = next;
+            count += 1;
+        }
+        count
+    }
+
+    #[test]
+    fn test_multiply_optimized_switches_down_deep_in_a_circuit() {
+        let ctx = Context::with_coeff_modulus(8192, &[43, 43, 44, 44, 44], 1032193).unwrap();
+        let encoder = BatchEncoder::new(&ctx).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        let values = [3, 2, -1, 5];
+        let fresh = encryptor.encrypt(&encoder.encode(&values).unwrap()).unwrap();
+        let top = fresh.chain_index(&ctx).unwrap();
+        assert_eq!(top, 3); // four data primes
+
+        // x^8 by three squarings, both ways
+        let mut plain = evaluator.multiply(&fresh, &fresh).unwrap();
+        let mut optimized = evaluator.multiply_optimized(&fresh, &fresh).unwrap();
+        assert_eq!(optimized.chain_index(&ctx).unwrap(), top, "fresh operands should not move");
+        for _ in 0..2 {
+            plain = evaluator.relinearize(&plain, &relin_keys).unwrap();
+            plain = evaluator.multiply(&plain, &plain).unwrap();
+            optimized = evaluator.relinearize(&optimized, &relin_keys).unwrap();
+            optimized = evaluator.multiply_optimized(&optimized, &optimized).unwrap();
+        }
+        assert_eq!(plain.chain_index(&ctx).unwrap(), top);
+        assert!(optimized.chain_index(&ctx).unwrap() < top);
+
+        let decode = |cipher: &Ciphertext| encoder.decode(&decryptor.decrypt(cipher).unwrap()).unwrap()[..4].to_vec();
+        assert_eq!(decode(&optimized), decode(&plain));
+        assert_eq!(decode(&optimized), vec![6561, 256, 1, 390625]);
+        assert!(decryptor.invariant_noise_budget(&optimized).unwrap() > 0);
+
+        let ckks = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        assert!(matches!(
+            Evaluator::new(&ckks).unwrap().multiply_optimized(&fresh, &fresh),
+            Err(SealError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_presets_depth_ordering() {
+        let fast = multiplications_before_noise_exhaustion(ContextPreset::FastLowDepth);
+        let deep = multiplications_before_noise_exhaustion(ContextPreset::DeepML);
+
+        assert!(fast >= 1, "FastLowDepth should allow one multiplication, got {}", fast);
+        assert!(deep > fast, "DeepML ({}) should outlast FastLowDepth ({})", deep, fast);
+    }
+
+    #[test]
+    fn test_builder_matches_legacy_new() {
+        let legacy = Context::new(8192, 1032193).unwrap();
+        let built = Context::builder()
+            .plain_modulus(1032193)
+            .poly_modulus_degree(8192)
+            .scheme(Scheme::Bfv)
+            .security_level(SecurityLevel::TC128)
+            .build()
+            .unwrap();
+
+        assert_eq!(built.scheme(), legacy.scheme());
+        assert_eq!(built.plain_modulus(), legacy.plain_modulus());
+
+        let run = |ctx: &Context| {
+            let encoder = BatchEncoder::new(ctx).unwrap();
+            let encryptor = Encryptor::new(ctx).unwrap();
+            let decryptor = Decryptor::new(ctx).unwrap();
+            let evaluator = Evaluator::new(ctx).unwrap();
+            let a = encryptor.encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap();
+            let b = encryptor.encrypt(&encoder.encode(&[4, 5, 6]).unwrap()).unwrap();
+            let product = evaluator.multiply(&a, &b).unwrap();
+            let decoded = encoder.decode(&decryptor.decrypt(&product).unwrap()).unwrap();
+            (encoder.slot_count(), product.coeff_count(), decoded[..3].to_vec())
+        };
+        assert_eq!(run(&built), run(&legacy));
+        assert_eq!(run(&built).2, vec![4, 10, 18]);
+
+        let missing = Context::builder().plain_modulus(1032193).build();
+        assert!(matches!(missing, Err(SealError::InvalidParameter(msg)) if msg.contains("poly_modulus_degree")));
+        let missing = Context::builder().poly_modulus_degree(8192).build();
+        assert!(matches!(missing, Err(SealError::InvalidParameter(msg)) if msg.contains("plain_modulus")));
+        // 218 bits is fine at 128-bit security but too much for 192
+        let too_big = Context::builder()
+            .poly_modulus_degree(8192)
+            .plain_modulus(1032193)
+            .coeff_modulus_bits(&[43, 43, 44, 44, 44])
+            .security_level(SecurityLevel::TC192)
+            .build();
+        assert!(matches!(too_big, Err(SealError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_ckks_auto_supports_the_requested_depth_and_precision() {
+        let (depth, precision_bits) = (6, 20);
+        let ctx = Context::ckks_auto(depth, precision_bits, SecurityLevel::TC128).unwrap();
+        // 60 + 6 * 40 + 60 = 360 bits: too many for 8192 (218), fine for 16384 (438)
+        assert_eq!(ctx.scheme(), Scheme::Ckks);
+        assert_eq!(ctx.poly_modulus_degree(), 16384);
+
+        let encoder = CKKSEncoder::new(&ctx, Context::ckks_auto_scale(precision_bits)).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let relin_keys = RelinKeys::generate(&ctx).unwrap();
+
+        // Six squarings: x^64
+        let x = 1.01f64;
+        let mut cipher = encryptor.encrypt(&encoder.encode(&[x]).unwrap()).unwrap();
+        for _ in 0..depth {
+            let squared = evaluator.relinearize(&evaluator.multiply(&cipher, &cipher).unwrap(), &relin_keys).unwrap();
+            cipher = evaluator.rescale_to_next(&squared).unwrap();
+        }
+        let got = encoder.decode(&decryptor.decrypt(&cipher).unwrap()).unwrap()[0];
+        let want = x.powi(64);
+        assert!(((got - want) / want).abs() < 2f64.powi(-(precision_bits as i32)), "expected {}, got {}", want, got);
+        assert_eq!(cipher.chain_index(&ctx).unwrap(), 0);
+
+        // Shallower circuits get a smaller ring
+        assert_eq!(Context::ckks_auto(2, 20, SecurityLevel::TC128).unwrap().poly_modulus_degree(), 8192);
+        assert!(matches!(Context::ckks_auto(30, 30, SecurityLevel::TC256), Err(SealError::InvalidParameter(_))));
+        assert!(matches!(Context::ckks_auto(2, 0, SecurityLevel::TC128), Err(SealError::InvalidParameter(_))));
+    }
+}
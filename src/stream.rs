@@ -0,0 +1,256 @@
+// Benchmark results as an async Stream, one item per timed operation
+//
+// For a consumer (e.g. a dashboard backend) that wants results as they are
+// produced without going through gRPC. The HE work runs on its own thread:
+// SEAL encryptors and evaluators aren't Send, and a single multiplication is
+// long enough to stall an async executor. The stream itself only waits on a
+// channel, so it is Send + 'static and can be handed to any runtime.
+//
+// The channel is bounded: when the consumer falls behind, the benchmark thread
+// waits for it instead of piling results up in memory. Each result says how
+// long that wait was.
+
+use crate::seal::{BatchEncoder, Ciphertext, Context, Decryptor, Encryptor, Evaluator, Plaintext, Result};
+use futures_channel::mpsc;
+use futures_core::Stream;
+use std::fmt;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+// Results the benchmark thread may get ahead of the consumer by
+const STREAM_BUFFER: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// Context, secret/public keys and relinearization keys (always the first item)
+    KeyGen,
+    Encrypt,
+    Add,
+    Multiply,
+    Decrypt,
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyGen => write!(f, "keygen"),
+            Self::Encrypt => write!(f, "encrypt"),
+            Self::Add => write!(f, "add"),
+            Self::Multiply => write!(f, "multiply"),
+            Self::Decrypt => write!(f, "decrypt"),
+        }
+    }
+}
+
+/// SEAL BFV benchmark to run; `num_operations` of each kind in `operations`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    pub poly_modulus_degree: u64,
+    pub plain_modulus: u64,
+    pub num_operations: usize,
+    pub operations: Vec<OperationKind>,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        BenchmarkConfig {
+            poly_modulus_degree: 8192,
+            plain_modulus: 1032193,
+            num_operations: 10,
+            operations: vec![OperationKind::Encrypt, OperationKind::Add, OperationKind::Multiply, OperationKind::Decrypt],
+        }
+    }
+}
+
+/// One completed operation
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationResult {
+    pub operation: OperationKind,
+    /// 0-based count within this kind
+    pub index: usize,
+    pub duration: Duration,
+    /// Set if the operation failed; a failed KeyGen ends the stream
+    pub error: Option<String>,
+    /// How long the benchmark waited for the consumer before it could send this
+    /// result (zero unless the consumer is falling behind). Not part of `duration`.
+    pub blocked: Duration,
+}
+
+/// Run a benchmark, yielding a KeyGen item and then one item per operation
+/// as soon as it completes
+///
+/// Nothing runs until the stream is first polled. Dropping the stream stops
+/// the benchmark after the operation in progress. A slow consumer slows the
+/// benchmark down (see `OperationResult::blocked`); no result is ever dropped.
+pub fn run_benchmark_stream(config: BenchmarkConfig) -> impl Stream<Item = OperationResult> + Send + 'static {
+    async_stream::stream! {
+        let (mut tx, mut rx) = mpsc::channel(STREAM_BUFFER);
+        thread::spawn(move || run_operations(&config, |result| send_blocking(&mut tx, result)));
+
+        while let Some(result) = poll_fn(|cx| Pin::new(&mut rx).poll_next(cx)).await {
+            yield result;
+        }
+    }
+}
+
+// Wakes the benchmark thread parked in send_blocking
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Send from a plain thread, parking while the channel is full. False once the
+// stream has been dropped.
+fn send_blocking(tx: &mut mpsc::Sender<OperationResult>, mut result: OperationResult) -> bool {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = TaskContext::from_waker(&waker);
+    let mut blocked_since = None;
+    loop {
+        match tx.poll_ready(&mut cx) {
+            Poll::Ready(Ok(())) => break,
+            Poll::Ready(Err(_)) => return false,
+            Poll::Pending => {
+                blocked_since.get_or_insert_with(Instant::now);
+                thread::park();
+            }
+        }
+    }
+    result.blocked = blocked_since.map_or(Duration::ZERO, |since| since.elapsed());
+    tx.start_send(result).is_ok()
+}
+
+// Everything the timed operations need, set up once per benchmark
+struct Engine<'a> {
+    encryptor: Encryptor<'a>,
+    decryptor: Decryptor<'a>,
+    evaluator: Evaluator<'a>,
+    plain: Plaintext,
+    a: Ciphertext,
+    b: Ciphertext,
+}
+
+impl<'a> Engine<'a> {
+    fn new(context: &'a Context) -> Result<Self> {
+        let encoder = BatchEncoder::new(context)?;
+        let encryptor = Encryptor::new(context)?;
+        let values: Vec<i64> = (0..encoder.slot_count() as i64).collect();
+        let plain = encoder.encode(&values)?;
+        let a = encryptor.encrypt(&plain)?;
+        let b = encryptor.encrypt(&plain)?;
+        Ok(Engine {
+            decryptor: Decryptor::new(context)?,
+            evaluator: Evaluator::new(context)?,
+            encryptor,
+            plain,
+            a,
+            b,
+        })
+    }
+
+    fn run(&self, operation: OperationKind) -> Result<()> {
+        match operation {
+            OperationKind::KeyGen => Ok(()),
+            OperationKind::Encrypt => self.encryptor.encrypt(&self.plain).map(drop),
+            OperationKind::Add => self.evaluator.add(&self.a, &self.b).map(drop),
+            OperationKind::Multiply => self.evaluator.multiply(&self.a, &self.b).map(drop),
+            // Unchecked, as in the server benchmark: time decryption alone
+            OperationKind::Decrypt => self.decryptor.decrypt_unchecked(&self.a).map(drop),
+        }
+    }
+}
+
+// Runs on the benchmark thread; stops as soon as `emit` reports the receiver is gone
+fn run_operations(config: &BenchmarkConfig, mut emit: impl FnMut(OperationResult) -> bool) {
+    let key_start = Instant::now();
+    let setup = Context::new(config.poly_modulus_degree, config.plain_modulus);
+    let key_gen_time = key_start.elapsed();
+    let context = match setup {
+        Ok(context) => context,
+        Err(e) => {
+            emit(OperationResult { operation: OperationKind::KeyGen, index: 0, duration: key_gen_time, error: Some(e.to_string()), blocked: Duration::ZERO });
+            return;
+        }
+    };
+
+    // Encoding and encrypting the operands isn't timed
+    let engine = match Engine::new(&context) {
+        Ok(engine) => engine,
+        Err(e) => {
+            emit(OperationResult { operation: OperationKind::KeyGen, index: 0, duration: key_gen_time, error: Some(e.to_string()), blocked: Duration::ZERO });
+            return;
+        }
+    };
+    if !emit(OperationResult { operation: OperationKind::KeyGen, index: 0, duration: key_gen_time, error: None, blocked: Duration::ZERO }) {
+        return;
+    }
+
+    for &operation in config.operations.iter().filter(|&&op| op != OperationKind::KeyGen) {
+        for index in 0..config.num_operations {
+            let start = Instant::now();
+            let outcome = engine.run(operation);
+            let duration = start.elapsed();
+            if !emit(OperationResult { operation, index, duration, error: outcome.err().map(|e| e.to_string()), blocked: Duration::ZERO }) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_yields_one_item_per_operation() {
+        let config = BenchmarkConfig { num_operations: 3, ..Default::default() };
+        let results: Vec<_> = futures_executor::block_on_stream(Box::pin(run_benchmark_stream(config.clone()))).collect();
+
+        assert_eq!(results.len(), 1 + config.num_operations * config.operations.len());
+        assert_eq!(results[0].operation, OperationKind::KeyGen);
+        assert!(results.iter().all(|r| r.error.is_none()), "{:?}", results);
+        for &operation in &config.operations {
+            let indices: Vec<_> = results.iter().filter(|r| r.operation == operation).map(|r| r.index).collect();
+            assert_eq!(indices, vec![0, 1, 2], "{}", operation);
+        }
+    }
+
+    #[test]
+    fn test_slow_consumer_blocks_the_benchmark_without_dropping_results() {
+        let config = BenchmarkConfig {
+            num_operations: 4 * STREAM_BUFFER,
+            operations: vec![OperationKind::Add],
+            ..Default::default()
+        };
+        let mut results = Vec::new();
+        for result in futures_executor::block_on_stream(Box::pin(run_benchmark_stream(config.clone()))) {
+            // Far slower than an addition
+            thread::sleep(Duration::from_millis(10));
+            results.push(result);
+        }
+
+        let indices: Vec<_> = results[1..].iter().map(|r| r.index).collect();
+        assert_eq!(indices, (0..config.num_operations).collect::<Vec<_>>());
+        // Once the buffer was full, the benchmark only ever ran ahead by a bounded
+        // amount and spent the rest of its time waiting for the consumer
+        let tail = &results[results.len() - STREAM_BUFFER..];
+        assert!(tail.iter().all(|r| r.blocked >= Duration::from_millis(5)), "{:?}", tail);
+        assert_eq!(results[0].blocked, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bad_parameters_end_the_stream_after_keygen() {
+        let config = BenchmarkConfig { poly_modulus_degree: 1000, ..Default::default() };
+        let results: Vec<_> = futures_executor::block_on_stream(Box::pin(run_benchmark_stream(config))).collect();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].operation, OperationKind::KeyGen);
+        assert!(results[0].error.is_some());
+    }
+}
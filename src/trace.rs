@@ -0,0 +1,57 @@
+// Tracing spans around FFI calls
+//
+// `ffi!(bindings::seal_encrypt(...))` makes the call inside a TRACE span named
+// after the C function ("seal_encrypt"). Under a timing subscriber (e.g.
+// tracing-flame) that separates the time spent in the wrapper and the library
+// from the Rust work around it. With no subscriber, or one that filters out
+// TRACE, a span is a check of a cached flag and nothing else.
+
+macro_rules! ffi {
+    ($module:ident :: $function:ident ( $($arg:expr),* $(,)? )) => {{
+        let _span = ::tracing::trace_span!(stringify!($function)).entered();
+        $module::$function($($arg),*)
+    }};
+}
+
+#[cfg(all(test, feature = "seal"))]
+mod tests {
+    use crate::seal::{BatchEncoder, Context, Decryptor, Encryptor, Evaluator};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    // Records the name of every span created
+    #[derive(Clone, Default)]
+    struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+    impl<S: Subscriber> Layer<S> for SpanNames {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: LayerContext<'_, S>) {
+            self.0.lock().unwrap().push(attrs.metadata().name());
+        }
+    }
+
+    #[test]
+    fn test_ffi_calls_emit_spans() {
+        let names = SpanNames::default();
+        let subscriber = tracing_subscriber::registry().with(names.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let ctx = Context::new(8192, 1032193).unwrap();
+            let encoder = BatchEncoder::new(&ctx).unwrap();
+            let encryptor = Encryptor::new(&ctx).unwrap();
+            let decryptor = Decryptor::new(&ctx).unwrap();
+            let evaluator = Evaluator::new(&ctx).unwrap();
+
+            let a = encryptor.encrypt(&encoder.encode(&[1, 2, 3]).unwrap()).unwrap();
+            let sum = evaluator.add(&a, &a).unwrap();
+            encoder.decode(&decryptor.decrypt(&sum).unwrap()).unwrap();
+        });
+
+        let names = names.0.lock().unwrap();
+        for expected in ["seal_create_context", "seal_encrypt", "seal_add", "seal_decrypt", "seal_batch_decode"] {
+            assert!(names.contains(&expected), "no {} span in {:?}", expected, names);
+        }
+    }
+}
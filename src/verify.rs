@@ -0,0 +1,198 @@
+// Cross-check decrypted HE results against the same computation in the clear
+//
+// A bare assert_eq! on two 4096-slot vectors says "not equal" and dumps both.
+// ResultVerifier compares slot by slot (exactly for BFV, within a tolerance for
+// CKKS) and reports which slots differ, by how much.
+
+use std::fmt;
+
+// Slots listed in a report before the rest are summarized as "... and N more"
+const MAX_REPORTED_SLOTS: usize = 10;
+
+/// A decoded slot value: i64 (BFV / BGV) or f64 (CKKS)
+pub trait SlotValue: Copy + fmt::Display {
+    /// |self - other| as a float
+    fn distance(self, other: Self) -> f64;
+}
+
+impl SlotValue for i64 {
+    fn distance(self, other: Self) -> f64 {
+        self.abs_diff(other) as f64
+    }
+}
+
+impl SlotValue for f64 {
+    fn distance(self, other: Self) -> f64 {
+        // NaN never matches anything
+        let d = (self - other).abs();
+        if d.is_nan() { f64::INFINITY } else { d }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResultVerifier {
+    tolerance: f64,
+}
+
+impl ResultVerifier {
+    /// Every slot must match exactly (BFV, BGV)
+    pub fn exact() -> Self {
+        ResultVerifier { tolerance: 0.0 }
+    }
+
+    /// Slots may be off by up to `tolerance` (CKKS)
+    pub fn approx(tolerance: f64) -> Self {
+        assert!(tolerance >= 0.0, "tolerance must be non-negative, got {}", tolerance);
+        ResultVerifier { tolerance }
+    }
+
+    /// Compare a decrypted result with `reference()` computed in the clear
+    ///
+    /// Only the first `reference().len()` slots are checked, so a decoded vector
+    /// with unused trailing slots can be passed as is.
+    pub fn verify<T: SlotValue>(
+        &self,
+        decrypted: &[T],
+        reference: impl FnOnce() -> Vec<T>,
+    ) -> Result<(), VerificationError> {
+        let expected = reference();
+        if decrypted.len() < expected.len() {
+            return Err(VerificationError::TooFewSlots { expected: expected.len(), got: decrypted.len() });
+        }
+
+        let mismatches: Vec<SlotMismatch> = expected
+            .iter()
+            .zip(decrypted)
+            .enumerate()
+            .filter_map(|(slot, (&want, &got))| {
+                let error = got.distance(want);
+                (error > self.tolerance).then(|| SlotMismatch {
+                    slot,
+                    expected: want.to_string(),
+                    got: got.to_string(),
+                    error,
+                })
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+        Err(VerificationError::Mismatch {
+            checked: expected.len(),
+            tolerance: self.tolerance,
+            mismatches,
+        })
+    }
+
+    /// `verify`, panicking with the report on mismatch (for tests)
+    #[track_caller]
+    pub fn assert_matches<T: SlotValue>(&self, decrypted: &[T], reference: impl FnOnce() -> Vec<T>) {
+        if let Err(e) = self.verify(decrypted, reference) {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// One slot where the HE result and the reference disagree
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotMismatch {
+    pub slot: usize,
+    pub expected: String,
+    pub got: String,
+    pub error: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationError {
+    /// The decrypted result has fewer slots than the reference
+    TooFewSlots { expected: usize, got: usize },
+    /// Slots outside the tolerance, in slot order
+    Mismatch { checked: usize, tolerance: f64, mismatches: Vec<SlotMismatch> },
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::TooFewSlots { expected, got } => write!(
+                f,
+                "HE result has {} slots but the plaintext reference has {}",
+                got, expected
+            ),
+            VerificationError::Mismatch { checked, tolerance, mismatches } => {
+                write!(
+                    f,
+                    "HE result differs from the plaintext reference in {} of {} slots",
+                    mismatches.len(),
+                    checked
+                )?;
+                if *tolerance > 0.0 {
+                    write!(f, " (tolerance {:e})", tolerance)?;
+                }
+                writeln!(f, ":")?;
+                for m in mismatches.iter().take(MAX_REPORTED_SLOTS) {
+                    writeln!(f, "  slot {}: expected {}, got {} (off by {:e})", m.slot, m.expected, m.got, m.error)?;
+                }
+                if mismatches.len() > MAX_REPORTED_SLOTS {
+                    writeln!(f, "  ... and {} more", mismatches.len() - MAX_REPORTED_SLOTS)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_mismatch_lists_slots() {
+        let verifier = ResultVerifier::exact();
+        // Trailing slots beyond the reference are ignored
+        assert!(verifier.verify(&[2i64, 4, 6, 0, 0], || vec![2, 4, 6]).is_ok());
+
+        let err = verifier.verify(&[2i64, 5, 6, 9], || vec![2, 4, 6, 8]).unwrap_err();
+        let VerificationError::Mismatch { mismatches, .. } = &err else {
+            panic!("expected a mismatch, got {:?}", err);
+        };
+        assert_eq!(mismatches.iter().map(|m| m.slot).collect::<Vec<_>>(), vec![1, 3]);
+        assert!(err.to_string().contains("slot 1: expected 4, got 5"), "{}", err);
+
+        assert_eq!(
+            verifier.verify(&[1i64], || vec![1, 2]),
+            Err(VerificationError::TooFewSlots { expected: 2, got: 1 })
+        );
+    }
+
+    #[cfg(feature = "seal")]
+    #[test]
+    fn test_misconfigured_scale_gets_per_slot_report() {
+        use crate::seal::{CKKSEncoder, Context, Decryptor, Encryptor, Evaluator};
+
+        let ctx = Context::new_ckks(8192, &[60, 40, 40, 60]).unwrap();
+        let encryptor = Encryptor::new(&ctx).unwrap();
+        let decryptor = Decryptor::new(&ctx).unwrap();
+        let evaluator = Evaluator::new(&ctx).unwrap();
+        let values: Vec<f64> = (0..16).map(|i| i as f64 / 16.0).collect();
+        let verifier = ResultVerifier::approx(1e-3);
+
+        let doubled = |scale: f64| {
+            let encoder = CKKSEncoder::new(&ctx, scale).unwrap();
+            let cipher = encryptor.encrypt(&encoder.encode(&values).unwrap()).unwrap();
+            let sum = evaluator.add(&cipher, &cipher).unwrap();
+            encoder.decode(&decryptor.decrypt(&sum).unwrap()).unwrap()
+        };
+        let reference = || values.iter().map(|v| v * 2.0).collect::<Vec<_>>();
+
+        verifier.assert_matches(&doubled(2f64.powi(40)), reference);
+
+        // A 2^4 scale leaves the encryption noise at the same size as the values
+        let err = verifier.verify(&doubled(16.0), reference).unwrap_err();
+        let report = err.to_string();
+        assert!(report.contains("of 16 slots (tolerance 1e-3)"), "{}", report);
+        assert!(report.contains("  slot "), "{}", report);
+    }
+}
@@ -0,0 +1,187 @@
+// Deadlines for SEAL and HElib calls that can't be interrupted
+//
+// Key generation for unusual parameters can sit in a slow path for minutes, and
+// an FFI call can't be cancelled: there is no safe way to stop C++ halfway. So
+// `with_deadline` runs the call on a thread of its own and stops waiting after
+// the limit. The caller gets `SealError::TimedOut` (`HElibError::TimedOut` from
+// `helib_with_deadline`); the detached thread runs to completion in the
+// background and its result is dropped.
+//
+// Only the expensive setup calls get wrappers here (Context::new generates the
+// key pair; for HElib, HEContext::new and HESecretKey::generate). There is
+// nothing to bound for bootstrapping: neither library runs it here.
+
+#[cfg(feature = "helib")]
+use crate::helib::{HEContext, HESecretKey, HElibError};
+#[cfg(feature = "seal")]
+use crate::seal::{Context, GaloisKeys, RelinKeys, Result, SealError};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+// Why `run_detached` came back without a result
+enum Missed {
+    TimedOut,
+    Failed,
+}
+
+// Runs `f` on a thread named `name` and waits at most `limit` for it
+fn run_detached<T, F>(name: String, limit: Duration, f: F) -> std::result::Result<T, Missed>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    // Capacity 1: a late result is parked in the channel and dropped with it,
+    // so the detached thread never blocks on a receiver that gave up
+    let (tx, rx) = mpsc::sync_channel(1);
+    thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            let _ = tx.send(f());
+        })
+        .map_err(|_| Missed::Failed)?;
+
+    match rx.recv_timeout(limit) {
+        Ok(result) => Ok(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Missed::TimedOut),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(Missed::Failed),
+    }
+}
+
+/// Run `f` on a new thread and wait at most `limit` for its result
+///
+/// `TimedOut(limit)` past the limit (`f` keeps running detached), and
+/// `OperationFailed` if `f` panicked or the thread couldn't be started.
+#[cfg(feature = "seal")]
+pub fn with_deadline<T, F>(operation: &str, limit: Duration, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match run_detached(format!("seal-{}", operation), limit, f) {
+        Ok(result) => result,
+        Err(Missed::TimedOut) => {
+            tracing::warn!(operation, ?limit, "SEAL call timed out; it keeps running in the background");
+            Err(SealError::TimedOut(limit))
+        }
+        Err(Missed::Failed) => Err(SealError::OperationFailed),
+    }
+}
+
+/// `with_deadline` for HElib calls: `HElibError::TimedOut(limit)` past the
+/// limit, `HElibError::OperationFailed` if `f` panicked
+#[cfg(feature = "helib")]
+pub fn helib_with_deadline<T, F>(operation: &str, limit: Duration, f: F) -> crate::helib::Result<T>
+where
+    F: FnOnce() -> crate::helib::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    match run_detached(format!("helib-{}", operation), limit, f) {
+        Ok(result) => result,
+        Err(Missed::TimedOut) => {
+            tracing::warn!(operation, ?limit, "HElib call timed out; it keeps running in the background");
+            Err(HElibError::TimedOut(limit))
+        }
+        Err(Missed::Failed) => Err(HElibError::OperationFailed),
+    }
+}
+
+#[cfg(feature = "seal")]
+impl Context {
+    /// `Context::new`, giving up after `limit`
+    pub fn new_with_deadline(poly_modulus_degree: u64, plain_modulus: u64, limit: Duration) -> Result<Self> {
+        with_deadline("keygen", limit, move || Context::new(poly_modulus_degree, plain_modulus))
+    }
+}
+
+#[cfg(feature = "seal")]
+impl RelinKeys {
+    /// `RelinKeys::generate`, giving up after `limit` (the Arc keeps the context
+    /// alive for a call that outlives the wait)
+    pub fn generate_with_deadline(context: &Arc<Context>, limit: Duration) -> Result<Self> {
+        let context = Arc::clone(context);
+        with_deadline("relin_keys", limit, move || RelinKeys::generate(&context))
+    }
+}
+
+#[cfg(feature = "seal")]
+impl GaloisKeys {
+    /// `GaloisKeys::generate`, giving up after `limit`
+    pub fn generate_with_deadline(context: &Arc<Context>, limit: Duration) -> Result<Self> {
+        let context = Arc::clone(context);
+        with_deadline("galois_keys", limit, move || GaloisKeys::generate(&context))
+    }
+}
+
+#[cfg(feature = "helib")]
+impl HEContext {
+    /// `HEContext::new`, giving up after `limit`
+    pub fn new_with_deadline(m: u64, p: u64, r: u64, limit: Duration) -> crate::helib::Result<Self> {
+        helib_with_deadline("context", limit, move || HEContext::new(m, p, r))
+    }
+}
+
+#[cfg(feature = "helib")]
+impl HESecretKey {
+    /// `HESecretKey::generate`, giving up after `limit`. The key points into
+    /// the context, so the Arc keeps it alive for a call that outlives the wait;
+    /// the caller has to keep its own handle for as long as it uses the key.
+    pub fn generate_with_deadline(context: &Arc<HEContext>, limit: Duration) -> crate::helib::Result<Self> {
+        let context = Arc::clone(context);
+        helib_with_deadline("keygen", limit, move || HESecretKey::generate(&context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "seal")]
+    use std::sync::atomic::{AtomicBool, Ordering};
+    #[cfg(feature = "seal")]
+    use std::time::Instant;
+
+    #[cfg(feature = "seal")]
+    #[test]
+    fn test_slow_call_times_out_and_finishes_detached() {
+        // Stands in for a binding stuck in a slow path
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&finished);
+        let started = Instant::now();
+        let result = with_deadline("keygen", Duration::from_millis(50), move || {
+            thread::sleep(Duration::from_millis(500));
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(SealError::TimedOut(limit)) if limit == Duration::from_millis(50)));
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert!(!finished.load(Ordering::SeqCst));
+        thread::sleep(Duration::from_millis(700));
+        assert!(finished.load(Ordering::SeqCst));
+
+        let result: Result<()> = with_deadline("keygen", Duration::from_secs(5), || panic!("binding crashed"));
+        assert!(matches!(result, Err(SealError::OperationFailed)));
+    }
+
+    #[cfg(feature = "seal")]
+    #[test]
+    fn test_fast_keygen_returns_within_the_deadline() {
+        let context = Arc::new(Context::new_with_deadline(8192, 1032193, Duration::from_secs(60)).unwrap());
+        RelinKeys::generate_with_deadline(&context, Duration::from_secs(60)).unwrap();
+        GaloisKeys::generate_with_deadline(&context, Duration::from_secs(60)).unwrap();
+    }
+
+    #[cfg(feature = "helib")]
+    #[test]
+    fn test_helib_keygen_within_and_past_the_deadline() {
+        let context = Arc::new(HEContext::new_with_deadline(4095, 2, 1, Duration::from_secs(60)).unwrap());
+        let secret_key = HESecretKey::generate_with_deadline(&context, Duration::from_secs(60)).unwrap();
+        secret_key.public_key().unwrap();
+
+        let result: crate::helib::Result<()> = helib_with_deadline("keygen", Duration::from_millis(50), || {
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        });
+        assert!(matches!(result, Err(HElibError::TimedOut(limit)) if limit == Duration::from_millis(50)));
+    }
+}
@@ -0,0 +1,10 @@
+// tests/compile_fail.rs
+//
+// Misuse that must be rejected at compile time (see tests/ui/)
+
+#[cfg(feature = "seal")]
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
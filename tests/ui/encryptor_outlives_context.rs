@@ -0,0 +1,15 @@
+// An Encryptor borrows the Context it was created from, so the context can't be
+// dropped while the encryptor is still in use.
+
+use he_benchmark::{BatchEncoder, Context, Encryptor};
+
+fn main() {
+    let context = Context::new(8192, 1032193).unwrap();
+    let encoder = BatchEncoder::new(&context).unwrap();
+    let encryptor = Encryptor::new(&context).unwrap();
+
+    drop(context);
+
+    let plain = encoder.encode(&[1, 2, 3]).unwrap();
+    let _ = encryptor.encrypt(&plain);
+}